@@ -7,11 +7,13 @@ use shared_vcx::validation::verkey::validate_verkey;
 
 use crate::configuration::AgencyClientConfig;
 use crate::errors::error::{AgencyClientError, AgencyClientErrorKind, AgencyClientResult};
+use crate::httpclient::{HttpClient, ReqwestHttpClient};
 use crate::wallet::base_agency_client_wallet::{BaseAgencyClientWallet, StubAgencyClientWallet};
 
 #[derive(Clone, Debug)]
 pub struct AgencyClient {
     wallet: Arc<dyn BaseAgencyClientWallet>,
+    http_client: Arc<dyn HttpClient>,
     pub agency_url: Url,
     pub agency_did: String,
     pub agency_vk: String,
@@ -26,6 +28,10 @@ impl AgencyClient {
         Arc::clone(&self.wallet)
     }
 
+    pub fn get_http_client(&self) -> Arc<dyn HttpClient> {
+        Arc::clone(&self.http_client)
+    }
+
     pub fn get_agency_url_full(&self) -> AgencyClientResult<Url> {
         self.agency_url.join("agency/msg").map_err(|_| {
             AgencyClientError::from_msg(
@@ -60,6 +66,10 @@ impl AgencyClient {
         self.wallet = wallet
     }
 
+    pub fn set_http_client(&mut self, http_client: Arc<dyn HttpClient>) {
+        self.http_client = http_client
+    }
+
     pub(crate) fn set_agency_url(&mut self, url: Url) {
         self.agency_url = url;
     }
@@ -127,6 +137,7 @@ impl AgencyClient {
     pub fn new() -> Self {
         AgencyClient {
             wallet: Arc::new(StubAgencyClientWallet {}),
+            http_client: Arc::new(ReqwestHttpClient::new()),
             agency_url: "http://127.0.0.1:8080".parse().expect("should be valid url"),
             agency_did: "".to_string(),
             agency_vk: "".to_string(),
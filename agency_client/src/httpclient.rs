@@ -1,6 +1,7 @@
 use std::env;
 use std::time::Duration;
 
+use async_trait::async_trait;
 use reqwest::header::{CONTENT_TYPE, USER_AGENT};
 use reqwest::Client;
 use reqwest::{self, Url};
@@ -22,25 +23,80 @@ lazy_static! {
     };
 }
 
-pub async fn post_message(body_content: Vec<u8>, url: Url) -> AgencyClientResult<Vec<u8>> {
-    if mocking::agency_mocks_enabled() {
-        if HttpClientMockResponse::has_response() {
-            warn!("post_message >> mocking response for POST {}", &url);
-            return HttpClientMockResponse::get_response();
-        }
-        if AgencyMockDecrypted::has_decrypted_mock_responses() {
-            warn!("post_message >> will use mocked decrypted response for POST {}", &url);
-            return Ok(vec![]);
+/// Transport used by [`AgencyClient`](crate::agency_client::AgencyClient) to POST packed messages
+/// to the agency. Implement it to plug in a custom HTTP stack (proxies, mTLS, custom headers,
+/// retries, or a local stub in tests) and set it with
+/// [`AgencyClient::set_http_client`](crate::agency_client::AgencyClient::set_http_client).
+#[async_trait]
+pub trait HttpClient: std::fmt::Debug + Send + Sync {
+    /// POSTs `body_content` to `url` and returns the body of a successful response.
+    async fn post_message(&self, body_content: Vec<u8>, url: Url) -> AgencyClientResult<Vec<u8>>;
+}
+
+/// Default [`HttpClient`], backed by [reqwest](https://docs.rs/reqwest).
+#[derive(Clone, Debug)]
+pub struct ReqwestHttpClient {
+    client: Client,
+}
+
+impl ReqwestHttpClient {
+    pub fn new() -> Self {
+        Self {
+            client: HTTP_CLIENT.clone(),
         }
-        let mocked_response = AgencyMock::get_response();
-        warn!(
-            "post_message >> mocking response of length {} for POST {}",
-            mocked_response.len(),
-            &url
-        );
-        return Ok(mocked_response);
     }
 
+    /// Uses a preconfigured reqwest client, e.g. one built with a proxy or a client certificate.
+    pub fn with_client(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl Default for ReqwestHttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl HttpClient for ReqwestHttpClient {
+    async fn post_message(&self, body_content: Vec<u8>, url: Url) -> AgencyClientResult<Vec<u8>> {
+        send_post_message(&self.client, body_content, url).await
+    }
+}
+
+pub async fn post_message(body_content: Vec<u8>, url: Url) -> AgencyClientResult<Vec<u8>> {
+    if let Some(mocked_response) = mocked_response(&url) {
+        return mocked_response;
+    }
+
+    send_post_message(&HTTP_CLIENT, body_content, url).await
+}
+
+/// Returns the mocked response to a POST to `url` when agency mocks are enabled.
+pub(crate) fn mocked_response(url: &Url) -> Option<AgencyClientResult<Vec<u8>>> {
+    if !mocking::agency_mocks_enabled() {
+        return None;
+    }
+
+    if HttpClientMockResponse::has_response() {
+        warn!("post_message >> mocking response for POST {}", url);
+        return Some(HttpClientMockResponse::get_response());
+    }
+    if AgencyMockDecrypted::has_decrypted_mock_responses() {
+        warn!("post_message >> will use mocked decrypted response for POST {}", url);
+        return Some(Ok(vec![]));
+    }
+    let mocked_response = AgencyMock::get_response();
+    warn!(
+        "post_message >> mocking response of length {} for POST {}",
+        mocked_response.len(),
+        url
+    );
+    Some(Ok(mocked_response))
+}
+
+async fn send_post_message(client: &Client, body_content: Vec<u8>, url: Url) -> AgencyClientResult<Vec<u8>> {
     // Setting SSL Certs location. This is needed on android
     // platform. Or openssl will fail to verify the certs
     if cfg!(target_os = "android") {
@@ -50,7 +106,7 @@ pub async fn post_message(body_content: Vec<u8>, url: Url) -> AgencyClientResult
 
     debug!("post_message >> http client sending request POST {}", &url);
 
-    let response = HTTP_CLIENT
+    let response = client
         .post(url)
         .body(body_content)
         .header(CONTENT_TYPE, "application/ssi-agent-wire")
@@ -66,7 +122,7 @@ pub async fn post_message(body_content: Vec<u8>, url: Url) -> AgencyClientResult
 
     let content_length = response.content_length();
     let response_status = response.status();
-     log::warn!("HTTP Status: {response_status}");
+    log::warn!("HTTP Status: {response_status}");
     match response.text().await {
         Ok(payload) => {
             if response_status.is_success() {
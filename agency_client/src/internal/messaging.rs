@@ -10,7 +10,10 @@ use serde_json::Value;
 impl AgencyClient {
     pub async fn post_to_agency(&self, body_content: Vec<u8>) -> AgencyClientResult<Vec<u8>> {
         let url = self.get_agency_url_full()?;
-        httpclient::post_message(body_content, url).await
+        if let Some(mocked_response) = httpclient::mocked_response(&url) {
+            return mocked_response;
+        }
+        self.get_http_client().post_message(body_content, url).await
     }
 
     pub async fn prepare_message_for_agency(
@@ -202,3 +205,45 @@ impl AgencyClient {
         self.parse_response_from_agency(&response).await
     }
 }
+
+#[cfg(feature = "general_test")]
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use url::Url;
+
+    use crate::agency_client::AgencyClient;
+    use crate::errors::error::AgencyClientResult;
+    use crate::httpclient::HttpClient;
+
+    #[derive(Debug, Default)]
+    struct StubHttpClient {
+        requests: Mutex<Vec<(Vec<u8>, Url)>>,
+    }
+
+    #[async_trait]
+    impl HttpClient for StubHttpClient {
+        async fn post_message(&self, body_content: Vec<u8>, url: Url) -> AgencyClientResult<Vec<u8>> {
+            self.requests.lock().unwrap().push((body_content, url));
+            Ok(b"stub response".to_vec())
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "general_test")]
+    async fn test_post_to_agency_uses_http_client() {
+        let http_client = Arc::new(StubHttpClient::default());
+        let mut client = AgencyClient::new();
+        client.set_http_client(http_client.clone());
+
+        let response = client.post_to_agency(b"packed message".to_vec()).await.unwrap();
+
+        assert_eq!(response, b"stub response".to_vec());
+        let requests = http_client.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].0, b"packed message".to_vec());
+        assert_eq!(requests[0].1, client.get_agency_url_full().unwrap());
+    }
+}
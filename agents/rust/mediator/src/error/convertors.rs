@@ -0,0 +1,30 @@
+use std::convert::From;
+
+use aries_vcx::errors::error::AriesVcxError;
+use aries_vcx_core::errors::error::AriesVcxCoreError;
+
+use crate::error::*;
+
+impl From<AriesVcxError> for MediatorError {
+    fn from(err: AriesVcxError) -> MediatorError {
+        let kind = MediatorErrorKind::GenericAriesVcxError;
+        let message = format!("AriesVCX Error: {}", err);
+        MediatorError { message, kind }
+    }
+}
+
+impl From<AriesVcxCoreError> for MediatorError {
+    fn from(err: AriesVcxCoreError) -> Self {
+        let kind = MediatorErrorKind::GenericAriesVcxError;
+        let message = format!("AriesVcxCore Error: {}", err);
+        MediatorError { message, kind }
+    }
+}
+
+impl From<serde_json::Error> for MediatorError {
+    fn from(serde_err: serde_json::Error) -> MediatorError {
+        let kind = MediatorErrorKind::SerializationError;
+        let message = format!("(De)serialization failed; err: {:?}", serde_err.to_string());
+        MediatorError { message, kind }
+    }
+}
@@ -0,0 +1,21 @@
+#[derive(Copy, Clone, Eq, PartialEq, Debug, thiserror::Error)]
+pub enum MediatorErrorKind {
+    #[error("AriesVCX error")]
+    GenericAriesVcxError,
+    #[error("No object found with specified ID")]
+    NotFound,
+    #[error("Unable to lock storage")]
+    LockError,
+    #[error("Serialization error")]
+    SerializationError,
+    #[error("Invalid message received")]
+    InvalidMessage,
+    #[error("Message is not supported by the mediator")]
+    UnsupportedMessage,
+    #[error("No connection found for the message")]
+    ConnectionNotFound,
+    #[error("Mediation was not granted for the connection")]
+    MediationNotGranted,
+    #[error("No mediation found for the recipient key")]
+    UnknownRecipient,
+}
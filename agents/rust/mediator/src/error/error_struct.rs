@@ -0,0 +1,27 @@
+use crate::error::MediatorErrorKind;
+
+#[derive(Debug)]
+pub struct MediatorError {
+    pub message: String,
+    pub kind: MediatorErrorKind,
+}
+
+impl std::fmt::Display for MediatorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::result::Result<(), std::fmt::Error> {
+        f.write_str(&format!("{}: {}", self.kind, self.message))
+    }
+}
+
+impl MediatorError {
+    pub fn from_msg(kind: MediatorErrorKind, msg: &str) -> Self {
+        MediatorError {
+            kind,
+            message: msg.to_string(),
+        }
+    }
+
+    pub fn from_kind(kind: MediatorErrorKind) -> Self {
+        let message = kind.to_string();
+        MediatorError { kind, message }
+    }
+}
@@ -0,0 +1,6 @@
+mod convertors;
+mod error_kind;
+mod error_struct;
+mod result;
+
+pub use {error_kind::MediatorErrorKind, error_struct::MediatorError, result::MediatorResult};
@@ -0,0 +1,3 @@
+use crate::error::*;
+
+pub type MediatorResult<T> = Result<T, MediatorError>;
@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Json, Router,
+};
+
+use crate::{error::*, mediator::Mediator};
+
+/// Builds the routes of the mediator: inbound messages are posted to `/`, and the
+/// invitation to connect to the mediator is served on `/invitation`.
+pub fn build_router(mediator: Arc<Mediator>) -> Router {
+    Router::new()
+        .route("/", post(receive_message))
+        .route("/invitation", get(get_invitation))
+        .with_state(mediator)
+}
+
+async fn receive_message(State(mediator): State<Arc<Mediator>>, body: Bytes) -> Response {
+    match mediator.receive_message(body.to_vec()).await {
+        Ok(Some(reply)) => ([(CONTENT_TYPE, "application/ssi-agent-wire")], reply).into_response(),
+        Ok(None) => StatusCode::ACCEPTED.into_response(),
+        Err(err) => {
+            error!("Failed to process inbound message: {}", err);
+            (status_code(err.kind), err.to_string()).into_response()
+        }
+    }
+}
+
+async fn get_invitation(State(mediator): State<Arc<Mediator>>) -> impl IntoResponse {
+    Json(mediator.invitation().clone())
+}
+
+fn status_code(kind: MediatorErrorKind) -> StatusCode {
+    match kind {
+        MediatorErrorKind::InvalidMessage | MediatorErrorKind::SerializationError => StatusCode::BAD_REQUEST,
+        MediatorErrorKind::UnsupportedMessage => StatusCode::NOT_IMPLEMENTED,
+        MediatorErrorKind::MediationNotGranted => StatusCode::FORBIDDEN,
+        MediatorErrorKind::NotFound | MediatorErrorKind::ConnectionNotFound | MediatorErrorKind::UnknownRecipient => {
+            StatusCode::NOT_FOUND
+        }
+        MediatorErrorKind::GenericAriesVcxError | MediatorErrorKind::LockError => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
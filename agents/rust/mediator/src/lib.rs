@@ -0,0 +1,10 @@
+#[macro_use]
+extern crate log;
+
+mod error;
+pub mod http;
+pub mod mediator;
+pub mod persistence;
+mod transport;
+
+pub use error::*;
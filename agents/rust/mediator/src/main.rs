@@ -0,0 +1,55 @@
+use std::{env, net::SocketAddr, sync::Arc};
+
+use aries_vcx::global::settings::WALLET_KDF_DEFAULT;
+use aries_vcx_core::{
+    indy::wallet::{create_and_open_wallet, WalletConfig},
+    wallet::{base_wallet::BaseWallet, indy_wallet::IndySdkWallet},
+};
+use log::info;
+use mediator::{http::build_router, mediator::Mediator, persistence::in_memory::InMemoryPersistence};
+use url::Url;
+
+fn env_or(name: &str, default: &str) -> String {
+    env::var(name).unwrap_or_else(|_| default.to_owned())
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    let service_endpoint: Url = env_or("MEDIATOR_ENDPOINT", "http://localhost:8005")
+        .parse()
+        .expect("MEDIATOR_ENDPOINT should be a valid URL");
+    let bind_address: SocketAddr = env_or("MEDIATOR_BIND_ADDRESS", "0.0.0.0:8005")
+        .parse()
+        .expect("MEDIATOR_BIND_ADDRESS should be a valid socket address");
+
+    let config_wallet = WalletConfig {
+        wallet_name: env_or("MEDIATOR_WALLET_NAME", "mediator_wallet"),
+        wallet_key: env_or("MEDIATOR_WALLET_KEY", "mediator_wallet_key"),
+        wallet_key_derivation: env_or("MEDIATOR_WALLET_KDF", WALLET_KDF_DEFAULT),
+        wallet_type: None,
+        storage_config: None,
+        storage_credentials: None,
+        rekey: None,
+        rekey_derivation_method: None,
+    };
+    let wallet_handle = create_and_open_wallet(&config_wallet)
+        .await
+        .expect("Failed to open the mediator wallet");
+    let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(wallet_handle));
+
+    let mediator = Mediator::new(wallet, Arc::new(InMemoryPersistence::new()), service_endpoint)
+        .await
+        .expect("Failed to create the mediator");
+    info!(
+        "Mediator invitation: {}",
+        serde_json::to_string(mediator.invitation()).expect("Invitation should be serializable")
+    );
+
+    info!("Mediator listening on {}", bind_address);
+    axum::Server::bind(&bind_address)
+        .serve(build_router(Arc::new(mediator)).into_make_service())
+        .await
+        .expect("Mediator server failed");
+}
@@ -0,0 +1,90 @@
+use aries_vcx::{
+    messages::{msg_fields::protocols::connection::request::Request, AriesMessage},
+    protocols::connection::{
+        inviter::{states::responded::Responded, InviterConnection},
+        GenericConnection, State, ThinState,
+    },
+};
+
+use super::Mediator;
+use crate::{
+    error::*,
+    transport::{HttpClient, ReturnRouteTransport},
+};
+
+impl Mediator {
+    /// Accepts a connection request sent for the invitation of the mediator. Every request
+    /// results in a new connection, so a single invitation can be used by any client.
+    pub(super) async fn handle_connection_request(
+        &self,
+        request: Request,
+        return_route: bool,
+    ) -> MediatorResult<Option<Vec<u8>>> {
+        let requested = self
+            .inviter
+            .clone()
+            .handle_request(
+                &self.wallet,
+                request,
+                self.service_endpoint.clone(),
+                vec![],
+                &HttpClient,
+            )
+            .await?;
+
+        let (responded, reply) = if return_route {
+            let transport = ReturnRouteTransport::default();
+            let responded = requested.send_response(&self.wallet, &transport).await?;
+            (responded, transport.into_reply())
+        } else {
+            (requested.send_response(&self.wallet, &HttpClient).await?, None)
+        };
+
+        self.save_connection(responded.into()).await?;
+        Ok(reply)
+    }
+
+    /// Loads the connection the message was sent over, making sure it comes from the
+    /// counterparty of the connection and completing the connection if needed.
+    pub(super) async fn acknowledged_connection(
+        &self,
+        pw_vk: &str,
+        sender_verkey: &str,
+        message: &AriesMessage,
+    ) -> MediatorResult<GenericConnection> {
+        let connection = self.persistence.get_connection(pw_vk).await?.ok_or_else(|| {
+            MediatorError::from_msg(
+                MediatorErrorKind::ConnectionNotFound,
+                &format!("No connection found for key {}", pw_vk),
+            )
+        })?;
+        let connection: GenericConnection = serde_json::from_str(&connection)?;
+
+        if connection.remote_vk()? != sender_verkey {
+            return Err(MediatorError::from_msg(
+                MediatorErrorKind::InvalidMessage,
+                &format!(
+                    "Message on connection {} was not sent by its counterparty, but by {}",
+                    pw_vk, sender_verkey
+                ),
+            ));
+        }
+
+        match connection.state() {
+            ThinState::Inviter(State::Responded) => {
+                let responded = InviterConnection::<Responded>::try_from(connection)?;
+                let completed: GenericConnection = responded.acknowledge_connection(message)?.into();
+                self.save_connection(completed.clone()).await?;
+                Ok(completed)
+            }
+            _ => Ok(connection),
+        }
+    }
+
+    async fn save_connection(&self, connection: GenericConnection) -> MediatorResult<()> {
+        let pw_vk = connection.pairwise_info().pw_vk.clone();
+        self.persistence
+            .save_connection(&pw_vk, serde_json::to_string(&connection)?)
+            .await
+    }
+}
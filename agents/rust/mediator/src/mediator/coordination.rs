@@ -0,0 +1,138 @@
+use aries_vcx::{
+    common::ledger::transactions::ed25519_public_key_to_did_key,
+    messages::msg_fields::protocols::coordinate_mediation::{
+        keylist::{Keylist, KeylistContent, KeylistDecorators, KeylistItem, KeylistPagination},
+        keylist_query::KeylistQuery,
+        keylist_update::{KeylistUpdate, KeylistUpdateAction},
+        keylist_update_response::{
+            KeylistUpdateResponse, KeylistUpdateResponseContent, KeylistUpdateResponseDecorators,
+            KeylistUpdateResponseItem, KeylistUpdateResult,
+        },
+        mediate_grant::{MediateGrant, MediateGrantContent, MediateGrantDecorators},
+        mediate_request::MediateRequest,
+    },
+};
+use uuid::Uuid;
+
+use super::{naked_key, reply_thread, Mediator};
+use crate::error::*;
+
+impl Mediator {
+    /// Grants mediation to every client, routing its messages through the key of the mediator.
+    pub(super) async fn handle_mediate_request(
+        &self,
+        account: &str,
+        request: MediateRequest,
+    ) -> MediatorResult<MediateGrant> {
+        self.persistence.create_account(account).await?;
+
+        let routing_key = ed25519_public_key_to_did_key(self.routing_key())?;
+        let content = MediateGrantContent::new(self.service_endpoint.clone(), vec![routing_key]);
+        let decorators = MediateGrantDecorators::new(reply_thread(&request.id, request.decorators.thread.as_ref()));
+
+        Ok(MediateGrant::with_decorators(
+            Uuid::new_v4().to_string(),
+            content,
+            decorators,
+        ))
+    }
+
+    pub(super) async fn handle_keylist_update(
+        &self,
+        account: &str,
+        update: KeylistUpdate,
+    ) -> MediatorResult<KeylistUpdateResponse> {
+        self.ensure_mediation_granted(account).await?;
+
+        let mut updated = Vec::with_capacity(update.content.updates.len());
+        for item in update.content.updates {
+            let result = self
+                .apply_keylist_update(account, &item.recipient_key, item.action)
+                .await;
+            updated.push(KeylistUpdateResponseItem::new(item.recipient_key, item.action, result));
+        }
+
+        let content = KeylistUpdateResponseContent::new(updated);
+        let decorators =
+            KeylistUpdateResponseDecorators::new(reply_thread(&update.id, update.decorators.thread.as_ref()));
+
+        Ok(KeylistUpdateResponse::with_decorators(
+            Uuid::new_v4().to_string(),
+            content,
+            decorators,
+        ))
+    }
+
+    async fn apply_keylist_update(
+        &self,
+        account: &str,
+        recipient_key: &str,
+        action: KeylistUpdateAction,
+    ) -> KeylistUpdateResult {
+        let Ok(recipient_key) = naked_key(recipient_key) else {
+            return KeylistUpdateResult::ClientError;
+        };
+
+        let res = match action {
+            KeylistUpdateAction::Add => self.persistence.add_recipient(account, &recipient_key).await,
+            KeylistUpdateAction::Remove => self.persistence.remove_recipient(account, &recipient_key).await,
+        };
+
+        match res {
+            Ok(true) => KeylistUpdateResult::Success,
+            Ok(false) => KeylistUpdateResult::NoChange,
+            Err(err) if err.kind == MediatorErrorKind::InvalidMessage => KeylistUpdateResult::ClientError,
+            Err(err) => {
+                error!("Failed to update keylist of account {}: {}", account, err);
+                KeylistUpdateResult::ServerError
+            }
+        }
+    }
+
+    pub(super) async fn handle_keylist_query(&self, account: &str, query: KeylistQuery) -> MediatorResult<Keylist> {
+        self.ensure_mediation_granted(account).await?;
+
+        let keys = self.persistence.list_recipient_keys(account).await?;
+        let total = keys.len() as u32;
+
+        let (keys, pagination) = match &query.content.paginate {
+            Some(paginate) => {
+                let page: Vec<String> = keys
+                    .into_iter()
+                    .skip(paginate.offset as usize)
+                    .take(paginate.limit as usize)
+                    .collect();
+                let count = page.len() as u32;
+                let remaining = total.saturating_sub(paginate.offset.saturating_add(count));
+                (page, Some(KeylistPagination::new(count, paginate.offset, remaining)))
+            }
+            None => (keys, None),
+        };
+
+        let keys = keys
+            .iter()
+            .map(|key| ed25519_public_key_to_did_key(key).map(KeylistItem::new))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut content = KeylistContent::new(keys);
+        content.pagination = pagination;
+        let decorators = KeylistDecorators::new(reply_thread(&query.id, query.decorators.thread.as_ref()));
+
+        Ok(Keylist::with_decorators(
+            Uuid::new_v4().to_string(),
+            content,
+            decorators,
+        ))
+    }
+
+    pub(super) async fn ensure_mediation_granted(&self, account: &str) -> MediatorResult<()> {
+        if self.persistence.has_account(account).await? {
+            Ok(())
+        } else {
+            Err(MediatorError::from_msg(
+                MediatorErrorKind::MediationNotGranted,
+                &format!("Mediation was not requested over connection {}", account),
+            ))
+        }
+    }
+}
@@ -0,0 +1,241 @@
+mod connection;
+mod coordination;
+mod pickup;
+mod routing;
+
+use std::sync::Arc;
+
+use aries_vcx::{
+    common::ledger::transactions::normalize_keys_as_naked,
+    handlers::util::AnyInvitation,
+    messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::{
+            connection::Connection, coordinate_mediation::CoordinateMediation, pickup::Pickup, trust_ping::TrustPing,
+        },
+        AriesMessage,
+    },
+    protocols::{
+        connection::{
+            inviter::{states::invited::Invited, InviterConnection},
+            pairwise_info::PairwiseInfo,
+            GenericConnection,
+        },
+        trustping::build_ping_response,
+    },
+};
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use serde::Deserialize;
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    error::*,
+    persistence::MediatorPersistence,
+    transport::{HttpClient, ReturnRouteTransport},
+};
+
+/// Content of a message unpacked by the wallet.
+#[derive(Deserialize)]
+struct UnpackedMessage {
+    message: String,
+    recipient_verkey: String,
+    sender_verkey: Option<String>,
+}
+
+/// Mediator routing `forward` messages into queues of the recipient keys registered
+/// through the coordinate mediation protocol, from where they are picked up by their
+/// owner using the message pickup protocol.
+///
+/// Clients connect to the mediator by accepting its (multi-use) connection invitation.
+pub struct Mediator {
+    wallet: Arc<dyn BaseWallet>,
+    persistence: Arc<dyn MediatorPersistence>,
+    service_endpoint: Url,
+    inviter: InviterConnection<Invited>,
+}
+
+impl Mediator {
+    /// Creates a mediator reachable at `service_endpoint`, generating the key its
+    /// invitation is issued for. The same key is used as the routing key of the mediator.
+    pub async fn new(
+        wallet: Arc<dyn BaseWallet>,
+        persistence: Arc<dyn MediatorPersistence>,
+        service_endpoint: Url,
+    ) -> MediatorResult<Self> {
+        let pairwise_info = PairwiseInfo::create(&wallet).await?;
+        let inviter = InviterConnection::new_inviter("mediator".to_owned(), pairwise_info)
+            .create_invitation(vec![], service_endpoint.clone());
+
+        Ok(Self {
+            wallet,
+            persistence,
+            service_endpoint,
+            inviter,
+        })
+    }
+
+    pub fn invitation(&self) -> &AnyInvitation {
+        self.inviter.get_invitation()
+    }
+
+    /// Naked verkey senders wrap `forward` messages for.
+    pub fn routing_key(&self) -> &str {
+        &self.inviter.pairwise_info().pw_vk
+    }
+
+    pub fn service_endpoint(&self) -> &Url {
+        &self.service_endpoint
+    }
+
+    /// Processes a packed inbound message, returning the packed reply to be sent back
+    /// in the response if the sender asked for the return route.
+    pub async fn receive_message(&self, payload: Vec<u8>) -> MediatorResult<Option<Vec<u8>>> {
+        let unpacked = self.wallet.unpack_message(&payload).await?;
+        let unpacked: UnpackedMessage = serde_json::from_slice(&unpacked)?;
+
+        let message: Value = serde_json::from_str(&unpacked.message)?;
+        let return_route = requests_return_route(&message);
+        let message: AriesMessage = serde_json::from_value(message)?;
+        trace!(
+            "Mediator::receive_message >>> message: {:?}, recipient: {}, return_route: {}",
+            message,
+            unpacked.recipient_verkey,
+            return_route
+        );
+
+        match message {
+            AriesMessage::Routing(forward) => {
+                self.handle_forward(forward).await?;
+                Ok(None)
+            }
+            AriesMessage::Connection(Connection::Request(request)) => {
+                self.handle_connection_request(request, return_route).await
+            }
+            message => {
+                let sender_verkey = unpacked.sender_verkey.ok_or_else(|| {
+                    MediatorError::from_msg(
+                        MediatorErrorKind::InvalidMessage,
+                        "Messages to the mediator must be authcrypted",
+                    )
+                })?;
+                let connection = self
+                    .acknowledged_connection(&unpacked.recipient_verkey, &sender_verkey, &message)
+                    .await?;
+
+                match self
+                    .handle_connection_message(&unpacked.recipient_verkey, message)
+                    .await?
+                {
+                    Some(reply) => self.send_reply(&connection, reply, return_route).await,
+                    None => Ok(None),
+                }
+            }
+        }
+    }
+
+    async fn handle_connection_message(
+        &self,
+        account: &str,
+        message: AriesMessage,
+    ) -> MediatorResult<Option<AriesMessage>> {
+        let reply = match message {
+            AriesMessage::CoordinateMediation(CoordinateMediation::MediateRequest(request)) => {
+                self.handle_mediate_request(account, request).await?.into()
+            }
+            AriesMessage::CoordinateMediation(CoordinateMediation::KeylistUpdate(update)) => {
+                self.handle_keylist_update(account, update).await?.into()
+            }
+            AriesMessage::CoordinateMediation(CoordinateMediation::KeylistQuery(query)) => {
+                self.handle_keylist_query(account, query).await?.into()
+            }
+            AriesMessage::Pickup(Pickup::StatusRequest(request)) => {
+                self.handle_status_request(account, request).await?.into()
+            }
+            AriesMessage::Pickup(Pickup::DeliveryRequest(request)) => {
+                self.handle_delivery_request(account, request).await?
+            }
+            AriesMessage::Pickup(Pickup::MessagesReceived(received)) => {
+                self.handle_messages_received(account, received).await?.into()
+            }
+            AriesMessage::Pickup(Pickup::LiveDeliveryChange(change)) => self.handle_live_delivery_change(change).into(),
+            AriesMessage::TrustPing(TrustPing::Ping(ping)) if ping.content.response_requested => {
+                build_ping_response(&ping).into()
+            }
+            AriesMessage::TrustPing(_) | AriesMessage::ReportProblem(_) => return Ok(None),
+            message => {
+                return Err(MediatorError::from_msg(
+                    MediatorErrorKind::UnsupportedMessage,
+                    &format!("Mediator cannot process message {:?}", message),
+                ))
+            }
+        };
+        Ok(Some(reply))
+    }
+
+    async fn send_reply(
+        &self,
+        connection: &GenericConnection,
+        reply: AriesMessage,
+        return_route: bool,
+    ) -> MediatorResult<Option<Vec<u8>>> {
+        if return_route {
+            let transport = ReturnRouteTransport::default();
+            connection.send_message(&self.wallet, &reply, &transport).await?;
+            Ok(transport.into_reply())
+        } else {
+            connection.send_message(&self.wallet, &reply, &HttpClient).await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Checks the `~transport` decorator, which most of the typed messages do not keep.
+fn requests_return_route(message: &Value) -> bool {
+    matches!(
+        message["~transport"]["return_route"].as_str(),
+        Some("all") | Some("thread")
+    )
+}
+
+/// Returns the thread a reply to a message with the given ID and thread belongs to.
+fn reply_thread(id: &str, thread: Option<&Thread>) -> Thread {
+    Thread::new(thread.map(|t| t.thid.as_str()).unwrap_or(id).to_owned())
+}
+
+/// Converts a recipient key, possibly given as a `did:key`, to its naked form.
+fn naked_key(key: &str) -> MediatorResult<String> {
+    normalize_keys_as_naked(vec![key.to_owned()])?
+        .pop()
+        .ok_or_else(|| MediatorError::from_msg(MediatorErrorKind::InvalidMessage, "Missing recipient key"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_requests_return_route() {
+        assert!(requests_return_route(&json!({"~transport": {"return_route": "all"}})));
+        assert!(requests_return_route(
+            &json!({"~transport": {"return_route": "thread"}})
+        ));
+        assert!(!requests_return_route(&json!({"~transport": {"return_route": "none"}})));
+        assert!(!requests_return_route(&json!({"@id": "1234"})));
+    }
+
+    #[test]
+    fn test_naked_key() {
+        assert_eq!(
+            naked_key("did:key:z6Mkgh2ogU3JLPyNBBjBjSTvGZfDkLqByUSPZaBe6XCbQDQ6").unwrap(),
+            "3Emm6DnrzrUu4gtV3sW5RU7DvmZLZbC2sZGiGFEaUzci"
+        );
+        assert_eq!(
+            naked_key("3Emm6DnrzrUu4gtV3sW5RU7DvmZLZbC2sZGiGFEaUzci").unwrap(),
+            "3Emm6DnrzrUu4gtV3sW5RU7DvmZLZbC2sZGiGFEaUzci"
+        );
+    }
+}
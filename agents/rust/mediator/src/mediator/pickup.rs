@@ -0,0 +1,123 @@
+use aries_vcx::messages::{
+    decorators::{
+        attachment::{Attachment, AttachmentData, AttachmentType},
+        thread::Thread,
+    },
+    msg_fields::protocols::{
+        pickup::{
+            delivery::{Delivery, DeliveryContent, DeliveryDecorators},
+            delivery_request::DeliveryRequest,
+            live_delivery_change::LiveDeliveryChange,
+            messages_received::MessagesReceived,
+            status::{Status, StatusContent, StatusDecorators},
+            status_request::StatusRequest,
+        },
+        report_problem::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
+    },
+    AriesMessage,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{naked_key, reply_thread, Mediator};
+use crate::error::*;
+
+impl Mediator {
+    pub(super) async fn handle_status_request(&self, account: &str, request: StatusRequest) -> MediatorResult<Status> {
+        self.ensure_mediation_granted(account).await?;
+
+        let thread = reply_thread(&request.id, request.decorators.thread.as_ref());
+        self.build_status(account, request.content.recipient_key, thread).await
+    }
+
+    /// Delivers the oldest queued messages, or a `status` if there are none.
+    pub(super) async fn handle_delivery_request(
+        &self,
+        account: &str,
+        request: DeliveryRequest,
+    ) -> MediatorResult<AriesMessage> {
+        self.ensure_mediation_granted(account).await?;
+
+        let thread = reply_thread(&request.id, request.decorators.thread.as_ref());
+        let recipient_key = request.content.recipient_key.as_deref().map(naked_key).transpose()?;
+        let messages = self
+            .persistence
+            .retrieve_pending_messages(account, recipient_key.as_deref(), request.content.limit as usize)
+            .await?;
+
+        if messages.is_empty() {
+            let status = self
+                .build_status(account, request.content.recipient_key, thread)
+                .await?;
+            return Ok(status.into());
+        }
+
+        let attach = messages
+            .into_iter()
+            .map(|msg| {
+                let encoded = base64::encode_config(&msg.message, base64::URL_SAFE);
+                let mut attach = Attachment::new(AttachmentData::new(AttachmentType::Base64(encoded)));
+                attach.id = Some(msg.id);
+                attach
+            })
+            .collect();
+
+        let mut content = DeliveryContent::new(attach);
+        content.recipient_key = request.content.recipient_key;
+        let mut decorators = DeliveryDecorators::default();
+        decorators.thread = Some(thread);
+
+        Ok(Delivery::with_decorators(Uuid::new_v4().to_string(), content, decorators).into())
+    }
+
+    pub(super) async fn handle_messages_received(
+        &self,
+        account: &str,
+        received: MessagesReceived,
+    ) -> MediatorResult<Status> {
+        self.ensure_mediation_granted(account).await?;
+
+        self.persistence
+            .remove_messages(account, &received.content.message_id_list)
+            .await?;
+
+        let thread = reply_thread(&received.id, received.decorators.thread.as_ref());
+        self.build_status(account, None, thread).await
+    }
+
+    /// Messages are only delivered on request, so live mode is declined as the RFC asks.
+    pub(super) fn handle_live_delivery_change(&self, change: LiveDeliveryChange) -> ProblemReport {
+        let mut content = ProblemReportContent::new("e.m.live-mode-not-supported".to_owned());
+        content.fix_hint = Some("Messages have to be requested with delivery-request".to_owned());
+
+        let mut decorators = ProblemReportDecorators::default();
+        decorators.thread = Some(reply_thread(&change.id, change.decorators.thread.as_ref()));
+
+        ProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+    }
+
+    async fn build_status(
+        &self,
+        account: &str,
+        recipient_key: Option<String>,
+        thread: Thread,
+    ) -> MediatorResult<Status> {
+        let queue_key = recipient_key.as_deref().map(naked_key).transpose()?;
+        let queue_status = self.persistence.queue_status(account, queue_key.as_deref()).await?;
+
+        let mut content = StatusContent::new(queue_status.message_count);
+        content.recipient_key = recipient_key;
+        content.total_bytes = Some(queue_status.total_bytes);
+        content.oldest_received_time = queue_status.oldest_received_time;
+        content.newest_received_time = queue_status.newest_received_time;
+        content.longest_waited_seconds = queue_status
+            .oldest_received_time
+            .map(|time| (Utc::now() - time).num_seconds().max(0) as u64);
+        content.live_delivery = Some(false);
+
+        let mut decorators = StatusDecorators::default();
+        decorators.thread = Some(thread);
+
+        Ok(Status::with_decorators(Uuid::new_v4().to_string(), content, decorators))
+    }
+}
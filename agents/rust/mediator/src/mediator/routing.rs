@@ -0,0 +1,22 @@
+use aries_vcx::messages::msg_fields::protocols::routing::Forward;
+
+use super::{naked_key, Mediator};
+use crate::error::*;
+
+impl Mediator {
+    /// Queues the packed message of a `forward` for its recipient key.
+    pub(super) async fn handle_forward(&self, forward: Forward) -> MediatorResult<()> {
+        let recipient_key = naked_key(&forward.content.to)?;
+        let message = serde_json::to_vec(&forward.content.msg)?;
+
+        let message_id = self
+            .persistence
+            .persist_forward_message(&recipient_key, message)
+            .await?;
+        debug!(
+            "Mediator::handle_forward >>> queued message {} for recipient key {}",
+            message_id, recipient_key
+        );
+        Ok(())
+    }
+}
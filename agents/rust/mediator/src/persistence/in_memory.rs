@@ -0,0 +1,307 @@
+use std::collections::{BTreeSet, HashMap, VecDeque};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::error::*;
+
+use super::{MediatorPersistence, QueueStatus, QueuedMessage};
+
+#[derive(Default)]
+struct InMemoryState {
+    connections: HashMap<String, String>,
+    accounts: HashMap<String, BTreeSet<String>>,
+    recipients: HashMap<String, String>,
+    // Messages are numbered on arrival, so they can be delivered in order across queues.
+    queues: HashMap<String, VecDeque<(u64, QueuedMessage)>>,
+    sequence: u64,
+}
+
+impl InMemoryState {
+    fn account_keys(&self, account: &str) -> MediatorResult<&BTreeSet<String>> {
+        self.accounts.get(account).ok_or_else(|| {
+            MediatorError::from_msg(
+                MediatorErrorKind::MediationNotGranted,
+                &format!("No mediation account found for {}", account),
+            )
+        })
+    }
+
+    fn account_queues<'a>(
+        &'a self,
+        account: &str,
+        recipient_key: Option<&'a str>,
+    ) -> MediatorResult<impl Iterator<Item = &'a (u64, QueuedMessage)>> {
+        let keys: Vec<&String> = self
+            .account_keys(account)?
+            .iter()
+            .filter(|key| recipient_key.is_none() || recipient_key == Some(key.as_str()))
+            .collect();
+        Ok(keys
+            .into_iter()
+            .filter_map(|key| self.queues.get(key))
+            .flat_map(|queue| queue.iter()))
+    }
+}
+
+/// [`MediatorPersistence`] keeping everything in memory, lost when the mediator stops.
+///
+/// Suitable for tests and development setups.
+#[derive(Default)]
+pub struct InMemoryPersistence {
+    state: RwLock<InMemoryState>,
+}
+
+impl InMemoryPersistence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn _lock_read(&self) -> MediatorResult<RwLockReadGuard<InMemoryState>> {
+        self.state.read().map_err(|e| {
+            error!("Unable to read-lock mediator storage: {:?}", e);
+            MediatorError::from_msg(
+                MediatorErrorKind::LockError,
+                &format!("Unable to lock mediator storage: {:?}", e),
+            )
+        })
+    }
+
+    fn _lock_write(&self) -> MediatorResult<RwLockWriteGuard<InMemoryState>> {
+        self.state.write().map_err(|e| {
+            error!("Unable to write-lock mediator storage: {:?}", e);
+            MediatorError::from_msg(
+                MediatorErrorKind::LockError,
+                &format!("Unable to lock mediator storage: {:?}", e),
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl MediatorPersistence for InMemoryPersistence {
+    async fn save_connection(&self, pw_vk: &str, connection: String) -> MediatorResult<()> {
+        self._lock_write()?.connections.insert(pw_vk.to_owned(), connection);
+        Ok(())
+    }
+
+    async fn get_connection(&self, pw_vk: &str) -> MediatorResult<Option<String>> {
+        Ok(self._lock_read()?.connections.get(pw_vk).cloned())
+    }
+
+    async fn create_account(&self, account: &str) -> MediatorResult<()> {
+        self._lock_write()?.accounts.entry(account.to_owned()).or_default();
+        Ok(())
+    }
+
+    async fn has_account(&self, account: &str) -> MediatorResult<bool> {
+        Ok(self._lock_read()?.accounts.contains_key(account))
+    }
+
+    async fn add_recipient(&self, account: &str, recipient_key: &str) -> MediatorResult<bool> {
+        let mut state = self._lock_write()?;
+        state.account_keys(account)?;
+
+        if let Some(owner) = state.recipients.get(recipient_key) {
+            if owner != account {
+                return Err(MediatorError::from_msg(
+                    MediatorErrorKind::InvalidMessage,
+                    &format!("Recipient key {} is registered by another account", recipient_key),
+                ));
+            }
+            return Ok(false);
+        }
+
+        state.recipients.insert(recipient_key.to_owned(), account.to_owned());
+        state
+            .accounts
+            .entry(account.to_owned())
+            .or_default()
+            .insert(recipient_key.to_owned());
+        Ok(true)
+    }
+
+    async fn remove_recipient(&self, account: &str, recipient_key: &str) -> MediatorResult<bool> {
+        let mut state = self._lock_write()?;
+        state.account_keys(account)?;
+
+        if state.recipients.get(recipient_key).map(String::as_str) != Some(account) {
+            return Ok(false);
+        }
+
+        state.recipients.remove(recipient_key);
+        state.queues.remove(recipient_key);
+        if let Some(keys) = state.accounts.get_mut(account) {
+            keys.remove(recipient_key);
+        }
+        Ok(true)
+    }
+
+    async fn list_recipient_keys(&self, account: &str) -> MediatorResult<Vec<String>> {
+        let state = self._lock_read()?;
+        Ok(state.account_keys(account)?.iter().cloned().collect())
+    }
+
+    async fn get_account_for_recipient(&self, recipient_key: &str) -> MediatorResult<Option<String>> {
+        Ok(self._lock_read()?.recipients.get(recipient_key).cloned())
+    }
+
+    async fn persist_forward_message(&self, recipient_key: &str, message: Vec<u8>) -> MediatorResult<String> {
+        let mut state = self._lock_write()?;
+        if !state.recipients.contains_key(recipient_key) {
+            return Err(MediatorError::from_msg(
+                MediatorErrorKind::UnknownRecipient,
+                &format!("No mediation found for recipient key {}", recipient_key),
+            ));
+        }
+
+        let id = Uuid::new_v4().to_string();
+        let queued = QueuedMessage {
+            id: id.clone(),
+            recipient_key: recipient_key.to_owned(),
+            received_time: Utc::now(),
+            message,
+        };
+        state.sequence += 1;
+        let sequence = state.sequence;
+        state
+            .queues
+            .entry(recipient_key.to_owned())
+            .or_default()
+            .push_back((sequence, queued));
+        Ok(id)
+    }
+
+    async fn retrieve_pending_messages(
+        &self,
+        account: &str,
+        recipient_key: Option<&str>,
+        limit: usize,
+    ) -> MediatorResult<Vec<QueuedMessage>> {
+        let state = self._lock_read()?;
+        let mut messages: Vec<&(u64, QueuedMessage)> = state.account_queues(account, recipient_key)?.collect();
+        messages.sort_by_key(|(sequence, _)| *sequence);
+        Ok(messages.into_iter().take(limit).map(|(_, msg)| msg.clone()).collect())
+    }
+
+    async fn remove_messages(&self, account: &str, message_ids: &[String]) -> MediatorResult<()> {
+        let mut state = self._lock_write()?;
+        let keys: Vec<String> = state.account_keys(account)?.iter().cloned().collect();
+        for key in keys {
+            if let Some(queue) = state.queues.get_mut(&key) {
+                queue.retain(|(_, msg)| !message_ids.contains(&msg.id));
+            }
+        }
+        Ok(())
+    }
+
+    async fn queue_status(&self, account: &str, recipient_key: Option<&str>) -> MediatorResult<QueueStatus> {
+        let state = self._lock_read()?;
+        let status =
+            state
+                .account_queues(account, recipient_key)?
+                .fold(QueueStatus::default(), |mut status, (_, msg)| {
+                    status.message_count += 1;
+                    status.total_bytes += msg.message.len() as u64;
+                    status.oldest_received_time = Some(
+                        status
+                            .oldest_received_time
+                            .map_or(msg.received_time, |time| time.min(msg.received_time)),
+                    );
+                    status.newest_received_time = Some(
+                        status
+                            .newest_received_time
+                            .map_or(msg.received_time, |time| time.max(msg.received_time)),
+                    );
+                    status
+                });
+        Ok(status)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    const ACCOUNT: &str = "2ZHFFhzA2XtTD6hJqzL7ux";
+    const RECIPIENT_KEY: &str = "VsKV7grR1BUE29mG2Fm2kX";
+    const OTHER_RECIPIENT_KEY: &str = "Hezce2UWMZ3wUhVkh2LfKSs";
+
+    async fn setup() -> InMemoryPersistence {
+        let persistence = InMemoryPersistence::new();
+        persistence.create_account(ACCOUNT).await.unwrap();
+        persistence.add_recipient(ACCOUNT, RECIPIENT_KEY).await.unwrap();
+        persistence
+    }
+
+    #[tokio::test]
+    async fn test_add_recipient_requires_account() {
+        let persistence = InMemoryPersistence::new();
+        let err = persistence.add_recipient(ACCOUNT, RECIPIENT_KEY).await.unwrap_err();
+        assert_eq!(err.kind, MediatorErrorKind::MediationNotGranted);
+    }
+
+    #[tokio::test]
+    async fn test_add_and_remove_recipient() {
+        let persistence = setup().await;
+
+        assert!(!persistence.add_recipient(ACCOUNT, RECIPIENT_KEY).await.unwrap());
+        assert!(persistence.add_recipient(ACCOUNT, OTHER_RECIPIENT_KEY).await.unwrap());
+        assert_eq!(
+            persistence.list_recipient_keys(ACCOUNT).await.unwrap(),
+            vec![OTHER_RECIPIENT_KEY.to_owned(), RECIPIENT_KEY.to_owned()]
+        );
+
+        assert!(persistence.remove_recipient(ACCOUNT, RECIPIENT_KEY).await.unwrap());
+        assert!(!persistence.remove_recipient(ACCOUNT, RECIPIENT_KEY).await.unwrap());
+        assert_eq!(
+            persistence.get_account_for_recipient(RECIPIENT_KEY).await.unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forward_message_to_unknown_recipient_fails() {
+        let persistence = setup().await;
+        let err = persistence
+            .persist_forward_message(OTHER_RECIPIENT_KEY, b"msg".to_vec())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind, MediatorErrorKind::UnknownRecipient);
+    }
+
+    #[tokio::test]
+    async fn test_queue_retrieve_and_remove_messages() {
+        let persistence = setup().await;
+        persistence.add_recipient(ACCOUNT, OTHER_RECIPIENT_KEY).await.unwrap();
+
+        let first = persistence
+            .persist_forward_message(RECIPIENT_KEY, b"first".to_vec())
+            .await
+            .unwrap();
+        persistence
+            .persist_forward_message(OTHER_RECIPIENT_KEY, b"second".to_vec())
+            .await
+            .unwrap();
+
+        let status = persistence.queue_status(ACCOUNT, None).await.unwrap();
+        assert_eq!(status.message_count, 2);
+        assert_eq!(status.total_bytes, 11);
+
+        let status = persistence.queue_status(ACCOUNT, Some(RECIPIENT_KEY)).await.unwrap();
+        assert_eq!(status.message_count, 1);
+
+        let messages = persistence.retrieve_pending_messages(ACCOUNT, None, 1).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].id, first);
+        assert_eq!(messages[0].message, b"first".to_vec());
+
+        persistence.remove_messages(ACCOUNT, &[first]).await.unwrap();
+        let messages = persistence.retrieve_pending_messages(ACCOUNT, None, 10).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].recipient_key, OTHER_RECIPIENT_KEY);
+    }
+}
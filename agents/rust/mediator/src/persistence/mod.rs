@@ -0,0 +1,71 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::error::MediatorResult;
+
+pub mod in_memory;
+
+/// Message forwarded to one of the recipient keys of an account, waiting to be picked up.
+#[derive(Clone, Debug, PartialEq)]
+pub struct QueuedMessage {
+    pub id: String,
+    pub recipient_key: String,
+    pub received_time: DateTime<Utc>,
+    /// The packed message, as found in the `msg` field of the `forward` message.
+    pub message: Vec<u8>,
+}
+
+/// Summary of the messages queued for an account, as reported in pickup `status` messages.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct QueueStatus {
+    pub message_count: u32,
+    pub total_bytes: u64,
+    pub oldest_received_time: Option<DateTime<Utc>>,
+    pub newest_received_time: Option<DateTime<Utc>>,
+}
+
+/// Storage used by the mediator for connections, mediation accounts and message queues.
+///
+/// An account is created when mediation is granted over a connection and is identified by
+/// the verkey the mediator uses on that connection. Each account owns a set of recipient
+/// keys, and every recipient key has its own queue of forwarded messages.
+///
+/// Recipient keys are expected in their naked (base58) form.
+#[async_trait]
+pub trait MediatorPersistence: Send + Sync {
+    async fn save_connection(&self, pw_vk: &str, connection: String) -> MediatorResult<()>;
+
+    async fn get_connection(&self, pw_vk: &str) -> MediatorResult<Option<String>>;
+
+    async fn create_account(&self, account: &str) -> MediatorResult<()>;
+
+    async fn has_account(&self, account: &str) -> MediatorResult<bool>;
+
+    /// Registers a recipient key for the account, returning `false` if it was already registered.
+    async fn add_recipient(&self, account: &str, recipient_key: &str) -> MediatorResult<bool>;
+
+    /// Removes a recipient key of the account along with its queue, returning `false` if
+    /// the key was not registered.
+    async fn remove_recipient(&self, account: &str, recipient_key: &str) -> MediatorResult<bool>;
+
+    async fn list_recipient_keys(&self, account: &str) -> MediatorResult<Vec<String>>;
+
+    async fn get_account_for_recipient(&self, recipient_key: &str) -> MediatorResult<Option<String>>;
+
+    /// Queues a message for the recipient key, returning the identifier of the queued message.
+    async fn persist_forward_message(&self, recipient_key: &str, message: Vec<u8>) -> MediatorResult<String>;
+
+    /// Returns up to `limit` of the oldest messages queued for the account, optionally
+    /// restricted to a single recipient key.
+    async fn retrieve_pending_messages(
+        &self,
+        account: &str,
+        recipient_key: Option<&str>,
+        limit: usize,
+    ) -> MediatorResult<Vec<QueuedMessage>>;
+
+    /// Removes messages from the queues of the account; unknown identifiers are ignored.
+    async fn remove_messages(&self, account: &str, message_ids: &[String]) -> MediatorResult<()>;
+
+    async fn queue_status(&self, account: &str, recipient_key: Option<&str>) -> MediatorResult<QueueStatus>;
+}
@@ -0,0 +1,37 @@
+use std::sync::{Mutex, PoisonError};
+
+use aries_vcx::{agency_client::httpclient::post_message, errors::error::VcxResult, transport::Transport};
+use async_trait::async_trait;
+use url::Url;
+
+/// Sends messages to the service endpoint of the counterparty.
+pub struct HttpClient;
+
+#[async_trait]
+impl Transport for HttpClient {
+    async fn send_message(&self, msg: Vec<u8>, service_endpoint: Url) -> VcxResult<()> {
+        post_message(msg, service_endpoint).await?;
+        Ok(())
+    }
+}
+
+/// Keeps the packed message instead of sending it, so that it can be returned in the
+/// response to the inbound request, as asked by the `~transport` decorator.
+#[derive(Default)]
+pub struct ReturnRouteTransport {
+    reply: Mutex<Option<Vec<u8>>>,
+}
+
+impl ReturnRouteTransport {
+    pub fn into_reply(self) -> Option<Vec<u8>> {
+        self.reply.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl Transport for ReturnRouteTransport {
+    async fn send_message(&self, msg: Vec<u8>, _service_endpoint: Url) -> VcxResult<()> {
+        *self.reply.lock().unwrap_or_else(PoisonError::into_inner) = Some(msg);
+        Ok(())
+    }
+}
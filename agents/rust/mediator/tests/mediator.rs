@@ -0,0 +1,311 @@
+#![allow(clippy::unwrap_used)]
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use aries_vcx::{
+    common::signing::decode_signed_connection_response,
+    errors::error::VcxResult,
+    global::settings::{DEFAULT_WALLET_KEY, WALLET_KDF_RAW},
+    handlers::{coordinate_mediation::MediationClient, pickup::PickupClient},
+    messages::{
+        msg_fields::protocols::{
+            connection::{
+                request::{Request, RequestContent, RequestDecorators},
+                Connection, ConnectionData,
+            },
+            coordinate_mediation::{keylist_update_response::KeylistUpdateResult, CoordinateMediation},
+            pickup::Pickup,
+            trust_ping::TrustPing,
+        },
+        AriesMessage,
+    },
+    protocols::{
+        connection::pairwise_info::PairwiseInfo, pickup::build_status_request, trustping::build_ping, SendClosure,
+    },
+    utils::encryption_envelope::EncryptionEnvelope,
+};
+use aries_vcx_core::{
+    indy::wallet::{close_wallet, create_and_open_wallet, delete_wallet, WalletConfig},
+    wallet::{base_wallet::BaseWallet, indy_wallet::IndySdkWallet},
+    WalletHandle,
+};
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use mediator::{mediator::Mediator, persistence::in_memory::InMemoryPersistence, MediatorErrorKind, MediatorResult};
+use serde_json::json;
+use uuid::Uuid;
+
+struct TestWallet {
+    config: WalletConfig,
+    handle: WalletHandle,
+    wallet: Arc<dyn BaseWallet>,
+}
+
+impl TestWallet {
+    async fn create() -> Self {
+        let config = WalletConfig {
+            wallet_name: format!("mediator_test_wallet_{}", Uuid::new_v4()),
+            wallet_key: DEFAULT_WALLET_KEY.to_owned(),
+            wallet_key_derivation: WALLET_KDF_RAW.to_owned(),
+            wallet_type: None,
+            storage_config: None,
+            storage_credentials: None,
+            rekey: None,
+            rekey_derivation_method: None,
+        };
+        let handle = create_and_open_wallet(&config).await.unwrap();
+        let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(handle));
+
+        Self { config, handle, wallet }
+    }
+
+    async fn delete(self) {
+        close_wallet(self.handle).await.unwrap();
+        delete_wallet(&self.config).await.unwrap();
+    }
+}
+
+/// Authcrypts a message for the mediator, asking for the reply on the return route.
+async fn pack(wallet: &Arc<dyn BaseWallet>, sender_vk: &str, recipient_vk: &str, message: AriesMessage) -> Vec<u8> {
+    let mut message = serde_json::to_value(message).unwrap();
+    message["~transport"] = json!({ "return_route": "all" });
+
+    wallet
+        .pack_message(
+            Some(sender_vk),
+            &json!([recipient_vk]).to_string(),
+            message.to_string().as_bytes(),
+        )
+        .await
+        .unwrap()
+}
+
+/// Client connected to the mediator, receiving the replies of the mediator on the return route.
+struct Client {
+    wallet: Arc<dyn BaseWallet>,
+    mediator: Arc<Mediator>,
+    pairwise_info: PairwiseInfo,
+    mediator_vk: String,
+    replies: Arc<Mutex<VecDeque<Vec<u8>>>>,
+}
+
+impl Client {
+    async fn connect(mediator: &Arc<Mediator>, wallet: &Arc<dyn BaseWallet>) -> Self {
+        let pairwise_info = PairwiseInfo::create(wallet).await.unwrap();
+
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.set_id(pairwise_info.pw_did.clone());
+        did_doc.set_service_endpoint("http://localhost:8006".parse().unwrap());
+        did_doc.set_recipient_keys(vec![pairwise_info.pw_vk.clone()]);
+        let content = RequestContent::new(
+            "client".to_owned(),
+            ConnectionData::new(pairwise_info.pw_did.clone(), did_doc),
+        );
+        let request = Request::with_decorators(Uuid::new_v4().to_string(), content, RequestDecorators::default());
+
+        let packed = pack(wallet, &pairwise_info.pw_vk, mediator.routing_key(), request.into()).await;
+        let reply = mediator.receive_message(packed).await.unwrap().unwrap();
+        let (reply, _) = EncryptionEnvelope::anon_unpack(wallet, reply).await.unwrap();
+        let AriesMessage::Connection(Connection::Response(response)) = reply else {
+            panic!("Expected a connection response, got {:?}", reply);
+        };
+        let connection_data = decode_signed_connection_response(wallet, response.content, mediator.routing_key())
+            .await
+            .unwrap();
+
+        Self {
+            wallet: wallet.clone(),
+            mediator: mediator.clone(),
+            pairwise_info,
+            mediator_vk: connection_data.did_doc.recipient_keys().unwrap().remove(0),
+            replies: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    async fn send(&self, message: AriesMessage) -> MediatorResult<Option<Vec<u8>>> {
+        let packed = pack(&self.wallet, &self.pairwise_info.pw_vk, &self.mediator_vk, message).await;
+        self.mediator.receive_message(packed).await
+    }
+
+    /// Closure used by the aries_vcx clients, queueing the replies of the mediator.
+    fn send_closure(&self) -> SendClosure {
+        let wallet = self.wallet.clone();
+        let mediator = self.mediator.clone();
+        let sender_vk = self.pairwise_info.pw_vk.clone();
+        let mediator_vk = self.mediator_vk.clone();
+        let replies = self.replies.clone();
+
+        Box::new(move |message: AriesMessage| {
+            Box::pin(async move {
+                let packed = pack(&wallet, &sender_vk, &mediator_vk, message).await;
+                if let Some(reply) = mediator.receive_message(packed).await.unwrap() {
+                    replies.lock().unwrap().push_back(reply);
+                }
+                VcxResult::Ok(())
+            })
+        })
+    }
+
+    async fn next_reply(&self) -> AriesMessage {
+        let reply = self
+            .replies
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("No reply from the mediator");
+        EncryptionEnvelope::anon_unpack(&self.wallet, reply).await.unwrap().0
+    }
+
+    async fn request_mediation(&self) -> MediationClient {
+        let mediation = MediationClient::build()
+            .send_mediation_request(self.send_closure())
+            .await
+            .unwrap();
+        let AriesMessage::CoordinateMediation(CoordinateMediation::MediateGrant(grant)) = self.next_reply().await
+        else {
+            panic!("Expected a mediate-grant");
+        };
+        mediation.handle_mediation_grant(grant).unwrap()
+    }
+
+    async fn add_key(&self, mediation: MediationClient, recipient_key: &str) -> MediationClient {
+        mediation.add_key(recipient_key, self.send_closure()).await.unwrap();
+        let AriesMessage::CoordinateMediation(CoordinateMediation::KeylistUpdateResponse(response)) =
+            self.next_reply().await
+        else {
+            panic!("Expected a keylist-update-response");
+        };
+        assert_eq!(response.content.updated[0].result, KeylistUpdateResult::Success);
+        mediation.handle_keylist_update_response(response).unwrap()
+    }
+}
+
+async fn create_mediator(wallet: &TestWallet) -> Arc<Mediator> {
+    let mediator = Mediator::new(
+        wallet.wallet.clone(),
+        Arc::new(InMemoryPersistence::new()),
+        "http://localhost:8005".parse().unwrap(),
+    )
+    .await
+    .unwrap();
+    Arc::new(mediator)
+}
+
+#[tokio::test]
+async fn test_forwarded_message_is_picked_up() {
+    let mediator_wallet = TestWallet::create().await;
+    let client_wallet = TestWallet::create().await;
+    let sender_wallet = TestWallet::create().await;
+
+    let mediator = create_mediator(&mediator_wallet).await;
+    let client = Client::connect(&mediator, &client_wallet.wallet).await;
+
+    let mediation = client.request_mediation().await;
+    let (endpoint, routing_keys) = mediation.get_routing_info().unwrap();
+    assert_eq!(&endpoint, mediator.service_endpoint());
+    assert_eq!(routing_keys, vec![mediator.routing_key().to_owned()]);
+
+    let recipient = PairwiseInfo::create(&client_wallet.wallet).await.unwrap();
+    let mediation = client.add_key(mediation, &recipient.pw_vk).await;
+    assert_eq!(mediation.get_keys().unwrap(), vec![recipient.pw_vk.clone()]);
+
+    let did_doc = mediation.create_did_doc(&recipient).unwrap();
+    let ping: AriesMessage = build_ping(false, None).into();
+    let envelope = EncryptionEnvelope::create(&sender_wallet.wallet, &ping, None, &did_doc)
+        .await
+        .unwrap();
+    assert!(mediator.receive_message(envelope.0).await.unwrap().is_none());
+
+    let mut pickup = PickupClient::build();
+    pickup
+        .send_status_request(Some(recipient.pw_vk.clone()), client.send_closure())
+        .await
+        .unwrap();
+    let AriesMessage::Pickup(Pickup::Status(status)) = client.next_reply().await else {
+        panic!("Expected a status");
+    };
+    pickup.handle_status(status).unwrap();
+    assert_eq!(pickup.get_status().unwrap().message_count, 1);
+
+    pickup
+        .send_delivery_request(10, None, client.send_closure())
+        .await
+        .unwrap();
+    let AriesMessage::Pickup(Pickup::Delivery(delivery)) = client.next_reply().await else {
+        panic!("Expected a delivery");
+    };
+    let messages = pickup.handle_delivery(&client_wallet.wallet, delivery).await.unwrap();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].recipient_key, recipient.pw_vk);
+    assert!(matches!(
+        messages[0].message,
+        AriesMessage::TrustPing(TrustPing::Ping(_))
+    ));
+
+    pickup
+        .send_messages_received(vec![messages[0].id.clone()], client.send_closure())
+        .await
+        .unwrap();
+    let AriesMessage::Pickup(Pickup::Status(status)) = client.next_reply().await else {
+        panic!("Expected a status");
+    };
+    assert_eq!(status.content.message_count, 0);
+
+    mediator_wallet.delete().await;
+    client_wallet.delete().await;
+    sender_wallet.delete().await;
+}
+
+#[tokio::test]
+async fn test_pickup_without_mediation_fails() {
+    let mediator_wallet = TestWallet::create().await;
+    let client_wallet = TestWallet::create().await;
+
+    let mediator = create_mediator(&mediator_wallet).await;
+    let client = Client::connect(&mediator, &client_wallet.wallet).await;
+
+    let err = client.send(build_status_request(None).into()).await.unwrap_err();
+    assert_eq!(err.kind, MediatorErrorKind::MediationNotGranted);
+
+    mediator_wallet.delete().await;
+    client_wallet.delete().await;
+}
+
+#[tokio::test]
+async fn test_forward_to_removed_key_fails() {
+    let mediator_wallet = TestWallet::create().await;
+    let client_wallet = TestWallet::create().await;
+    let sender_wallet = TestWallet::create().await;
+
+    let mediator = create_mediator(&mediator_wallet).await;
+    let client = Client::connect(&mediator, &client_wallet.wallet).await;
+    let mediation = client.request_mediation().await;
+
+    let recipient = PairwiseInfo::create(&client_wallet.wallet).await.unwrap();
+    let mediation = client.add_key(mediation, &recipient.pw_vk).await;
+    let did_doc = mediation.create_did_doc(&recipient).unwrap();
+
+    mediation
+        .remove_key(&recipient.pw_vk, client.send_closure())
+        .await
+        .unwrap();
+    let AriesMessage::CoordinateMediation(CoordinateMediation::KeylistUpdateResponse(response)) =
+        client.next_reply().await
+    else {
+        panic!("Expected a keylist-update-response");
+    };
+    assert_eq!(response.content.updated[0].result, KeylistUpdateResult::Success);
+
+    let ping: AriesMessage = build_ping(false, None).into();
+    let envelope = EncryptionEnvelope::create(&sender_wallet.wallet, &ping, None, &did_doc)
+        .await
+        .unwrap();
+    let err = mediator.receive_message(envelope.0).await.unwrap_err();
+    assert_eq!(err.kind, MediatorErrorKind::UnknownRecipient);
+
+    mediator_wallet.delete().await;
+    client_wallet.delete().await;
+    sender_wallet.delete().await;
+}
@@ -8,6 +8,7 @@ use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 use super::primitives::revocation_registry_delta::RevocationRegistryDelta;
 
 pub mod encoding;
+pub mod w3c;
 
 #[derive(Serialize, Deserialize)]
 struct ProverCredential {
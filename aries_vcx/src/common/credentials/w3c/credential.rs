@@ -0,0 +1,154 @@
+use chrono::{DateTime, SubsecRound, Utc};
+use serde_json::{Map, Value};
+
+use super::{one_or_many, proof::Proof, W3C_CREDENTIALS_CONTEXT};
+
+pub const VERIFIABLE_CREDENTIAL_TYPE: &str = "VerifiableCredential";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableCredential {
+    #[serde(rename = "@context", with = "one_or_many")]
+    pub context: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", with = "one_or_many")]
+    pub credential_type: Vec<String>,
+    pub issuer: Issuer,
+    pub issuance_date: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiration_date: Option<DateTime<Utc>>,
+    #[serde(with = "one_or_many")]
+    pub credential_subject: Vec<CredentialSubject>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<CredentialStatus>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "one_or_many")]
+    pub proof: Vec<Proof>,
+    /// Properties not modelled above, such as `credentialSchema` or `evidence`.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl VerifiableCredential {
+    /// Creates an unsigned credential issued now, using the base W3C credentials context.
+    pub fn new(issuer: Issuer, credential_subject: CredentialSubject) -> Self {
+        Self {
+            context: vec![Value::from(W3C_CREDENTIALS_CONTEXT)],
+            id: None,
+            credential_type: vec![VERIFIABLE_CREDENTIAL_TYPE.to_owned()],
+            issuer,
+            issuance_date: Utc::now().trunc_subsecs(0),
+            expiration_date: None,
+            credential_subject: vec![credential_subject],
+            credential_status: None,
+            proof: Vec::new(),
+            extra: Map::new(),
+        }
+    }
+
+    pub fn is_expired(&self) -> bool {
+        matches!(self.expiration_date, Some(expiration) if expiration < Utc::now())
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum Issuer {
+    Id(String),
+    Object {
+        id: String,
+        #[serde(flatten)]
+        extra: Map<String, Value>,
+    },
+}
+
+impl Issuer {
+    pub fn id(&self) -> &str {
+        match self {
+            Self::Id(id) | Self::Object { id, .. } => id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct CredentialSubject {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(flatten)]
+    pub claims: Map<String, Value>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct CredentialStatus {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub status_type: String,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_credential_serde() {
+        let json = json!({
+            "@context": [
+                "https://www.w3.org/2018/credentials/v1",
+                "https://www.w3.org/2018/credentials/examples/v1"
+            ],
+            "id": "http://example.edu/credentials/1872",
+            "type": ["VerifiableCredential", "AlumniCredential"],
+            "issuer": {
+                "id": "did:example:76e12ec712ebc6f1c221ebfeb1f",
+                "name": "Example University"
+            },
+            "issuanceDate": "2010-01-01T19:23:24Z",
+            "credentialSubject": {
+                "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+                "alumniOf": "Example University"
+            },
+            "credentialSchema": {
+                "id": "https://example.org/examples/degree.json",
+                "type": "JsonSchemaValidator2018"
+            }
+        });
+
+        let credential: VerifiableCredential = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(credential.issuer.id(), "did:example:76e12ec712ebc6f1c221ebfeb1f");
+        assert_eq!(credential.credential_subject.len(), 1);
+        assert_eq!(
+            credential.credential_subject[0].claims["alumniOf"],
+            json!("Example University")
+        );
+        assert!(credential.extra.contains_key("credentialSchema"));
+        assert!(credential.proof.is_empty());
+        assert!(!credential.is_expired());
+
+        assert_eq!(serde_json::to_value(&credential).unwrap(), json);
+    }
+
+    #[test]
+    fn test_credential_accepts_single_context_and_type() {
+        let json = json!({
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": "VerifiableCredential",
+            "issuer": "did:example:76e12ec712ebc6f1c221ebfeb1f",
+            "issuanceDate": "2010-01-01T19:23:24Z",
+            "expirationDate": "2020-01-01T19:23:24Z",
+            "credentialSubject": [{ "name": "Alice" }, { "name": "Bob" }]
+        });
+
+        let credential: VerifiableCredential = serde_json::from_value(json).unwrap();
+        assert_eq!(credential.context, vec![json!(W3C_CREDENTIALS_CONTEXT)]);
+        assert_eq!(credential.credential_type, vec![VERIFIABLE_CREDENTIAL_TYPE.to_owned()]);
+        assert_eq!(
+            credential.issuer,
+            Issuer::Id("did:example:76e12ec712ebc6f1c221ebfeb1f".to_owned())
+        );
+        assert_eq!(credential.credential_subject.len(), 2);
+        assert!(credential.is_expired());
+    }
+}
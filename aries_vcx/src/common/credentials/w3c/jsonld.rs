@@ -0,0 +1,1039 @@
+//! Conversion of JSON-LD documents to RDF datasets, following the expansion and RDF
+//! serialization algorithms of [JSON-LD 1.1](https://www.w3.org/TR/json-ld11-api/), as needed to
+//! canonicalize the documents secured by linked data signatures.
+//!
+//! Documents are never dereferenced over the network: only the contexts bundled with this module
+//! can be loaded. Properties and types which the contexts of a document leave undefined are
+//! rejected instead of being dropped, so that no claim can escape the signature, and the JSON-LD
+//! features W3C credentials do not use (such as index maps or reverse properties) are rejected
+//! as unsupported.
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+};
+
+use serde_json::{Map, Value};
+
+use super::{
+    suites::canonicalize,
+    urdna2015::{Quad, Term, RDF_LANG_STRING, XSD_STRING},
+    W3C_CREDENTIALS_CONTEXT,
+};
+use crate::errors::error::prelude::*;
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+
+const KEYWORDS: [&str; 23] = [
+    "@base",
+    "@container",
+    "@context",
+    "@direction",
+    "@graph",
+    "@id",
+    "@import",
+    "@included",
+    "@index",
+    "@json",
+    "@language",
+    "@list",
+    "@nest",
+    "@none",
+    "@prefix",
+    "@propagate",
+    "@protected",
+    "@reverse",
+    "@set",
+    "@type",
+    "@value",
+    "@version",
+    "@vocab",
+];
+
+fn load_context(url: &str) -> VcxResult<Value> {
+    let document = match url {
+        W3C_CREDENTIALS_CONTEXT => include_str!("contexts/credentials_v1.jsonld"),
+        "https://w3id.org/security/suites/ed25519-2020/v1" => include_str!("contexts/ed25519_2020_v1.jsonld"),
+        "https://w3id.org/security/suites/jws-2020/v1" => include_str!("contexts/jws_2020_v1.jsonld"),
+        "https://w3id.org/security/data-integrity/v2" => include_str!("contexts/data_integrity_v2.jsonld"),
+        _ => return Err(invalid(format!("Context {url} is not available"))),
+    };
+    let mut document: Value = serde_json::from_str(document)?;
+    Ok(document["@context"].take())
+}
+
+fn invalid(msg: impl Into<String>) -> AriesVcxError {
+    AriesVcxError::from_msg(AriesVcxErrorKind::InvalidJson, msg.into())
+}
+
+fn is_keyword(value: &str) -> bool {
+    KEYWORDS.contains(&value)
+}
+
+// Reserved for future keywords, and ignored
+fn looks_like_keyword(value: &str) -> bool {
+    value.len() > 1 && value.starts_with('@') && value[1..].chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn is_blank_node(value: &str) -> bool {
+    value.starts_with("_:")
+}
+
+fn is_absolute_iri(value: &str) -> bool {
+    match value.split_once(':') {
+        Some((scheme, rest)) => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+                && !rest.contains(char::is_whitespace)
+        }
+        None => false,
+    }
+}
+
+fn as_array(value: &Value) -> &[Value] {
+    match value {
+        Value::Array(values) => values,
+        value => std::slice::from_ref(value),
+    }
+}
+
+fn into_array(value: Value) -> Vec<Value> {
+    match value {
+        Value::Null => Vec::new(),
+        Value::Array(values) => values,
+        value => vec![value],
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Context {
+    terms: HashMap<String, TermDefinition>,
+    vocab: Option<String>,
+    language: Option<String>,
+    /// Context to revert to in nested nodes, when the context was scoped to a type.
+    previous: Option<Box<Context>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TermDefinition {
+    iri: Option<String>,
+    prefix: bool,
+    protected: bool,
+    type_mapping: Option<String>,
+    container: BTreeSet<String>,
+    /// Language of the term, `Some(None)` when the default language is cleared for it.
+    language: Option<Option<String>>,
+    /// Context scoped to the values of the term, or to the nodes of the type it defines.
+    context: Option<Value>,
+}
+
+impl TermDefinition {
+    fn has_container(&self, container: &str) -> bool {
+        self.container.contains(container)
+    }
+}
+
+/// Local context whose terms are being defined.
+struct TermDefiner<'a> {
+    local: &'a Map<String, Value>,
+    defined: HashMap<String, bool>,
+    protected: bool,
+    override_protected: bool,
+}
+
+impl Context {
+    fn process(&self, local: &Value, remote: &[String], override_protected: bool, propagate: bool) -> VcxResult<Self> {
+        let mut result = self.clone();
+        let propagate = local.get("@propagate").and_then(Value::as_bool).unwrap_or(propagate);
+        if !propagate && result.previous.is_none() {
+            result.previous = Some(Box::new(self.clone()));
+        }
+
+        for context in as_array(local) {
+            match context {
+                Value::Null => {
+                    if !override_protected && result.terms.values().any(|term| term.protected) {
+                        return Err(invalid("Contexts with protected terms cannot be cleared"));
+                    }
+                    let previous = (!propagate).then(|| Box::new(result));
+                    result = Self {
+                        previous,
+                        ..Self::default()
+                    };
+                }
+                Value::String(url) => {
+                    if remote.contains(url) {
+                        return Err(invalid(format!("Context {url} includes itself")));
+                    }
+                    let mut remote = remote.to_vec();
+                    remote.push(url.clone());
+                    result = result.process(&load_context(url)?, &remote, false, true)?;
+                }
+                Value::Object(definitions) => result.define_terms(definitions, override_protected)?,
+                _ => return Err(invalid("Context is neither an IRI nor a map")),
+            }
+        }
+        Ok(result)
+    }
+
+    fn define_terms(&mut self, definitions: &Map<String, Value>, override_protected: bool) -> VcxResult<()> {
+        for keyword in ["@base", "@direction", "@import"] {
+            if definitions.contains_key(keyword) {
+                return Err(invalid(format!("Contexts with {keyword} are not supported")));
+            }
+        }
+        if let Some(version) = definitions.get("@version") {
+            if version.as_f64() != Some(1.1) {
+                return Err(invalid(format!("Unsupported JSON-LD version {version}")));
+            }
+        }
+        match definitions.get("@vocab") {
+            Some(Value::Null) => self.vocab = None,
+            Some(Value::String(vocab)) => match self.expand_iri(vocab, true) {
+                Some(vocab) if is_absolute_iri(&vocab) || is_blank_node(&vocab) => self.vocab = Some(vocab),
+                _ => return Err(invalid(format!("Invalid vocabulary mapping {vocab}"))),
+            },
+            Some(_) => return Err(invalid("Invalid vocabulary mapping")),
+            None => {}
+        }
+        match definitions.get("@language") {
+            Some(Value::Null) => self.language = None,
+            Some(Value::String(language)) => self.language = Some(language.to_lowercase()),
+            Some(_) => return Err(invalid("Invalid default language")),
+            None => {}
+        }
+        if definitions
+            .get("@propagate")
+            .map_or(false, |propagate| !propagate.is_boolean())
+        {
+            return Err(invalid("Invalid @propagate value"));
+        }
+
+        let mut definer = TermDefiner {
+            local: definitions,
+            defined: HashMap::new(),
+            protected: match definitions.get("@protected") {
+                Some(Value::Bool(protected)) => *protected,
+                Some(_) => return Err(invalid("Invalid @protected value")),
+                None => false,
+            },
+            override_protected,
+        };
+        for term in definitions.keys() {
+            if !["@language", "@propagate", "@protected", "@version", "@vocab"].contains(&term.as_str()) {
+                self.define_term(&mut definer, term)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn define_term(&mut self, definer: &mut TermDefiner, term: &str) -> VcxResult<()> {
+        match definer.defined.get(term) {
+            Some(true) => return Ok(()),
+            Some(false) => return Err(invalid(format!("Term {term} is defined cyclically"))),
+            None => {}
+        }
+        if term.is_empty() {
+            return Err(invalid("Empty terms cannot be defined"));
+        }
+        definer.defined.insert(term.to_owned(), false);
+
+        let value = &definer.local[term];
+        if term == "@type" {
+            // Only the container of @type can be set, which does not affect expansion
+            let container_only = value.as_object().map_or(false, |value| {
+                value.get("@container").map_or(true, |container| container == "@set")
+                    && value.keys().all(|key| key == "@container" || key == "@protected")
+            });
+            if !container_only {
+                return Err(invalid("Keyword @type cannot be redefined"));
+            }
+            definer.defined.insert(term.to_owned(), true);
+            return Ok(());
+        }
+        if is_keyword(term) {
+            return Err(invalid(format!("Keyword {term} cannot be redefined")));
+        }
+        if looks_like_keyword(term) {
+            definer.defined.insert(term.to_owned(), true);
+            return Ok(());
+        }
+
+        let previous = self.terms.remove(term);
+        let (value, simple_term) = match value {
+            Value::Null => (Map::from_iter([("@id".to_owned(), Value::Null)]), false),
+            Value::String(iri) => (Map::from_iter([("@id".to_owned(), Value::from(iri.as_str()))]), true),
+            Value::Object(value) => (value.clone(), false),
+            _ => return Err(invalid(format!("Invalid definition of term {term}"))),
+        };
+
+        let mut definition = TermDefinition {
+            protected: match value.get("@protected") {
+                Some(Value::Bool(protected)) => *protected,
+                Some(_) => return Err(invalid(format!("Invalid @protected value of term {term}"))),
+                None => definer.protected,
+            },
+            ..TermDefinition::default()
+        };
+
+        if let Some(type_mapping) = value.get("@type") {
+            let type_mapping = type_mapping
+                .as_str()
+                .ok_or_else(|| invalid(format!("Invalid type mapping of term {term}")))?;
+            match self.define_and_expand_iri(definer, type_mapping)? {
+                Some(type_mapping)
+                    if ["@id", "@json", "@none", "@vocab"].contains(&type_mapping.as_str())
+                        || is_absolute_iri(&type_mapping) =>
+                {
+                    definition.type_mapping = Some(type_mapping)
+                }
+                _ => return Err(invalid(format!("Invalid type mapping of term {term}"))),
+            }
+        }
+
+        for unsupported in ["@reverse", "@index", "@direction", "@nest"] {
+            if value.contains_key(unsupported) {
+                return Err(invalid(format!("Terms with {unsupported} are not supported")));
+            }
+        }
+
+        let inner_colon = term
+            .char_indices()
+            .any(|(i, c)| c == ':' && i > 0 && i + 1 < term.len());
+        match value.get("@id") {
+            Some(id) if id.as_str() != Some(term) => match id {
+                Value::Null => definition.iri = None,
+                Value::String(id) if !is_keyword(id) && looks_like_keyword(id) => {
+                    definer.defined.insert(term.to_owned(), true);
+                    return Ok(());
+                }
+                Value::String(id) => {
+                    let iri = match self.define_and_expand_iri(definer, id)? {
+                        Some(iri) if is_keyword(&iri) || is_absolute_iri(&iri) || is_blank_node(&iri) => iri,
+                        _ => return Err(invalid(format!("Invalid IRI mapping of term {term}"))),
+                    };
+                    if iri == "@context" {
+                        return Err(invalid(format!("Term {term} cannot alias @context")));
+                    }
+                    if inner_colon || term.contains('/') {
+                        definer.defined.insert(term.to_owned(), true);
+                        if self.define_and_expand_iri(definer, term)?.as_deref() != Some(iri.as_str()) {
+                            return Err(invalid(format!("Term {term} does not expand to its IRI mapping")));
+                        }
+                    }
+                    if !term.contains(':')
+                        && !term.contains('/')
+                        && simple_term
+                        && (iri.ends_with([':', '/', '?', '#', '[', ']', '@']) || is_blank_node(&iri))
+                    {
+                        definition.prefix = true;
+                    }
+                    definition.iri = Some(iri);
+                }
+                _ => return Err(invalid(format!("Invalid IRI mapping of term {term}"))),
+            },
+            _ if term.chars().skip(1).any(|c| c == ':') => {
+                if let Some((prefix, suffix)) = term.split_once(':') {
+                    if definer.local.contains_key(prefix) {
+                        self.define_term(definer, prefix)?;
+                    }
+                    definition.iri = match self.terms.get(prefix).and_then(|prefix| prefix.iri.as_ref()) {
+                        Some(prefix) => Some(format!("{prefix}{suffix}")),
+                        None => Some(term.to_owned()),
+                    };
+                }
+            }
+            _ => match &self.vocab {
+                Some(vocab) if !term.contains('/') => definition.iri = Some(format!("{vocab}{term}")),
+                _ => return Err(invalid(format!("Term {term} has no IRI mapping"))),
+            },
+        }
+
+        if let Some(container) = value.get("@container") {
+            let container = as_array(container)
+                .iter()
+                .map(|container| container.as_str().map(str::to_owned))
+                .collect::<Option<BTreeSet<String>>>()
+                .unwrap_or_default();
+            let supported = [&["@list"][..], &["@set"], &["@graph"], &["@graph", "@set"]]
+                .iter()
+                .any(|supported| container.iter().eq(supported.iter()));
+            if !supported {
+                return Err(invalid(format!("Container of term {term} is not supported")));
+            }
+            definition.container = container;
+        }
+
+        definition.context = value.get("@context").cloned();
+
+        if !value.contains_key("@type") {
+            definition.language = match value.get("@language") {
+                Some(Value::Null) => Some(None),
+                Some(Value::String(language)) => Some(Some(language.to_lowercase())),
+                Some(_) => return Err(invalid(format!("Invalid language of term {term}"))),
+                None => None,
+            };
+        }
+
+        if let Some(prefix) = value.get("@prefix") {
+            match prefix {
+                Value::Bool(prefix) if !term.contains(':') && !term.contains('/') => {
+                    if *prefix && definition.iri.as_deref().map_or(false, is_keyword) {
+                        return Err(invalid(format!("Keyword alias {term} cannot be a prefix")));
+                    }
+                    definition.prefix = *prefix;
+                }
+                _ => return Err(invalid(format!("Invalid @prefix value of term {term}"))),
+            }
+        }
+
+        let allowed = [
+            "@id",
+            "@container",
+            "@context",
+            "@language",
+            "@prefix",
+            "@protected",
+            "@type",
+        ];
+        if let Some(key) = value.keys().find(|key| !allowed.contains(&key.as_str())) {
+            return Err(invalid(format!("Invalid entry {key} in the definition of term {term}")));
+        }
+
+        if let Some(previous) = previous.filter(|previous| previous.protected && !definer.override_protected) {
+            if (TermDefinition {
+                protected: true,
+                ..definition
+            }) != previous
+            {
+                return Err(invalid(format!("Protected term {term} cannot be redefined")));
+            }
+            definition = previous;
+        }
+        self.terms.insert(term.to_owned(), definition);
+        definer.defined.insert(term.to_owned(), true);
+        Ok(())
+    }
+
+    /// Expands the IRI within a context being processed, defining the terms it depends on first.
+    fn define_and_expand_iri(&mut self, definer: &mut TermDefiner, value: &str) -> VcxResult<Option<String>> {
+        if is_keyword(value) {
+            return Ok(Some(value.to_owned()));
+        }
+        if definer.local.contains_key(value) {
+            self.define_term(definer, value)?;
+        }
+        if let Some((prefix, suffix)) = value.split_once(':') {
+            if prefix != "_" && !suffix.starts_with("//") && definer.local.contains_key(prefix) {
+                self.define_term(definer, prefix)?;
+            }
+        }
+        Ok(self.expand_iri(value, true))
+    }
+
+    /// Expands a term, compact IRI or keyword alias to an IRI. Relative IRIs are kept as is, as
+    /// documents have no base IRI to resolve them against. Returns `None` for ignored values.
+    fn expand_iri(&self, value: &str, vocab: bool) -> Option<String> {
+        if is_keyword(value) {
+            return Some(value.to_owned());
+        }
+        if looks_like_keyword(value) {
+            return None;
+        }
+
+        if let Some(definition) = self.terms.get(value) {
+            if definition.iri.as_deref().map_or(false, is_keyword) || vocab {
+                return definition.iri.clone();
+            }
+        }
+
+        if let Some((prefix, suffix)) = value.split_once(':').filter(|(prefix, _)| !prefix.is_empty()) {
+            if prefix == "_" || suffix.starts_with("//") {
+                return Some(value.to_owned());
+            }
+            if let Some(definition) = self.terms.get(prefix).filter(|definition| definition.prefix) {
+                if let Some(iri) = &definition.iri {
+                    return Some(format!("{iri}{suffix}"));
+                }
+            }
+            if is_absolute_iri(value) {
+                return Some(value.to_owned());
+            }
+        }
+
+        match &self.vocab {
+            Some(vocab_mapping) if vocab => Some(format!("{vocab_mapping}{value}")),
+            _ => Some(value.to_owned()),
+        }
+    }
+
+    fn expand_key(&self, key: &str) -> Option<String> {
+        self.expand_iri(key, true)
+    }
+}
+
+/// Expands the element, returning `Value::Null` when it does not contribute to the document.
+fn expand(context: &Context, active_property: Option<&str>, element: &Value) -> VcxResult<Value> {
+    let definition = active_property.and_then(|property| context.terms.get(property));
+    match element {
+        Value::Null => Ok(Value::Null),
+        Value::Array(items) => {
+            let mut expanded = Vec::new();
+            for item in items {
+                match expand(context, active_property, item)? {
+                    Value::Array(item) if definition.map_or(false, |definition| definition.has_container("@list")) => {
+                        expanded.push(json!({ "@list": item }))
+                    }
+                    Value::Array(item) => expanded.extend(item),
+                    Value::Null => {}
+                    item => expanded.push(item),
+                }
+            }
+            Ok(Value::Array(expanded))
+        }
+        Value::Object(element) => expand_object(context, active_property, element),
+        _ if matches!(active_property, None | Some("@graph")) => Ok(Value::Null),
+        scalar => {
+            let scoped_context;
+            let context = match definition.and_then(|definition| definition.context.as_ref()) {
+                Some(local) => {
+                    scoped_context = context.process(local, &[], true, true)?;
+                    &scoped_context
+                }
+                None => context,
+            };
+            Ok(expand_value(context, active_property, scalar))
+        }
+    }
+}
+
+fn expand_value(context: &Context, active_property: Option<&str>, value: &Value) -> Value {
+    let definition = active_property.and_then(|property| context.terms.get(property));
+    let type_mapping = definition.and_then(|definition| definition.type_mapping.as_deref());
+    match (type_mapping, value) {
+        (Some("@id"), Value::String(id)) => json!({ "@id": context.expand_iri(id, false) }),
+        (Some("@vocab"), Value::String(id)) => json!({ "@id": context.expand_iri(id, true) }),
+        _ => {
+            let mut result = json!({ "@value": value });
+            match type_mapping {
+                Some(type_mapping) if !["@id", "@vocab", "@none"].contains(&type_mapping) => {
+                    result["@type"] = Value::from(type_mapping);
+                }
+                _ if value.is_string() => {
+                    let language = match definition.and_then(|definition| definition.language.as_ref()) {
+                        Some(language) => language.as_ref(),
+                        None => context.language.as_ref(),
+                    };
+                    if let Some(language) = language {
+                        result["@language"] = Value::from(language.as_str());
+                    }
+                }
+                _ => {}
+            }
+            result
+        }
+    }
+}
+
+fn expand_object(context: &Context, active_property: Option<&str>, element: &Map<String, Value>) -> VcxResult<Value> {
+    let mut active = Cow::Borrowed(context);
+
+    // Contexts scoped to a type do not apply to the nodes nested in the typed node
+    if let Some(previous) = &context.previous {
+        let keys: Vec<Option<String>> = element.keys().map(|key| context.expand_key(key)).collect();
+        let is_value = keys.iter().any(|key| key.as_deref() == Some("@value"));
+        let is_reference = keys.len() == 1 && keys[0].as_deref() == Some("@id");
+        if !is_value && !is_reference {
+            active = Cow::Owned((**previous).clone());
+        }
+    }
+    let property_context = active_property
+        .and_then(|property| context.terms.get(property))
+        .and_then(|definition| definition.context.as_ref());
+    if let Some(local) = property_context {
+        active = Cow::Owned(active.process(local, &[], true, true)?);
+    }
+    if let Some(local) = element.get("@context") {
+        active = Cow::Owned(active.process(local, &[], false, true)?);
+    }
+
+    let type_scoped = active.clone().into_owned();
+    let type_keys: Vec<&String> = element
+        .keys()
+        .filter(|key| active.expand_key(key).as_deref() == Some("@type"))
+        .collect();
+    for key in &type_keys {
+        let mut types: Vec<&str> = as_array(&element[key.as_str()])
+            .iter()
+            .filter_map(Value::as_str)
+            .collect();
+        types.sort_unstable();
+        for type_name in types {
+            if let Some(local) = type_scoped.terms.get(type_name).and_then(|term| term.context.as_ref()) {
+                active = Cow::Owned(active.process(local, &[], false, false)?);
+            }
+        }
+    }
+    let input_type = type_keys
+        .first()
+        .and_then(|key| as_array(&element[key.as_str()]).last())
+        .and_then(Value::as_str)
+        .and_then(|type_name| type_scoped.expand_iri(type_name, true));
+
+    let mut result = Map::new();
+    for (key, value) in element {
+        if key == "@context" {
+            continue;
+        }
+        let property = match active.expand_key(key) {
+            Some(property) if is_keyword(&property) || property.contains(':') => property,
+            _ => {
+                return Err(invalid(format!(
+                    "Term {key} is not defined by the context of the document"
+                )))
+            }
+        };
+
+        if is_keyword(&property) {
+            if result.contains_key(&property) && property != "@type" {
+                return Err(invalid(format!("Document has colliding {property} entries")));
+            }
+            let expanded = match property.as_str() {
+                "@id" => match value {
+                    Value::String(id) => Value::from(active.expand_iri(id, false)),
+                    _ => return Err(invalid("Invalid @id value")),
+                },
+                "@type" => {
+                    let types = match value {
+                        Value::String(_) | Value::Array(_) => as_array(value),
+                        _ => return Err(invalid("Invalid @type value")),
+                    };
+                    let mut expanded = Vec::new();
+                    for type_name in types {
+                        let type_name = type_name.as_str().ok_or_else(|| invalid("Invalid @type value"))?;
+                        expanded.extend(type_scoped.expand_iri(type_name, true).map(Value::from));
+                    }
+                    match result.remove("@type") {
+                        Some(previous) => Value::Array(into_array(previous).into_iter().chain(expanded).collect()),
+                        None if value.is_string() => expanded.pop().unwrap_or_default(),
+                        None => Value::Array(expanded),
+                    }
+                }
+                "@graph" => Value::Array(into_array(expand(&active, Some("@graph"), value)?)),
+                "@value" => match value {
+                    _ if input_type.as_deref() == Some("@json") => value.clone(),
+                    Value::Array(_) | Value::Object(_) => return Err(invalid("Invalid @value value")),
+                    Value::Null => {
+                        result.insert(property, Value::Null);
+                        continue;
+                    }
+                    value => value.clone(),
+                },
+                "@language" => match value {
+                    Value::String(language) => Value::from(language.to_lowercase()),
+                    _ => return Err(invalid("Invalid @language value")),
+                },
+                "@index" => match value {
+                    Value::String(_) => value.clone(),
+                    _ => return Err(invalid("Invalid @index value")),
+                },
+                "@list" if matches!(active_property, None | Some("@graph")) => continue,
+                "@list" => Value::Array(into_array(expand(&active, active_property, value)?)),
+                "@set" => expand(&active, active_property, value)?,
+                _ => return Err(invalid(format!("Keyword {property} is not supported"))),
+            };
+            if !expanded.is_null() {
+                result.insert(property, expanded);
+            }
+            continue;
+        }
+
+        let definition = active.terms.get(key.as_str());
+        let has_container = |container| definition.map_or(false, |definition| definition.has_container(container));
+        let mut expanded = match definition.and_then(|definition| definition.type_mapping.as_deref()) {
+            Some("@json") => json!({ "@value": value, "@type": "@json" }),
+            _ => expand(&active, Some(key), value)?,
+        };
+        if expanded.is_null() {
+            continue;
+        }
+        if has_container("@list") && expanded.get("@list").is_none() {
+            expanded = json!({ "@list": into_array(expanded) });
+        }
+        if has_container("@graph") {
+            expanded = into_array(expanded)
+                .into_iter()
+                .map(|value| json!({ "@graph": into_array(value) }))
+                .collect();
+        }
+        if let Value::Array(values) = result.entry(property).or_insert_with(|| Value::Array(Vec::new())) {
+            values.extend(into_array(expanded));
+        }
+    }
+
+    if let Some(value) = result.get("@value") {
+        let allowed = ["@index", "@language", "@type", "@value"];
+        if result.keys().any(|key| !allowed.contains(&key.as_str())) {
+            return Err(invalid("Value objects cannot have properties"));
+        }
+        let datatype = result.get("@type");
+        if datatype.is_some() && result.contains_key("@language") {
+            return Err(invalid("Value objects cannot have both a type and a language"));
+        }
+        if datatype.map_or(false, |datatype| datatype == "@json") {
+            return Ok(Value::Object(result));
+        }
+        if value.is_null() {
+            return Ok(Value::Null);
+        }
+        if !value.is_string() && result.contains_key("@language") {
+            return Err(invalid("Only strings can have a language"));
+        }
+        if datatype.map_or(false, |datatype| !datatype.as_str().map_or(false, is_absolute_iri)) {
+            return Err(invalid("Value objects must be typed with an IRI"));
+        }
+    } else if let Some(types) = result.get_mut("@type") {
+        if !types.is_array() {
+            *types = Value::Array(vec![types.take()]);
+        }
+    } else if result.contains_key("@set") || result.contains_key("@list") {
+        if result.len() > 2 || (result.len() == 2 && !result.contains_key("@index")) {
+            return Err(invalid("Sets and lists cannot have properties"));
+        }
+        if let Some(set) = result.remove("@set") {
+            return Ok(set);
+        }
+    }
+
+    if result.len() == 1 && result.contains_key("@language") {
+        return Ok(Value::Null);
+    }
+    // Values and references outside of any node do not contribute to the document
+    if matches!(active_property, None | Some("@graph"))
+        && (result.is_empty()
+            || result.contains_key("@value")
+            || result.contains_key("@list")
+            || (result.len() == 1 && result.contains_key("@id")))
+    {
+        return Ok(Value::Null);
+    }
+    Ok(Value::Object(result))
+}
+
+/// Converts the JSON-LD document to the quads of its RDF dataset.
+pub(super) fn to_rdf(document: &Value) -> VcxResult<Vec<Quad>> {
+    let mut expanded = expand(&Context::default(), None, document)?;
+    if let Some(graph) = expanded
+        .as_object_mut()
+        .filter(|node| node.len() == 1)
+        .and_then(|node| node.remove("@graph"))
+    {
+        expanded = graph;
+    }
+
+    let mut serializer = RdfSerializer::default();
+    for node in into_array(expanded) {
+        serializer.node(&node, None)?;
+    }
+
+    let mut quads = Vec::new();
+    for quad in serializer.quads {
+        if !quads.contains(&quad) {
+            quads.push(quad);
+        }
+    }
+    Ok(quads)
+}
+
+#[derive(Default)]
+struct RdfSerializer {
+    quads: Vec<Quad>,
+    blank_nodes: HashMap<String, Term>,
+    blank_node_count: usize,
+}
+
+impl RdfSerializer {
+    // Blank nodes of the document are relabelled, so as not to collide with generated ones
+    fn blank_node(&mut self, label: Option<&str>) -> Term {
+        if let Some(blank_node) = label.and_then(|label| self.blank_nodes.get(label)) {
+            return blank_node.clone();
+        }
+        let blank_node = Term::BlankNode(format!("_:b{}", self.blank_node_count));
+        self.blank_node_count += 1;
+        if let Some(label) = label {
+            self.blank_nodes.insert(label.to_owned(), blank_node.clone());
+        }
+        blank_node
+    }
+
+    fn identifier(&mut self, id: &str) -> VcxResult<Term> {
+        if is_blank_node(id) {
+            Ok(self.blank_node(Some(id)))
+        } else if is_absolute_iri(id) {
+            Ok(Term::Iri(id.to_owned()))
+        } else {
+            Err(invalid(format!("Relative IRI {id} cannot be converted to RDF")))
+        }
+    }
+
+    fn push(&mut self, subject: &Term, predicate: &str, object: Term, graph: Option<&Term>) {
+        self.quads.push(Quad {
+            subject: subject.clone(),
+            predicate: Term::Iri(predicate.to_owned()),
+            object,
+            graph: graph.cloned(),
+        });
+    }
+
+    /// Adds the statements of the node object to the graph, returning its subject.
+    fn node(&mut self, node: &Value, graph: Option<&Term>) -> VcxResult<Term> {
+        let node = node.as_object().ok_or_else(|| invalid("Invalid node object"))?;
+        let subject = match node.get("@id").and_then(Value::as_str) {
+            Some(id) => self.identifier(id)?,
+            None => self.blank_node(None),
+        };
+
+        for (property, values) in node {
+            match property.as_str() {
+                "@id" | "@index" => {}
+                "@type" => {
+                    for type_name in as_array(values) {
+                        let type_name = self.identifier(type_name.as_str().unwrap_or_default())?;
+                        self.push(&subject, RDF_TYPE, type_name, graph);
+                    }
+                }
+                "@graph" => {
+                    for node in as_array(values) {
+                        self.node(node, Some(&subject))?;
+                    }
+                }
+                property if is_absolute_iri(property) && !is_blank_node(property) => {
+                    for value in as_array(values) {
+                        let object = self.object(value, graph)?;
+                        self.push(&subject, property, object, graph);
+                    }
+                }
+                property => return Err(invalid(format!("Property {property} cannot be converted to RDF"))),
+            }
+        }
+        Ok(subject)
+    }
+
+    fn object(&mut self, value: &Value, graph: Option<&Term>) -> VcxResult<Term> {
+        if value.get("@value").is_some() {
+            literal(value)
+        } else if let Some(items) = value.get("@list") {
+            self.list(as_array(items), graph)
+        } else {
+            self.node(value, graph)
+        }
+    }
+
+    fn list(&mut self, items: &[Value], graph: Option<&Term>) -> VcxResult<Term> {
+        let mut head = Term::Iri(RDF_NIL.to_owned());
+        for item in items.iter().rev() {
+            let node = self.blank_node(None);
+            let first = self.object(item, graph)?;
+            self.push(&node, RDF_FIRST, first, graph);
+            self.push(&node, RDF_REST, head, graph);
+            head = node;
+        }
+        Ok(head)
+    }
+}
+
+fn literal(value: &Value) -> VcxResult<Term> {
+    let datatype = value.get("@type").and_then(Value::as_str);
+    let language = value.get("@language").and_then(Value::as_str);
+    let literal = |value: String, default_datatype: &str| {
+        let datatype = datatype.unwrap_or(default_datatype).to_owned();
+        if !is_absolute_iri(&datatype) {
+            return Err(invalid(format!("Datatype {datatype} is not an IRI")));
+        }
+        Ok(Term::Literal {
+            value,
+            datatype,
+            language: None,
+        })
+    };
+
+    if datatype == Some("@json") {
+        return Ok(Term::Literal {
+            value: canonicalize(&value["@value"]),
+            datatype: RDF_JSON.to_owned(),
+            language: None,
+        });
+    }
+    match &value["@value"] {
+        Value::Bool(value) => literal(value.to_string(), XSD_BOOLEAN),
+        Value::Number(number) => {
+            // Adding zero formats -0 as 0
+            let number = number.as_f64().unwrap_or_default() + 0.0;
+            if number.fract() != 0.0 || number.abs() >= 1e21 || datatype == Some(XSD_DOUBLE) {
+                literal(canonical_double(number), XSD_DOUBLE)
+            } else {
+                literal(format!("{number:.0}"), XSD_INTEGER)
+            }
+        }
+        Value::String(value) => match language {
+            Some(language) => Ok(Term::Literal {
+                value: value.clone(),
+                datatype: RDF_LANG_STRING.to_owned(),
+                language: Some(language.to_owned()),
+            }),
+            None => literal(value.clone(), XSD_STRING),
+        },
+        _ => Err(invalid("Invalid @value value")),
+    }
+}
+
+/// Formats the number in the canonical lexical form of `xsd:double`, e.g. `1.5E-7`.
+fn canonical_double(number: f64) -> String {
+    let scientific = format!("{number:.15e}");
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let mantissa = mantissa.trim_end_matches('0');
+    let mantissa = mantissa
+        .strip_suffix('.')
+        .map_or(mantissa.to_owned(), |integer| format!("{integer}.0"));
+    format!("{mantissa}E{exponent}")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+    use crate::common::credentials::w3c::urdna2015;
+
+    fn to_nquads(document: Value) -> String {
+        urdna2015::canonicalize(&to_rdf(&document).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_credential_to_rdf() {
+        let credential = json!({
+            "@context": [
+                "https://www.w3.org/2018/credentials/v1",
+                "https://w3id.org/security/suites/ed25519-2020/v1",
+                { "name": "https://schema.org/name", "age": "https://schema.org/age" }
+            ],
+            "id": "urn:uuid:3978344f-8596-4c3a-a978-8fcaba3903c5",
+            "type": ["VerifiableCredential"],
+            "issuer": "did:example:issuer",
+            "issuanceDate": "2023-06-01T00:00:00Z",
+            "credentialSubject": { "id": "did:example:holder", "name": "Alice \"A\"", "age": 30 },
+            "proof": {
+                "type": "Ed25519Signature2020",
+                "created": "2023-06-01T00:00:00Z",
+                "verificationMethod": "did:example:issuer#key-1",
+                "proofPurpose": "assertionMethod",
+                "proofValue": "z3FXQ"
+            }
+        });
+
+        assert_eq!(
+            to_nquads(credential),
+            "<did:example:holder> <https://schema.org/age> \"30\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n\
+             <did:example:holder> <https://schema.org/name> \"Alice \\\"A\\\"\" .\n\
+             <urn:uuid:3978344f-8596-4c3a-a978-8fcaba3903c5> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://www.w3.org/2018/credentials#VerifiableCredential> .\n\
+             <urn:uuid:3978344f-8596-4c3a-a978-8fcaba3903c5> <https://w3id.org/security#proof> _:c14n1 .\n\
+             <urn:uuid:3978344f-8596-4c3a-a978-8fcaba3903c5> <https://www.w3.org/2018/credentials#credentialSubject> <did:example:holder> .\n\
+             <urn:uuid:3978344f-8596-4c3a-a978-8fcaba3903c5> <https://www.w3.org/2018/credentials#issuanceDate> \"2023-06-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> .\n\
+             <urn:uuid:3978344f-8596-4c3a-a978-8fcaba3903c5> <https://www.w3.org/2018/credentials#issuer> <did:example:issuer> .\n\
+             _:c14n0 <http://purl.org/dc/terms/created> \"2023-06-01T00:00:00Z\"^^<http://www.w3.org/2001/XMLSchema#dateTime> _:c14n1 .\n\
+             _:c14n0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <https://w3id.org/security#Ed25519Signature2020> _:c14n1 .\n\
+             _:c14n0 <https://w3id.org/security#proofPurpose> <https://w3id.org/security#assertionMethod> _:c14n1 .\n\
+             _:c14n0 <https://w3id.org/security#proofValue> \"z3FXQ\"^^<https://w3id.org/security#multibase> _:c14n1 .\n\
+             _:c14n0 <https://w3id.org/security#verificationMethod> <did:example:issuer#key-1> _:c14n1 .\n"
+        );
+    }
+
+    #[test]
+    fn test_to_rdf_literals_and_lists() {
+        let document = json!({
+            "@context": {
+                "@vocab": "https://example.com/#",
+                "@language": "en",
+                "tags": { "@container": "@list" },
+                "data": { "@type": "@json" },
+                "code": { "@language": null }
+            },
+            "@id": "https://example.com/node",
+            "ratio": 1.5,
+            "big": 1e21,
+            "flag": true,
+            "label": "Label",
+            "code": "x1",
+            "tags": ["a", "b"],
+            "data": { "b": [1.0, null], "a": "\u{20ac}" }
+        });
+
+        assert_eq!(
+            to_nquads(document),
+            "<https://example.com/node> <https://example.com/#big> \"1.0E21\"^^<http://www.w3.org/2001/XMLSchema#double> .\n\
+             <https://example.com/node> <https://example.com/#code> \"x1\" .\n\
+             <https://example.com/node> <https://example.com/#data> \"{\\\"a\\\":\\\"€\\\",\\\"b\\\":[1,null]}\"^^<http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON> .\n\
+             <https://example.com/node> <https://example.com/#flag> \"true\"^^<http://www.w3.org/2001/XMLSchema#boolean> .\n\
+             <https://example.com/node> <https://example.com/#label> \"Label\"@en .\n\
+             <https://example.com/node> <https://example.com/#ratio> \"1.5E0\"^^<http://www.w3.org/2001/XMLSchema#double> .\n\
+             <https://example.com/node> <https://example.com/#tags> _:c14n1 .\n\
+             _:c14n0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"b\"@en .\n\
+             _:c14n0 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .\n\
+             _:c14n1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> \"a\"@en .\n\
+             _:c14n1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:c14n0 .\n"
+        );
+    }
+
+    #[test]
+    fn test_canonical_double() {
+        assert_eq!(canonical_double(1.0), "1.0E0");
+        assert_eq!(canonical_double(-0.000015), "-1.5E-5");
+        assert_eq!(canonical_double(1.2345e300), "1.2345E300");
+    }
+
+    #[test]
+    fn test_to_rdf_rejects_undefined_terms() {
+        let credential = json!({
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": "VerifiableCredential",
+            "credentialSubject": { "id": "did:example:holder", "name": "Alice" }
+        });
+        assert_eq!(to_rdf(&credential).unwrap_err().kind(), AriesVcxErrorKind::InvalidJson);
+    }
+
+    #[test]
+    fn test_to_rdf_rejects_protected_term_redefinition() {
+        let credential = json!({
+            "@context": [
+                "https://www.w3.org/2018/credentials/v1",
+                { "VerifiableCredential": "https://example.com/#Credential" }
+            ],
+            "type": "VerifiableCredential"
+        });
+        assert_eq!(to_rdf(&credential).unwrap_err().kind(), AriesVcxErrorKind::InvalidJson);
+
+        let credential = json!({
+            "@context": [
+                "https://www.w3.org/2018/credentials/v1",
+                { "proof": { "@id": "https://w3id.org/security#proof", "@type": "@id", "@container": "@graph" } }
+            ],
+            "type": "VerifiableCredential"
+        });
+        assert!(to_rdf(&credential).is_ok());
+    }
+
+    #[test]
+    fn test_to_rdf_rejects_unknown_contexts() {
+        let credential = json!({
+            "@context": ["https://www.w3.org/2018/credentials/v1", "https://example.com/context.jsonld"],
+            "type": "VerifiableCredential"
+        });
+        assert_eq!(to_rdf(&credential).unwrap_err().kind(), AriesVcxErrorKind::InvalidJson);
+    }
+}
@@ -0,0 +1,21 @@
+//! Verifiable credentials and presentations of the
+//! [W3C VC Data Model 1.1](https://www.w3.org/TR/vc-data-model/), secured with linked data
+//! proofs instead of AnonCreds signatures.
+//!
+//! `Ed25519Signature2020` and `JsonWebSignature2020` proofs, over documents canonicalized with
+//! URDNA2015, and data integrity proofs of the `eddsa-jcs-2022` cryptosuite are created with keys
+//! held by the [`BaseWallet`](aries_vcx_core::wallet::base_wallet::BaseWallet) and verified
+//! against the verification methods of DIDs resolved through a
+//! [`ResolverRegistry`](did_resolver_registry::ResolverRegistry).
+
+pub mod credential;
+mod jsonld;
+mod one_or_many;
+pub mod presentation;
+pub mod proof;
+pub mod storage;
+pub mod suites;
+mod urdna2015;
+mod verification_method;
+
+pub const W3C_CREDENTIALS_CONTEXT: &str = "https://www.w3.org/2018/credentials/v1";
@@ -0,0 +1,39 @@
+use serde_json::{Map, Value};
+
+use super::{credential::VerifiableCredential, one_or_many, proof::Proof, W3C_CREDENTIALS_CONTEXT};
+
+pub const VERIFIABLE_PRESENTATION_TYPE: &str = "VerifiablePresentation";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiablePresentation {
+    #[serde(rename = "@context", with = "one_or_many")]
+    pub context: Vec<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", with = "one_or_many")]
+    pub presentation_type: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub holder: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "one_or_many")]
+    pub verifiable_credential: Vec<VerifiableCredential>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "one_or_many")]
+    pub proof: Vec<Proof>,
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl VerifiablePresentation {
+    /// Creates an unsigned presentation of the credentials, using the base W3C credentials context.
+    pub fn new(holder: Option<String>, verifiable_credential: Vec<VerifiableCredential>) -> Self {
+        Self {
+            context: vec![Value::from(W3C_CREDENTIALS_CONTEXT)],
+            id: None,
+            presentation_type: vec![VERIFIABLE_PRESENTATION_TYPE.to_owned()],
+            holder,
+            verifiable_credential,
+            proof: Vec::new(),
+            extra: Map::new(),
+        }
+    }
+}
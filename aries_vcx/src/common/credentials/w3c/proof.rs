@@ -0,0 +1,99 @@
+use chrono::{DateTime, SubsecRound, Utc};
+
+/// Types of the proofs securing documents, along with the parameters specific to each type.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum ProofType {
+    /// Linked data signature over the document canonicalized with URDNA2015, encoded as a
+    /// base58btc multibase `proofValue`.
+    Ed25519Signature2020,
+    /// Linked data signature over the document canonicalized with URDNA2015, encoded as a
+    /// compact JWS with a detached, unencoded payload.
+    JsonWebSignature2020,
+    /// Data integrity proof, its [`Cryptosuite`] telling how the document was canonicalized
+    /// and signed.
+    DataIntegrityProof { cryptosuite: Cryptosuite },
+}
+
+impl ProofType {
+    /// JSON-LD context defining the terms of the proof, which secured documents must include.
+    pub fn context(&self) -> &'static str {
+        match self {
+            Self::Ed25519Signature2020 => "https://w3id.org/security/suites/ed25519-2020/v1",
+            Self::JsonWebSignature2020 => "https://w3id.org/security/suites/jws-2020/v1",
+            Self::DataIntegrityProof { cryptosuite } => cryptosuite.context(),
+        }
+    }
+}
+
+impl From<Cryptosuite> for ProofType {
+    fn from(cryptosuite: Cryptosuite) -> Self {
+        Self::DataIntegrityProof { cryptosuite }
+    }
+}
+
+/// Cryptosuites of the [W3C Data Integrity](https://www.w3.org/TR/vc-data-integrity/) proofs.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Cryptosuite {
+    /// EdDSA signature over the document canonicalized with the JSON Canonicalization Scheme
+    /// ([eddsa-jcs-2022](https://www.w3.org/TR/vc-di-eddsa/#eddsa-jcs-2022)), encoded as a
+    /// base58btc multibase `proofValue`.
+    #[serde(rename = "eddsa-jcs-2022")]
+    EddsaJcs2022,
+}
+
+impl Cryptosuite {
+    /// JSON-LD context defining the terms of the proof, which secured documents must include.
+    pub fn context(&self) -> &'static str {
+        match self {
+            Self::EddsaJcs2022 => "https://w3id.org/security/data-integrity/v2",
+        }
+    }
+}
+
+/// Verification relationship the verification method of a proof must be listed under.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProofPurpose {
+    /// Used by issuers to assert credentials.
+    AssertionMethod,
+    /// Used by holders to authenticate as the presenter of a presentation.
+    Authentication,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Proof {
+    #[serde(flatten)]
+    pub proof_type: ProofType,
+    pub created: DateTime<Utc>,
+    /// DID URL of the verification method the proof can be verified with.
+    pub verification_method: String,
+    pub proof_purpose: ProofPurpose,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jws: Option<String>,
+}
+
+impl Proof {
+    /// Creates the options of a proof, to be completed with the signature by
+    /// [`sign_credential`](super::suites::sign_credential) or
+    /// [`sign_presentation`](super::suites::sign_presentation).
+    pub fn new(proof_type: impl Into<ProofType>, verification_method: String, proof_purpose: ProofPurpose) -> Self {
+        Self {
+            proof_type: proof_type.into(),
+            created: Utc::now().trunc_subsecs(0),
+            verification_method,
+            proof_purpose,
+            challenge: None,
+            domain: None,
+            proof_value: None,
+            jws: None,
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::{collections::HashMap, sync::Arc};
+
+use aries_vcx_core::wallet::base_wallet::{AsyncFnIteratorCollect, BaseWallet};
+
+use super::credential::VerifiableCredential;
+use crate::errors::error::prelude::*;
+
+pub const CATEGORY_W3C_CREDENTIAL: &str = "VerifiableCredential";
+
+const RECORD_OPTIONS: &str = r#"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"#;
+
+/// A record as returned by [`BaseWallet::iterate_wallet_records`].
+#[derive(Debug, Deserialize)]
+struct CredentialRecord {
+    id: String,
+    value: String,
+}
+
+/// Stores a credential received by the holder, tagged with its issuer.
+pub async fn store_credential(
+    wallet: &Arc<dyn BaseWallet>,
+    credential_id: &str,
+    credential: &VerifiableCredential,
+) -> VcxResult<()> {
+    let tags = HashMap::from([("issuer".to_owned(), credential.issuer.id().to_owned())]);
+    wallet
+        .add_wallet_record(
+            CATEGORY_W3C_CREDENTIAL,
+            credential_id,
+            &serde_json::to_string(credential)?,
+            Some(tags),
+        )
+        .await?;
+    Ok(())
+}
+
+pub async fn get_credential(wallet: &Arc<dyn BaseWallet>, credential_id: &str) -> VcxResult<VerifiableCredential> {
+    let credential = wallet
+        .get_wallet_record_value(CATEGORY_W3C_CREDENTIAL, credential_id)
+        .await?;
+    Ok(serde_json::from_str(&credential)?)
+}
+
+/// Returns the stored credentials along with their identifiers.
+pub async fn get_credentials(wallet: &Arc<dyn BaseWallet>) -> VcxResult<Vec<(String, VerifiableCredential)>> {
+    let records = wallet
+        .iterate_wallet_records(CATEGORY_W3C_CREDENTIAL, "{}", RECORD_OPTIONS)
+        .await?
+        .collect()
+        .await?;
+
+    records
+        .iter()
+        .map(|record| {
+            let record: CredentialRecord = serde_json::from_str(record)?;
+            Ok((record.id, serde_json::from_str(&record.value)?))
+        })
+        .collect()
+}
+
+pub async fn delete_credential(wallet: &Arc<dyn BaseWallet>, credential_id: &str) -> VcxResult<()> {
+    wallet
+        .delete_wallet_record(CATEGORY_W3C_CREDENTIAL, credential_id)
+        .await?;
+    Ok(())
+}
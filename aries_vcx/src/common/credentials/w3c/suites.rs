@@ -0,0 +1,633 @@
+//! Signer and verifier of the `Ed25519Signature2020` and `JsonWebSignature2020` linked data
+//! signature suites, and of the `eddsa-jcs-2022` data integrity cryptosuite.
+//!
+//! The signed data is the SHA-256 hash of the proof options (with the `@context` of the
+//! document) followed by the SHA-256 hash of the document without its proofs. Linked data
+//! signatures canonicalize both as RDF datasets with URDNA2015, while `eddsa-jcs-2022`
+//! canonicalizes them with the JSON Canonicalization Scheme ([RFC 8785](https://www.rfc-editor.org/rfc/rfc8785)).
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use did_resolver_registry::ResolverRegistry;
+use openssl::sha::sha256;
+use serde_json::{Number, Value};
+
+use super::{
+    credential::VerifiableCredential,
+    jsonld,
+    presentation::VerifiablePresentation,
+    proof::{Cryptosuite, Proof, ProofPurpose, ProofType},
+    urdna2015,
+    verification_method::resolve_verification_key,
+};
+use crate::errors::error::prelude::*;
+
+/// Adds a proof created with `signing_key`, the wallet key of `proof.verification_method`,
+/// to the credential.
+pub async fn sign_credential(
+    wallet: &Arc<dyn BaseWallet>,
+    credential: &mut VerifiableCredential,
+    proof: Proof,
+    signing_key: &str,
+) -> VcxResult<()> {
+    add_suite_context(&mut credential.context, proof.proof_type);
+    let proof = create_proof(wallet, &serde_json::to_value(&*credential)?, proof, signing_key).await?;
+    credential.proof.push(proof);
+    Ok(())
+}
+
+/// Adds a proof created with `signing_key`, the wallet key of `proof.verification_method`,
+/// to the presentation. The proof has to carry the `challenge` (and `domain`) of the verifier.
+pub async fn sign_presentation(
+    wallet: &Arc<dyn BaseWallet>,
+    presentation: &mut VerifiablePresentation,
+    proof: Proof,
+    signing_key: &str,
+) -> VcxResult<()> {
+    add_suite_context(&mut presentation.context, proof.proof_type);
+    let proof = create_proof(wallet, &serde_json::to_value(&*presentation)?, proof, signing_key).await?;
+    presentation.proof.push(proof);
+    Ok(())
+}
+
+/// Verifies every proof of the credential, each of which has to be made with an
+/// assertion method of the issuer DID.
+pub async fn verify_credential(
+    wallet: &Arc<dyn BaseWallet>,
+    registry: &ResolverRegistry,
+    credential: &VerifiableCredential,
+) -> VcxResult<()> {
+    if credential.is_expired() {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidProof,
+            "Credential has expired",
+        ));
+    }
+
+    let document = serde_json::to_value(credential)?;
+    for proof in require_proofs(&credential.proof)? {
+        let signer = verify_proof(wallet, registry, &document, proof, ProofPurpose::AssertionMethod).await?;
+        if signer != credential.issuer.id() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidProof,
+                format!(
+                    "Credential is issued by {}, but signed by {}",
+                    credential.issuer.id(),
+                    signer
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Verifies the proofs of the presentation, made by its holder for the given `challenge`,
+/// and the credentials it contains.
+///
+/// The challenge is required, as presentation proofs authenticating the holder could
+/// otherwise be replayed.
+pub async fn verify_presentation(
+    wallet: &Arc<dyn BaseWallet>,
+    registry: &ResolverRegistry,
+    presentation: &VerifiablePresentation,
+    challenge: &str,
+) -> VcxResult<()> {
+    let document = serde_json::to_value(presentation)?;
+    for proof in require_proofs(&presentation.proof)? {
+        if proof.challenge.as_deref() != Some(challenge) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidProof,
+                "Presentation proof was not created for the requested challenge",
+            ));
+        }
+
+        let signer = verify_proof(wallet, registry, &document, proof, ProofPurpose::Authentication).await?;
+        if let Some(holder) = &presentation.holder {
+            if &signer != holder {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidProof,
+                    format!("Presentation is held by {holder}, but signed by {signer}"),
+                ));
+            }
+        }
+    }
+
+    for credential in &presentation.verifiable_credential {
+        verify_credential(wallet, registry, credential).await?;
+    }
+    Ok(())
+}
+
+fn add_suite_context(context: &mut Vec<Value>, proof_type: ProofType) {
+    let suite_context = Value::from(proof_type.context());
+    if !context.contains(&suite_context) {
+        context.push(suite_context);
+    }
+}
+
+fn require_proofs(proofs: &[Proof]) -> VcxResult<&[Proof]> {
+    if proofs.is_empty() {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidProof,
+            "Document is not secured by any proof",
+        ));
+    }
+    Ok(proofs)
+}
+
+async fn create_proof(
+    wallet: &Arc<dyn BaseWallet>,
+    document: &Value,
+    mut proof: Proof,
+    signing_key: &str,
+) -> VcxResult<Proof> {
+    let data = signed_data(document, &proof)?;
+
+    match proof.proof_type {
+        ProofType::Ed25519Signature2020 | ProofType::DataIntegrityProof { .. } => {
+            let signature = wallet.sign(signing_key, &data).await?;
+            proof.proof_value = Some(format!("z{}", bs58::encode(signature).into_string()));
+        }
+        ProofType::JsonWebSignature2020 => {
+            let protected = jws_protected_header();
+            let signature = wallet.sign(signing_key, &jws_signing_input(&protected, &data)).await?;
+            let signature = base64::encode_config(&signature, base64::URL_SAFE_NO_PAD);
+            proof.jws = Some(format!("{protected}..{signature}"));
+        }
+    }
+    Ok(proof)
+}
+
+/// Verifies a proof of the document, returning the DID of the verification method it was made with.
+async fn verify_proof(
+    wallet: &Arc<dyn BaseWallet>,
+    registry: &ResolverRegistry,
+    document: &Value,
+    proof: &Proof,
+    purpose: ProofPurpose,
+) -> VcxResult<String> {
+    let invalid_proof = |msg: &str| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidProof, msg);
+
+    if proof.proof_purpose != purpose {
+        return Err(invalid_proof(&format!(
+            "Expected a proof for {:?}, found {:?}",
+            purpose, proof.proof_purpose
+        )));
+    }
+
+    let key = resolve_verification_key(registry, &proof.verification_method, purpose).await?;
+    let data = signed_data(document, proof)?;
+
+    let (signing_input, signature) = match proof.proof_type {
+        ProofType::Ed25519Signature2020 | ProofType::DataIntegrityProof { .. } => {
+            let proof_value = proof
+                .proof_value
+                .as_deref()
+                .and_then(|value| value.strip_prefix('z'))
+                .ok_or_else(|| invalid_proof("proofValue is not a base58btc multibase value"))?;
+            let signature = bs58::decode(proof_value)
+                .into_vec()
+                .map_err(|_| invalid_proof("proofValue is not a base58btc multibase value"))?;
+            (data, signature)
+        }
+        ProofType::JsonWebSignature2020 => {
+            let jws = proof.jws.as_deref().unwrap_or_default();
+            let (protected, signature) = match jws.split('.').collect::<Vec<_>>()[..] {
+                [protected, "", signature] => (protected, signature),
+                _ => return Err(invalid_proof("jws is not a compact JWS with a detached payload")),
+            };
+            if protected != jws_protected_header() {
+                return Err(invalid_proof("jws is not an unencoded EdDSA signature"));
+            }
+            let signature = base64::decode_config(signature, base64::URL_SAFE_NO_PAD)
+                .map_err(|_| invalid_proof("jws signature is not base64url encoded"))?;
+            (jws_signing_input(protected, &data), signature)
+        }
+    };
+
+    if !wallet.verify(&key.verkey, &signing_input, &signature).await? {
+        return Err(invalid_proof(&format!(
+            "Signature of {} is invalid",
+            proof.verification_method
+        )));
+    }
+    Ok(key.did)
+}
+
+/// Hashes of the proof options and of the document, which the signature is created over.
+fn signed_data(document: &Value, proof: &Proof) -> VcxResult<Vec<u8>> {
+    let mut options = serde_json::to_value(proof)?;
+    let mut document = document.clone();
+    if let (Some(options), Some(document)) = (options.as_object_mut(), document.as_object_mut()) {
+        options.remove("proofValue");
+        options.remove("jws");
+        if let Some(context) = document.get("@context") {
+            options.insert("@context".to_owned(), context.clone());
+        }
+        document.remove("proof");
+    }
+
+    let (options, document) = match proof.proof_type {
+        ProofType::Ed25519Signature2020 | ProofType::JsonWebSignature2020 => (
+            urdna2015::canonicalize(&jsonld::to_rdf(&options)?)?,
+            urdna2015::canonicalize(&jsonld::to_rdf(&document)?)?,
+        ),
+        ProofType::DataIntegrityProof {
+            cryptosuite: Cryptosuite::EddsaJcs2022,
+        } => (canonicalize(&options), canonicalize(&document)),
+    };
+
+    let mut data = sha256(options.as_bytes()).to_vec();
+    data.extend(sha256(document.as_bytes()));
+    Ok(data)
+}
+
+// The header is fixed, so the encoded header of received proofs can be compared as is
+fn jws_protected_header() -> String {
+    let header = json!({ "alg": "EdDSA", "b64": false, "crit": ["b64"] });
+    base64::encode_config(canonicalize(&header).as_bytes(), base64::URL_SAFE_NO_PAD)
+}
+
+// With `b64` disabled, the payload is appended to the header without being encoded (RFC 7797)
+fn jws_signing_input(protected: &str, payload: &[u8]) -> Vec<u8> {
+    let mut input = format!("{protected}.").into_bytes();
+    input.extend(payload);
+    input
+}
+
+/// Serializes the value with its object members sorted by their UTF-16 encoded names, its
+/// numbers formatted as by ECMAScript and without any whitespace, as specified by RFC 8785.
+pub(super) fn canonicalize(value: &Value) -> String {
+    match value {
+        Value::Array(values) => {
+            let values: Vec<String> = values.iter().map(canonicalize).collect();
+            format!("[{}]", values.join(","))
+        }
+        Value::Object(members) => {
+            let mut members: Vec<(&String, &Value)> = members.iter().collect();
+            members.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            let members: Vec<String> = members
+                .into_iter()
+                .map(|(name, value)| format!("{}:{}", Value::from(name.as_str()), canonicalize(value)))
+                .collect();
+            format!("{{{}}}", members.join(","))
+        }
+        Value::Number(number) => canonicalize_number(number),
+        value => value.to_string(),
+    }
+}
+
+/// Formats the number as ECMAScript's `Number.prototype.toString` does for its double value.
+fn canonicalize_number(number: &Number) -> String {
+    let value = number.as_f64().unwrap_or_default();
+    if value == 0.0 {
+        return "0".to_owned();
+    }
+
+    // The shortest digits which round trip to the value, followed by their exponent, e.g. 1.5e-7
+    let scientific = format!("{:e}", value.abs());
+    let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+    let digits = mantissa.replace('.', "");
+    let exponent: i32 = exponent.parse().unwrap_or_default();
+    let digit_count = digits.len() as i32;
+    let point = exponent + 1;
+
+    let magnitude = if digit_count <= point && point <= 21 {
+        format!("{}{}", digits, "0".repeat((point - digit_count) as usize))
+    } else if 0 < point && point <= 21 {
+        let (integer, fraction) = digits.split_at(point as usize);
+        format!("{integer}.{fraction}")
+    } else if -6 < point && point <= 0 {
+        format!("0.{}{}", "0".repeat(-point as usize), digits)
+    } else {
+        let (first, rest) = digits.split_at(1);
+        let exponent_sign = if exponent < 0 { '-' } else { '+' };
+        if rest.is_empty() {
+            format!("{first}e{exponent_sign}{}", exponent.abs())
+        } else {
+            format!("{first}.{rest}e{exponent_sign}{}", exponent.abs())
+        }
+    };
+
+    if value < 0.0 {
+        format!("-{magnitude}")
+    } else {
+        magnitude
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use aries_vcx_core::wallet::indy_wallet::IndySdkWallet;
+    use async_trait::async_trait;
+    use did_resolver_peer::did_resolver::{
+        did_doc::schema::did_doc::DidDocument,
+        did_parser::Did,
+        error::GenericError,
+        traits::resolvable::{
+            resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput, DidResolvable,
+        },
+    };
+
+    use super::*;
+    use crate::common::credentials::w3c::credential::{CredentialSubject, Issuer};
+    use crate::common::ledger::transactions::ed25519_public_key_to_did_key;
+    use crate::utils::devsetup::SetupLibraryWallet;
+    use crate::utils::mockdata::profile::mock_wallet::MockWallet;
+
+    const ISSUER_DID: &str = "did:example:issuer";
+    const ISSUER_METHOD: &str = "did:example:issuer#key-1";
+    const ISSUER_VERKEY: &str = "3Emm6DnrzrUu4gtV3sW5RU7DvmZLZbC2sZGiGFEaUzci";
+
+    struct StaticResolver(DidDocument<Value>);
+
+    #[async_trait]
+    impl DidResolvable for StaticResolver {
+        type ExtraFields = Value;
+
+        async fn resolve(
+            &self,
+            _did: &Did,
+            _options: &DidResolutionOptions,
+        ) -> Result<DidResolutionOutput<Value>, GenericError> {
+            Ok(DidResolutionOutput::builder(self.0.clone()).build())
+        }
+    }
+
+    fn make_registry() -> ResolverRegistry {
+        make_registry_for_key(ISSUER_VERKEY)
+    }
+
+    // The issuer key is listed as an assertion method only, and can thus not authenticate
+    fn make_registry_for_key(verkey: &str) -> ResolverRegistry {
+        let did_key = ed25519_public_key_to_did_key(verkey).unwrap();
+        let document = serde_json::from_value(json!({
+            "id": ISSUER_DID,
+            "verificationMethod": [{
+                "id": ISSUER_METHOD,
+                "controller": ISSUER_DID,
+                "type": "Ed25519VerificationKey2020",
+                "publicKeyMultibase": did_key.strip_prefix("did:key:").unwrap()
+            }],
+            "assertionMethod": ["#key-1"]
+        }))
+        .unwrap();
+
+        let mut registry = ResolverRegistry::new();
+        registry.register_resolver("example".to_owned(), Box::new(StaticResolver(document)));
+        registry
+    }
+
+    // Linked data signatures require the claims to be defined by the context of the credential
+    fn make_credential(issuer: &str) -> VerifiableCredential {
+        let subject = CredentialSubject {
+            id: Some("did:example:holder".to_owned()),
+            claims: json!({ "name": "Alice" }).as_object().unwrap().clone(),
+        };
+        let mut credential = VerifiableCredential::new(Issuer::Id(issuer.to_owned()), subject);
+        credential.context.push(json!({ "name": "https://schema.org/name" }));
+        credential
+    }
+
+    async fn make_signed_credential() -> VerifiableCredential {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let mut credential = make_credential(ISSUER_DID);
+        let proof = Proof::new(
+            Cryptosuite::EddsaJcs2022,
+            ISSUER_METHOD.to_owned(),
+            ProofPurpose::AssertionMethod,
+        );
+        sign_credential(&wallet, &mut credential, proof, ISSUER_VERKEY)
+            .await
+            .unwrap();
+        credential
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let value = json!({ "b": [1, { "d": true, "c": null }], "a": "\u{20ac}" });
+        assert_eq!(canonicalize(&value), r#"{"a":"€","b":[1,{"c":null,"d":true}]}"#);
+    }
+
+    #[test]
+    fn test_canonicalize_numbers() {
+        let value: Value = serde_json::from_str(
+            "[1.0, -0.0, 4.50, 1E3, 1e20, 1e21, 1e-7, 1.5e-7, 0.000001, 333333333.3333333, 1e23, \
+             123456789012345678, 295147905179352830000, 5e-324, -1.7976931348623157e308]",
+        )
+        .unwrap();
+        assert_eq!(
+            canonicalize(&value),
+            "[1,0,4.5,1000,100000000000000000000,1e+21,1e-7,1.5e-7,0.000001,333333333.3333333,1e+23,\
+             123456789012345680,295147905179352830000,5e-324,-1.7976931348623157e+308]"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_verify_eddsa_jcs_2022() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let credential = make_signed_credential().await;
+
+        assert!(credential.context.contains(&json!(Cryptosuite::EddsaJcs2022.context())));
+        let credential_json = serde_json::to_value(&credential).unwrap();
+        assert_eq!(credential_json["proof"][0]["type"], "DataIntegrityProof");
+        assert_eq!(credential_json["proof"][0]["cryptosuite"], "eddsa-jcs-2022");
+        assert!(credential.proof[0].proof_value.as_ref().unwrap().starts_with('z'));
+
+        let credential: VerifiableCredential = serde_json::from_value(credential_json).unwrap();
+        verify_credential(&wallet, &make_registry(), &credential).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_verify_ed25519_signature_2020() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let mut credential = make_credential(ISSUER_DID);
+        let proof = Proof::new(
+            ProofType::Ed25519Signature2020,
+            ISSUER_METHOD.to_owned(),
+            ProofPurpose::AssertionMethod,
+        );
+        sign_credential(&wallet, &mut credential, proof, ISSUER_VERKEY)
+            .await
+            .unwrap();
+
+        assert!(credential
+            .context
+            .contains(&json!("https://w3id.org/security/suites/ed25519-2020/v1")));
+        let credential_json = serde_json::to_value(&credential).unwrap();
+        assert_eq!(credential_json["proof"][0]["type"], "Ed25519Signature2020");
+        assert!(credential_json["proof"][0].get("cryptosuite").is_none());
+        // The mock wallet signs with the signed data itself: the hashes of the proof options and the document
+        let proof_value = credential.proof[0].proof_value.as_ref().unwrap();
+        assert_eq!(bs58::decode(&proof_value[1..]).into_vec().unwrap().len(), 64);
+
+        let credential: VerifiableCredential = serde_json::from_value(credential_json).unwrap();
+        verify_credential(&wallet, &make_registry(), &credential).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sign_and_verify_json_web_signature_2020() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let mut credential = make_credential(ISSUER_DID);
+        let proof = Proof::new(
+            ProofType::JsonWebSignature2020,
+            ISSUER_METHOD.to_owned(),
+            ProofPurpose::AssertionMethod,
+        );
+        sign_credential(&wallet, &mut credential, proof, ISSUER_VERKEY)
+            .await
+            .unwrap();
+
+        let jws = credential.proof[0].jws.as_ref().unwrap();
+        let (protected, _) = jws.split_once("..").unwrap();
+        let header = base64::decode_config(protected, base64::URL_SAFE_NO_PAD).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&header).unwrap(),
+            json!({ "alg": "EdDSA", "b64": false, "crit": ["b64"] })
+        );
+        assert!(credential.proof[0].proof_value.is_none());
+
+        let credential: VerifiableCredential =
+            serde_json::from_value(serde_json::to_value(&credential).unwrap()).unwrap();
+        verify_credential(&wallet, &make_registry(), &credential).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sign_credential_fails_for_undefined_terms() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let mut credential = make_credential(ISSUER_DID);
+        credential.credential_subject[0]
+            .claims
+            .insert("nickname".to_owned(), json!("Ali"));
+        let proof = Proof::new(
+            ProofType::Ed25519Signature2020,
+            ISSUER_METHOD.to_owned(),
+            ProofPurpose::AssertionMethod,
+        );
+
+        let err = sign_credential(&wallet, &mut credential, proof, ISSUER_VERKEY)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+
+    #[tokio::test]
+    async fn test_verify_credential_fails_for_modified_claims() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let registry = make_registry_for_key(&verkey);
+
+            for proof_type in [
+                ProofType::Ed25519Signature2020,
+                ProofType::JsonWebSignature2020,
+                Cryptosuite::EddsaJcs2022.into(),
+            ] {
+                let mut credential = make_credential(ISSUER_DID);
+                let proof = Proof::new(proof_type, ISSUER_METHOD.to_owned(), ProofPurpose::AssertionMethod);
+                sign_credential(&wallet, &mut credential, proof, &verkey).await.unwrap();
+                verify_credential(&wallet, &registry, &credential).await.unwrap();
+
+                credential.credential_subject[0]
+                    .claims
+                    .insert("name".to_owned(), json!("Bob"));
+                let err = verify_credential(&wallet, &registry, &credential).await.unwrap_err();
+                assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProof);
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_verify_credential_fails_for_other_issuer() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let mut credential = make_signed_credential().await;
+        credential.issuer = Issuer::Id("did:example:other".to_owned());
+
+        let err = verify_credential(&wallet, &make_registry(), &credential)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProof);
+    }
+
+    #[tokio::test]
+    async fn test_verify_credential_fails_without_proof() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let err = verify_credential(&wallet, &make_registry(), &make_credential(ISSUER_DID))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProof);
+    }
+
+    #[tokio::test]
+    async fn test_verify_presentation_requires_authentication_method() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let credential = make_signed_credential().await;
+        let mut presentation = VerifiablePresentation::new(Some(ISSUER_DID.to_owned()), vec![credential]);
+        let mut proof = Proof::new(
+            Cryptosuite::EddsaJcs2022,
+            ISSUER_METHOD.to_owned(),
+            ProofPurpose::Authentication,
+        );
+        proof.challenge = Some("1f44d55f-f161-4938-a659-f8026467f126".to_owned());
+        sign_presentation(&wallet, &mut presentation, proof, ISSUER_VERKEY)
+            .await
+            .unwrap();
+
+        let err = verify_presentation(
+            &wallet,
+            &make_registry(),
+            &presentation,
+            "1f44d55f-f161-4938-a659-f8026467f126",
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProof);
+    }
+
+    #[tokio::test]
+    async fn test_verify_presentation_fails_for_other_challenge() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let mut presentation = VerifiablePresentation::new(None, vec![]);
+        let mut proof = Proof::new(
+            Cryptosuite::EddsaJcs2022,
+            ISSUER_METHOD.to_owned(),
+            ProofPurpose::Authentication,
+        );
+        proof.challenge = Some("1f44d55f-f161-4938-a659-f8026467f126".to_owned());
+        sign_presentation(&wallet, &mut presentation, proof, ISSUER_VERKEY)
+            .await
+            .unwrap();
+
+        let err = verify_presentation(&wallet, &make_registry(), &presentation, "other-challenge")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProof);
+    }
+
+    #[tokio::test]
+    async fn test_verify_presentation_fails_without_challenge() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let mut presentation = VerifiablePresentation::new(None, vec![]);
+        let proof = Proof::new(
+            Cryptosuite::EddsaJcs2022,
+            ISSUER_METHOD.to_owned(),
+            ProofPurpose::Authentication,
+        );
+        sign_presentation(&wallet, &mut presentation, proof, ISSUER_VERKEY)
+            .await
+            .unwrap();
+
+        let err = verify_presentation(
+            &wallet,
+            &make_registry(),
+            &presentation,
+            "1f44d55f-f161-4938-a659-f8026467f126",
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidProof);
+    }
+}
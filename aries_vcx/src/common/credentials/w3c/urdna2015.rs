@@ -0,0 +1,518 @@
+//! RDF dataset canonicalization with the
+//! [URDNA2015](https://www.w3.org/TR/rdf-canon/) algorithm, which labels the blank nodes of a
+//! dataset deterministically so that isomorphic datasets serialize to the same N-Quads.
+
+use std::collections::{BTreeMap, HashMap};
+
+use openssl::sha::sha256;
+
+use crate::errors::error::prelude::*;
+
+pub(super) const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+pub(super) const RDF_LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+
+// Bounds the permutations tried to tell apart blank nodes of identical shape, as datasets
+// crafted to have many of them would take exponential time to canonicalize
+const MAX_PERMUTATIONS: usize = 100_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) enum Term {
+    Iri(String),
+    /// Blank node, labelled with its `_:` prefix.
+    BlankNode(String),
+    Literal {
+        value: String,
+        datatype: String,
+        language: Option<String>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(super) struct Quad {
+    pub subject: Term,
+    pub predicate: Term,
+    pub object: Term,
+    /// Name of the graph of the quad, `None` for the default graph.
+    pub graph: Option<Term>,
+}
+
+impl Quad {
+    fn blank_nodes(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("s", Some(&self.subject)),
+            ("o", Some(&self.object)),
+            ("g", self.graph.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(position, term)| match term {
+            Some(Term::BlankNode(label)) => Some((position, label.as_str())),
+            _ => None,
+        })
+    }
+
+    /// Serializes the quad as an N-Quads statement, relabelling its blank nodes with `label`.
+    fn to_nquad(&self, label: &dyn Fn(&str) -> String) -> String {
+        let term = |term: &Term| match term {
+            Term::Iri(iri) => format!("<{iri}>"),
+            Term::BlankNode(blank_node) => label(blank_node),
+            Term::Literal {
+                value,
+                datatype,
+                language,
+            } => match language {
+                Some(language) => format!("\"{}\"@{}", escape(value), language),
+                None if datatype == XSD_STRING => format!("\"{}\"", escape(value)),
+                None => format!("\"{}\"^^<{}>", escape(value), datatype),
+            },
+        };
+
+        let mut nquad = format!(
+            "{} {} {} ",
+            term(&self.subject),
+            term(&self.predicate),
+            term(&self.object)
+        );
+        if let Some(graph) = &self.graph {
+            nquad.push_str(&term(graph));
+            nquad.push(' ');
+        }
+        nquad.push_str(".\n");
+        nquad
+    }
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn hash(data: &str) -> String {
+    sha256(data.as_bytes())
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Issues sequential blank node identifiers, remembering the order they were issued in.
+#[derive(Clone, Debug)]
+struct IdentifierIssuer {
+    prefix: &'static str,
+    issued: HashMap<String, String>,
+    order: Vec<String>,
+}
+
+impl IdentifierIssuer {
+    fn new(prefix: &'static str) -> Self {
+        Self {
+            prefix,
+            issued: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    fn get(&self, blank_node: &str) -> Option<&String> {
+        self.issued.get(blank_node)
+    }
+
+    fn issue(&mut self, blank_node: &str) -> String {
+        if let Some(identifier) = self.issued.get(blank_node) {
+            return identifier.clone();
+        }
+        let identifier = format!("{}{}", self.prefix, self.order.len());
+        self.issued.insert(blank_node.to_owned(), identifier.clone());
+        self.order.push(blank_node.to_owned());
+        identifier
+    }
+}
+
+/// Returns the canonical N-Quads of the dataset, with its statements sorted and its blank
+/// nodes labelled `_:c14n0`, `_:c14n1`, ...
+pub(super) fn canonicalize(quads: &[Quad]) -> VcxResult<String> {
+    let mut canonicalizer = Canonicalizer::new(quads);
+    canonicalizer.issue_canonical_identifiers()?;
+
+    let canonical_issuer = &canonicalizer.canonical_issuer;
+    let mut nquads: Vec<String> = quads
+        .iter()
+        .map(|quad| {
+            quad.to_nquad(&|blank_node| {
+                canonical_issuer
+                    .get(blank_node)
+                    .cloned()
+                    .unwrap_or_else(|| blank_node.to_owned())
+            })
+        })
+        .collect();
+    nquads.sort();
+    nquads.dedup();
+    Ok(nquads.concat())
+}
+
+struct Canonicalizer<'a> {
+    blank_node_quads: HashMap<&'a str, Vec<&'a Quad>>,
+    // Blank nodes in the order they appear in the dataset
+    blank_nodes: Vec<&'a str>,
+    first_degree_hashes: HashMap<&'a str, String>,
+    canonical_issuer: IdentifierIssuer,
+    permutations: usize,
+}
+
+impl<'a> Canonicalizer<'a> {
+    fn new(quads: &'a [Quad]) -> Self {
+        let mut blank_node_quads: HashMap<&str, Vec<&Quad>> = HashMap::new();
+        let mut blank_nodes = Vec::new();
+        for quad in quads {
+            for (_, blank_node) in quad.blank_nodes() {
+                let mentions = blank_node_quads.entry(blank_node).or_insert_with(|| {
+                    blank_nodes.push(blank_node);
+                    Vec::new()
+                });
+                // A quad mentioning the blank node twice is listed once
+                if !mentions.last().map_or(false, |last| std::ptr::eq(*last, quad)) {
+                    mentions.push(quad);
+                }
+            }
+        }
+
+        Self {
+            blank_node_quads,
+            blank_nodes,
+            first_degree_hashes: HashMap::new(),
+            canonical_issuer: IdentifierIssuer::new("_:c14n"),
+            permutations: 0,
+        }
+    }
+
+    fn issue_canonical_identifiers(&mut self) -> VcxResult<()> {
+        let mut hash_to_blank_nodes: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+        for blank_node in self.blank_nodes.clone() {
+            let hash = self.hash_first_degree_quads(blank_node);
+            hash_to_blank_nodes.entry(hash).or_default().push(blank_node);
+        }
+
+        // Blank nodes with a unique hash are labelled first, in the order of their hashes
+        let mut shared_hashes = Vec::new();
+        for blank_nodes in hash_to_blank_nodes.into_values() {
+            match blank_nodes[..] {
+                [blank_node] => {
+                    self.canonical_issuer.issue(blank_node);
+                }
+                _ => shared_hashes.push(blank_nodes),
+            }
+        }
+
+        for blank_nodes in shared_hashes {
+            let mut hash_paths = Vec::new();
+            for blank_node in blank_nodes {
+                if self.canonical_issuer.get(blank_node).is_some() {
+                    continue;
+                }
+                let mut issuer = IdentifierIssuer::new("_:b");
+                issuer.issue(blank_node);
+                hash_paths.push(self.hash_n_degree_quads(blank_node, issuer)?);
+            }
+
+            hash_paths.sort_by(|(a, _), (b, _)| a.cmp(b));
+            for (_, issuer) in hash_paths {
+                for blank_node in &issuer.order {
+                    self.canonical_issuer.issue(blank_node);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn hash_first_degree_quads(&mut self, blank_node: &'a str) -> String {
+        if let Some(hash) = self.first_degree_hashes.get(blank_node) {
+            return hash.clone();
+        }
+
+        let mut nquads: Vec<String> = self.blank_node_quads[blank_node]
+            .iter()
+            .map(|quad| {
+                quad.to_nquad(&|label| {
+                    if label == blank_node {
+                        "_:a".to_owned()
+                    } else {
+                        "_:z".to_owned()
+                    }
+                })
+            })
+            .collect();
+        nquads.sort();
+
+        let hash = hash(&nquads.concat());
+        self.first_degree_hashes.insert(blank_node, hash.clone());
+        hash
+    }
+
+    fn hash_related_blank_node(
+        &mut self,
+        related: &'a str,
+        quad: &Quad,
+        issuer: &IdentifierIssuer,
+        position: &str,
+    ) -> String {
+        let identifier = match (self.canonical_issuer.get(related), issuer.get(related)) {
+            (Some(identifier), _) | (None, Some(identifier)) => identifier.clone(),
+            (None, None) => self.hash_first_degree_quads(related),
+        };
+
+        let mut input = position.to_owned();
+        if position != "g" {
+            if let Term::Iri(predicate) = &quad.predicate {
+                input.push_str(&format!("<{predicate}>"));
+            }
+        }
+        input.push_str(&identifier);
+        hash(&input)
+    }
+
+    fn hash_n_degree_quads(
+        &mut self,
+        blank_node: &'a str,
+        mut issuer: IdentifierIssuer,
+    ) -> VcxResult<(String, IdentifierIssuer)> {
+        let mut hash_to_related: BTreeMap<String, Vec<&'a str>> = BTreeMap::new();
+        for quad in self.blank_node_quads[blank_node].clone() {
+            for (position, related) in quad.blank_nodes() {
+                if related != blank_node {
+                    let hash = self.hash_related_blank_node(related, quad, &issuer, position);
+                    hash_to_related.entry(hash).or_default().push(related);
+                }
+            }
+        }
+
+        let mut data_to_hash = String::new();
+        for (related_hash, mut permutation) in hash_to_related {
+            data_to_hash.push_str(&related_hash);
+
+            let mut chosen: Option<(String, IdentifierIssuer)> = None;
+            permutation.sort_unstable();
+            loop {
+                self.permutations += 1;
+                if self.permutations > MAX_PERMUTATIONS {
+                    return Err(AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidJson,
+                        "Document has too many indistinguishable blank nodes to be canonicalized",
+                    ));
+                }
+
+                let chosen_path = chosen.as_ref().map(|(path, _)| path.as_str());
+                if let Some((path, path_issuer)) = self.hash_permutation(&permutation, &issuer, chosen_path)? {
+                    if chosen_path.map_or(true, |chosen_path| path.as_str() < chosen_path) {
+                        chosen = Some((path, path_issuer));
+                    }
+                }
+                if !next_permutation(&mut permutation) {
+                    break;
+                }
+            }
+
+            // The first permutation is never skipped, so a path is always chosen
+            if let Some((path, path_issuer)) = chosen {
+                data_to_hash.push_str(&path);
+                issuer = path_issuer;
+            }
+        }
+        Ok((hash(&data_to_hash), issuer))
+    }
+
+    /// Labels the related blank nodes in the order of the permutation, returning `None` as
+    /// soon as the path is known to sort after the one chosen so far.
+    fn hash_permutation(
+        &mut self,
+        permutation: &[&'a str],
+        issuer: &IdentifierIssuer,
+        chosen_path: Option<&str>,
+    ) -> VcxResult<Option<(String, IdentifierIssuer)>> {
+        let longer_than_chosen = |path: &str| {
+            chosen_path.map_or(false, |chosen_path| {
+                path.len() >= chosen_path.len() && path > chosen_path
+            })
+        };
+
+        let mut issuer = issuer.clone();
+        let mut path = String::new();
+        let mut recursion = Vec::new();
+        for &blank_node in permutation {
+            if let Some(identifier) = self.canonical_issuer.get(blank_node) {
+                path.push_str(identifier);
+            } else {
+                if issuer.get(blank_node).is_none() {
+                    recursion.push(blank_node);
+                }
+                path.push_str(&issuer.issue(blank_node));
+            }
+            if longer_than_chosen(&path) {
+                return Ok(None);
+            }
+        }
+
+        for blank_node in recursion {
+            let (hash, result_issuer) = self.hash_n_degree_quads(blank_node, issuer.clone())?;
+            path.push_str(&issuer.issue(blank_node));
+            path.push_str(&format!("<{hash}>"));
+            issuer = result_issuer;
+            if longer_than_chosen(&path) {
+                return Ok(None);
+            }
+        }
+        Ok(Some((path, issuer)))
+    }
+}
+
+/// Rearranges the items into their next permutation in lexicographic order, returning false
+/// once all were visited. Items listed more than once, such as blank nodes related through
+/// several quads, are not permuted with each other, as that would only repeat the same paths.
+fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    let pivot = match items.windows(2).rposition(|pair| pair[0] < pair[1]) {
+        Some(pivot) => pivot,
+        None => return false,
+    };
+    let successor = items.iter().rposition(|item| *item > items[pivot]).unwrap_or(pivot);
+    items.swap(pivot, successor);
+    items[pivot + 1..].reverse();
+    true
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    fn iri(iri: &str) -> Term {
+        Term::Iri(format!("http://example.com/#{iri}"))
+    }
+
+    fn blank(label: &str) -> Term {
+        Term::BlankNode(format!("_:{label}"))
+    }
+
+    fn quad(subject: Term, predicate: &str, object: Term) -> Quad {
+        Quad {
+            subject,
+            predicate: iri(predicate),
+            object,
+            graph: None,
+        }
+    }
+
+    #[test]
+    fn test_canonicalize_unique_hashes() {
+        let quads = vec![
+            quad(iri("p"), "q", blank("e0")),
+            quad(iri("p"), "r", blank("e1")),
+            quad(blank("e0"), "s", iri("u")),
+            quad(blank("e1"), "t", iri("u")),
+        ];
+
+        let mut canonicalizer = Canonicalizer::new(&quads);
+        assert_eq!(
+            canonicalizer.hash_first_degree_quads("_:e0"),
+            "21d1dd5ba21f3dee9d76c0c00c260fa6f5d5d65315099e553026f4828d0dc77a"
+        );
+        assert_eq!(
+            canonicalizer.hash_first_degree_quads("_:e1"),
+            "6fa0b9bdb376852b5743ff39ca4cbf7ea14d34966b2828478fbf222e7c764473"
+        );
+
+        assert_eq!(
+            canonicalize(&quads).unwrap(),
+            "<http://example.com/#p> <http://example.com/#q> _:c14n0 .\n\
+             <http://example.com/#p> <http://example.com/#r> _:c14n1 .\n\
+             _:c14n0 <http://example.com/#s> <http://example.com/#u> .\n\
+             _:c14n1 <http://example.com/#t> <http://example.com/#u> .\n"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_shared_hashes() {
+        let quads = vec![
+            quad(iri("p"), "q", blank("e0")),
+            quad(iri("p"), "q", blank("e1")),
+            quad(blank("e0"), "p", blank("e2")),
+            quad(blank("e1"), "p", blank("e3")),
+            quad(blank("e2"), "r", blank("e3")),
+        ];
+
+        assert_eq!(
+            canonicalize(&quads).unwrap(),
+            "<http://example.com/#p> <http://example.com/#q> _:c14n2 .\n\
+             <http://example.com/#p> <http://example.com/#q> _:c14n3 .\n\
+             _:c14n0 <http://example.com/#r> _:c14n1 .\n\
+             _:c14n2 <http://example.com/#p> _:c14n1 .\n\
+             _:c14n3 <http://example.com/#p> _:c14n0 .\n"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_ignores_blank_node_labels_and_order() {
+        let literal = Term::Literal {
+            value: "line\n\"quoted\"".to_owned(),
+            datatype: XSD_STRING.to_owned(),
+            language: None,
+        };
+        let quads = vec![
+            quad(blank("x"), "next", blank("y")),
+            quad(blank("y"), "next", blank("z")),
+            quad(blank("z"), "next", blank("x")),
+            quad(blank("x"), "label", literal.clone()),
+            Quad {
+                graph: Some(blank("g")),
+                ..quad(blank("y"), "in", iri("graph"))
+            },
+        ];
+        let relabelled = vec![
+            Quad {
+                graph: Some(blank("b9")),
+                ..quad(blank("b2"), "in", iri("graph"))
+            },
+            quad(blank("b1"), "label", literal),
+            quad(blank("b3"), "next", blank("b1")),
+            quad(blank("b2"), "next", blank("b3")),
+            quad(blank("b1"), "next", blank("b2")),
+        ];
+
+        let canonical = canonicalize(&quads).unwrap();
+        assert_eq!(canonical, canonicalize(&relabelled).unwrap());
+        assert!(canonical.contains(r#""line\n\"quoted\"""#));
+        assert!(!canonical.contains("_:b") && !canonical.contains("_:x"));
+    }
+
+    #[test]
+    fn test_next_permutation() {
+        let mut items = vec![0, 1, 2];
+        let mut permutations = vec![items.clone()];
+        while next_permutation(&mut items) {
+            permutations.push(items.clone());
+        }
+        assert_eq!(
+            permutations,
+            vec![
+                vec![0, 1, 2],
+                vec![0, 2, 1],
+                vec![1, 0, 2],
+                vec![1, 2, 0],
+                vec![2, 0, 1],
+                vec![2, 1, 0]
+            ]
+        );
+
+        let mut items = vec!["a", "a", "b"];
+        let mut permutations = vec![items.clone()];
+        while next_permutation(&mut items) {
+            permutations.push(items.clone());
+        }
+        assert_eq!(permutations, vec![["a", "a", "b"], ["a", "b", "a"], ["b", "a", "a"]]);
+    }
+}
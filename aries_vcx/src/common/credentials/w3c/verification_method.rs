@@ -0,0 +1,189 @@
+use did_resolver_peer::did_resolver::{
+    did_doc::schema::{
+        did_doc::DidDocument,
+        verification_method::{VerificationMethod, VerificationMethodKind},
+    },
+    did_parser::{Did, DidUrl},
+    traits::resolvable::resolution_options::DidResolutionOptions,
+};
+use did_resolver_registry::ResolverRegistry;
+use serde_json::Value;
+
+use super::proof::ProofPurpose;
+use crate::errors::error::prelude::*;
+
+// Multicodec prefix of Ed25519 public keys, as used by `Ed25519VerificationKey2020`
+const ED25519_MULTICODEC_PREFIX: [u8; 2] = [0xed, 0x01];
+const ED25519_PUBLIC_KEY_LENGTH: usize = 32;
+
+/// Ed25519 key of a verification method, along with the DID whose document lists it.
+pub(super) struct VerificationKey {
+    pub did: String,
+    pub verkey: String,
+}
+
+/// Resolves the DID of the verification method and returns its key, provided the method
+/// is authorized for the purpose of the proof.
+pub(super) async fn resolve_verification_key(
+    registry: &ResolverRegistry,
+    verification_method: &str,
+    purpose: ProofPurpose,
+) -> VcxResult<VerificationKey> {
+    let method_url = DidUrl::parse(verification_method.to_owned()).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("Invalid verification method {verification_method}: {err}"),
+        )
+    })?;
+    let did = Did::try_from(&method_url).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("Verification method {verification_method} does not identify a DID: {err}"),
+        )
+    })?;
+    let output = registry
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidDid,
+                format!("Failed to resolve DID {did}: {err}"),
+            )
+        })?;
+
+    let method = find_authorized_method(output.did_document(), &method_url, purpose).ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidProof,
+            format!("Verification method {verification_method} is not authorized for {purpose:?}"),
+        )
+    })?;
+
+    Ok(VerificationKey {
+        did: did.did().to_owned(),
+        verkey: ed25519_verkey(method)?,
+    })
+}
+
+fn find_authorized_method<'a>(
+    document: &'a DidDocument<Value>,
+    method_url: &DidUrl,
+    purpose: ProofPurpose,
+) -> Option<&'a VerificationMethod> {
+    let relationship = match purpose {
+        ProofPurpose::AssertionMethod => document.assertion_method(),
+        ProofPurpose::Authentication => document.authentication(),
+    };
+
+    relationship.iter().find_map(|method| match method {
+        VerificationMethodKind::Resolved(method) if same_method(method.id(), method_url) => Some(method),
+        VerificationMethodKind::Resolvable(reference) if same_method(reference, method_url) => document
+            .verification_method()
+            .iter()
+            .find(|method| same_method(method.id(), method_url)),
+        _ => None,
+    })
+}
+
+// Methods may be identified relative to the document, by their fragment only
+fn same_method(id: &DidUrl, method_url: &DidUrl) -> bool {
+    if id.did().is_some() {
+        id.did() == method_url.did() && id.fragment() == method_url.fragment()
+    } else {
+        id.fragment().is_some() && id.fragment() == method_url.fragment()
+    }
+}
+
+/// Returns the base58 encoded Ed25519 key of the method, the way the wallet expects verkeys.
+fn ed25519_verkey(method: &VerificationMethod) -> VcxResult<String> {
+    let invalid_key = |msg: &str| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidVerkey,
+            format!("Verification method {}: {msg}", method.id()),
+        )
+    };
+
+    let key = if let Some(multibase) = method.public_key_multibase() {
+        let key: &[u8] = multibase.as_ref();
+        match key.strip_prefix(&ED25519_MULTICODEC_PREFIX) {
+            Some(key) if key.len() == ED25519_PUBLIC_KEY_LENGTH => key.to_vec(),
+            _ => key.to_vec(),
+        }
+    } else if let Some(base58) = method.extra_field("publicKeyBase58").and_then(Value::as_str) {
+        bs58::decode(base58)
+            .into_vec()
+            .map_err(|_| invalid_key("publicKeyBase58 is not base58 encoded"))?
+    } else if let Some(jwk) = method.public_key_jwk() {
+        let jwk = serde_json::to_value(jwk)?;
+        if jwk["kty"] != "OKP" || jwk["crv"] != "Ed25519" {
+            return Err(invalid_key("publicKeyJwk is not an Ed25519 key"));
+        }
+        let x = jwk["x"].as_str().unwrap_or_default();
+        base64::decode_config(x, base64::URL_SAFE_NO_PAD)
+            .map_err(|_| invalid_key("publicKeyJwk is not base64url encoded"))?
+    } else {
+        return Err(invalid_key("no supported public key found"));
+    };
+
+    if key.len() != ED25519_PUBLIC_KEY_LENGTH {
+        return Err(invalid_key("public key is not an Ed25519 key"));
+    }
+    Ok(bs58::encode(key).into_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    const VERKEY: &str = "3Emm6DnrzrUu4gtV3sW5RU7DvmZLZbC2sZGiGFEaUzci";
+
+    fn make_method(key: Value) -> VerificationMethod {
+        let mut method = json!({
+            "id": "did:example:issuer#key-1",
+            "controller": "did:example:issuer",
+            "type": "Ed25519VerificationKey2020"
+        });
+        method.as_object_mut().unwrap().extend(key.as_object().unwrap().clone());
+        serde_json::from_value(method).unwrap()
+    }
+
+    #[test]
+    fn test_ed25519_verkey_from_key_formats() {
+        let multibase = make_method(json!({
+            "publicKeyMultibase": "z6Mkgh2ogU3JLPyNBBjBjSTvGZfDkLqByUSPZaBe6XCbQDQ6"
+        }));
+        assert_eq!(ed25519_verkey(&multibase).unwrap(), VERKEY);
+
+        let base58 = make_method(json!({ "publicKeyBase58": VERKEY }));
+        assert_eq!(ed25519_verkey(&base58).unwrap(), VERKEY);
+
+        let x = base64::encode_config(&bs58::decode(VERKEY).into_vec().unwrap(), base64::URL_SAFE_NO_PAD);
+        let jwk = make_method(json!({
+            "publicKeyJwk": { "kty": "OKP", "crv": "Ed25519", "x": x }
+        }));
+        assert_eq!(ed25519_verkey(&jwk).unwrap(), VERKEY);
+    }
+
+    #[test]
+    fn test_ed25519_verkey_rejects_other_curves() {
+        let jwk = make_method(json!({
+            "publicKeyJwk": { "kty": "EC", "crv": "P-256", "x": "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU" }
+        }));
+        assert_eq!(
+            ed25519_verkey(&jwk).unwrap_err().kind(),
+            AriesVcxErrorKind::InvalidVerkey
+        );
+    }
+
+    #[test]
+    fn test_same_method_matches_relative_ids() {
+        let method_url = DidUrl::parse("did:example:issuer#key-1".to_owned()).unwrap();
+        assert!(same_method(&DidUrl::parse("#key-1".to_owned()).unwrap(), &method_url));
+        assert!(same_method(&method_url, &method_url));
+        assert!(!same_method(
+            &DidUrl::parse("did:example:other#key-1".to_owned()).unwrap(),
+            &method_url
+        ));
+        assert!(!same_method(&DidUrl::parse("#key-2".to_owned()).unwrap(), &method_url));
+    }
+}
@@ -1,4 +1,6 @@
 use bs58;
+use did_resolver_peer::did_resolver::did_parser::Did;
+use did_resolver_peer::peer_did::{PeerDid, Purpose};
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use diddoc_legacy::aries::service::AriesService;
 use messages::msg_fields::protocols::connection::invitation::Invitation;
@@ -62,15 +64,64 @@ pub struct ReplyDataV1 {
 }
 
 const DID_KEY_PREFIX: &str = "did:key:";
+const DID_PEER_PREFIX: &str = "did:peer:";
 const ED25519_MULTIBASE_CODEC: [u8; 2] = [0xed, 0x01];
 
 pub async fn resolve_service(profile: &Arc<dyn Profile>, service: &OobService) -> VcxResult<AriesService> {
     match service {
         OobService::AriesService(service) => Ok(service.clone()),
+        OobService::Did(did) if did.starts_with(DID_PEER_PREFIX) => get_service_from_peer_did(did),
         OobService::Did(did) => get_service(profile, did).await,
     }
 }
 
+pub fn get_service_from_peer_did(did: &str) -> VcxResult<AriesService> {
+    let did = Did::parse(did.to_string()).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("Failed to parse peer DID: {err}"),
+        )
+    })?;
+    let (keys, services) = match PeerDid::parse(&did)? {
+        PeerDid::Numalgo2 { keys, services } => (keys, services),
+        PeerDid::Numalgo0(_) => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidDid,
+                format!("Numalgo 0 peer DID {did} does not contain a service"),
+            ))
+        }
+    };
+    let service = services.first().ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("Peer DID {did} does not contain a service"),
+        )
+    })?;
+    let recipient_keys = keys
+        .iter()
+        .filter(|(purpose, _)| *purpose == Purpose::Verification)
+        .map(|(_, key)| key.base58())
+        .collect();
+    let routing_keys = normalize_keys_as_naked(
+        service
+            .routing_keys()
+            .iter()
+            .map(|key| key.split('#').next().unwrap_or(key).to_string())
+            .collect(),
+    )?;
+    let service_endpoint = service.service_endpoint().parse().map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidUrl,
+            format!("Invalid service endpoint in peer DID {did}: {err}"),
+        )
+    })?;
+
+    Ok(AriesService::create()
+        .set_recipient_keys(recipient_keys)
+        .set_service_endpoint(service_endpoint)
+        .set_routing_keys(routing_keys))
+}
+
 pub async fn add_new_did(
     profile: &Arc<dyn Profile>,
     submitter_did: &str,
@@ -136,7 +187,7 @@ pub async fn into_did_doc(profile: &Arc<dyn Profile>, invitation: &AnyInvitation
     Ok(did_doc)
 }
 
-fn _ed25519_public_key_to_did_key(public_key_base58: &str) -> VcxResult<String> {
+pub fn ed25519_public_key_to_did_key(public_key_base58: &str) -> VcxResult<String> {
     let public_key_bytes = bs58::decode(public_key_base58).into_vec().map_err(|_| {
         AriesVcxError::from_msg(
             AriesVcxErrorKind::InvalidDid,
@@ -150,7 +201,7 @@ fn _ed25519_public_key_to_did_key(public_key_base58: &str) -> VcxResult<String>
     Ok(did_key)
 }
 
-fn normalize_keys_as_naked(keys_list: Vec<String>) -> VcxResult<Vec<String>> {
+pub fn normalize_keys_as_naked(keys_list: Vec<String>) -> VcxResult<Vec<String>> {
     let mut result = Vec::new();
     for key in keys_list {
         if let Some(stripped_didkey) = key.strip_prefix(DID_KEY_PREFIX) {
@@ -388,7 +439,7 @@ fn get_data_from_response(resp: &str) -> VcxResult<serde_json::Value> {
 //     async fn test_public_key_to_did_naked_with_previously_known_keys_suggested() {
 //         let did_pub_with_key = "did:key:z6MkwHgArrRJq3tTdhQZKVAa1sdFgSAs5P5N1C4RJcD11Ycv".to_string();
 //         let did_pub = "HqR8GcAsVWPzXCZrdvCjAn5Frru1fVq1KB9VULEz6KqY".to_string();
-//         let did_raw = _ed25519_public_key_to_did_key(&did_pub).unwrap();
+//         let did_raw = ed25519_public_key_to_did_key(&did_pub).unwrap();
 //         let recipient_keys = vec![did_raw];
 //         let expected_output = vec![did_pub_with_key];
 //         assert_eq!(recipient_keys, expected_output);
@@ -398,7 +449,7 @@ fn get_data_from_response(resp: &str) -> VcxResult<serde_json::Value> {
 //     async fn test_public_key_to_did_naked_with_previously_known_keys_rfc_0360() {
 //         let did_pub_with_key_rfc_0360 = "did:key:z6MkmjY8GnV5i9YTDtPETC2uUAW6ejw3nk5mXF5yci5ab7th".to_string();
 //         let did_pub_rfc_0360 = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K".to_string();
-//         let did_raw = _ed25519_public_key_to_did_key(&did_pub_rfc_0360).unwrap();
+//         let did_raw = ed25519_public_key_to_did_key(&did_pub_rfc_0360).unwrap();
 //         let recipient_keys = vec![did_raw];
 //         let expected_output = vec![did_pub_with_key_rfc_0360];
 //         assert_eq!(recipient_keys, expected_output);
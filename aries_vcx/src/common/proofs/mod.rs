@@ -1,3 +1,4 @@
+pub mod presentation_exchange;
 pub mod proof_request;
 pub mod proof_request_internal;
 pub mod prover;
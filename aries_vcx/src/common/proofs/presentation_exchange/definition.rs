@@ -0,0 +1,136 @@
+use serde_json::Value;
+
+/// Requirements of a verifier on the credentials to be presented.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PresentationDefinition {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    /// Claim formats and proof types accepted by the verifier, e.g. `{"ldp_vc": {"proof_type": [...]}}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<Value>,
+    pub input_descriptors: Vec<InputDescriptor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submission_requirements: Option<Vec<SubmissionRequirement>>,
+}
+
+/// Describes a credential to be submitted.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct InputDescriptor {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    /// Groups the descriptor belongs to, referenced by [`SubmissionRequirement::from`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub group: Vec<String>,
+    #[serde(default)]
+    pub constraints: Constraints,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Constraints {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit_disclosure: Option<Preference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Preference {
+    Required,
+    Preferred,
+}
+
+/// Constraint on a property of the credential.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Field {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// JSONPath expressions locating the property, evaluated in order until one yields a value.
+    pub path: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    /// JSON Schema the value of the property must be valid against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Value>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+}
+
+/// Requires input descriptors of a group, or nested requirements, to be satisfied.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct SubmissionRequirement {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    pub rule: Rule,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_nested: Option<Vec<SubmissionRequirement>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Rule {
+    All,
+    Pick,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_presentation_definition_serde() {
+        let json = json!({
+            "id": "32f54163-7166-48f1-93d8-ff217bdb0653",
+            "submission_requirements": [{
+                "name": "Proof of citizenship",
+                "rule": "pick",
+                "count": 1,
+                "from": "A"
+            }],
+            "input_descriptors": [{
+                "id": "citizenship_input",
+                "name": "EU Driver's License",
+                "group": ["A"],
+                "constraints": {
+                    "limit_disclosure": "preferred",
+                    "fields": [{
+                        "path": ["$.credentialSubject.dob", "$.credentialSubject.birth_date"],
+                        "filter": { "type": "string", "pattern": "^19" }
+                    }, {
+                        "path": ["$.credentialSubject.nickname"],
+                        "optional": true
+                    }]
+                }
+            }]
+        });
+
+        let definition: PresentationDefinition = serde_json::from_value(json.clone()).unwrap();
+        let requirements = definition.submission_requirements.as_ref().unwrap();
+        assert_eq!(requirements[0].rule, Rule::Pick);
+        assert_eq!(requirements[0].from.as_deref(), Some("A"));
+
+        let constraints = &definition.input_descriptors[0].constraints;
+        assert_eq!(constraints.limit_disclosure, Some(Preference::Preferred));
+        assert_eq!(constraints.fields[0].path.len(), 2);
+        assert!(constraints.fields[1].optional);
+
+        assert_eq!(serde_json::to_value(&definition).unwrap(), json);
+    }
+}
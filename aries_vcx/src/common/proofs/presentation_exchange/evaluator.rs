@@ -0,0 +1,480 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::{
+    definition::{Field, InputDescriptor, Preference, PresentationDefinition, Rule, SubmissionRequirement},
+    filter::matches_filter,
+    submission::{InputDescriptorMapping, PresentationSubmission},
+};
+use crate::common::credentials::w3c::{credential::VerifiableCredential, presentation::VerifiablePresentation};
+use crate::errors::error::prelude::*;
+
+pub const PRESENTATION_SUBMISSION_CONTEXT: &str = "https://identity.foundation/presentation-exchange/submission/v1";
+pub const PRESENTATION_SUBMISSION_TYPE: &str = "PresentationSubmission";
+
+// Credentials are submitted as linked data proof secured W3C credentials
+const LDP_VC_FORMAT: &str = "ldp_vc";
+
+/// Finds, for every input descriptor, the identifiers of the credentials satisfying its constraints.
+///
+/// Credentials are presented whole, so descriptors requiring limited disclosure cannot be satisfied.
+pub fn match_credentials(
+    definition: &PresentationDefinition,
+    credentials: &[(String, VerifiableCredential)],
+) -> VcxResult<HashMap<String, Vec<String>>> {
+    let credentials = credentials
+        .iter()
+        .map(|(id, credential)| Ok((id, serde_json::to_value(credential)?)))
+        .collect::<VcxResult<Vec<_>>>()?;
+
+    let mut candidates = HashMap::new();
+    for descriptor in &definition.input_descriptors {
+        let mut matching = Vec::new();
+        if descriptor.constraints.limit_disclosure == Some(Preference::Required) {
+            warn!(
+                "match_credentials >>> input descriptor {} requires limited disclosure, which is not supported",
+                descriptor.id
+            );
+        } else {
+            for (id, credential) in &credentials {
+                if matches_descriptor(credential, descriptor)? {
+                    matching.push(id.to_string());
+                }
+            }
+        }
+        candidates.insert(descriptor.id.clone(), matching);
+    }
+    Ok(candidates)
+}
+
+/// Picks a credential for each input descriptor to be submitted, as required by the submission
+/// requirements of the definition or, if there are none, for every input descriptor.
+///
+/// Returns the selected credential identifiers keyed by the input descriptor they satisfy.
+pub fn select_credentials(
+    definition: &PresentationDefinition,
+    candidates: &HashMap<String, Vec<String>>,
+) -> VcxResult<HashMap<String, String>> {
+    let descriptors = match &definition.submission_requirements {
+        None => {
+            if let Some(descriptor) = definition
+                .input_descriptors
+                .iter()
+                .find(|descriptor| !is_satisfiable(&descriptor.id, candidates))
+            {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidInput,
+                    format!("No credential satisfies input descriptor {}", descriptor.id),
+                ));
+            }
+            definition
+                .input_descriptors
+                .iter()
+                .map(|descriptor| descriptor.id.clone())
+                .collect()
+        }
+        Some(requirements) => {
+            let mut descriptors = Vec::new();
+            for requirement in requirements {
+                let satisfied = satisfy_requirement(definition, requirement, candidates)?.ok_or_else(|| {
+                    AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidInput,
+                        format!(
+                            "Submission requirement {} cannot be satisfied",
+                            requirement.name.as_deref().unwrap_or_default()
+                        ),
+                    )
+                })?;
+                descriptors.extend(satisfied);
+            }
+            descriptors
+        }
+    };
+
+    Ok(descriptors
+        .into_iter()
+        .filter_map(|descriptor| {
+            let credential = candidates.get(&descriptor)?.first()?.clone();
+            Some((descriptor, credential))
+        })
+        .collect())
+}
+
+/// Checks a selection of credentials made by the holder against the candidates, returning
+/// the part of it needed to satisfy the definition.
+pub fn validate_selection(
+    definition: &PresentationDefinition,
+    candidates: &HashMap<String, Vec<String>>,
+    selection: &HashMap<String, String>,
+) -> VcxResult<HashMap<String, String>> {
+    let mut selected_candidates = HashMap::new();
+    for (descriptor, credential) in selection {
+        if !candidates
+            .get(descriptor)
+            .map(|candidates| candidates.contains(credential))
+            .unwrap_or_default()
+        {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                format!("Credential {credential} does not satisfy input descriptor {descriptor}"),
+            ));
+        }
+        selected_candidates.insert(descriptor.clone(), vec![credential.clone()]);
+    }
+    select_credentials(definition, &selected_candidates)
+}
+
+/// Builds an unsigned presentation of the selected credentials, along with the
+/// `presentation_submission` describing which input descriptors they satisfy.
+pub fn build_presentation(
+    definition: &PresentationDefinition,
+    holder: Option<String>,
+    credentials: &[(String, VerifiableCredential)],
+    selection: &HashMap<String, String>,
+) -> VcxResult<VerifiablePresentation> {
+    let mut presented: Vec<&str> = Vec::new();
+    let mut mappings: Vec<(String, usize)> = Vec::new();
+    for descriptor in &definition.input_descriptors {
+        let Some(credential) = selection.get(&descriptor.id) else {
+            continue;
+        };
+        let index = match presented.iter().position(|id| id == credential) {
+            Some(index) => index,
+            None => {
+                presented.push(credential);
+                presented.len() - 1
+            }
+        };
+        mappings.push((descriptor.id.clone(), index));
+    }
+
+    let verifiable_credential = presented
+        .iter()
+        .map(|id| {
+            credentials
+                .iter()
+                .find(|(credential_id, _)| credential_id == id)
+                .map(|(_, credential)| credential.clone())
+                .ok_or_else(|| {
+                    AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidInput,
+                        format!("Selected credential {id} was not found"),
+                    )
+                })
+        })
+        .collect::<VcxResult<Vec<_>>>()?;
+
+    // A single credential is serialized as an object rather than an array
+    let single = verifiable_credential.len() == 1;
+    let descriptor_map = mappings
+        .into_iter()
+        .map(|(id, index)| InputDescriptorMapping {
+            id,
+            format: LDP_VC_FORMAT.to_owned(),
+            path: if single {
+                "$.verifiableCredential".to_owned()
+            } else {
+                format!("$.verifiableCredential[{index}]")
+            },
+        })
+        .collect();
+    let submission = PresentationSubmission {
+        id: Uuid::new_v4().to_string(),
+        definition_id: definition.id.clone(),
+        descriptor_map,
+    };
+
+    let mut presentation = VerifiablePresentation::new(holder, verifiable_credential);
+    presentation.context.push(Value::from(PRESENTATION_SUBMISSION_CONTEXT));
+    presentation
+        .presentation_type
+        .push(PRESENTATION_SUBMISSION_TYPE.to_owned());
+    presentation
+        .extra
+        .insert("presentation_submission".to_owned(), serde_json::to_value(submission)?);
+    Ok(presentation)
+}
+
+fn is_satisfiable(descriptor: &str, candidates: &HashMap<String, Vec<String>>) -> bool {
+    candidates
+        .get(descriptor)
+        .map(|candidates| !candidates.is_empty())
+        .unwrap_or_default()
+}
+
+/// Returns the input descriptors to submit to satisfy the requirement, or `None` if it
+/// cannot be satisfied by the candidates.
+fn satisfy_requirement(
+    definition: &PresentationDefinition,
+    requirement: &SubmissionRequirement,
+    candidates: &HashMap<String, Vec<String>>,
+) -> VcxResult<Option<Vec<String>>> {
+    let units: Vec<Option<Vec<String>>> = match (&requirement.from, &requirement.from_nested) {
+        (Some(group), _) => definition
+            .input_descriptors
+            .iter()
+            .filter(|descriptor| descriptor.group.contains(group))
+            .map(|descriptor| is_satisfiable(&descriptor.id, candidates).then(|| vec![descriptor.id.clone()]))
+            .collect(),
+        (None, Some(nested)) => nested
+            .iter()
+            .map(|requirement| satisfy_requirement(definition, requirement, candidates))
+            .collect::<VcxResult<_>>()?,
+        (None, None) => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidProofRequest,
+                "Submission requirement has neither from nor from_nested",
+            ))
+        }
+    };
+
+    let satisfied = match requirement.rule {
+        Rule::All => units.into_iter().collect::<Option<Vec<_>>>(),
+        Rule::Pick => {
+            let satisfied: Vec<Vec<String>> = units.into_iter().flatten().collect();
+            let available = satisfied.len();
+            let pick = match requirement.count {
+                Some(count) => count,
+                None => requirement.max.map_or(available, |max| available.min(max)),
+            };
+            let min = requirement.count.or(requirement.min).unwrap_or_default();
+            (pick <= available && pick >= min).then(|| satisfied.into_iter().take(pick).collect())
+        }
+    };
+    Ok(satisfied.map(|units| units.into_iter().flatten().collect()))
+}
+
+fn matches_descriptor(credential: &Value, descriptor: &InputDescriptor) -> VcxResult<bool> {
+    for field in &descriptor.constraints.fields {
+        if !matches_field(credential, field)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Evaluates the filter of the field against the first value found by its paths.
+fn matches_field(credential: &Value, field: &Field) -> VcxResult<bool> {
+    for path in &field.path {
+        let values = jsonpath_lib::select(credential, path).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidProofRequest,
+                format!("Invalid field path {path}: {err:?}"),
+            )
+        })?;
+        if let Some(value) = values.first() {
+            return match &field.filter {
+                Some(filter) => matches_filter(value, filter),
+                None => Ok(true),
+            };
+        }
+    }
+    Ok(field.optional)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    fn make_credential(credential_type: &str, subject: Value) -> VerifiableCredential {
+        serde_json::from_value(json!({
+            "@context": "https://www.w3.org/2018/credentials/v1",
+            "type": ["VerifiableCredential", credential_type],
+            "issuer": "did:example:issuer",
+            "issuanceDate": "2023-01-01T00:00:00Z",
+            "credentialSubject": subject
+        }))
+        .unwrap()
+    }
+
+    fn make_credentials() -> Vec<(String, VerifiableCredential)> {
+        vec![
+            (
+                "degree".to_owned(),
+                make_credential("UniversityDegreeCredential", json!({ "degree": "BachelorDegree" })),
+            ),
+            (
+                "license".to_owned(),
+                make_credential("DriversLicenseCredential", json!({ "birth_date": "1990-05-01" })),
+            ),
+            (
+                "passport".to_owned(),
+                make_credential("PassportCredential", json!({ "dob": "1985-11-20" })),
+            ),
+        ]
+    }
+
+    fn make_descriptor(id: &str, group: &str, credential_type: &str) -> Value {
+        json!({
+            "id": id,
+            "group": [group],
+            "constraints": {
+                "fields": [{
+                    "path": ["$.type"],
+                    "filter": { "type": "array", "contains": { "const": credential_type } }
+                }]
+            }
+        })
+    }
+
+    fn make_definition(submission_requirements: Option<Value>) -> PresentationDefinition {
+        let mut definition = json!({
+            "id": "32f54163-7166-48f1-93d8-ff217bdb0653",
+            "input_descriptors": [
+                make_descriptor("degree_input", "A", "UniversityDegreeCredential"),
+                make_descriptor("license_input", "B", "DriversLicenseCredential"),
+                make_descriptor("passport_input", "B", "PassportCredential"),
+                make_descriptor("employment_input", "B", "EmploymentCredential")
+            ]
+        });
+        if let Some(requirements) = submission_requirements {
+            definition["submission_requirements"] = requirements;
+        }
+        serde_json::from_value(definition).unwrap()
+    }
+
+    #[test]
+    fn test_match_credentials_by_field_filters() {
+        let definition: PresentationDefinition = serde_json::from_value(json!({
+            "id": "32f54163-7166-48f1-93d8-ff217bdb0653",
+            "input_descriptors": [{
+                "id": "born_before_1989",
+                "constraints": {
+                    "fields": [{
+                        "path": ["$.credentialSubject.dob", "$.credentialSubject.birth_date"],
+                        "filter": { "type": "string", "pattern": "^19[0-8]" }
+                    }]
+                }
+            }, {
+                "id": "anything",
+                "constraints": {
+                    "fields": [{ "path": ["$.credentialSubject.nickname"], "optional": true }]
+                }
+            }, {
+                "id": "undisclosed",
+                "constraints": { "limit_disclosure": "required" }
+            }]
+        }))
+        .unwrap();
+
+        let candidates = match_credentials(&definition, &make_credentials()).unwrap();
+        assert_eq!(candidates["born_before_1989"], vec!["passport".to_owned()]);
+        assert_eq!(candidates["anything"].len(), 3);
+        assert!(candidates["undisclosed"].is_empty());
+    }
+
+    #[test]
+    fn test_select_credentials_requires_every_descriptor_without_requirements() {
+        let definition = make_definition(None);
+        let candidates = match_credentials(&definition, &make_credentials()).unwrap();
+
+        let err = select_credentials(&definition, &candidates).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_select_credentials_with_submission_requirements() {
+        let definition = make_definition(Some(json!([
+            { "name": "Degree", "rule": "all", "from": "A" },
+            { "name": "Identity", "rule": "pick", "count": 1, "from": "B" }
+        ])));
+        let candidates = match_credentials(&definition, &make_credentials()).unwrap();
+
+        let selection = select_credentials(&definition, &candidates).unwrap();
+        assert_eq!(
+            selection,
+            HashMap::from([
+                ("degree_input".to_owned(), "degree".to_owned()),
+                ("license_input".to_owned(), "license".to_owned()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_select_credentials_with_nested_requirements() {
+        let definition = make_definition(Some(json!([{
+            "name": "Identity or degree",
+            "rule": "pick",
+            "min": 2,
+            "from_nested": [
+                { "rule": "all", "from": "A" },
+                { "rule": "pick", "min": 3, "from": "B" },
+                { "rule": "pick", "max": 2, "from": "B" }
+            ]
+        }])));
+        let candidates = match_credentials(&definition, &make_credentials()).unwrap();
+
+        let selection = select_credentials(&definition, &candidates).unwrap();
+        assert_eq!(selection.len(), 3);
+        assert_eq!(selection["passport_input"], "passport");
+
+        let definition = make_definition(Some(json!([
+            { "name": "Identity", "rule": "pick", "min": 3, "from": "B" }
+        ])));
+        assert!(select_credentials(&definition, &candidates).is_err());
+    }
+
+    #[test]
+    fn test_validate_selection() {
+        let definition = make_definition(Some(json!([
+            { "name": "Identity", "rule": "pick", "count": 1, "from": "B" }
+        ])));
+        let candidates = match_credentials(&definition, &make_credentials()).unwrap();
+
+        let selection = HashMap::from([("passport_input".to_owned(), "passport".to_owned())]);
+        assert_eq!(
+            validate_selection(&definition, &candidates, &selection).unwrap(),
+            selection
+        );
+
+        let selection = HashMap::from([("passport_input".to_owned(), "license".to_owned())]);
+        assert!(validate_selection(&definition, &candidates, &selection).is_err());
+    }
+
+    #[test]
+    fn test_build_presentation_maps_descriptors_to_credentials() {
+        let definition = make_definition(None);
+        let credentials = make_credentials();
+        let selection = HashMap::from([
+            ("degree_input".to_owned(), "degree".to_owned()),
+            ("license_input".to_owned(), "passport".to_owned()),
+            ("passport_input".to_owned(), "passport".to_owned()),
+        ]);
+
+        let presentation = build_presentation(
+            &definition,
+            Some("did:example:holder".to_owned()),
+            &credentials,
+            &selection,
+        )
+        .unwrap();
+        assert_eq!(presentation.verifiable_credential.len(), 2);
+        assert!(presentation
+            .presentation_type
+            .contains(&PRESENTATION_SUBMISSION_TYPE.to_owned()));
+
+        let submission: PresentationSubmission =
+            serde_json::from_value(presentation.extra["presentation_submission"].clone()).unwrap();
+        assert_eq!(submission.definition_id, definition.id);
+        let paths: Vec<(&str, &str)> = submission
+            .descriptor_map
+            .iter()
+            .map(|mapping| (mapping.id.as_str(), mapping.path.as_str()))
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                ("degree_input", "$.verifiableCredential[0]"),
+                ("license_input", "$.verifiableCredential[1]"),
+                ("passport_input", "$.verifiableCredential[1]"),
+            ]
+        );
+
+        let presentation = serde_json::to_value(&presentation).unwrap();
+        let credential = jsonpath_lib::select(&presentation, "$.verifiableCredential[1]").unwrap();
+        assert_eq!(credential[0]["credentialSubject"]["dob"], json!("1985-11-20"));
+    }
+}
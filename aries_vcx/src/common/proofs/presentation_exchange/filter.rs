@@ -0,0 +1,208 @@
+//! Evaluation of the JSON Schema filters of input descriptor fields.
+//!
+//! Filters are usually simple schemas constraining a single value, so only the validation
+//! keywords for primitive values and arrays, and the `not`, `allOf`, `anyOf` and `oneOf`
+//! combinations are supported. Values are never considered to match a filter using another
+//! keyword, as they cannot be checked against it.
+
+use regex::Regex;
+use serde_json::Value;
+
+use crate::errors::error::prelude::*;
+
+// Keywords which do not constrain the value
+const ANNOTATION_KEYWORDS: [&str; 5] = ["$schema", "$id", "title", "description", "format"];
+
+pub(super) fn matches_filter(value: &Value, filter: &Value) -> VcxResult<bool> {
+    let filter = match filter {
+        Value::Bool(accept) => return Ok(*accept),
+        Value::Object(filter) => filter,
+        _ => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                format!("Field filter must be a JSON Schema, got {filter}"),
+            ))
+        }
+    };
+
+    for (keyword, constraint) in filter {
+        if !matches_keyword(value, keyword, constraint)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn matches_keyword(value: &Value, keyword: &str, constraint: &Value) -> VcxResult<bool> {
+    let matches = match keyword {
+        "type" => match constraint {
+            Value::String(schema_type) => matches_type(value, schema_type),
+            Value::Array(types) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .any(|schema_type| matches_type(value, schema_type)),
+            _ => return Err(invalid_keyword(keyword, constraint)),
+        },
+        "const" => value == constraint,
+        "enum" => constraint
+            .as_array()
+            .ok_or_else(|| invalid_keyword(keyword, constraint))?
+            .contains(value),
+        "pattern" => {
+            let pattern = constraint
+                .as_str()
+                .ok_or_else(|| invalid_keyword(keyword, constraint))?;
+            let regex = Regex::new(pattern).map_err(|_| invalid_keyword(keyword, constraint))?;
+            match value.as_str() {
+                Some(value) => regex.is_match(value),
+                None => true,
+            }
+        }
+        "minLength" | "maxLength" => {
+            let bound = constraint
+                .as_u64()
+                .ok_or_else(|| invalid_keyword(keyword, constraint))?;
+            match value.as_str().map(|value| value.chars().count() as u64) {
+                Some(length) if keyword == "minLength" => length >= bound,
+                Some(length) => length <= bound,
+                None => true,
+            }
+        }
+        "minimum" | "maximum" | "exclusiveMinimum" | "exclusiveMaximum" => {
+            let bound = constraint
+                .as_f64()
+                .ok_or_else(|| invalid_keyword(keyword, constraint))?;
+            match (value.as_f64(), keyword) {
+                (None, _) => true,
+                (Some(value), "minimum") => value >= bound,
+                (Some(value), "maximum") => value <= bound,
+                (Some(value), "exclusiveMinimum") => value > bound,
+                (Some(value), _) => value < bound,
+            }
+        }
+        "minItems" | "maxItems" => {
+            let bound = constraint
+                .as_u64()
+                .ok_or_else(|| invalid_keyword(keyword, constraint))?;
+            match value.as_array().map(|values| values.len() as u64) {
+                Some(length) if keyword == "minItems" => length >= bound,
+                Some(length) => length <= bound,
+                None => true,
+            }
+        }
+        "contains" => match value {
+            Value::Array(values) => {
+                let mut contains = false;
+                for value in values {
+                    if matches_filter(value, constraint)? {
+                        contains = true;
+                        break;
+                    }
+                }
+                contains
+            }
+            _ => true,
+        },
+        "not" => !matches_filter(value, constraint)?,
+        "allOf" | "anyOf" | "oneOf" => {
+            let schemas = constraint
+                .as_array()
+                .ok_or_else(|| invalid_keyword(keyword, constraint))?;
+            let mut matching = 0;
+            for schema in schemas {
+                if matches_filter(value, schema)? {
+                    matching += 1;
+                }
+            }
+            match keyword {
+                "allOf" => matching == schemas.len(),
+                "anyOf" => matching > 0,
+                _ => matching == 1,
+            }
+        }
+        keyword if ANNOTATION_KEYWORDS.contains(&keyword) => true,
+        _ => {
+            warn!(
+                "Field filter keyword {} is not supported, value {} is considered not to match",
+                keyword, value
+            );
+            false
+        }
+    };
+    Ok(matches)
+}
+
+fn matches_type(value: &Value, schema_type: &str) -> bool {
+    match schema_type {
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "boolean" => value.is_boolean(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        "null" => value.is_null(),
+        _ => false,
+    }
+}
+
+fn invalid_keyword(keyword: &str, constraint: &Value) -> AriesVcxError {
+    AriesVcxError::from_msg(
+        AriesVcxErrorKind::InvalidInput,
+        format!("Invalid value {constraint} of field filter keyword {keyword}"),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_filter_on_strings() {
+        let filter = json!({ "type": "string", "pattern": "^did:example:", "minLength": 12 });
+        assert!(matches_filter(&json!("did:example:issuer"), &filter).unwrap());
+        assert!(!matches_filter(&json!("did:sov:issuer"), &filter).unwrap());
+        assert!(!matches_filter(&json!(42), &filter).unwrap());
+    }
+
+    #[test]
+    fn test_matches_filter_on_numbers() {
+        let filter = json!({ "type": "integer", "minimum": 18, "exclusiveMaximum": 65 });
+        assert!(matches_filter(&json!(18), &filter).unwrap());
+        assert!(!matches_filter(&json!(65), &filter).unwrap());
+        assert!(!matches_filter(&json!(17.5), &filter).unwrap());
+    }
+
+    #[test]
+    fn test_matches_filter_on_arrays() {
+        let filter = json!({ "type": "array", "contains": { "const": "UniversityDegreeCredential" } });
+        assert!(matches_filter(&json!(["VerifiableCredential", "UniversityDegreeCredential"]), &filter).unwrap());
+        assert!(!matches_filter(&json!(["VerifiableCredential"]), &filter).unwrap());
+
+        let filter = json!({ "not": { "enum": ["revoked", "suspended"] } });
+        assert!(matches_filter(&json!("active"), &filter).unwrap());
+        assert!(!matches_filter(&json!("revoked"), &filter).unwrap());
+    }
+
+    #[test]
+    fn test_matches_filter_combinations() {
+        let filter = json!({ "anyOf": [{ "const": "BachelorDegree" }, { "const": "MasterDegree" }] });
+        assert!(matches_filter(&json!("MasterDegree"), &filter).unwrap());
+        assert!(!matches_filter(&json!("DoctorDegree"), &filter).unwrap());
+
+        let filter = json!({ "allOf": [{ "type": "array", "minItems": 2 }, { "maxItems": 3 }] });
+        assert!(matches_filter(&json!(["a", "b"]), &filter).unwrap());
+        assert!(!matches_filter(&json!(["a"]), &filter).unwrap());
+        assert!(!matches_filter(&json!(["a", "b", "c", "d"]), &filter).unwrap());
+
+        let filter = json!({ "oneOf": [{ "type": "integer" }, { "minimum": 10 }] });
+        assert!(matches_filter(&json!(5), &filter).unwrap());
+        assert!(!matches_filter(&json!(12), &filter).unwrap());
+    }
+
+    #[test]
+    fn test_matches_filter_does_not_match_unsupported_keywords() {
+        let filter = json!({ "type": "string", "$schema": "http://json-schema.org/draft-07/schema#", "if": {} });
+        assert!(!matches_filter(&json!("value"), &filter).unwrap());
+    }
+}
@@ -0,0 +1,10 @@
+//! [DIF Presentation Exchange 2.0](https://identity.foundation/presentation-exchange/spec/v2.0.0/)
+//! for W3C verifiable credentials.
+//!
+//! The evaluator selects the credentials of the holder satisfying a [`definition::PresentationDefinition`]
+//! and builds the presentation answering it, along with its [`submission::PresentationSubmission`].
+
+pub mod definition;
+pub mod evaluator;
+mod filter;
+pub mod submission;
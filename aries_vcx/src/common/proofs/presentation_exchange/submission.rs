@@ -0,0 +1,18 @@
+/// Describes how the credentials of a presentation satisfy the input descriptors of a
+/// [`PresentationDefinition`](super::definition::PresentationDefinition).
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PresentationSubmission {
+    pub id: String,
+    pub definition_id: String,
+    pub descriptor_map: Vec<InputDescriptorMapping>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct InputDescriptorMapping {
+    /// Identifier of the satisfied input descriptor.
+    pub id: String,
+    /// Claim format of the submitted credential, e.g. `ldp_vc`.
+    pub format: String,
+    /// JSONPath locating the credential within the presentation.
+    pub path: String,
+}
@@ -2,10 +2,13 @@ use std::sync::Arc;
 
 use aries_vcx_core::wallet::base_wallet::BaseWallet;
 use base64;
+use messages::decorators::attachment::{AttachmentJws, JwsHeader};
 use messages::msg_fields::protocols::connection::response::{ConnectionSignature, ResponseContent};
 use messages::msg_fields::protocols::connection::ConnectionData;
+use serde_json::Value;
 use time;
 
+use crate::common::ledger::transactions::{ed25519_public_key_to_did_key, normalize_keys_as_naked};
 use crate::errors::error::prelude::*;
 use crate::global::settings;
 
@@ -78,6 +81,90 @@ pub async fn decode_signed_connection_response(
     Ok(connection)
 }
 
+/// Signs the `base64` content of an attachment, returning the JWS to set as its `jws`,
+/// as described by the [attachments RFC](https://github.com/hyperledger/aries-rfcs/blob/main/concepts/0017-attachments/README.md#signing-attachments).
+pub async fn sign_attachment_jws(
+    wallet: &Arc<dyn BaseWallet>,
+    key: &str,
+    payload_b64: &str,
+) -> VcxResult<AttachmentJws> {
+    let kid = ed25519_public_key_to_did_key(key)?;
+    let public_key = bs58::decode(key).into_vec().map_err(|_| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidVerkey,
+            format!("Verkey {key} is not base58 encoded"),
+        )
+    })?;
+
+    let protected = json!({
+        "alg": "EdDSA",
+        "kid": kid,
+        "jwk": {
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "x": base64::encode_config(&public_key, base64::URL_SAFE_NO_PAD),
+            "kid": kid,
+        },
+    })
+    .to_string();
+    let protected = base64::encode_config(protected.as_bytes(), base64::URL_SAFE_NO_PAD);
+
+    let signing_input = jws_signing_input(&protected, payload_b64);
+    let signature = wallet.sign(key, signing_input.as_bytes()).await?;
+    let signature = base64::encode_config(&signature, base64::URL_SAFE_NO_PAD);
+
+    Ok(AttachmentJws::new(JwsHeader::new(kid), protected, signature))
+}
+
+/// Verifies the JWS of an attachment created by [`sign_attachment_jws`] against the attachment's
+/// `base64` content, returning the verkey of the signer.
+///
+/// The signer is taken from the `jwk` of the protected header, falling back to its `kid`.
+pub async fn verify_attachment_jws(
+    wallet: &Arc<dyn BaseWallet>,
+    jws: &AttachmentJws,
+    payload_b64: &str,
+) -> VcxResult<String> {
+    let invalid_jws =
+        |msg: &str| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidJson, format!("Invalid JWS: {msg}"));
+
+    let header = base64::decode_config(&jws.protected, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| invalid_jws("protected header is not base64url encoded"))?;
+    let header: Value =
+        serde_json::from_slice(&header).map_err(|_| invalid_jws("protected header is not a JSON object"))?;
+
+    let signer = match (header["jwk"]["x"].as_str(), header["kid"].as_str()) {
+        (Some(x), _) => {
+            let public_key = base64::decode_config(x, base64::URL_SAFE_NO_PAD)
+                .map_err(|_| invalid_jws("jwk of the protected header is not base64url encoded"))?;
+            bs58::encode(public_key).into_string()
+        }
+        (None, Some(kid)) => normalize_keys_as_naked(vec![kid.to_owned()])?
+            .pop()
+            .ok_or_else(|| invalid_jws("protected header does not contain a key id"))?,
+        (None, None) => return Err(invalid_jws("protected header does not identify the signing key")),
+    };
+
+    let signature = base64::decode_config(&jws.signature, base64::URL_SAFE_NO_PAD)
+        .map_err(|_| invalid_jws("signature is not base64url encoded"))?;
+    let signing_input = jws_signing_input(&jws.protected, payload_b64);
+
+    if !wallet.verify(&signer, signing_input.as_bytes(), &signature).await? {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "JWS signature is invalid",
+        ));
+    }
+
+    Ok(signer)
+}
+
+/// Attachments carry standard base64, while the JWS payload is unpadded base64url.
+fn jws_signing_input(protected: &str, payload_b64: &str) -> String {
+    let payload = payload_b64.trim_end_matches('=').replace('+', "-").replace('/', "_");
+    format!("{protected}.{payload}")
+}
+
 pub async fn unpack_message_to_string(wallet: &Arc<dyn BaseWallet>, msg: &[u8]) -> VcxResult<String> {
     if settings::indy_mocks_enabled() {
         return Ok(String::new());
@@ -97,6 +184,88 @@ pub async fn unpack_message_to_string(wallet: &Arc<dyn BaseWallet>, msg: &[u8])
     })
 }
 
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use aries_vcx_core::wallet::indy_wallet::IndySdkWallet;
+
+    use super::*;
+    use crate::utils::devsetup::SetupLibraryWallet;
+
+    // Standard base64 of a payload whose encoding contains padding as well as '+' and '/'
+    const PAYLOAD_B64: &str = "eyJpZCI6IjEyMyJ9+/8=";
+
+    #[tokio::test]
+    async fn test_attachment_jws_round_trip() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let jws = sign_attachment_jws(&wallet, &verkey, PAYLOAD_B64).await.unwrap();
+            assert_eq!(jws.header.kid, ed25519_public_key_to_did_key(&verkey).unwrap());
+            assert!(!jws.signature.ends_with('='));
+
+            let signer = verify_attachment_jws(&wallet, &jws, PAYLOAD_B64).await.unwrap();
+            assert_eq!(signer, verkey);
+
+            // The signing input does not depend on the base64 alphabet nor padding of the payload
+            let signer = verify_attachment_jws(&wallet, &jws, "eyJpZCI6IjEyMyJ9-_8")
+                .await
+                .unwrap();
+            assert_eq!(signer, verkey);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_attachment_jws_with_tampered_payload_fails() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let jws = sign_attachment_jws(&wallet, &verkey, PAYLOAD_B64).await.unwrap();
+            let err = verify_attachment_jws(&wallet, &jws, "eyJpZCI6IjQ1NiJ9")
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_attachment_jws_with_swapped_signer_fails() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let (_, other_verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let mut jws = sign_attachment_jws(&wallet, &verkey, PAYLOAD_B64).await.unwrap();
+            let other_jws = sign_attachment_jws(&wallet, &other_verkey, PAYLOAD_B64).await.unwrap();
+            jws.protected = other_jws.protected;
+            jws.header = other_jws.header;
+
+            let err = verify_attachment_jws(&wallet, &jws, PAYLOAD_B64).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_attachment_jws_with_malformed_protected_header_fails() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let mut jws = sign_attachment_jws(&wallet, &verkey, PAYLOAD_B64).await.unwrap();
+            jws.protected = base64::encode_config(r#"{"alg":"EdDSA"}"#, base64::URL_SAFE_NO_PAD);
+
+            let err = verify_attachment_jws(&wallet, &jws, PAYLOAD_B64).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+        })
+        .await;
+    }
+}
+
 // #[cfg(test)]
 // pub mod unit_tests {
 //     use crate::common::test_utils::{create_trustee_key, indy_handles_to_profile};
@@ -0,0 +1,340 @@
+//! Migration of the records of a vdrtools (indy) wallet to the record formats used by the modular
+//! libraries, so that a holder can move from `VdrtoolsProfile` to `ModularLibsProfile`.
+//!
+//! The migration only adds records to the target wallet and never modifies the source wallet.
+//! Records already present in the target wallet are skipped, so an interrupted migration
+//! can be resumed by running it again.
+
+use std::{collections::HashMap, sync::Arc};
+
+use aries_vcx_core::{
+    anoncreds::credx_anoncreds::{IndyCredxAnonCreds, CATEGORY_CREDENTIAL, CATEGORY_LINK_SECRET},
+    errors::error::AriesVcxCoreErrorKind,
+    wallet::{base_wallet::BaseWallet, indy_wallet::IndySdkWallet},
+};
+
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+/// Record type of the master (link) secrets stored by vdrtools.
+pub const INDY_MASTER_SECRET: &str = "Indy::MasterSecret";
+/// Record type of the credentials stored by vdrtools.
+pub const INDY_CREDENTIAL: &str = "Indy::Credential";
+/// Record type of the DIDs created by vdrtools.
+pub const INDY_DID: &str = "Indy::Did";
+/// Record type of the key pairs created by vdrtools.
+pub const INDY_KEY: &str = "Indy::Key";
+/// Record type of the DIDs of other parties stored by vdrtools.
+pub const INDY_THEIR_DID: &str = "Indy::TheirDid";
+/// Record type of the pairwise relationships stored by vdrtools.
+pub const INDY_PAIRWISE: &str = "Indy::Pairwise";
+
+const ED25519_SEED_LENGTH: usize = 32;
+const INDY_DID_LENGTH: usize = 16;
+
+/// A wallet record, identified by its type and id.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordRef {
+    pub record_type: String,
+    pub id: String,
+}
+
+/// A wallet record which could not be migrated.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FailedRecord {
+    pub record: RecordRef,
+    pub reason: String,
+}
+
+/// The outcome of a migration run.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationReport {
+    /// Records migrated by this run.
+    pub migrated: Vec<RecordRef>,
+    /// Records already present in the target wallet, e.g. migrated by a previous run.
+    pub skipped: Vec<RecordRef>,
+    /// Records which could not be migrated.
+    pub failed: Vec<FailedRecord>,
+}
+
+impl MigrationReport {
+    /// Returns `true` if every record of the source wallet is present in the target wallet.
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    fn add(&mut self, record_type: &str, id: &str, outcome: VcxResult<Outcome>) {
+        let record = RecordRef {
+            record_type: record_type.to_owned(),
+            id: id.to_owned(),
+        };
+
+        match outcome {
+            Ok(Outcome::Migrated) => self.migrated.push(record),
+            Ok(Outcome::AlreadyMigrated) => self.skipped.push(record),
+            Err(err) => {
+                warn!("Failed to migrate wallet record {:?}: {}", record, err);
+                self.failed.push(FailedRecord {
+                    record,
+                    reason: err.to_string(),
+                })
+            }
+        }
+    }
+}
+
+enum Outcome {
+    Migrated,
+    AlreadyMigrated,
+}
+
+/// A record as returned by [`IndySdkWallet::get_all_records_for_migration`].
+#[derive(Debug, Deserialize)]
+struct WalletRecord {
+    id: String,
+    value: Option<String>,
+    tags: Option<HashMap<String, String>>,
+}
+
+impl WalletRecord {
+    fn value(&self) -> VcxResult<&str> {
+        self.value.as_deref().ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::WalletRecordNotFound,
+                format!("Wallet record {} has no value", self.id),
+            )
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct IndyMasterSecret {
+    value: IndyMasterSecretValue,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndyMasterSecretValue {
+    ms: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndyDid {
+    did: String,
+    verkey: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IndyKey {
+    verkey: String,
+    signkey: String,
+}
+
+/// Migrates the records of a vdrtools wallet into another [`BaseWallet`]:
+/// * master secrets and credentials are re-encoded into the records used by [`IndyCredxAnonCreds`],
+/// * DIDs are recreated, along with their keys,
+/// * pairwise records, and the records of the types added through [`WalletMigrator::copy_record_types`], are copied
+///   as they are.
+pub struct WalletMigrator {
+    source: IndySdkWallet,
+    target: Arc<dyn BaseWallet>,
+    target_anoncreds: IndyCredxAnonCreds,
+    copied_record_types: Vec<String>,
+}
+
+impl WalletMigrator {
+    pub fn new(source: IndySdkWallet, target: Arc<dyn BaseWallet>) -> Self {
+        Self {
+            source,
+            target_anoncreds: IndyCredxAnonCreds::new(Arc::clone(&target)),
+            target,
+            copied_record_types: vec![INDY_THEIR_DID.to_owned(), INDY_PAIRWISE.to_owned()],
+        }
+    }
+
+    /// Adds record types whose records are copied as they are (e.g. the ones used by the application).
+    pub fn copy_record_types<I, T>(mut self, record_types: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.copied_record_types
+            .extend(record_types.into_iter().map(Into::into));
+        self
+    }
+
+    /// Migrates all the records, returning a report of the migrated, skipped and failed records.
+    ///
+    /// Failing to migrate a record doesn't stop the migration, while failing to walk the records
+    /// of the source wallet does.
+    pub async fn migrate(&self) -> VcxResult<MigrationReport> {
+        let mut report = MigrationReport::default();
+
+        for record in fetch_records(&self.source, INDY_MASTER_SECRET).await? {
+            let outcome = self.migrate_master_secret(&record).await;
+            report.add(INDY_MASTER_SECRET, &record.id, outcome);
+        }
+
+        for record in fetch_records(&self.source, INDY_CREDENTIAL).await? {
+            let outcome = self.migrate_credential(&record).await;
+            report.add(INDY_CREDENTIAL, &record.id, outcome);
+        }
+
+        let signkeys = self.fetch_signkeys().await?;
+        for record in fetch_records(&self.source, INDY_DID).await? {
+            let outcome = self.migrate_did(&record, &signkeys).await;
+            report.add(INDY_DID, &record.id, outcome);
+        }
+
+        for record_type in self.copied_record_types.iter() {
+            for record in fetch_records(&self.source, record_type).await? {
+                let outcome = self.copy_record(record_type, &record).await;
+                report.add(record_type, &record.id, outcome);
+            }
+        }
+
+        info!(
+            "WalletMigrator::migrate <<< migrated: {}, skipped: {}, failed: {}",
+            report.migrated.len(),
+            report.skipped.len(),
+            report.failed.len()
+        );
+
+        Ok(report)
+    }
+
+    async fn migrate_master_secret(&self, record: &WalletRecord) -> VcxResult<Outcome> {
+        if record_exists(&self.target, CATEGORY_LINK_SECRET, &record.id).await? {
+            return Ok(Outcome::AlreadyMigrated);
+        }
+
+        let master_secret: IndyMasterSecret = serde_json::from_str(record.value()?)?;
+        self.target_anoncreds
+            .prover_import_link_secret(&record.id, &master_secret.value.ms)
+            .await?;
+
+        Ok(Outcome::Migrated)
+    }
+
+    async fn migrate_credential(&self, record: &WalletRecord) -> VcxResult<Outcome> {
+        if record_exists(&self.target, CATEGORY_CREDENTIAL, &record.id).await? {
+            return Ok(Outcome::AlreadyMigrated);
+        }
+
+        self.target_anoncreds
+            .prover_import_credential(&record.id, record.value()?)
+            .await?;
+
+        Ok(Outcome::Migrated)
+    }
+
+    async fn fetch_signkeys(&self) -> VcxResult<HashMap<String, String>> {
+        fetch_records(&self.source, INDY_KEY)
+            .await?
+            .iter()
+            .map(|record| -> VcxResult<(String, String)> {
+                let key: IndyKey = serde_json::from_str(record.value()?)?;
+                Ok((key.verkey, key.signkey))
+            })
+            .collect()
+    }
+
+    async fn migrate_did(&self, record: &WalletRecord, signkeys: &HashMap<String, String>) -> VcxResult<Outcome> {
+        let IndyDid { did, verkey } = serde_json::from_str(record.value()?)?;
+
+        if let Ok(target_verkey) = self.target.key_for_local_did(&did).await {
+            return if target_verkey == verkey {
+                Ok(Outcome::AlreadyMigrated)
+            } else {
+                Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::DuplicationDid,
+                    format!("DID {} is already stored in the target wallet with another verkey", did),
+                ))
+            };
+        }
+
+        let (method_name, unqualified_did) = match did.strip_prefix("did:").and_then(|did| did.split_once(':')) {
+            Some((method_name, unqualified_did)) => (Some(method_name), unqualified_did),
+            None => (None, did.as_str()),
+        };
+
+        let verkey_bytes = decode_base58(&verkey)?;
+        if verkey_bytes.len() < INDY_DID_LENGTH
+            || bs58::encode(&verkey_bytes[..INDY_DID_LENGTH]).into_string() != unqualified_did
+        {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                format!(
+                    "DID {} is not derived from its verkey (e.g. its keys were rotated) and cannot be recreated",
+                    did
+                ),
+            ));
+        }
+
+        let signkey = signkeys.get(&verkey).ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::WalletRecordNotFound,
+                format!("No key pair is stored for the verkey {} of DID {}", verkey, did),
+            )
+        })?;
+
+        // The vdrtools signing key is the Ed25519 seed followed by the public key
+        let signkey_bytes = decode_base58(signkey)?;
+        if signkey_bytes.len() < ED25519_SEED_LENGTH {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                format!("Invalid signing key stored for the verkey {}", verkey),
+            ));
+        }
+        let seed = base64::encode(&signkey_bytes[..ED25519_SEED_LENGTH]);
+
+        let (new_did, new_verkey) = self.target.create_and_store_my_did(Some(&seed), method_name).await?;
+        if new_did != did || new_verkey != verkey {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                format!(
+                    "DID {} was recreated as DID {} with verkey {} instead of {}",
+                    did, new_did, new_verkey, verkey
+                ),
+            ));
+        }
+
+        Ok(Outcome::Migrated)
+    }
+
+    async fn copy_record(&self, record_type: &str, record: &WalletRecord) -> VcxResult<Outcome> {
+        if record_exists(&self.target, record_type, &record.id).await? {
+            return Ok(Outcome::AlreadyMigrated);
+        }
+
+        self.target
+            .add_wallet_record(record_type, &record.id, record.value()?, record.tags.clone())
+            .await?;
+
+        Ok(Outcome::Migrated)
+    }
+}
+
+async fn fetch_records(wallet: &IndySdkWallet, record_type: &str) -> VcxResult<Vec<WalletRecord>> {
+    let records = wallet.get_all_records_for_migration(record_type).await?;
+
+    records
+        .iter()
+        .map(|record| serde_json::from_str(record).map_err(AriesVcxError::from))
+        .collect()
+}
+
+async fn record_exists(wallet: &Arc<dyn BaseWallet>, record_type: &str, id: &str) -> VcxResult<bool> {
+    match wallet.get_wallet_record(record_type, id, "{}").await {
+        Ok(_) => Ok(true),
+        Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn decode_base58(value: &str) -> VcxResult<Vec<u8>> {
+    bs58::decode(value).into_vec().map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::NotBase58,
+            format!("Value {} is not valid base58: {}", value, err),
+        )
+    })
+}
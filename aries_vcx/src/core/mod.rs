@@ -1 +1,3 @@
+#[cfg(all(feature = "vdrtools", feature = "modular_libs"))]
+pub mod migration;
 pub mod profile;
@@ -1,6 +1,7 @@
 use std::sync::PoisonError;
 
 use aries_vcx_core::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind};
+use did_resolver_peer::error::DidPeerError;
 
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind};
 use crate::protocols::revocation_notification::sender::state_machine::SenderConfigBuilderError;
@@ -18,6 +19,12 @@ impl From<serde_json::Error> for AriesVcxError {
     }
 }
 
+impl From<DidPeerError> for AriesVcxError {
+    fn from(err: DidPeerError) -> Self {
+        AriesVcxError::from_msg(AriesVcxErrorKind::InvalidDid, err.to_string())
+    }
+}
+
 impl<T> From<PoisonError<T>> for AriesVcxError {
     fn from(err: PoisonError<T>) -> Self {
         AriesVcxError::from_msg(AriesVcxErrorKind::InvalidState, err.to_string())
@@ -0,0 +1,329 @@
+use std::collections::HashMap;
+
+use chrono::Utc;
+use messages::decorators::thread::Thread;
+use messages::decorators::timing::Timing;
+use messages::msg_fields::protocols::action_menu::menu::{Menu, MenuContent, MenuDecorators, MenuOption};
+use messages::msg_fields::protocols::action_menu::menu_request::{
+    MenuRequest, MenuRequestContent, MenuRequestDecorators,
+};
+use messages::msg_fields::protocols::action_menu::perform::{Perform, PerformContent, PerformDecorators};
+use uuid::Uuid;
+
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+use crate::protocols::SendClosure;
+
+use super::util::matches_opt_thread_id;
+
+/// Menu option chosen by the requester, with the values of all the parameters of its form.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MenuSelection {
+    pub option: MenuOption,
+    pub params: HashMap<String, String>,
+}
+
+/// Displays an action menu to the other party of a connection and processes the options
+/// it performs.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ActionMenuResponder {
+    menu: Menu,
+}
+
+impl ActionMenuResponder {
+    /// Builds a menu starting a new thread.
+    pub fn build(content: MenuContent) -> Self {
+        Self {
+            menu: build_menu(content, None),
+        }
+    }
+
+    /// Builds a menu answering the `menu-request` of the other party.
+    pub fn build_for_request(content: MenuContent, request: &MenuRequest) -> Self {
+        let thread_id = request
+            .decorators
+            .thread
+            .as_ref()
+            .map(|t| t.thid.clone())
+            .unwrap_or(request.id.clone());
+        Self {
+            menu: build_menu(content, Some(thread_id)),
+        }
+    }
+
+    pub fn get_menu(&self) -> &Menu {
+        &self.menu
+    }
+
+    pub fn get_thread_id(&self) -> &str {
+        menu_thread_id(&self.menu)
+    }
+
+    pub async fn send_menu(&self, send_message: SendClosure) -> VcxResult<()> {
+        send_message(self.menu.clone().into()).await
+    }
+
+    /// Resolves the option selected by a `perform` message, filling in the default values of
+    /// the form parameters left out by the requester.
+    pub fn handle_perform(&self, perform: &Perform) -> VcxResult<MenuSelection> {
+        if !matches_opt_thread_id!(perform, self.get_thread_id()) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Thread ID of perform message does not match the menu",
+            ));
+        }
+        select_option(&self.menu.content, &perform.content.name, &perform.content.params)
+    }
+}
+
+/// Requests the action menu of the other party of a connection and performs its options.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ActionMenuRequester {
+    menu: Option<Menu>,
+}
+
+impl ActionMenuRequester {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_menu(&self) -> Option<&Menu> {
+        self.menu.as_ref()
+    }
+
+    pub async fn send_menu_request(&self, send_message: SendClosure) -> VcxResult<()> {
+        let decorators = MenuRequestDecorators {
+            timing: Some(out_timing()),
+            ..Default::default()
+        };
+        let request =
+            MenuRequest::with_decorators(Uuid::new_v4().to_string(), MenuRequestContent::default(), decorators);
+        send_message(request.into()).await
+    }
+
+    /// Stores the received menu, replacing the previously displayed one.
+    pub fn handle_menu(&mut self, menu: Menu) {
+        self.menu = Some(menu);
+    }
+
+    pub async fn perform(
+        &self,
+        name: &str,
+        params: HashMap<String, String>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        let menu = self.menu.as_ref().ok_or_else(|| {
+            AriesVcxError::from_msg(AriesVcxErrorKind::NotReady, "No action menu has been received yet")
+        })?;
+        let selection = select_option(&menu.content, name, &params)?;
+
+        let mut content = PerformContent::new(selection.option.name);
+        content.params = selection.params;
+        let decorators = PerformDecorators {
+            thread: Some(Thread::new(menu_thread_id(menu).to_owned())),
+            timing: Some(out_timing()),
+        };
+        let perform = Perform::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+        send_message(perform.into()).await
+    }
+}
+
+fn build_menu(content: MenuContent, thread_id: Option<String>) -> Menu {
+    let decorators = MenuDecorators {
+        thread: thread_id.map(Thread::new),
+        timing: Some(out_timing()),
+    };
+    Menu::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+fn menu_thread_id(menu: &Menu) -> &str {
+    menu.decorators
+        .thread
+        .as_ref()
+        .map(|t| t.thid.as_str())
+        .unwrap_or(menu.id.as_str())
+}
+
+fn out_timing() -> Timing {
+    Timing {
+        out_time: Some(Utc::now()),
+        ..Default::default()
+    }
+}
+
+fn select_option(menu: &MenuContent, name: &str, params: &HashMap<String, String>) -> VcxResult<MenuSelection> {
+    let option = menu.options.iter().find(|option| option.name == name).ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            format!("Menu has no option named {name}"),
+        )
+    })?;
+    if option.disabled {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::ActionNotSupported,
+            format!("Menu option {name} is disabled"),
+        ));
+    }
+
+    let form_params = option
+        .form
+        .as_ref()
+        .map(|form| form.params.as_slice())
+        .unwrap_or_default();
+    if let Some(unknown) = params.keys().find(|key| !form_params.iter().any(|p| &p.name == *key)) {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            format!("Menu option {name} has no parameter named {unknown}"),
+        ));
+    }
+
+    let mut selected_params = HashMap::new();
+    for param in form_params {
+        match params.get(&param.name).or(param.default.as_ref()) {
+            Some(value) => {
+                selected_params.insert(param.name.clone(), value.clone());
+            }
+            None if param.required => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidInput,
+                    format!("Missing required parameter {} of menu option {name}", param.name),
+                ))
+            }
+            None => {}
+        }
+    }
+
+    Ok(MenuSelection {
+        option: option.clone(),
+        params: selected_params,
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::{Arc, Mutex};
+
+    use messages::msg_fields::protocols::action_menu::menu::{MenuForm, MenuFormParam};
+    use messages::msg_fields::protocols::action_menu::ActionMenu;
+    use messages::AriesMessage;
+
+    use super::*;
+
+    fn _menu_content() -> MenuContent {
+        let mut query = MenuFormParam::new("query".to_owned(), "Participant name".to_owned());
+        query.required = true;
+        let mut limit = MenuFormParam::new("limit".to_owned(), "Maximum results".to_owned());
+        limit.default = Some("10".to_owned());
+        let form = MenuForm {
+            params: vec![query, limit],
+            ..Default::default()
+        };
+
+        let mut search = MenuOption::new("search".to_owned(), "Search introductions".to_owned());
+        search.form = Some(form);
+        let mut disabled = MenuOption::new("obtain-email-cred".to_owned(), "Obtain email credential".to_owned());
+        disabled.disabled = true;
+
+        MenuContent::new(vec![search, disabled])
+    }
+
+    fn _send_message(sent: Arc<Mutex<Vec<AriesMessage>>>) -> SendClosure {
+        Box::new(move |message: AriesMessage| {
+            sent.lock().unwrap().push(message);
+            Box::pin(async { VcxResult::Ok(()) })
+        })
+    }
+
+    fn _params(params: &[(&str, &str)]) -> HashMap<String, String> {
+        params
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_select_option_applies_defaults() {
+        let selection = select_option(&_menu_content(), "search", &_params(&[("query", "Alice")])).unwrap();
+        assert_eq!(selection.option.name, "search");
+        assert_eq!(selection.params, _params(&[("query", "Alice"), ("limit", "10")]));
+    }
+
+    #[test]
+    fn test_select_option_rejects_invalid_selections() {
+        let menu = _menu_content();
+        assert_eq!(
+            select_option(&menu, "unknown", &HashMap::new()).unwrap_err().kind(),
+            AriesVcxErrorKind::InvalidInput
+        );
+        assert_eq!(
+            select_option(&menu, "obtain-email-cred", &HashMap::new())
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::ActionNotSupported
+        );
+        assert_eq!(
+            select_option(&menu, "search", &HashMap::new()).unwrap_err().kind(),
+            AriesVcxErrorKind::InvalidInput
+        );
+        assert_eq!(
+            select_option(&menu, "search", &_params(&[("query", "Alice"), ("sort", "asc")]))
+                .unwrap_err()
+                .kind(),
+            AriesVcxErrorKind::InvalidInput
+        );
+    }
+
+    #[tokio::test]
+    async fn test_menu_request_menu_perform() {
+        let sent = Arc::new(Mutex::new(Vec::new()));
+
+        let requester = ActionMenuRequester::new();
+        requester.send_menu_request(_send_message(sent.clone())).await.unwrap();
+        let request = match sent.lock().unwrap().pop().unwrap() {
+            AriesMessage::ActionMenu(ActionMenu::MenuRequest(request)) => request,
+            message => panic!("Unexpected message {message:?}"),
+        };
+
+        let responder = ActionMenuResponder::build_for_request(_menu_content(), &request);
+        assert_eq!(responder.get_thread_id(), request.id);
+        responder.send_menu(_send_message(sent.clone())).await.unwrap();
+        let menu = match sent.lock().unwrap().pop().unwrap() {
+            AriesMessage::ActionMenu(ActionMenu::Menu(menu)) => menu,
+            message => panic!("Unexpected message {message:?}"),
+        };
+
+        let mut requester = requester;
+        requester.handle_menu(menu);
+        requester
+            .perform("search", _params(&[("query", "Alice")]), _send_message(sent.clone()))
+            .await
+            .unwrap();
+        let perform = match sent.lock().unwrap().pop().unwrap() {
+            AriesMessage::ActionMenu(ActionMenu::Perform(perform)) => perform,
+            message => panic!("Unexpected message {message:?}"),
+        };
+
+        let selection = responder.handle_perform(&perform).unwrap();
+        assert_eq!(selection.option.name, "search");
+        assert_eq!(selection.params, _params(&[("query", "Alice"), ("limit", "10")]));
+    }
+
+    #[test]
+    fn test_handle_perform_rejects_other_thread() {
+        let responder = ActionMenuResponder::build(_menu_content());
+        let decorators = PerformDecorators {
+            thread: Some(Thread::new("other_thread".to_owned())),
+            ..Default::default()
+        };
+        let perform = Perform::with_decorators(
+            Uuid::new_v4().to_string(),
+            PerformContent::new("search".to_owned()),
+            decorators,
+        );
+
+        assert_eq!(
+            responder.handle_perform(&perform).unwrap_err().kind(),
+            AriesVcxErrorKind::InvalidState
+        );
+    }
+}
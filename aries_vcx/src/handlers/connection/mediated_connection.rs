@@ -13,6 +13,8 @@ use messages::msg_fields::protocols::connection::invitation::Invitation;
 use messages::msg_fields::protocols::connection::request::Request;
 use messages::msg_fields::protocols::connection::Connection;
 use messages::msg_fields::protocols::discover_features::disclose::Disclose;
+use messages::msg_fields::protocols::discover_features::disclosures::Disclosures;
+use messages::msg_fields::protocols::discover_features::query::QueryContent;
 use messages::msg_fields::protocols::discover_features::{DiscoverFeatures, ProtocolDescriptor};
 use messages::msg_fields::protocols::out_of_band::OutOfBand;
 use messages::msg_fields::protocols::trust_ping::TrustPing;
@@ -31,7 +33,7 @@ use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::cloud_agent::CloudAgentInfo;
 use crate::handlers::connection::legacy_agent_info::LegacyAgentInfo;
-use crate::handlers::discovery::{respond_discovery_query, send_discovery_query};
+use crate::handlers::discovery::{respond_discovery_queries, respond_discovery_query, send_discovery_query};
 use crate::handlers::trust_ping::TrustPingSender;
 use crate::handlers::util::AnyInvitation;
 use crate::protocols::mediated_connection::invitee::state_machine::{
@@ -425,7 +427,9 @@ impl MediatedConnection {
                 | AriesMessage::OutOfBand(OutOfBand::HandshakeReuse(_))
                 | AriesMessage::OutOfBand(OutOfBand::HandshakeReuseAccepted(_))
                 | AriesMessage::DiscoverFeatures(DiscoverFeatures::Query(_))
-                | AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclose(_)) => return Some((uid, message)),
+                | AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclose(_))
+                | AriesMessage::DiscoverFeatures(DiscoverFeatures::Queries(_))
+                | AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclosures(_)) => return Some((uid, message)),
                 _ => {}
             }
         }
@@ -477,12 +481,33 @@ impl MediatedConnection {
 
                 respond_discovery_query(&profile.inject_wallet(), query, &did_doc, pw_vk, supported_protocols).await?;
             }
+            AriesMessage::DiscoverFeatures(DiscoverFeatures::Queries(queries)) => {
+                let supported_protocols = QueryContent::new("*".to_owned()).lookup();
+
+                info!("Answering discover features 2.0 queries, @id: {}", &queries.id);
+
+                respond_discovery_queries(
+                    &profile.inject_wallet(),
+                    queries,
+                    &did_doc,
+                    pw_vk,
+                    supported_protocols,
+                    Vec::new(),
+                )
+                .await?;
+            }
             AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclose(disclose)) => {
                 let thread_id = disclose.decorators.thread.thid.as_str();
                 info!("Handling disclose message, thread: {}", thread_id);
 
                 self.connection_sm = self.handle_disclose(disclose).await?;
             }
+            AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclosures(disclosures)) => {
+                let thread_id = disclosures.decorators.thread.thid.as_str();
+                info!("Handling disclosures message, thread: {}", thread_id);
+
+                self.connection_sm = self.handle_disclosures(disclosures).await?;
+            }
             _ => {
                 // todo: implement to_string for A2AMessage, printing only type of the message, not entire payload
                 // todo: attempt to print @id / thread_id of the message
@@ -661,6 +686,17 @@ impl MediatedConnection {
         }
     }
 
+    async fn handle_disclosures(&self, disclosures: Disclosures) -> VcxResult<SmConnection> {
+        match &self.connection_sm {
+            SmConnection::Inviter(sm_inviter) => Ok(SmConnection::Inviter(
+                sm_inviter.clone().handle_disclosures(disclosures)?,
+            )),
+            SmConnection::Invitee(sm_invitee) => Ok(SmConnection::Invitee(
+                sm_invitee.clone().handle_disclosures(disclosures)?,
+            )),
+        }
+    }
+
     pub async fn connect(
         &mut self,
         profile: &Arc<dyn Profile>,
@@ -1111,6 +1147,41 @@ impl From<(SmConnectionState, PairwiseInfo, Option<CloudAgentInfo>, String, Stri
     }
 }
 
+#[cfg(test)]
+mod unit_tests {
+    use messages::decorators::thread::Thread;
+    use messages::msg_fields::protocols::discover_features::disclosures::{
+        Disclosure, DisclosuresContent, DisclosuresDecorators,
+    };
+    use messages::msg_fields::protocols::discover_features::FeatureType;
+
+    use crate::utils::mockdata::mockdata_mediated_connection::CONNECTION_SM_INVITER_COMPLETED;
+    use crate::utils::mockdata::profile::mock_profile::MockProfile;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_handle_disclosures_updates_remote_protocols() {
+        let mut connection = MediatedConnection::from_string(CONNECTION_SM_INVITER_COMPLETED).unwrap();
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+        let content = DisclosuresContent::new(vec![
+            Disclosure::new(FeatureType::Protocol, "https://didcomm.org/trust_ping/1.0".to_owned()),
+            Disclosure::new(FeatureType::GoalCode, "aries.sell.goods".to_owned()),
+        ]);
+        let decorators = DisclosuresDecorators::new(Thread::new("query_id".to_owned()));
+        let disclosures = Disclosures::with_decorators("disclosures_id".to_owned(), content, decorators);
+
+        let messages = HashMap::from([("uid".to_owned(), AriesMessage::from(disclosures))]);
+        let (uid, message) = connection.find_message_to_handle(messages).unwrap();
+        assert_eq!(uid, "uid");
+        connection.handle_message(message, &profile).await.unwrap();
+
+        let protocols = connection.get_remote_protocols().unwrap();
+        assert_eq!(protocols.len(), 1);
+        assert_eq!(protocols[0].str_pid(), "https://didcomm.org/trust_ping/1.0");
+    }
+}
+
 // #[cfg(test)]
 // mod tests {
 
@@ -0,0 +1,122 @@
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::msg_fields::protocols::coordinate_mediation::keylist::Keylist;
+use messages::msg_fields::protocols::coordinate_mediation::keylist_query::KeylistQueryPaginate;
+use messages::msg_fields::protocols::coordinate_mediation::keylist_update::KeylistUpdateAction;
+use messages::msg_fields::protocols::coordinate_mediation::keylist_update_response::KeylistUpdateResponse;
+use messages::msg_fields::protocols::coordinate_mediation::mediate_deny::MediateDeny;
+use messages::msg_fields::protocols::coordinate_mediation::mediate_grant::MediateGrant;
+
+use crate::errors::error::prelude::*;
+use crate::protocols::connection::pairwise_info::PairwiseInfo;
+use crate::protocols::coordinate_mediation::client::state_machine::MediationClientSM;
+use crate::protocols::SendClosure;
+
+/// Client of a mediator speaking the coordinate mediation protocol, as an alternative to
+/// the proprietary agency protocol of `agency_client`.
+///
+/// Messages are exchanged over an established connection with the mediator, through the
+/// provided [`SendClosure`]s.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MediationClient {
+    client_sm: MediationClientSM,
+}
+
+impl MediationClient {
+    pub fn build() -> Self {
+        Self {
+            client_sm: MediationClientSM::create(),
+        }
+    }
+
+    pub fn get_thread_id(&self) -> VcxResult<String> {
+        self.client_sm.get_thread_id()
+    }
+
+    pub fn is_granted(&self) -> bool {
+        self.client_sm.is_granted()
+    }
+
+    pub fn is_denied(&self) -> bool {
+        self.client_sm.is_denied()
+    }
+
+    /// Returns the endpoint and routing keys granted by the mediator, to be passed when
+    /// creating the DID document of a new connection (e.g. to `Connection::send_request`).
+    pub fn get_routing_info(&self) -> VcxResult<(url::Url, Vec<String>)> {
+        self.client_sm.get_routing_info()
+    }
+
+    /// Returns the recipient keys the mediator confirmed to route messages for.
+    pub fn get_keys(&self) -> VcxResult<Vec<String>> {
+        self.client_sm.get_keys()
+    }
+
+    /// Creates the DID document of a new pairwise connection routed through the mediator.
+    pub fn create_did_doc(&self, pairwise_info: &PairwiseInfo) -> VcxResult<AriesDidDoc> {
+        let (service_endpoint, routing_keys) = self.get_routing_info()?;
+
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.set_id(pairwise_info.pw_did.clone());
+        did_doc.set_service_endpoint(service_endpoint);
+        did_doc.set_routing_keys(routing_keys);
+        did_doc.set_recipient_keys(vec![pairwise_info.pw_vk.clone()]);
+        Ok(did_doc)
+    }
+
+    pub async fn send_mediation_request(self, send_message: SendClosure) -> VcxResult<Self> {
+        let client_sm = self.client_sm.send_request(send_message).await?;
+        Ok(Self { client_sm })
+    }
+
+    pub fn handle_mediation_grant(self, grant: MediateGrant) -> VcxResult<Self> {
+        let client_sm = self.client_sm.handle_grant(grant)?;
+        Ok(Self { client_sm })
+    }
+
+    pub fn handle_mediation_deny(self, deny: MediateDeny) -> VcxResult<Self> {
+        let client_sm = self.client_sm.handle_deny(deny)?;
+        Ok(Self { client_sm })
+    }
+
+    /// Asks the mediator to route messages sent to `recipient_key`, typically the verkey
+    /// of a new pairwise connection.
+    pub async fn add_key(&self, recipient_key: &str, send_message: SendClosure) -> VcxResult<()> {
+        self.send_keylist_update(vec![(recipient_key.to_owned(), KeylistUpdateAction::Add)], send_message)
+            .await
+    }
+
+    /// Asks the mediator to stop routing messages sent to `recipient_key`.
+    pub async fn remove_key(&self, recipient_key: &str, send_message: SendClosure) -> VcxResult<()> {
+        self.send_keylist_update(
+            vec![(recipient_key.to_owned(), KeylistUpdateAction::Remove)],
+            send_message,
+        )
+        .await
+    }
+
+    pub async fn send_keylist_update(
+        &self,
+        updates: Vec<(String, KeylistUpdateAction)>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.client_sm.send_keylist_update(updates, send_message).await
+    }
+
+    pub fn handle_keylist_update_response(self, response: KeylistUpdateResponse) -> VcxResult<Self> {
+        let client_sm = self.client_sm.handle_keylist_update_response(response)?;
+        Ok(Self { client_sm })
+    }
+
+    pub async fn send_keylist_query(
+        &self,
+        paginate: Option<KeylistQueryPaginate>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.client_sm.send_keylist_query(paginate, send_message).await
+    }
+
+    pub fn handle_keylist(self, keylist: Keylist) -> VcxResult<Self> {
+        let client_sm = self.client_sm.handle_keylist(keylist)?;
+        Ok(Self { client_sm })
+    }
+}
@@ -7,8 +7,15 @@ use diddoc_legacy::aries::diddoc::AriesDidDoc;
 use messages::decorators::thread::Thread;
 use messages::decorators::timing::Timing;
 use messages::msg_fields::protocols::discover_features::disclose::{Disclose, DiscloseContent, DiscloseDecorators};
+use messages::msg_fields::protocols::discover_features::disclosures::{
+    Disclosure, Disclosures, DisclosuresContent, DisclosuresDecorators,
+};
+use messages::msg_fields::protocols::discover_features::queries::{
+    FeatureQuery, Queries, QueriesContent, QueriesDecorators,
+};
 use messages::msg_fields::protocols::discover_features::query::{Query, QueryContent, QueryDecorators};
-use messages::msg_fields::protocols::discover_features::ProtocolDescriptor;
+use messages::msg_fields::protocols::discover_features::{matches_query, FeatureType, ProtocolDescriptor};
+use shared_vcx::maybe_known::MaybeKnown;
 use uuid::Uuid;
 
 use crate::utils::send_message;
@@ -39,9 +46,11 @@ pub async fn respond_discovery_query(
     query: Query,
     did_doc: &AriesDidDoc,
     pw_vk: &str,
-    _supported_protocols: Vec<ProtocolDescriptor>,
+    supported_protocols: Vec<ProtocolDescriptor>,
 ) -> VcxResult<()> {
-    let content = DiscloseContent::default();
+    let content = DiscloseContent {
+        protocols: query.content.lookup_in(supported_protocols),
+    };
 
     let mut decorators = DiscloseDecorators::new(Thread::new(query.id));
     let mut timing = Timing::default();
@@ -52,3 +61,133 @@ pub async fn respond_discovery_query(
 
     send_message(Arc::clone(wallet), pw_vk.to_string(), did_doc.clone(), disclose.into()).await
 }
+
+pub async fn send_discovery_queries(
+    wallet: &Arc<dyn BaseWallet>,
+    queries: Vec<FeatureQuery>,
+    did_doc: &AriesDidDoc,
+    pw_vk: &str,
+) -> VcxResult<()> {
+    let content = QueriesContent::new(queries);
+
+    let mut decorators = QueriesDecorators::default();
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    let queries = Queries::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+    send_message(Arc::clone(wallet), pw_vk.to_string(), did_doc.clone(), queries.into()).await
+}
+
+pub async fn respond_discovery_queries(
+    wallet: &Arc<dyn BaseWallet>,
+    queries: Queries,
+    did_doc: &AriesDidDoc,
+    pw_vk: &str,
+    supported_protocols: Vec<ProtocolDescriptor>,
+    supported_goal_codes: Vec<String>,
+) -> VcxResult<()> {
+    let disclosures = build_disclosures(&queries.content, supported_protocols, supported_goal_codes);
+    let content = DisclosuresContent::new(disclosures);
+
+    let mut decorators = DisclosuresDecorators::new(Thread::new(queries.id));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    let disclose = Disclosures::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+    send_message(Arc::clone(wallet), pw_vk.to_string(), did_doc.clone(), disclose.into()).await
+}
+
+// Queries of unknown feature types are ignored, as mandated by the RFC
+fn build_disclosures(
+    queries: &QueriesContent,
+    supported_protocols: Vec<ProtocolDescriptor>,
+    supported_goal_codes: Vec<String>,
+) -> Vec<Disclosure> {
+    let mut disclosures = Vec::new();
+
+    for query in &queries.queries {
+        match query.feature_type {
+            MaybeKnown::Known(FeatureType::Protocol) => {
+                for pd in supported_protocols.iter() {
+                    let pid = pd.str_pid();
+                    if matches_query(&query.feature_match, &pid) {
+                        let mut disclosure = Disclosure::new(FeatureType::Protocol, pid);
+                        disclosure.roles = pd.clone().with_registry_roles().roles;
+                        disclosures.push(disclosure);
+                    }
+                }
+            }
+            MaybeKnown::Known(FeatureType::GoalCode) => {
+                for goal_code in supported_goal_codes.iter() {
+                    if matches_query(&query.feature_match, goal_code) {
+                        disclosures.push(Disclosure::new(FeatureType::GoalCode, goal_code.clone()));
+                    }
+                }
+            }
+            MaybeKnown::Unknown(_) => {}
+        }
+    }
+
+    disclosures
+}
+
+#[cfg(test)]
+mod unit_tests {
+    use messages::msg_types::protocols::connection::ConnectionTypeV1;
+    use messages::msg_types::protocols::trust_ping::TrustPingTypeV1;
+    use messages::msg_types::Protocol;
+
+    use super::*;
+
+    fn supported_protocols() -> Vec<ProtocolDescriptor> {
+        vec![
+            ProtocolDescriptor::new(MaybeKnown::Known(Protocol::from(ConnectionTypeV1::new_v1_0()))),
+            ProtocolDescriptor::new(MaybeKnown::Known(Protocol::from(TrustPingTypeV1::new_v1_0()))),
+        ]
+    }
+
+    #[test]
+    fn test_disclose_filters_by_query() {
+        let query = QueryContent::new("https://didcomm.org/connections/*".to_owned());
+        let protocols = query.lookup_in(supported_protocols());
+
+        assert_eq!(protocols.len(), 1);
+        assert_eq!(protocols[0].str_pid(), "https://didcomm.org/connections/1.0");
+        assert!(protocols[0].roles.is_some());
+    }
+
+    #[test]
+    fn test_build_disclosures() {
+        let queries = QueriesContent::new(vec![
+            FeatureQuery::new(FeatureType::Protocol, "*".to_owned()),
+            FeatureQuery::new(FeatureType::GoalCode, "aries.sell.*".to_owned()),
+        ]);
+        let goal_codes = vec!["aries.sell.goods".to_owned(), "aries.buy.goods".to_owned()];
+
+        let disclosures = build_disclosures(&queries, supported_protocols(), goal_codes);
+
+        let ids: Vec<&str> = disclosures.iter().map(|d| d.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "https://didcomm.org/connections/1.0",
+                "https://didcomm.org/trust_ping/1.0",
+                "aries.sell.goods"
+            ]
+        );
+        assert_eq!(disclosures[2].feature_type, MaybeKnown::Known(FeatureType::GoalCode));
+    }
+
+    #[test]
+    fn test_build_disclosures_ignores_unknown_feature_type() {
+        let mut query = FeatureQuery::new(FeatureType::Protocol, "*".to_owned());
+        query.feature_type = MaybeKnown::Unknown("header".to_owned());
+        let queries = QueriesContent::new(vec![query]);
+
+        assert!(build_disclosures(&queries, supported_protocols(), vec![]).is_empty());
+    }
+}
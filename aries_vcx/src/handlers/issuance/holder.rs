@@ -3,7 +3,6 @@ use std::collections::HashMap;
 use messages::msg_fields::protocols::cred_issuance::issue_credential::IssueCredential;
 use messages::msg_fields::protocols::cred_issuance::offer_credential::OfferCredential;
 use messages::msg_fields::protocols::cred_issuance::propose_credential::ProposeCredential;
-use messages::msg_fields::protocols::revocation::revoke::Revoke;
 use messages::AriesMessage;
 use std::sync::Arc;
 
@@ -16,6 +15,7 @@ use crate::handlers::connection::mediated_connection::MediatedConnection;
 use crate::handlers::revocation_notification::receiver::RevocationNotificationReceiver;
 use crate::protocols::issuance::actions::CredentialIssuanceAction;
 use crate::protocols::issuance::holder::state_machine::{HolderSM, HolderState};
+use crate::protocols::revocation_notification::receiver::notification::RevocationNotification;
 use crate::protocols::SendClosure;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -166,14 +166,18 @@ impl Holder {
         &self,
         profile: &Arc<dyn Profile>,
         connection: &MediatedConnection,
-        notification: Revoke,
+        notification: impl Into<RevocationNotification>,
     ) -> VcxResult<()> {
         if self.holder_sm.is_revokable(profile).await? {
             let send_message = connection.send_message_closure(profile).await?;
             // TODO: Store to remember notification was received along with details
-            RevocationNotificationReceiver::build(self.get_rev_reg_id()?, self.get_cred_rev_id(profile).await?)
-                .handle_revocation_notification(notification, send_message)
-                .await?;
+            RevocationNotificationReceiver::build(
+                self.get_rev_reg_id()?,
+                self.get_cred_rev_id(profile).await?,
+                self.get_thread_id()?,
+            )
+            .handle_revocation_notification(notification.into(), send_message)
+            .await?;
             Ok(())
         } else {
             Err(AriesVcxError::from_msg(
@@ -6,10 +6,13 @@ use crate::protocols::mediated_connection::inviter::state_machine::InviterState;
 use crate::protocols::proof_presentation::prover::state_machine::ProverState;
 use crate::protocols::proof_presentation::verifier::state_machine::VerifierState;
 
+pub mod action_menu;
 pub mod connection;
+pub mod coordinate_mediation;
 pub mod discovery;
 pub mod issuance;
 pub mod out_of_band;
+pub mod pickup;
 pub mod proof_presentation;
 pub mod revocation_notification;
 pub mod trust_ping;
@@ -15,16 +15,22 @@ use messages::msg_fields::protocols::out_of_band::OutOfBand;
 use messages::msg_fields::protocols::present_proof::present::Presentation;
 use messages::msg_fields::protocols::present_proof::request::RequestPresentation;
 use messages::msg_fields::protocols::present_proof::PresentProof;
+use messages::msg_types::protocols::did_exchange::{DidExchangeType, DidExchangeTypeV1};
+use messages::msg_types::Protocol;
 use messages::AriesMessage;
 use serde::Deserialize;
 use serde_json::Value;
+use shared_vcx::maybe_known::MaybeKnown;
 
 use crate::common::ledger::transactions::resolve_service;
 use crate::core::profile::profile::Profile;
 use crate::errors::error::prelude::*;
 use crate::handlers::connection::mediated_connection::MediatedConnection;
 use crate::handlers::util::{AnyInvitation, AttachmentId};
+use crate::protocols::connection::pairwise_info::PairwiseInfo;
 use crate::protocols::connection::GenericConnection;
+use crate::protocols::did_exchange::requester::states::Invited;
+use crate::protocols::did_exchange::RequesterDidExchange;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct OutOfBandReceiver {
@@ -276,6 +282,41 @@ impl OutOfBandReceiver {
         .await
     }
 
+    /// Returns whether the invitation lists `didexchange/1.0` among its handshake protocols.
+    pub fn supports_did_exchange(&self) -> bool {
+        self.oob.content.handshake_protocols.iter().flatten().any(|protocol| {
+            matches!(
+                protocol,
+                MaybeKnown::Known(Protocol::DidExchangeType(DidExchangeType::V1(DidExchangeTypeV1::V1_0(_))))
+            )
+        })
+    }
+
+    /// Accepts the invitation through the DID exchange protocol, creating a requester
+    /// that can then send its request to the inviter.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the invitation does not list `didexchange/1.0` among its handshake
+    /// protocols or if the DID document of the inviter cannot be resolved.
+    pub async fn build_did_exchange_requester(
+        &self,
+        profile: &Arc<dyn Profile>,
+        pairwise_info: PairwiseInfo,
+    ) -> VcxResult<RequesterDidExchange<Invited>> {
+        trace!("OutOfBandReceiver::build_did_exchange_requester >>>");
+        if !self.supports_did_exchange() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                format!(
+                    "Invitation does not support the DID exchange protocol, handshake protocols: {:?}",
+                    self.oob.content.handshake_protocols
+                ),
+            ));
+        }
+        RequesterDidExchange::accept_invitation(profile, pairwise_info, self.oob.clone()).await
+    }
+
     pub fn to_aries_message(&self) -> AriesMessage {
         self.oob.clone().into()
     }
@@ -290,3 +331,61 @@ impl OutOfBandReceiver {
         })
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use diddoc_legacy::aries::service::AriesService;
+    use messages::msg_types::protocols::connection::ConnectionTypeV1;
+
+    use super::*;
+    use crate::{
+        common::ledger::transactions::ed25519_public_key_to_did_key, handlers::out_of_band::sender::OutOfBandSender,
+        utils::mockdata::profile::mock_profile::MockProfile,
+    };
+
+    const VERKEY: &str = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+
+    fn receiver(handshake_protocol: Protocol) -> OutOfBandReceiver {
+        let service = AriesService::create()
+            .set_service_endpoint("https://example.org/agent".parse().unwrap())
+            .set_recipient_keys(vec![ed25519_public_key_to_did_key(VERKEY).unwrap()]);
+        let sender = OutOfBandSender::create()
+            .append_service(&OobService::AriesService(service))
+            .append_handshake_protocol(handshake_protocol)
+            .unwrap();
+
+        OutOfBandReceiver::create_from_a2a_msg(&sender.to_aries_message()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_build_did_exchange_requester() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+        let receiver = receiver(Protocol::from(DidExchangeTypeV1::new_v1_0()));
+        assert!(receiver.supports_did_exchange());
+
+        let requester = receiver
+            .build_did_exchange_requester(&profile, PairwiseInfo::default())
+            .await
+            .unwrap();
+        assert_eq!(requester.parent_thread_id(), receiver.get_id());
+        assert_eq!(requester.remote_vk().unwrap(), VERKEY);
+        assert_eq!(
+            requester.their_did_doc().get_endpoint().unwrap().as_str(),
+            "https://example.org/agent"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_did_exchange_requester_without_did_exchange_handshake() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+        let receiver = receiver(Protocol::from(ConnectionTypeV1::new_v1_0()));
+        assert!(!receiver.supports_did_exchange());
+
+        let err = receiver
+            .build_did_exchange_requester(&profile, PairwiseInfo::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::ActionNotSupported);
+    }
+}
@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use messages::decorators::attachment::{Attachment, AttachmentType};
+use messages::msg_fields::protocols::pickup::delivery::Delivery;
+use messages::msg_fields::protocols::pickup::status::{Status, StatusContent};
+use messages::AriesMessage;
+
+use crate::errors::error::prelude::*;
+use crate::protocols::pickup::{
+    build_delivery_request, build_live_delivery_change, build_messages_received, build_status_request,
+};
+use crate::protocols::SendClosure;
+use crate::utils::encryption_envelope::EncryptionEnvelope;
+
+/// Message picked up from a mediator, along with the key it was packed for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickedUpMessage {
+    /// Identifier of the message on the mediator, to be acknowledged with `messages-received`.
+    pub id: String,
+    pub recipient_key: String,
+    pub sender_key: Option<String>,
+    pub message: AriesMessage,
+}
+
+/// Client pulling messages queued by a mediator, using the message pickup 2.0 protocol
+/// instead of the `GET_MSGS` API of the agency.
+///
+/// Requests are sent over the connection with the mediator through the provided
+/// [`SendClosure`]s, and the `status` and `delivery` answers are expected on the return route.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct PickupClient {
+    status: Option<StatusContent>,
+    live_delivery: bool,
+}
+
+impl PickupClient {
+    pub fn build() -> Self {
+        Self::default()
+    }
+
+    /// Returns the content of the last `status` message received from the mediator.
+    pub fn get_status(&self) -> Option<&StatusContent> {
+        self.status.as_ref()
+    }
+
+    pub fn is_live_delivery(&self) -> bool {
+        self.live_delivery
+    }
+
+    pub async fn send_status_request(&self, recipient_key: Option<String>, send_message: SendClosure) -> VcxResult<()> {
+        send_message(build_status_request(recipient_key).into()).await
+    }
+
+    pub fn handle_status(&mut self, status: Status) -> VcxResult<()> {
+        if let Some(live_delivery) = status.content.live_delivery {
+            self.live_delivery = live_delivery;
+        }
+        self.status = Some(status.content);
+        Ok(())
+    }
+
+    pub async fn send_delivery_request(
+        &self,
+        limit: u32,
+        recipient_key: Option<String>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        send_message(build_delivery_request(limit, recipient_key).into()).await
+    }
+
+    /// Unpacks the messages attached to a `delivery` message.
+    ///
+    /// Messages are not authenticated, as they may belong to different connections; the
+    /// sender key is returned so it can be checked against the connection the message is
+    /// handled by.
+    pub async fn handle_delivery(
+        &self,
+        wallet: &Arc<dyn BaseWallet>,
+        delivery: Delivery,
+    ) -> VcxResult<Vec<PickedUpMessage>> {
+        let mut messages = Vec::with_capacity(delivery.content.attach.len());
+        for attach in delivery.content.attach {
+            messages.push(unpack_attachment(wallet, attach).await?);
+        }
+        Ok(messages)
+    }
+
+    /// Acknowledges messages, so that the mediator removes them from its queue.
+    pub async fn send_messages_received(&self, message_ids: Vec<String>, send_message: SendClosure) -> VcxResult<()> {
+        send_message(build_messages_received(message_ids).into()).await
+    }
+
+    /// Asks the mediator to push new messages over an open connection (e.g. a websocket),
+    /// instead of queueing them until they are requested.
+    pub async fn send_live_delivery_change(&mut self, live_delivery: bool, send_message: SendClosure) -> VcxResult<()> {
+        send_message(build_live_delivery_change(live_delivery).into()).await?;
+        self.live_delivery = live_delivery;
+        Ok(())
+    }
+
+    /// Collects the picked up messages packed for `recipient_key` (usually the `pw_vk` of a
+    /// connection), keyed by their identifier, as expected by `find_message_to_handle`.
+    pub fn messages_for_recipient(messages: &[PickedUpMessage], recipient_key: &str) -> HashMap<String, AriesMessage> {
+        messages
+            .iter()
+            .filter(|msg| msg.recipient_key == recipient_key)
+            .map(|msg| (msg.id.clone(), msg.message.clone()))
+            .collect()
+    }
+}
+
+async fn unpack_attachment(wallet: &Arc<dyn BaseWallet>, attach: Attachment) -> VcxResult<PickedUpMessage> {
+    let id = attach.id.ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidMessageFormat,
+            "Delivered message attachment is missing its id",
+        )
+    })?;
+
+    let payload = match attach.data.content {
+        AttachmentType::Base64(encoded) => decode_base64(&encoded).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("Cannot decode delivered message {id}: {err}"),
+            )
+        })?,
+        AttachmentType::Json(value) => serde_json::to_vec(&value)?,
+        AttachmentType::Links(_) => {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("Delivered message {id} is not embedded in its attachment"),
+            ))
+        }
+    };
+
+    let (message, recipient_key, sender_key) = EncryptionEnvelope::unpack_with_recipient(wallet, payload).await?;
+
+    Ok(PickedUpMessage {
+        id,
+        recipient_key,
+        sender_key,
+        message,
+    })
+}
+
+// Mediators differ in the base64 alphabet and padding of delivered messages, e.g. ACA-Py uses the
+// standard alphabet, so both alphabets are accepted with or without padding.
+fn decode_base64(encoded: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    base64::decode_config(encoded, base64::STANDARD)
+        .or_else(|_| base64::decode_config(encoded, base64::URL_SAFE))
+        .or_else(|_| base64::decode_config(encoded, base64::STANDARD_NO_PAD))
+        .or_else(|_| base64::decode_config(encoded, base64::URL_SAFE_NO_PAD))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::decorators::attachment::AttachmentData;
+    use messages::msg_fields::protocols::pickup::delivery::{DeliveryContent, DeliveryDecorators};
+    use messages::msg_fields::protocols::pickup::status::StatusDecorators;
+    use messages::msg_fields::protocols::trust_ping::TrustPing;
+
+    use crate::protocols::trustping::build_ping;
+    use crate::utils::mockdata::profile::mock_wallet::MockWallet;
+
+    use super::*;
+
+    // The mock wallet hands packed messages back as they are, so deliveries are built
+    // from the JSON the wallet would return after unpacking them.
+    fn make_attachment(id: &str, message: &AriesMessage, recipient_key: &str, config: base64::Config) -> Attachment {
+        let unpacked = json!({
+            "message": serde_json::to_string(message).unwrap(),
+            "recipient_verkey": recipient_key,
+            "sender_verkey": "DunkM3x1y7S4ECgSL4Wkru",
+        });
+        let encoded = base64::encode_config(unpacked.to_string(), config);
+        let mut attach = Attachment::new(AttachmentData::new(AttachmentType::Base64(encoded)));
+        attach.id = Some(id.to_owned());
+        attach
+    }
+
+    #[tokio::test]
+    async fn test_handle_delivery_surfaces_recipient_keys() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let ping: AriesMessage = build_ping(false, None).into();
+        let delivery = Delivery::with_decorators(
+            "delivery".to_owned(),
+            DeliveryContent::new(vec![
+                make_attachment("1", &ping, "VsKV7grR1BUE29mG2Fm2kX", base64::URL_SAFE),
+                make_attachment("2", &ping, "Hezce2UWMZ3wUhVkh2LfKSs", base64::URL_SAFE),
+            ]),
+            DeliveryDecorators::default(),
+        );
+
+        let messages = PickupClient::build().handle_delivery(&wallet, delivery).await.unwrap();
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].recipient_key, "VsKV7grR1BUE29mG2Fm2kX");
+        assert_eq!(messages[0].sender_key.as_deref(), Some("DunkM3x1y7S4ECgSL4Wkru"));
+        assert!(matches!(
+            messages[0].message,
+            AriesMessage::TrustPing(TrustPing::Ping(_))
+        ));
+
+        let for_recipient = PickupClient::messages_for_recipient(&messages, "Hezce2UWMZ3wUhVkh2LfKSs");
+        assert_eq!(for_recipient.len(), 1);
+        assert!(for_recipient.contains_key("2"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_delivery_decodes_standard_base64() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        // Runs of six characters cover an aligned group of three, which encodes to `Pz8/` and `Pj4+`
+        let ping: AriesMessage = build_ping(false, Some("??????>>>>>>".to_owned())).into();
+        let attach = make_attachment("1", &ping, "VsKV7grR1BUE29mG2Fm2kX", base64::STANDARD);
+        let AttachmentType::Base64(encoded) = &attach.data.content else {
+            panic!("expected a base64 attachment");
+        };
+        assert!(encoded.contains('+') && encoded.contains('/'));
+        let delivery = Delivery::with_decorators(
+            "delivery".to_owned(),
+            DeliveryContent::new(vec![attach]),
+            DeliveryDecorators::default(),
+        );
+
+        let messages = PickupClient::build().handle_delivery(&wallet, delivery).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].recipient_key, "VsKV7grR1BUE29mG2Fm2kX");
+    }
+
+    #[test]
+    fn test_decode_base64_accepts_both_alphabets_with_and_without_padding() {
+        let bytes = b"??>>\xff".to_vec();
+        for config in [
+            base64::STANDARD,
+            base64::URL_SAFE,
+            base64::STANDARD_NO_PAD,
+            base64::URL_SAFE_NO_PAD,
+        ] {
+            assert_eq!(decode_base64(&base64::encode_config(&bytes, config)).unwrap(), bytes);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_delivery_fails_for_attachment_without_id() {
+        let wallet: Arc<dyn BaseWallet> = Arc::new(MockWallet);
+        let ping: AriesMessage = build_ping(false, None).into();
+        let mut attach = make_attachment("1", &ping, "VsKV7grR1BUE29mG2Fm2kX", base64::URL_SAFE);
+        attach.id = None;
+        let delivery = Delivery::with_decorators(
+            "delivery".to_owned(),
+            DeliveryContent::new(vec![attach]),
+            DeliveryDecorators::default(),
+        );
+
+        let err = PickupClient::build()
+            .handle_delivery(&wallet, delivery)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidMessageFormat);
+    }
+
+    #[test]
+    fn test_handle_status_tracks_live_delivery() {
+        let mut client = PickupClient::build();
+        let mut content = StatusContent::new(3);
+        content.live_delivery = Some(true);
+        let status = Status::with_decorators("status".to_owned(), content, StatusDecorators::default());
+
+        client.handle_status(status).unwrap();
+        assert!(client.is_live_delivery());
+        assert_eq!(client.get_status().unwrap().message_count, 3);
+    }
+}
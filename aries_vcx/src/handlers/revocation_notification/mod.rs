@@ -4,23 +4,26 @@ pub mod sender;
 pub mod test_utils {
     use agency_client::agency_client::AgencyClient;
     use messages::msg_fields::protocols::revocation::ack::AckRevoke;
-    use messages::msg_fields::protocols::revocation::revoke::Revoke;
     use messages::msg_fields::protocols::revocation::Revocation;
     use messages::AriesMessage;
 
     use crate::errors::error::prelude::*;
     use crate::handlers::connection::mediated_connection::MediatedConnection;
+    use crate::protocols::revocation_notification::receiver::notification::RevocationNotification;
 
     pub async fn get_revocation_notification_messages(
         agency_client: &AgencyClient,
         connection: &MediatedConnection,
-    ) -> VcxResult<Vec<Revoke>> {
-        let mut messages = Vec::<Revoke>::new();
+    ) -> VcxResult<Vec<RevocationNotification>> {
+        let mut messages = Vec::<RevocationNotification>::new();
         for (uid, message) in connection.get_messages_noauth(&agency_client).await?.into_iter() {
-            if let AriesMessage::Revocation(Revocation::Revoke(message)) = message {
-                connection.update_message_status(&uid, &agency_client).await.ok();
-                messages.push(message);
-            }
+            let notification = match message {
+                AriesMessage::Revocation(Revocation::Revoke(message)) => message.into(),
+                AriesMessage::Revocation(Revocation::RevokeV1(message)) => message.into(),
+                _ => continue,
+            };
+            connection.update_message_status(&uid, &agency_client).await.ok();
+            messages.push(notification);
         }
         Ok(messages)
     }
@@ -1,6 +1,5 @@
-use messages::msg_fields::protocols::revocation::revoke::Revoke;
-
 use crate::errors::error::prelude::*;
+use crate::protocols::revocation_notification::receiver::notification::RevocationNotification;
 use crate::protocols::revocation_notification::receiver::state_machine::RevocationNotificationReceiverSM;
 use crate::protocols::SendClosure;
 
@@ -10,9 +9,9 @@ pub struct RevocationNotificationReceiver {
 }
 
 impl RevocationNotificationReceiver {
-    pub fn build(rev_reg_id: String, cred_rev_id: String) -> Self {
+    pub fn build(rev_reg_id: String, cred_rev_id: String, issuance_thread_id: String) -> Self {
         Self {
-            receiver_sm: RevocationNotificationReceiverSM::create(rev_reg_id, cred_rev_id),
+            receiver_sm: RevocationNotificationReceiverSM::create(rev_reg_id, cred_rev_id, issuance_thread_id),
         }
     }
 
@@ -22,7 +21,7 @@ impl RevocationNotificationReceiver {
 
     pub async fn handle_revocation_notification(
         self,
-        notification: Revoke,
+        notification: RevocationNotification,
         send_message: SendClosure,
     ) -> VcxResult<Self> {
         let receiver_sm = self
@@ -1,10 +1,14 @@
 use messages::{
     msg_fields::protocols::{
+        action_menu::ActionMenu,
         connection::{invitation::Invitation, Connection},
+        coordinate_mediation::CoordinateMediation,
         cred_issuance::CredentialIssuance,
+        did_exchange::DidExchange,
         discover_features::DiscoverFeatures,
         notification::Notification,
         out_of_band::{invitation::Invitation as OobInvitation, OutOfBand},
+        pickup::Pickup,
         present_proof::{
             propose::{Predicate, PresentationAttr},
             PresentProof,
@@ -78,6 +82,9 @@ pub(crate) use matches_thread_id;
 
 pub fn verify_thread_id(thread_id: &str, message: &AriesMessage) -> VcxResult<()> {
     let is_match = match message {
+        AriesMessage::ActionMenu(ActionMenu::Menu(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::ActionMenu(ActionMenu::MenuRequest(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::ActionMenu(ActionMenu::Perform(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::BasicMessage(msg) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::Connection(Connection::Invitation(Invitation::Public(msg))) => msg.id == thread_id,
         AriesMessage::Connection(Connection::Invitation(Invitation::Pairwise(msg))) => msg.id == thread_id,
@@ -85,6 +92,23 @@ pub fn verify_thread_id(thread_id: &str, message: &AriesMessage) -> VcxResult<()
         AriesMessage::Connection(Connection::ProblemReport(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::Connection(Connection::Request(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::Connection(Connection::Response(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::CoordinateMediation(CoordinateMediation::MediateRequest(msg)) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CoordinateMediation(CoordinateMediation::MediateDeny(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::CoordinateMediation(CoordinateMediation::MediateGrant(msg)) => {
+            matches_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CoordinateMediation(CoordinateMediation::KeylistUpdate(msg)) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CoordinateMediation(CoordinateMediation::KeylistUpdateResponse(msg)) => {
+            matches_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CoordinateMediation(CoordinateMediation::KeylistQuery(msg)) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CoordinateMediation(CoordinateMediation::Keylist(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::CredentialIssuance(CredentialIssuance::Ack(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::CredentialIssuance(CredentialIssuance::IssueCredential(msg)) => {
             matches_thread_id!(msg, thread_id)
@@ -101,21 +125,55 @@ pub fn verify_thread_id(thread_id: &str, message: &AriesMessage) -> VcxResult<()
         AriesMessage::CredentialIssuance(CredentialIssuance::ProblemReport(msg)) => {
             matches_opt_thread_id!(msg, thread_id)
         }
+        AriesMessage::CredentialIssuance(CredentialIssuance::AckV2(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::CredentialIssuance(CredentialIssuance::IssueCredentialV2(msg)) => {
+            matches_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::OfferCredentialV2(msg)) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::ProposeCredentialV2(msg)) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::RequestCredentialV2(msg)) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::CredentialIssuance(CredentialIssuance::ProblemReportV2(msg)) => {
+            matches_opt_thread_id!(msg, thread_id)
+        }
+        AriesMessage::DidExchange(DidExchange::Request(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::DidExchange(DidExchange::Response(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::DidExchange(DidExchange::Complete(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::DidExchange(DidExchange::ProblemReport(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::DiscoverFeatures(DiscoverFeatures::Query(msg)) => msg.id == thread_id,
         AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclose(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::DiscoverFeatures(DiscoverFeatures::Queries(msg)) => msg.id == thread_id,
+        AriesMessage::DiscoverFeatures(DiscoverFeatures::Disclosures(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::Notification(Notification::Ack(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::Notification(Notification::ProblemReport(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::OutOfBand(OutOfBand::Invitation(msg)) => msg.id == thread_id,
         AriesMessage::OutOfBand(OutOfBand::HandshakeReuse(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::OutOfBand(OutOfBand::HandshakeReuseAccepted(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::Pickup(Pickup::StatusRequest(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::Pickup(Pickup::Status(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::Pickup(Pickup::DeliveryRequest(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::Pickup(Pickup::Delivery(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::Pickup(Pickup::MessagesReceived(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::Pickup(Pickup::LiveDeliveryChange(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::PresentProof(PresentProof::Ack(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::PresentProof(PresentProof::Presentation(msg)) => matches_thread_id!(msg, thread_id),
         AriesMessage::PresentProof(PresentProof::ProposePresentation(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::PresentProof(PresentProof::RequestPresentation(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::PresentProof(PresentProof::ProblemReport(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::PresentProof(PresentProof::AckV2(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::PresentProof(PresentProof::PresentationV2(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::PresentProof(PresentProof::ProposePresentationV2(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::PresentProof(PresentProof::RequestPresentationV2(msg)) => matches_opt_thread_id!(msg, thread_id),
+        AriesMessage::PresentProof(PresentProof::ProblemReportV2(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::ReportProblem(msg) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::Revocation(Revocation::Revoke(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::Revocation(Revocation::Ack(msg)) => matches_thread_id!(msg, thread_id),
+        AriesMessage::Revocation(Revocation::RevokeV1(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::Routing(msg) => msg.id == thread_id,
         AriesMessage::TrustPing(TrustPing::Ping(msg)) => matches_opt_thread_id!(msg, thread_id),
         AriesMessage::TrustPing(TrustPing::PingResponse(msg)) => matches_thread_id!(msg, thread_id),
@@ -1,9 +1,18 @@
 use messages::{
-    decorators::thread::Thread,
-    msg_fields::protocols::report_problem::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
+    decorators::{
+        attachment::{Attachment, AttachmentType},
+        thread::Thread,
+    },
+    msg_fields::protocols::{
+        common::attachment_format_specifier::AttachmentFormatSpecifier,
+        report_problem::{ProblemReport, ProblemReportContent, ProblemReportDecorators},
+    },
 };
+use shared_vcx::maybe_known::MaybeKnown;
 use uuid::Uuid;
 
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
 pub fn build_problem_report_msg(comment: Option<String>, thread_id: &str) -> ProblemReport {
     let id = Uuid::new_v4().to_string();
     let content = ProblemReportContent::new(comment.unwrap_or_default());
@@ -14,6 +23,49 @@ pub fn build_problem_report_msg(comment: Option<String>, thread_id: &str) -> Pro
     ProblemReport::with_decorators(id, content, decorators)
 }
 
+/// Returns the content of the attachment the `formats` array maps to the given format.
+pub(crate) fn get_attachment_content<F>(
+    formats: &[AttachmentFormatSpecifier<F>],
+    attachments: &[Attachment],
+    format: F,
+) -> VcxResult<String>
+where
+    F: PartialEq + std::fmt::Debug,
+{
+    let format = MaybeKnown::Known(format);
+    let attachment = formats
+        .iter()
+        .find(|spec| spec.format == format)
+        .and_then(|spec| {
+            attachments
+                .iter()
+                .find(|attach| attach.id.as_deref() == Some(spec.attach_id.as_str()))
+        })
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidMessageFormat,
+                format!("No attachment found for format {:?}", format),
+            )
+        })?;
+
+    match &attachment.data.content {
+        AttachmentType::Base64(encoded) => base64::decode(encoded)
+            .ok()
+            .and_then(|bytes| String::from_utf8(bytes).ok())
+            .ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::SerializationError,
+                    format!("Attachment is not base 64 encoded JSON: {:?}", attachment),
+                )
+            }),
+        AttachmentType::Json(json) => Ok(json.to_string()),
+        AttachmentType::Links(_) => Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidMessageFormat,
+            format!("Linked attachments are not supported: {:?}", attachment),
+        )),
+    }
+}
+
 // #[cfg(test)]
 // mod test {
 //     use crate::protocols::common::build_problem_report_msg;
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
 use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use did_resolver_peer::peer_did::generate_numalgo2_from_verkey;
+use url::Url;
 
 use crate::errors::error::VcxResult;
 
@@ -15,4 +17,39 @@ impl PairwiseInfo {
         let (pw_did, pw_vk) = wallet.create_and_store_my_did(None, None).await?;
         Ok(PairwiseInfo { pw_did, pw_vk })
     }
+
+    /// Encodes the pairwise verkey together with the service endpoint and routing keys
+    /// into a `did:peer:2` DID, which can be advertised instead of an inline DIDDoc.
+    pub fn to_peer_did(&self, service_endpoint: &Url, routing_keys: Vec<String>) -> VcxResult<String> {
+        let did = generate_numalgo2_from_verkey(&self.pw_vk, service_endpoint.to_string(), routing_keys)?;
+        Ok(did.to_string())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use crate::common::ledger::transactions::get_service_from_peer_did;
+
+    use super::*;
+
+    #[test]
+    fn test_pairwise_info_to_peer_did() {
+        let pairwise_info = PairwiseInfo {
+            pw_did: "2ZHFFhzA2XtTD6hJqzL7ux".to_string(),
+            pw_vk: "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K".to_string(),
+        };
+        let routing_key = "Hezce2UWMZ3wUhVkh2LfKSs8nDzWwzs2Win7EzNN3YaR".to_string();
+        let service_endpoint: Url = "https://example.org/agent".parse().unwrap();
+
+        let did = pairwise_info
+            .to_peer_did(&service_endpoint, vec![routing_key.clone()])
+            .unwrap();
+        assert!(did.starts_with("did:peer:2."));
+
+        let service = get_service_from_peer_did(&did).unwrap();
+        assert_eq!(service.recipient_keys, vec![pairwise_info.pw_vk]);
+        assert_eq!(service.routing_keys, vec![routing_key]);
+        assert_eq!(service.service_endpoint, service_endpoint);
+    }
 }
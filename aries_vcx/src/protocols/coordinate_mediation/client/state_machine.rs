@@ -0,0 +1,388 @@
+use std::collections::BTreeSet;
+
+use chrono::Utc;
+use messages::decorators::timing::Timing;
+use messages::msg_fields::protocols::coordinate_mediation::keylist::Keylist;
+use messages::msg_fields::protocols::coordinate_mediation::keylist_query::{
+    KeylistQuery, KeylistQueryContent, KeylistQueryDecorators, KeylistQueryPaginate,
+};
+use messages::msg_fields::protocols::coordinate_mediation::keylist_update::{
+    KeylistUpdate, KeylistUpdateAction, KeylistUpdateContent, KeylistUpdateDecorators, KeylistUpdateItem,
+};
+use messages::msg_fields::protocols::coordinate_mediation::keylist_update_response::{
+    KeylistUpdateResponse, KeylistUpdateResult,
+};
+use messages::msg_fields::protocols::coordinate_mediation::mediate_deny::MediateDeny;
+use messages::msg_fields::protocols::coordinate_mediation::mediate_grant::MediateGrant;
+use messages::msg_fields::protocols::coordinate_mediation::mediate_request::{
+    MediateRequest, MediateRequestContent, MediateRequestDecorators,
+};
+use url::Url;
+use uuid::Uuid;
+
+use crate::common::ledger::transactions::{ed25519_public_key_to_did_key, normalize_keys_as_naked};
+use crate::errors::error::prelude::*;
+use crate::handlers::util::verify_thread_id;
+use crate::protocols::coordinate_mediation::client::states::{DeniedState, GrantedState, RequestSentState};
+use crate::protocols::SendClosure;
+
+/// State machine for the recipient role of the [coordinate mediation protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0211-route-coordination/README.md).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MediationClientSM {
+    state: ClientFullState,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ClientFullState {
+    Initial,
+    RequestSent(RequestSentState),
+    Granted(GrantedState),
+    Denied(DeniedState),
+}
+
+impl MediationClientSM {
+    pub fn create() -> Self {
+        Self {
+            state: ClientFullState::Initial,
+        }
+    }
+
+    pub fn is_granted(&self) -> bool {
+        matches!(self.state, ClientFullState::Granted(_))
+    }
+
+    pub fn is_denied(&self) -> bool {
+        matches!(self.state, ClientFullState::Denied(_))
+    }
+
+    pub fn get_thread_id(&self) -> VcxResult<String> {
+        match &self.state {
+            ClientFullState::RequestSent(state) => Ok(state.request.id.clone()),
+            ClientFullState::Granted(state) => Ok(state.grant.decorators.thread.thid.clone()),
+            ClientFullState::Denied(state) => Ok(state.deny.decorators.thread.thid.clone()),
+            ClientFullState::Initial => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Thread ID not yet known in this state",
+            )),
+        }
+    }
+
+    /// Returns the endpoint and the routing keys (as naked verkeys) granted by the mediator,
+    /// which are to be used in the DID documents of connections routed through it.
+    pub fn get_routing_info(&self) -> VcxResult<(Url, Vec<String>)> {
+        let state = self.granted_state()?;
+        let routing_keys = normalize_keys_as_naked(state.grant.content.routing_keys.clone())?;
+        Ok((state.grant.content.endpoint.clone(), routing_keys))
+    }
+
+    /// Returns the recipient keys the mediator confirmed to route messages for.
+    pub fn get_keys(&self) -> VcxResult<Vec<String>> {
+        Ok(self.granted_state()?.keys.iter().cloned().collect())
+    }
+
+    pub async fn send_request(self, send_message: SendClosure) -> VcxResult<Self> {
+        let state = match self.state {
+            ClientFullState::Initial | ClientFullState::Denied(_) => {
+                let mut decorators = MediateRequestDecorators::default();
+                let mut timing = Timing::default();
+                timing.out_time = Some(Utc::now());
+                decorators.timing = Some(timing);
+
+                let request = MediateRequest::with_decorators(
+                    Uuid::new_v4().to_string(),
+                    MediateRequestContent::default(),
+                    decorators,
+                );
+                send_message(request.clone().into()).await?;
+
+                ClientFullState::RequestSent(RequestSentState { request })
+            }
+            _ => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Mediation was already requested",
+                ))
+            }
+        };
+        Ok(Self { state })
+    }
+
+    pub fn handle_grant(self, grant: MediateGrant) -> VcxResult<Self> {
+        let state = match self.state {
+            ClientFullState::RequestSent(state) => {
+                verify_thread_id(&state.request.id, &grant.clone().into())?;
+                ClientFullState::Granted(GrantedState {
+                    grant,
+                    keys: BTreeSet::new(),
+                })
+            }
+            _ => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Mediation grant was not expected in this state",
+                ))
+            }
+        };
+        Ok(Self { state })
+    }
+
+    pub fn handle_deny(self, deny: MediateDeny) -> VcxResult<Self> {
+        let state = match self.state {
+            ClientFullState::RequestSent(state) => {
+                verify_thread_id(&state.request.id, &deny.clone().into())?;
+                ClientFullState::Denied(DeniedState { deny })
+            }
+            _ => {
+                return Err(AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidState,
+                    "Mediation deny was not expected in this state",
+                ))
+            }
+        };
+        Ok(Self { state })
+    }
+
+    /// Asks the mediator to add or remove the given recipient keys (as naked verkeys)
+    /// from the keys it routes messages for.
+    pub async fn send_keylist_update(
+        &self,
+        updates: Vec<(String, KeylistUpdateAction)>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.granted_state()?;
+
+        let updates = updates
+            .into_iter()
+            .map(|(key, action)| Ok(KeylistUpdateItem::new(ed25519_public_key_to_did_key(&key)?, action)))
+            .collect::<VcxResult<Vec<_>>>()?;
+
+        let mut decorators = KeylistUpdateDecorators::default();
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let update = KeylistUpdate::with_decorators(
+            Uuid::new_v4().to_string(),
+            KeylistUpdateContent::new(updates),
+            decorators,
+        );
+        send_message(update.into()).await
+    }
+
+    pub fn handle_keylist_update_response(self, response: KeylistUpdateResponse) -> VcxResult<Self> {
+        let ClientFullState::Granted(mut state) = self.state else {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Keylist update response was not expected in this state",
+            ));
+        };
+
+        for updated in response.content.updated {
+            match updated.result {
+                KeylistUpdateResult::Success | KeylistUpdateResult::NoChange => {}
+                KeylistUpdateResult::ClientError | KeylistUpdateResult::ServerError => {
+                    warn!(
+                        "Mediator failed to {:?} key {}: {:?}",
+                        updated.action, updated.recipient_key, updated.result
+                    );
+                    continue;
+                }
+            }
+            let key = normalize_keys_as_naked(vec![updated.recipient_key])?.remove(0);
+            match updated.action {
+                KeylistUpdateAction::Add => state.keys.insert(key),
+                KeylistUpdateAction::Remove => state.keys.remove(&key),
+            };
+        }
+
+        Ok(Self {
+            state: ClientFullState::Granted(state),
+        })
+    }
+
+    pub async fn send_keylist_query(
+        &self,
+        paginate: Option<KeylistQueryPaginate>,
+        send_message: SendClosure,
+    ) -> VcxResult<()> {
+        self.granted_state()?;
+
+        let mut content = KeylistQueryContent::default();
+        content.paginate = paginate;
+
+        let mut decorators = KeylistQueryDecorators::default();
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let query = KeylistQuery::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+        send_message(query.into()).await
+    }
+
+    /// Processes the [`Keylist`] sent in reply to a keylist query. The first page of the
+    /// keylist replaces the known keys, while the following ones extend them.
+    pub fn handle_keylist(self, keylist: Keylist) -> VcxResult<Self> {
+        let ClientFullState::Granted(mut state) = self.state else {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidState,
+                "Keylist was not expected in this state",
+            ));
+        };
+
+        let is_first_page = keylist
+            .content
+            .pagination
+            .as_ref()
+            .map(|pagination| pagination.offset == 0)
+            .unwrap_or(true);
+        if is_first_page {
+            state.keys.clear();
+        }
+
+        let keys = keylist
+            .content
+            .keys
+            .into_iter()
+            .map(|item| item.recipient_key)
+            .collect();
+        state.keys.extend(normalize_keys_as_naked(keys)?);
+
+        Ok(Self {
+            state: ClientFullState::Granted(state),
+        })
+    }
+
+    fn granted_state(&self) -> VcxResult<&GrantedState> {
+        match &self.state {
+            ClientFullState::Granted(state) => Ok(state),
+            _ => Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Mediation has not been granted",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+pub mod unit_tests {
+    use messages::decorators::thread::Thread;
+    use messages::msg_fields::protocols::coordinate_mediation::keylist::{
+        KeylistContent, KeylistDecorators, KeylistItem,
+    };
+    use messages::msg_fields::protocols::coordinate_mediation::keylist_update_response::{
+        KeylistUpdateResponseContent, KeylistUpdateResponseDecorators, KeylistUpdateResponseItem,
+    };
+    use messages::msg_fields::protocols::coordinate_mediation::mediate_deny::{
+        MediateDenyContent, MediateDenyDecorators,
+    };
+    use messages::msg_fields::protocols::coordinate_mediation::mediate_grant::{
+        MediateGrantContent, MediateGrantDecorators,
+    };
+    use messages::AriesMessage;
+
+    use super::*;
+
+    const VERKEY: &str = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+    const ROUTING_KEY: &str = "did:key:z6MkpTHR8VNsBxYAAWHut2Geadd9jSwuBV8xRoAnwWsdvktH";
+
+    fn _send_message() -> SendClosure {
+        Box::new(|_: AriesMessage| Box::pin(async { VcxResult::Ok(()) }))
+    }
+
+    fn _grant(thread_id: &str) -> MediateGrant {
+        let content = MediateGrantContent::new(
+            "https://mediator.example.org/".parse().unwrap(),
+            vec![ROUTING_KEY.to_owned()],
+        );
+        let decorators = MediateGrantDecorators::new(Thread::new(thread_id.to_owned()));
+        MediateGrant::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+    }
+
+    fn _keylist_update_response(result: KeylistUpdateResult) -> KeylistUpdateResponse {
+        let content = KeylistUpdateResponseContent::new(vec![KeylistUpdateResponseItem::new(
+            ed25519_public_key_to_did_key(VERKEY).unwrap(),
+            KeylistUpdateAction::Add,
+            result,
+        )]);
+        let decorators = KeylistUpdateResponseDecorators::new(Thread::new(Uuid::new_v4().to_string()));
+        KeylistUpdateResponse::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+    }
+
+    async fn _granted_sm() -> MediationClientSM {
+        let sm = MediationClientSM::create().send_request(_send_message()).await.unwrap();
+        let thread_id = sm.get_thread_id().unwrap();
+        sm.handle_grant(_grant(&thread_id)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_handle_grant() {
+        let sm = _granted_sm().await;
+        assert!(sm.is_granted());
+
+        let (endpoint, routing_keys) = sm.get_routing_info().unwrap();
+        assert_eq!(endpoint.as_str(), "https://mediator.example.org/");
+        assert_eq!(
+            routing_keys,
+            normalize_keys_as_naked(vec![ROUTING_KEY.to_owned()]).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_grant_fails_on_thread_id_mismatch() {
+        let sm = MediationClientSM::create().send_request(_send_message()).await.unwrap();
+        sm.handle_grant(_grant("other_thread_id")).unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_handle_deny() {
+        let sm = MediationClientSM::create().send_request(_send_message()).await.unwrap();
+        let thread_id = sm.get_thread_id().unwrap();
+        let deny = MediateDeny::with_decorators(
+            Uuid::new_v4().to_string(),
+            MediateDenyContent::default(),
+            MediateDenyDecorators::new(Thread::new(thread_id)),
+        );
+
+        let sm = sm.handle_deny(deny).unwrap();
+        assert!(sm.is_denied());
+        sm.get_routing_info().unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_keylist_update_requires_grant() {
+        let sm = MediationClientSM::create();
+        sm.send_keylist_update(vec![(VERKEY.to_owned(), KeylistUpdateAction::Add)], _send_message())
+            .await
+            .unwrap_err();
+    }
+
+    #[tokio::test]
+    async fn test_handle_keylist_update_response() {
+        let sm = _granted_sm().await;
+        sm.send_keylist_update(vec![(VERKEY.to_owned(), KeylistUpdateAction::Add)], _send_message())
+            .await
+            .unwrap();
+
+        let sm = sm
+            .handle_keylist_update_response(_keylist_update_response(KeylistUpdateResult::ServerError))
+            .unwrap();
+        assert!(sm.get_keys().unwrap().is_empty());
+
+        let sm = sm
+            .handle_keylist_update_response(_keylist_update_response(KeylistUpdateResult::Success))
+            .unwrap();
+        assert_eq!(sm.get_keys().unwrap(), vec![VERKEY.to_owned()]);
+    }
+
+    #[tokio::test]
+    async fn test_handle_keylist() {
+        let sm = _granted_sm().await;
+        let content = KeylistContent::new(vec![KeylistItem::new(ed25519_public_key_to_did_key(VERKEY).unwrap())]);
+        let decorators = KeylistDecorators::new(Thread::new(Uuid::new_v4().to_string()));
+        let keylist = Keylist::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        let sm = sm.handle_keylist(keylist).unwrap();
+        assert_eq!(sm.get_keys().unwrap(), vec![VERKEY.to_owned()]);
+    }
+}
@@ -0,0 +1,22 @@
+use std::collections::BTreeSet;
+
+use messages::msg_fields::protocols::coordinate_mediation::{
+    mediate_deny::MediateDeny, mediate_grant::MediateGrant, mediate_request::MediateRequest,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RequestSentState {
+    pub(super) request: MediateRequest,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GrantedState {
+    pub(super) grant: MediateGrant,
+    /// The recipient keys the mediator confirmed to route messages for, as naked verkeys.
+    pub(super) keys: BTreeSet<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DeniedState {
+    pub(super) deny: MediateDeny,
+}
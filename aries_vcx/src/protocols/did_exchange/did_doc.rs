@@ -0,0 +1,290 @@
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use did_resolver_peer::did_resolver::{
+    did_doc::schema::{
+        did_doc::DidDocument,
+        verification_method::{VerificationMethod, VerificationMethodKind},
+    },
+    did_parser::Did,
+    traits::resolvable::resolution_options::DidResolutionOptions,
+};
+use did_resolver_registry::ResolverRegistry;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::{
+    decorators::attachment::{Attachment, AttachmentData, AttachmentType},
+    misc::MimeType,
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    common::{
+        ledger::transactions::normalize_keys_as_naked,
+        signing::{sign_attachment_jws, verify_attachment_jws},
+    },
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+};
+
+/// Resolves a public DID through the [`ResolverRegistry`] and converts the resolved
+/// document into an [`AriesDidDoc`] that messages can be packed for.
+pub(crate) async fn resolve_did_doc(registry: &ResolverRegistry, did: &str) -> VcxResult<AriesDidDoc> {
+    let parsed_did = Did::parse(did.to_owned()).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("Failed to parse DID {did}: {err}"),
+        )
+    })?;
+    let output = registry
+        .resolve(&parsed_did, &DidResolutionOptions::default())
+        .await
+        .map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidDid,
+                format!("Failed to resolve DID {did}: {err}"),
+            )
+        })?;
+
+    into_aries_did_doc(did, output.did_document())
+}
+
+fn into_aries_did_doc(did: &str, document: &DidDocument<Value>) -> VcxResult<AriesDidDoc> {
+    let service = document.service().first().ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("DID document of {did} does not contain any service"),
+        )
+    })?;
+    let service_endpoint = service.service_endpoint().to_string().parse().map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidUrl,
+            format!("Invalid service endpoint in DID document of {did}: {err}"),
+        )
+    })?;
+
+    let recipient_keys = match string_list(&service.extra()["recipientKeys"]) {
+        keys if !keys.is_empty() => keys,
+        _ => authentication_keys(document)?,
+    };
+    let routing_keys = string_list(&service.extra()["routingKeys"]);
+
+    let mut did_doc = AriesDidDoc::default();
+    did_doc.set_id(did.to_owned());
+    did_doc.set_service_endpoint(service_endpoint);
+    did_doc.set_recipient_keys(normalize_keys_as_naked(recipient_keys)?);
+    did_doc.set_routing_keys(normalize_keys_as_naked(routing_keys)?);
+    Ok(did_doc)
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|values| values.iter().filter_map(Value::as_str).map(ToOwned::to_owned).collect())
+        .unwrap_or_default()
+}
+
+/// Collects the base58 encoded keys of the authentication verification methods,
+/// dereferencing the ones referenced by their DID URL.
+fn authentication_keys(document: &DidDocument<Value>) -> VcxResult<Vec<String>> {
+    let mut keys = Vec::new();
+
+    for method in document.authentication() {
+        let method = match method {
+            VerificationMethodKind::Resolved(method) => Some(method),
+            VerificationMethodKind::Resolvable(reference) => document
+                .verification_method()
+                .iter()
+                .find(|method| method.id().to_string() == reference.to_string()),
+        };
+        if let Some(key) = method.and_then(verification_method_key) {
+            keys.push(key);
+        }
+    }
+
+    if keys.is_empty() {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidDid,
+            format!("DID document of {} does not contain any recipient key", document.id()),
+        ));
+    }
+    Ok(keys)
+}
+
+fn verification_method_key(method: &VerificationMethod) -> Option<String> {
+    if let Some(key) = method.extra_field("publicKeyBase58").and_then(Value::as_str) {
+        return Some(key.to_owned());
+    }
+    // Multibase encoded Ed25519 keys share their encoding with did:key fingerprints
+    let multibase = serde_json::to_value(method.public_key_multibase()?).ok()?;
+    multibase.as_str().map(|key| format!("did:key:{key}"))
+}
+
+/// Creates a `did_doc~attach` attachment containing the DID document, signed with `signing_key`.
+pub(crate) async fn attach_signed_did_doc(
+    wallet: &Arc<dyn BaseWallet>,
+    did_doc: &AriesDidDoc,
+    signing_key: &str,
+) -> VcxResult<Attachment> {
+    let payload = base64::encode(serde_json::to_string(did_doc)?);
+    let jws = sign_attachment_jws(wallet, signing_key, &payload).await?;
+
+    let mut attach_data = AttachmentData::new(AttachmentType::Base64(payload));
+    attach_data.jws = Some(jws);
+
+    let mut attach = Attachment::new(attach_data);
+    attach.id = Some(Uuid::new_v4().to_string());
+    attach.mime_type = Some(MimeType::Json);
+    Ok(attach)
+}
+
+/// Verifies the signature of a `did_doc~attach` attachment, returning the attached
+/// DID document along with the verkey it was signed with.
+pub(crate) async fn did_doc_from_attach(
+    wallet: &Arc<dyn BaseWallet>,
+    attach: &Attachment,
+) -> VcxResult<(AriesDidDoc, String)> {
+    let AttachmentType::Base64(payload) = &attach.data.content else {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidMessageFormat,
+            "DID document attachment is not base64 encoded",
+        ));
+    };
+    let jws = attach.data.jws.as_ref().ok_or_else(|| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidMessageFormat,
+            "DID document attachment is not signed",
+        )
+    })?;
+    let signer = verify_attachment_jws(wallet, jws, payload).await?;
+
+    let did_doc = base64::decode(payload).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidMessageFormat,
+            format!("Cannot decode DID document attachment: {err}"),
+        )
+    })?;
+    let did_doc = serde_json::from_slice(&did_doc)?;
+
+    Ok((did_doc, signer))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use aries_vcx_core::wallet::indy_wallet::IndySdkWallet;
+
+    use super::*;
+    use crate::utils::devsetup::SetupLibraryWallet;
+
+    fn did_doc(verkey: &str) -> AriesDidDoc {
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.set_id("did:sov:2wJPyULfLLnYTEFYzByfUR".to_owned());
+        did_doc.set_service_endpoint("https://example.org/agent".parse().unwrap());
+        did_doc.set_recipient_keys(vec![verkey.to_owned()]);
+        did_doc
+    }
+
+    #[test]
+    fn test_into_aries_did_doc_from_sov_service() {
+        let document: DidDocument<Value> = serde_json::from_value(json!({
+            "id": "did:sov:2wJPyULfLLnYTEFYzByfUR",
+            "service": [{
+                "id": "did:sov:2wJPyULfLLnYTEFYzByfUR#did-communication",
+                "type": "did-communication",
+                "serviceEndpoint": "https://example.org/agent",
+                "recipientKeys": ["8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K"],
+                "routingKeys": ["Hezce2UWMZ3wUhVkh2LfKSs8nDzWwzs2Win7EzNN3YaR"]
+            }]
+        }))
+        .unwrap();
+
+        let did_doc = into_aries_did_doc("did:sov:2wJPyULfLLnYTEFYzByfUR", &document).unwrap();
+        assert_eq!(did_doc.id, "did:sov:2wJPyULfLLnYTEFYzByfUR");
+        assert_eq!(
+            did_doc.recipient_keys().unwrap(),
+            vec!["8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K".to_owned()]
+        );
+        assert_eq!(
+            did_doc.routing_keys(),
+            vec!["Hezce2UWMZ3wUhVkh2LfKSs8nDzWwzs2Win7EzNN3YaR".to_owned()]
+        );
+        assert_eq!(did_doc.get_endpoint().unwrap().as_str(), "https://example.org/agent");
+    }
+
+    #[test]
+    fn test_into_aries_did_doc_without_service_fails() {
+        let document: DidDocument<Value> = serde_json::from_value(json!({
+            "id": "did:sov:2wJPyULfLLnYTEFYzByfUR"
+        }))
+        .unwrap();
+
+        let err = into_aries_did_doc("did:sov:2wJPyULfLLnYTEFYzByfUR", &document).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidDid);
+    }
+
+    #[tokio::test]
+    async fn test_signed_did_doc_attachment_round_trip() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let attach = attach_signed_did_doc(&wallet, &did_doc(&verkey), &verkey)
+                .await
+                .unwrap();
+
+            let attach_json = serde_json::to_value(&attach).unwrap();
+            let payload = attach_json["data"]["base64"].as_str().unwrap();
+            assert_eq!(
+                base64::decode(payload).unwrap(),
+                serde_json::to_vec(&did_doc(&verkey)).unwrap()
+            );
+            assert!(attach_json["data"]["jws"]["header"]["kid"]
+                .as_str()
+                .unwrap()
+                .starts_with("did:key:"));
+            assert!(attach_json["data"]["jws"]["protected"].is_string());
+            assert!(attach_json["data"]["jws"]["signature"].is_string());
+
+            let (received_did_doc, signer) = did_doc_from_attach(&wallet, &attach).await.unwrap();
+            assert_eq!(received_did_doc, did_doc(&verkey));
+            assert_eq!(signer, verkey);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_tampered_did_doc_attachment_fails() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let (_, other_verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let mut attach = attach_signed_did_doc(&wallet, &did_doc(&verkey), &verkey)
+                .await
+                .unwrap();
+            let payload = base64::encode(serde_json::to_string(&did_doc(&other_verkey)).unwrap());
+            attach.data.content = AttachmentType::Base64(payload);
+
+            let err = did_doc_from_attach(&wallet, &attach).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_did_doc_attachment_fails() {
+        SetupLibraryWallet::run(|setup| async move {
+            let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(setup.wallet_handle));
+            let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+            let mut attach = attach_signed_did_doc(&wallet, &did_doc(&verkey), &verkey)
+                .await
+                .unwrap();
+            attach.data.jws = None;
+
+            let err = did_doc_from_attach(&wallet, &attach).await.unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidMessageFormat);
+        })
+        .await;
+    }
+}
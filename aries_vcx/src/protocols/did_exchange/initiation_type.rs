@@ -0,0 +1,7 @@
+/// Unit struct illustrating that the DID exchange is driven from the requester side.
+#[derive(Clone, Copy, Debug)]
+pub struct Requester;
+
+/// Unit struct illustrating that the DID exchange is driven from the responder side.
+#[derive(Clone, Copy, Debug)]
+pub struct Responder;
@@ -0,0 +1,459 @@
+//! State machines for progressing through the [DID exchange protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0023-did-exchange/README.md).
+//!
+//! Unlike the connection protocol, the DID exchange lets both parties either attach a signed
+//! DID document to their message (`did_doc~attach`) or refer to a public DID that the counterparty
+//! resolves through a [`did_resolver_registry::ResolverRegistry`].
+
+mod did_doc;
+pub mod initiation_type;
+pub mod requester;
+pub mod responder;
+mod trait_bounds;
+
+use std::{error::Error, sync::Arc};
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use chrono::Utc;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::did_exchange::problem_report::{
+        ProblemCode, ProblemReport, ProblemReportContent, ProblemReportDecorators,
+    },
+    AriesMessage,
+};
+use uuid::Uuid;
+
+use self::trait_bounds::{TheirDidDoc, ThreadId};
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    protocols::connection::{pairwise_info::PairwiseInfo, wrap_and_send_msg},
+    transport::Transport,
+};
+
+pub use self::{requester::RequesterDidExchange, responder::ResponderDidExchange};
+
+/// A state machine for progressing through the DID exchange protocol, typed by the role
+/// of the party driving it (`I`) and the current state of the exchange (`S`).
+#[derive(Clone, Debug)]
+pub struct DidExchange<I, S> {
+    pairwise_info: PairwiseInfo,
+    initiation_type: I,
+    state: S,
+}
+
+impl<I, S> DidExchange<I, S> {
+    pub fn from_parts(pairwise_info: PairwiseInfo, initiation_type: I, state: S) -> Self {
+        Self {
+            pairwise_info,
+            initiation_type,
+            state,
+        }
+    }
+
+    pub fn into_parts(self) -> (PairwiseInfo, I, S) {
+        let Self {
+            pairwise_info,
+            initiation_type,
+            state,
+        } = self;
+        (pairwise_info, initiation_type, state)
+    }
+
+    pub fn pairwise_info(&self) -> &PairwiseInfo {
+        &self.pairwise_info
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    fn transit<T>(self, state: T) -> DidExchange<I, T> {
+        DidExchange {
+            pairwise_info: self.pairwise_info,
+            initiation_type: self.initiation_type,
+            state,
+        }
+    }
+}
+
+impl<I, S> DidExchange<I, S>
+where
+    S: ThreadId,
+{
+    pub fn thread_id(&self) -> &str {
+        self.state.thread_id()
+    }
+}
+
+impl<I, S> DidExchange<I, S>
+where
+    S: TheirDidDoc,
+{
+    pub fn their_did_doc(&self) -> &AriesDidDoc {
+        self.state.their_did_doc()
+    }
+
+    pub fn remote_did(&self) -> &str {
+        &self.their_did_doc().id
+    }
+
+    pub fn remote_vk(&self) -> VcxResult<String> {
+        self.their_did_doc()
+            .recipient_keys()?
+            .first()
+            .map(ToOwned::to_owned)
+            .ok_or(AriesVcxError::from_msg(
+                AriesVcxErrorKind::NotReady,
+                "Can't resolve recipient key from the counterparty diddoc.",
+            ))
+    }
+
+    pub async fn send_message<T>(
+        &self,
+        wallet: &Arc<dyn BaseWallet>,
+        message: &AriesMessage,
+        transport: &T,
+    ) -> VcxResult<()>
+    where
+        T: Transport,
+    {
+        let sender_verkey = &self.pairwise_info.pw_vk;
+        wrap_and_send_msg(wallet, message, sender_verkey, self.their_did_doc(), transport).await
+    }
+}
+
+/// Builds a DID exchange [`ProblemReport`] explaining the given error.
+fn build_problem_report<E>(err: &E, problem_code: ProblemCode, thread: Thread) -> ProblemReport
+where
+    E: Error,
+{
+    let mut content = ProblemReportContent::default();
+    content.problem_code = Some(problem_code);
+    content.explain = Some(err.to_string());
+
+    let mut decorators = ProblemReportDecorators::new(thread);
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    ProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+/// Sends a [`ProblemReport`] on a best effort basis, as it's only sent while
+/// already failing to process a message of the counterparty.
+async fn send_problem_report<T>(
+    wallet: &Arc<dyn BaseWallet>,
+    problem_report: ProblemReport,
+    sender_verkey: &str,
+    did_doc: &AriesDidDoc,
+    transport: &T,
+) where
+    T: Transport,
+{
+    let res = wrap_and_send_msg(wallet, &problem_report.into(), sender_verkey, did_doc, transport).await;
+
+    if let Err(e) = res {
+        trace!("Error encountered when sending ProblemReport: {}", e);
+    } else {
+        info!("Error report sent!");
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use aries_vcx_core::INVALID_POOL_HANDLE;
+    use did_resolver_registry::ResolverRegistry;
+    use diddoc_legacy::aries::service::AriesService;
+    use messages::{
+        msg_fields::protocols::{
+            did_exchange::{
+                complete::{Complete, CompleteContent, CompleteDecorators},
+                problem_report::ProblemCode as DidExchangeProblemCode,
+                request::Request,
+                response::Response,
+                DidExchange as DidExchangeMsg,
+            },
+            out_of_band::invitation::{Invitation, OobService},
+        },
+        msg_types::{protocols::did_exchange::DidExchangeTypeV1, Protocol},
+    };
+    use url::Url;
+
+    use super::{
+        did_doc::{attach_signed_did_doc, did_doc_from_attach},
+        requester::{states as requester_states, RequesterDidExchange},
+        responder::{states as responder_states, ResponderDidExchange},
+        *,
+    };
+    use crate::{
+        common::test_utils::indy_handles_to_profile,
+        core::profile::profile::Profile,
+        handlers::out_of_band::sender::OutOfBandSender,
+        transport::{in_process::InProcessTransport, inbound::InboundTransport},
+        utils::{devsetup::SetupLibraryWallet, encryption_envelope::EncryptionEnvelope},
+    };
+
+    fn inviter_endpoint() -> Url {
+        "https://inviter.example.org/agent".parse().unwrap()
+    }
+
+    fn requester_endpoint() -> Url {
+        "https://requester.example.org/agent".parse().unwrap()
+    }
+
+    fn invitation(invitation_key: &str) -> Invitation {
+        let service = AriesService::create()
+            .set_service_endpoint(inviter_endpoint())
+            .set_recipient_keys(vec![invitation_key.to_owned()]);
+
+        OutOfBandSender::create()
+            .append_service(&OobService::AriesService(service))
+            .append_handshake_protocol(Protocol::from(DidExchangeTypeV1::new_v1_0()))
+            .unwrap()
+            .oob
+    }
+
+    async fn receive(wallet: &Arc<dyn BaseWallet>, transport: &InProcessTransport, endpoint: Url) -> DidExchangeMsg {
+        let msg = transport.endpoint(endpoint).receive_message().await.unwrap().unwrap();
+        match EncryptionEnvelope::anon_unpack(wallet, msg).await.unwrap().0 {
+            AriesMessage::DidExchange(msg) => msg,
+            msg => panic!("Expected a DID exchange message, got {msg:?}"),
+        }
+    }
+
+    async fn receive_request(wallet: &Arc<dyn BaseWallet>, transport: &InProcessTransport) -> Request {
+        match receive(wallet, transport, inviter_endpoint()).await {
+            DidExchangeMsg::Request(request) => request,
+            msg => panic!("Expected a request, got {msg:?}"),
+        }
+    }
+
+    async fn receive_response(wallet: &Arc<dyn BaseWallet>, transport: &InProcessTransport) -> Response {
+        match receive(wallet, transport, requester_endpoint()).await {
+            DidExchangeMsg::Response(response) => response,
+            msg => panic!("Expected a response, got {msg:?}"),
+        }
+    }
+
+    async fn receive_complete(wallet: &Arc<dyn BaseWallet>, transport: &InProcessTransport) -> Complete {
+        match receive(wallet, transport, inviter_endpoint()).await {
+            DidExchangeMsg::Complete(complete) => complete,
+            msg => panic!("Expected a complete message, got {msg:?}"),
+        }
+    }
+
+    /// Drives the exchange until the responder processed the request, returning the invitation key along
+    /// with both parties.
+    async fn requested(
+        profile: &Arc<dyn Profile>,
+        transport: &InProcessTransport,
+    ) -> (
+        String,
+        RequesterDidExchange<requester_states::Requested>,
+        ResponderDidExchange<responder_states::Requested>,
+    ) {
+        let wallet = profile.inject_wallet();
+        let (_, invitation_key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        let requester: RequesterDidExchange<requester_states::Invited> = RequesterDidExchange::accept_invitation(
+            profile,
+            PairwiseInfo::create(&wallet).await.unwrap(),
+            invitation(&invitation_key),
+        )
+        .await
+        .unwrap();
+        let requester = requester
+            .send_request(
+                &wallet,
+                "requester".to_owned(),
+                requester_endpoint(),
+                vec![],
+                None,
+                transport,
+            )
+            .await
+            .unwrap();
+
+        let request = receive_request(&wallet, transport).await;
+        let responder = ResponderDidExchange::receive_request(
+            &wallet,
+            &ResolverRegistry::new(),
+            PairwiseInfo::create(&wallet).await.unwrap(),
+            request,
+            invitation_key.clone(),
+        )
+        .await
+        .unwrap();
+
+        (invitation_key, requester, responder)
+    }
+
+    #[tokio::test]
+    async fn test_did_exchange_flow() {
+        SetupLibraryWallet::run(|setup| async move {
+            let profile = indy_handles_to_profile(setup.wallet_handle, INVALID_POOL_HANDLE);
+            let wallet = profile.inject_wallet();
+            let transport = InProcessTransport::new();
+
+            let (invitation_key, requester, responder) = requested(&profile, &transport).await;
+            assert_eq!(requester.thread_id(), responder.thread_id());
+            assert_eq!(responder.remote_vk().unwrap(), requester.pairwise_info().pw_vk);
+
+            let responder = responder
+                .send_response(&wallet, inviter_endpoint(), vec![], &transport)
+                .await
+                .unwrap();
+            let response = receive_response(&wallet, &transport).await;
+            assert_eq!(response.content.did, responder.pairwise_info().pw_did);
+
+            let requester = requester
+                .handle_response(&wallet, &ResolverRegistry::new(), response, &transport)
+                .await
+                .unwrap();
+            assert_eq!(requester.remote_vk().unwrap(), responder.pairwise_info().pw_vk);
+            assert_ne!(requester.remote_vk().unwrap(), invitation_key);
+
+            let requester = requester.send_complete(&wallet, &transport).await.unwrap();
+            let complete = receive_complete(&wallet, &transport).await;
+            let responder = responder.handle_complete(complete).unwrap();
+
+            assert_eq!(requester.thread_id(), responder.thread_id());
+            assert_eq!(requester.remote_did(), responder.pairwise_info().pw_did);
+            assert_eq!(responder.remote_did(), requester.pairwise_info().pw_did);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_did_exchange_request_with_tampered_did_doc_fails() {
+        SetupLibraryWallet::run(|setup| async move {
+            let profile = indy_handles_to_profile(setup.wallet_handle, INVALID_POOL_HANDLE);
+            let wallet = profile.inject_wallet();
+            let transport = InProcessTransport::new();
+
+            let (invitation_key, requester, _) = requested(&profile, &transport).await;
+
+            let mut request = requester.request().clone();
+            let mut did_doc = requester.their_did_doc().clone();
+            did_doc.set_recipient_keys(vec![invitation_key.clone()]);
+            let mut attach = attach_signed_did_doc(&wallet, &did_doc, &invitation_key).await.unwrap();
+            attach.data.jws = request.content.did_doc.as_ref().unwrap().data.jws.clone();
+            request.content.did_doc = Some(attach);
+
+            let err = ResponderDidExchange::receive_request(
+                &wallet,
+                &ResolverRegistry::new(),
+                PairwiseInfo::create(&wallet).await.unwrap(),
+                request,
+                invitation_key,
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_did_exchange_request_signed_with_foreign_key_fails() {
+        SetupLibraryWallet::run(|setup| async move {
+            let profile = indy_handles_to_profile(setup.wallet_handle, INVALID_POOL_HANDLE);
+            let wallet = profile.inject_wallet();
+            let transport = InProcessTransport::new();
+
+            let (invitation_key, requester, _) = requested(&profile, &transport).await;
+
+            // A validly signed DID document, but not by any of its own keys
+            let mut request = requester.request().clone();
+            let (_, foreign_key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let attach = attach_signed_did_doc(&wallet, requester.their_did_doc(), &foreign_key)
+                .await
+                .unwrap();
+            request.content.did_doc = Some(attach);
+
+            let err = ResponderDidExchange::receive_request(
+                &wallet,
+                &ResolverRegistry::new(),
+                PairwiseInfo::create(&wallet).await.unwrap(),
+                request,
+                invitation_key,
+            )
+            .await
+            .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_did_exchange_response_not_signed_with_invitation_key_fails() {
+        SetupLibraryWallet::run(|setup| async move {
+            let profile = indy_handles_to_profile(setup.wallet_handle, INVALID_POOL_HANDLE);
+            let wallet = profile.inject_wallet();
+            let transport = InProcessTransport::new();
+
+            let (_, requester, responder) = requested(&profile, &transport).await;
+            responder
+                .send_response(&wallet, inviter_endpoint(), vec![], &transport)
+                .await
+                .unwrap();
+            let mut response = receive_response(&wallet, &transport).await;
+
+            let (_, foreign_key) = wallet.create_and_store_my_did(None, None).await.unwrap();
+            let (did_doc, _) = did_doc_from_attach(&wallet, response.content.did_doc.as_ref().unwrap())
+                .await
+                .unwrap();
+            response.content.did_doc = Some(attach_signed_did_doc(&wallet, &did_doc, &foreign_key).await.unwrap());
+
+            let err = requester
+                .handle_response(&wallet, &ResolverRegistry::new(), response, &transport)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+
+            // The responder is notified through a problem report
+            match receive(&wallet, &transport, inviter_endpoint()).await {
+                DidExchangeMsg::ProblemReport(problem_report) => assert_eq!(
+                    problem_report.content.problem_code,
+                    Some(DidExchangeProblemCode::ResponseNotAccepted)
+                ),
+                msg => panic!("Expected a problem report, got {msg:?}"),
+            }
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_did_exchange_messages_with_other_thread_id_fail() {
+        SetupLibraryWallet::run(|setup| async move {
+            let profile = indy_handles_to_profile(setup.wallet_handle, INVALID_POOL_HANDLE);
+            let wallet = profile.inject_wallet();
+            let transport = InProcessTransport::new();
+
+            let (_, requester, responder) = requested(&profile, &transport).await;
+            let responder = responder
+                .send_response(&wallet, inviter_endpoint(), vec![], &transport)
+                .await
+                .unwrap();
+
+            let mut response = receive_response(&wallet, &transport).await;
+            response.decorators.thread = Thread::new("other_thread_id".to_owned());
+            let err = requester
+                .handle_response(&wallet, &ResolverRegistry::new(), response, &transport)
+                .await
+                .unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+
+            let complete = Complete::with_decorators(
+                Uuid::new_v4().to_string(),
+                CompleteContent::default(),
+                CompleteDecorators::new(Thread::new("other_thread_id".to_owned())),
+            );
+            let err = responder.handle_complete(complete).unwrap_err();
+            assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,277 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use chrono::Utc;
+use did_resolver_registry::ResolverRegistry;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        did_exchange::{
+            complete::{Complete, CompleteContent, CompleteDecorators},
+            problem_report::ProblemCode,
+            request::{Request, RequestContent, RequestDecorators},
+            response::Response,
+        },
+        out_of_band::invitation::Invitation,
+    },
+};
+use url::Url;
+use uuid::Uuid;
+
+use self::states::{Completed, Invited, Requested, Responded};
+use super::{
+    build_problem_report,
+    did_doc::{attach_signed_did_doc, did_doc_from_attach, resolve_did_doc},
+    initiation_type::Requester,
+    send_problem_report, DidExchange,
+};
+use crate::{
+    common::ledger::transactions::into_did_doc,
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::{verify_thread_id, AnyInvitation},
+    protocols::connection::{pairwise_info::PairwiseInfo, wrap_and_send_msg},
+    transport::Transport,
+};
+
+/// Convenience alias
+pub type RequesterDidExchange<S> = DidExchange<Requester, S>;
+
+impl RequesterDidExchange<Invited> {
+    /// Accepts an out-of-band [`Invitation`] listing `didexchange/1.0` among its handshake
+    /// protocols, resolving the DID document of the inviter from the invitation's service.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if a DID document could not be resolved from the [`Invitation`].
+    pub async fn accept_invitation(
+        profile: &Arc<dyn Profile>,
+        pairwise_info: PairwiseInfo,
+        invitation: Invitation,
+    ) -> VcxResult<Self> {
+        trace!("DidExchange::accept_invitation >>> invitation: {:?}", &invitation);
+
+        let parent_thread_id = invitation.id.clone();
+        let did_doc = into_did_doc(profile, &AnyInvitation::Oob(invitation)).await?;
+
+        Ok(Self::from_parts(
+            pairwise_info,
+            Requester,
+            Invited {
+                did_doc,
+                parent_thread_id,
+            },
+        ))
+    }
+
+    /// Starts an exchange with the owner of a public DID, which acts as an implicit invitation.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the DID could not be resolved through the [`ResolverRegistry`].
+    pub async fn accept_public_did(
+        registry: &ResolverRegistry,
+        pairwise_info: PairwiseInfo,
+        did: &str,
+    ) -> VcxResult<Self> {
+        trace!("DidExchange::accept_public_did >>> did: {}", did);
+
+        let did_doc = resolve_did_doc(registry, did).await?;
+
+        Ok(Self::from_parts(
+            pairwise_info,
+            Requester,
+            Invited {
+                did_doc,
+                parent_thread_id: did.to_owned(),
+            },
+        ))
+    }
+
+    /// Returns the ID of the invitation, or the public DID, the exchange was started from.
+    pub fn parent_thread_id(&self) -> &str {
+        &self.state.parent_thread_id
+    }
+
+    /// Sends a [`Request`] to the responder and transitions to [`RequesterDidExchange<Requested>`].
+    ///
+    /// When `public_did` is provided, the request only refers to it and the responder is expected
+    /// to resolve it. Otherwise a DID document built from the pairwise info, the `service_endpoint`
+    /// and the `routing_keys` is attached to the request, signed with the pairwise verkey.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if signing the DID document or sending the request fails.
+    pub async fn send_request<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        label: String,
+        service_endpoint: Url,
+        routing_keys: Vec<String>,
+        public_did: Option<String>,
+        transport: &T,
+    ) -> VcxResult<RequesterDidExchange<Requested>>
+    where
+        T: Transport,
+    {
+        trace!(
+            "DidExchange::send_request >>> label: {}, public_did: {:?}",
+            label,
+            public_did
+        );
+
+        let content = match public_did {
+            Some(did) => RequestContent::new(label, did),
+            None => {
+                let mut did_doc = AriesDidDoc::default();
+                did_doc.id = self.pairwise_info.pw_did.clone();
+                did_doc.set_service_endpoint(service_endpoint);
+                did_doc.set_routing_keys(routing_keys);
+                did_doc.set_recipient_keys(vec![self.pairwise_info.pw_vk.clone()]);
+
+                let mut content = RequestContent::new(label, self.pairwise_info.pw_did.clone());
+                content.did_doc = Some(attach_signed_did_doc(wallet, &did_doc, &self.pairwise_info.pw_vk).await?);
+                content
+            }
+        };
+
+        // The request starts a new thread, as multiple requesters can use the same invitation.
+        let id = Uuid::new_v4().to_string();
+        let mut thread = Thread::new(id.clone());
+        thread.pthid = Some(self.state.parent_thread_id.clone());
+
+        let mut decorators = RequestDecorators::new(thread);
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let request = Request::with_decorators(id, content, decorators);
+
+        wrap_and_send_msg(
+            wallet,
+            &request.clone().into(),
+            &self.pairwise_info.pw_vk,
+            &self.state.did_doc,
+            transport,
+        )
+        .await?;
+
+        let did_doc = self.state.did_doc.clone();
+        Ok(self.transit(Requested { did_doc, request }))
+    }
+}
+
+impl RequesterDidExchange<Requested> {
+    pub fn request(&self) -> &Request {
+        &self.state.request
+    }
+
+    /// Processes the [`Response`] of the responder and transitions to [`RequesterDidExchange<Responded>`].
+    ///
+    /// The responder's DID document is either taken from the response attachment, which must be
+    /// signed with the key of the invitation, or resolved from the DID of the response.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the thread ID does not match or the DID document cannot be obtained. In the
+    /// latter case, a problem report is also sent to the responder.
+    pub async fn handle_response<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        registry: &ResolverRegistry,
+        response: Response,
+        transport: &T,
+    ) -> VcxResult<RequesterDidExchange<Responded>>
+    where
+        T: Transport,
+    {
+        trace!("DidExchange::handle_response >>> response: {:?}", response);
+
+        verify_thread_id(self.thread_id(), &response.clone().into())?;
+
+        let did_doc = match self.their_response_did_doc(wallet, registry, &response).await {
+            Ok(did_doc) => did_doc,
+            Err(err) => {
+                let problem_report = build_problem_report(
+                    &err,
+                    ProblemCode::ResponseNotAccepted,
+                    self.state.request.decorators.thread.clone(),
+                );
+                send_problem_report(
+                    wallet,
+                    problem_report,
+                    &self.pairwise_info.pw_vk,
+                    &self.state.did_doc,
+                    transport,
+                )
+                .await;
+                return Err(err);
+            }
+        };
+
+        let thread = &self.state.request.decorators.thread;
+        let thread_id = thread.thid.clone();
+        let parent_thread_id = thread.pthid.clone().unwrap_or_default();
+
+        Ok(self.transit(Responded {
+            did_doc,
+            thread_id,
+            parent_thread_id,
+        }))
+    }
+
+    async fn their_response_did_doc(
+        &self,
+        wallet: &Arc<dyn BaseWallet>,
+        registry: &ResolverRegistry,
+        response: &Response,
+    ) -> VcxResult<AriesDidDoc> {
+        let Some(attach) = &response.content.did_doc else {
+            return resolve_did_doc(registry, &response.content.did).await;
+        };
+
+        let (did_doc, signer) = did_doc_from_attach(wallet, attach).await?;
+        if !self.state.did_doc.recipient_keys()?.contains(&signer) {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("DID document attachment was signed with {signer}, which is not the invitation key"),
+            ));
+        }
+        Ok(did_doc)
+    }
+}
+
+impl RequesterDidExchange<Responded> {
+    /// Sends a [`Complete`] message to the responder and transitions to [`RequesterDidExchange<Completed>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if sending the message fails.
+    pub async fn send_complete<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        transport: &T,
+    ) -> VcxResult<RequesterDidExchange<Completed>>
+    where
+        T: Transport,
+    {
+        trace!("DidExchange::send_complete");
+
+        let mut thread = Thread::new(self.state.thread_id.clone());
+        thread.pthid = Some(self.state.parent_thread_id.clone());
+
+        let mut decorators = CompleteDecorators::new(thread);
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let complete = Complete::with_decorators(Uuid::new_v4().to_string(), CompleteContent::default(), decorators);
+        self.send_message(wallet, &complete.into(), transport).await?;
+
+        let Responded { did_doc, thread_id, .. } = self.state.clone();
+        Ok(self.transit(Completed { did_doc, thread_id }))
+    }
+}
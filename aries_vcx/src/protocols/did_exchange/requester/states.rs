@@ -0,0 +1,72 @@
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::msg_fields::protocols::did_exchange::request::Request;
+
+use crate::protocols::did_exchange::trait_bounds::{TheirDidDoc, ThreadId};
+
+/// The requester accepted an explicit (out-of-band) or implicit (public DID) invitation.
+#[derive(Clone, Debug)]
+pub struct Invited {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) parent_thread_id: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Requested {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) request: Request,
+}
+
+#[derive(Clone, Debug)]
+pub struct Responded {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) thread_id: String,
+    pub(crate) parent_thread_id: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Completed {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) thread_id: String,
+}
+
+impl TheirDidDoc for Invited {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl TheirDidDoc for Requested {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl TheirDidDoc for Responded {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl TheirDidDoc for Completed {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl ThreadId for Requested {
+    fn thread_id(&self) -> &str {
+        &self.request.decorators.thread.thid
+    }
+}
+
+impl ThreadId for Responded {
+    fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+}
+
+impl ThreadId for Completed {
+    fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+}
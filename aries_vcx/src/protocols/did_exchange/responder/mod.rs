@@ -0,0 +1,169 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use chrono::Utc;
+use did_resolver_registry::ResolverRegistry;
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::{
+    decorators::timing::Timing,
+    msg_fields::protocols::did_exchange::{
+        complete::Complete,
+        problem_report::ProblemCode,
+        request::Request,
+        response::{Response, ResponseContent, ResponseDecorators},
+    },
+};
+use url::Url;
+use uuid::Uuid;
+
+use self::states::{Completed, Requested, Responded};
+use super::{
+    build_problem_report,
+    did_doc::{attach_signed_did_doc, did_doc_from_attach, resolve_did_doc},
+    initiation_type::Responder,
+    send_problem_report, DidExchange,
+};
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::verify_thread_id,
+    protocols::connection::pairwise_info::PairwiseInfo,
+    transport::Transport,
+};
+
+/// Convenience alias
+pub type ResponderDidExchange<S> = DidExchange<Responder, S>;
+
+impl ResponderDidExchange<Requested> {
+    /// Processes a [`Request`] received in reply to an invitation and creates a
+    /// [`ResponderDidExchange<Requested>`].
+    ///
+    /// The requester's DID document is either taken from the request attachment, which must be
+    /// signed with one of the document's keys, or resolved from the DID of the request.
+    /// The `invitation_key` is the key of the invitation the request replies to (or of the public
+    /// DID acting as an implicit invitation) and is used to sign the DID document of the response.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the requester's DID document cannot be obtained.
+    pub async fn receive_request(
+        wallet: &Arc<dyn BaseWallet>,
+        registry: &ResolverRegistry,
+        pairwise_info: PairwiseInfo,
+        request: Request,
+        invitation_key: String,
+    ) -> VcxResult<Self> {
+        trace!("DidExchange::receive_request >>> request: {:?}", request);
+
+        let did_doc = match &request.content.did_doc {
+            Some(attach) => {
+                let (did_doc, signer) = did_doc_from_attach(wallet, attach).await?;
+                if !did_doc.recipient_keys()?.contains(&signer) {
+                    return Err(AriesVcxError::from_msg(
+                        AriesVcxErrorKind::InvalidJson,
+                        format!("DID document attachment was signed with {signer}, which is not one of its keys"),
+                    ));
+                }
+                did_doc
+            }
+            None => resolve_did_doc(registry, &request.content.did).await?,
+        };
+
+        Ok(Self::from_parts(
+            pairwise_info,
+            Responder,
+            Requested {
+                did_doc,
+                request,
+                invitation_key,
+            },
+        ))
+    }
+
+    pub fn request(&self) -> &Request {
+        &self.state.request
+    }
+
+    /// Sends a [`Response`] to the requester and transitions to [`ResponderDidExchange<Responded>`].
+    ///
+    /// The response attaches a DID document built from the pairwise info, the `service_endpoint`
+    /// and the `routing_keys`, signed with the invitation key.
+    ///
+    /// # Errors
+    ///
+    /// Will error out if signing the DID document or sending the response fails. If signing fails,
+    /// a problem report is sent to the requester.
+    pub async fn send_response<T>(
+        self,
+        wallet: &Arc<dyn BaseWallet>,
+        service_endpoint: Url,
+        routing_keys: Vec<String>,
+        transport: &T,
+    ) -> VcxResult<ResponderDidExchange<Responded>>
+    where
+        T: Transport,
+    {
+        trace!("DidExchange::send_response");
+
+        let mut did_doc = AriesDidDoc::default();
+        did_doc.id = self.pairwise_info.pw_did.clone();
+        did_doc.set_service_endpoint(service_endpoint);
+        did_doc.set_routing_keys(routing_keys);
+        did_doc.set_recipient_keys(vec![self.pairwise_info.pw_vk.clone()]);
+
+        let thread = self.state.request.decorators.thread.clone();
+
+        let attach = match attach_signed_did_doc(wallet, &did_doc, &self.state.invitation_key).await {
+            Ok(attach) => attach,
+            Err(err) => {
+                let problem_report = build_problem_report(&err, ProblemCode::RequestProcessingError, thread);
+                send_problem_report(
+                    wallet,
+                    problem_report,
+                    &self.pairwise_info.pw_vk,
+                    &self.state.did_doc,
+                    transport,
+                )
+                .await;
+                return Err(err);
+            }
+        };
+
+        let mut content = ResponseContent::new(self.pairwise_info.pw_did.clone());
+        content.did_doc = Some(attach);
+
+        let mut decorators = ResponseDecorators::new(thread);
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let response = Response::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+        self.send_message(wallet, &response.clone().into(), transport).await?;
+
+        let did_doc = self.state.did_doc.clone();
+        Ok(self.transit(Responded { did_doc, response }))
+    }
+}
+
+impl ResponderDidExchange<Responded> {
+    pub fn response(&self) -> &Response {
+        &self.state.response
+    }
+
+    /// Processes the [`Complete`] message of the requester and transitions to
+    /// [`ResponderDidExchange<Completed>`].
+    ///
+    /// # Errors
+    ///
+    /// Will error out if the thread ID does not match.
+    pub fn handle_complete(self, complete: Complete) -> VcxResult<ResponderDidExchange<Completed>> {
+        trace!("DidExchange::handle_complete >>> complete: {:?}", complete);
+
+        verify_thread_id(self.thread_id(), &complete.into())?;
+
+        let did_doc = self.state.did_doc.clone();
+        let thread_id = self.thread_id().to_owned();
+        Ok(self.transit(Completed { did_doc, thread_id }))
+    }
+}
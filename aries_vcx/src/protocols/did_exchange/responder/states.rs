@@ -0,0 +1,59 @@
+use diddoc_legacy::aries::diddoc::AriesDidDoc;
+use messages::msg_fields::protocols::did_exchange::{request::Request, response::Response};
+
+use crate::protocols::did_exchange::trait_bounds::{TheirDidDoc, ThreadId};
+
+#[derive(Clone, Debug)]
+pub struct Requested {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) request: Request,
+    pub(crate) invitation_key: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct Responded {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) response: Response,
+}
+
+#[derive(Clone, Debug)]
+pub struct Completed {
+    pub(crate) did_doc: AriesDidDoc,
+    pub(crate) thread_id: String,
+}
+
+impl TheirDidDoc for Requested {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl TheirDidDoc for Responded {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl TheirDidDoc for Completed {
+    fn their_did_doc(&self) -> &AriesDidDoc {
+        &self.did_doc
+    }
+}
+
+impl ThreadId for Requested {
+    fn thread_id(&self) -> &str {
+        &self.request.decorators.thread.thid
+    }
+}
+
+impl ThreadId for Responded {
+    fn thread_id(&self) -> &str {
+        &self.response.decorators.thread.thid
+    }
+}
+
+impl ThreadId for Completed {
+    fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+}
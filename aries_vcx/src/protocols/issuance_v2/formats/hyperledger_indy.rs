@@ -0,0 +1,170 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::cred_issuance::v2::{
+    issue_credential::IssueCredentialAttachmentFormatType, offer_credential::OfferCredentialAttachmentFormatType,
+    request_credential::RequestCredentialAttachmentFormatType, CredentialPreviewV2,
+};
+
+use super::{HolderCredentialFormat, IssuerCredentialFormat};
+use crate::{
+    common::credentials::encoding::encode_attributes,
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    protocols::issuance::holder::state_machine::{
+        _parse_rev_reg_id_from_credential, create_credential_request, parse_cred_def_id_from_cred_offer,
+    },
+};
+
+/// Issues Hyperledger Indy credentials (`hlindy/cred@v2.0`) of a credential definition.
+#[derive(Clone, Debug)]
+pub struct HyperledgerIndyIssuerFormat {
+    cred_def_id: String,
+    rev_reg_id: Option<String>,
+    tails_file: Option<String>,
+}
+
+impl HyperledgerIndyIssuerFormat {
+    pub fn new(cred_def_id: String, rev_reg_id: Option<String>, tails_file: Option<String>) -> Self {
+        Self {
+            cred_def_id,
+            rev_reg_id,
+            tails_file,
+        }
+    }
+}
+
+#[async_trait]
+impl IssuerCredentialFormat for HyperledgerIndyIssuerFormat {
+    fn offer_format(&self) -> OfferCredentialAttachmentFormatType {
+        OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0
+    }
+
+    fn request_format(&self) -> RequestCredentialAttachmentFormatType {
+        RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0
+    }
+
+    fn credential_format(&self) -> IssueCredentialAttachmentFormatType {
+        IssueCredentialAttachmentFormatType::HyperledgerIndyCredential2_0
+    }
+
+    async fn create_offer(&self, profile: &Arc<dyn Profile>) -> VcxResult<String> {
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        let offer = anoncreds.issuer_create_credential_offer(&self.cred_def_id).await?;
+        Ok(offer)
+    }
+
+    async fn create_credential(
+        &self,
+        profile: &Arc<dyn Profile>,
+        offer: &str,
+        request: &str,
+        preview: &CredentialPreviewV2,
+    ) -> VcxResult<(String, Option<String>)> {
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+
+        let cred_data: HashMap<&str, &str> = preview
+            .attributes
+            .iter()
+            .map(|attr| (attr.name.as_str(), attr.value.as_str()))
+            .collect();
+        let cred_data = serde_json::to_string(&cred_data).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Cannot serialize credential preview attributes: {}", err),
+            )
+        })?;
+        let cred_data = encode_attributes(&cred_data)?;
+
+        let (credential, cred_rev_id, _) = anoncreds
+            .issuer_create_credential(
+                offer,
+                request,
+                &cred_data,
+                self.rev_reg_id.clone(),
+                self.tails_file.clone(),
+            )
+            .await?;
+        Ok((credential, cred_rev_id))
+    }
+}
+
+/// Requests and stores Hyperledger Indy credentials (`hlindy/cred@v2.0`).
+#[derive(Clone, Debug, Default)]
+pub struct HyperledgerIndyHolderFormat;
+
+/// The metadata kept by the holder between requesting and storing a credential.
+#[derive(Serialize, Deserialize)]
+struct RequestMetadata {
+    req_meta: String,
+    cred_def_json: String,
+}
+
+#[async_trait]
+impl HolderCredentialFormat for HyperledgerIndyHolderFormat {
+    fn offer_format(&self) -> OfferCredentialAttachmentFormatType {
+        OfferCredentialAttachmentFormatType::HyperledgerIndyCredentialAbstract2_0
+    }
+
+    fn request_format(&self) -> RequestCredentialAttachmentFormatType {
+        RequestCredentialAttachmentFormatType::HyperledgerIndyCredentialRequest2_0
+    }
+
+    fn credential_format(&self) -> IssueCredentialAttachmentFormatType {
+        IssueCredentialAttachmentFormatType::HyperledgerIndyCredential2_0
+    }
+
+    async fn create_request(
+        &self,
+        profile: &Arc<dyn Profile>,
+        offer: &str,
+        my_pw_did: &str,
+    ) -> VcxResult<(String, String)> {
+        let cred_def_id = parse_cred_def_id_from_cred_offer(offer)?;
+        let (request, req_meta, _, cred_def_json) =
+            create_credential_request(profile, &cred_def_id, my_pw_did, offer).await?;
+
+        let metadata = serde_json::to_string(&RequestMetadata {
+            req_meta,
+            cred_def_json,
+        })
+        .map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Cannot serialize credential request metadata: {}", err),
+            )
+        })?;
+        Ok((request, metadata))
+    }
+
+    async fn store_credential(
+        &self,
+        profile: &Arc<dyn Profile>,
+        credential: &str,
+        request_metadata: &str,
+    ) -> VcxResult<String> {
+        let RequestMetadata {
+            req_meta,
+            cred_def_json,
+        } = serde_json::from_str(request_metadata).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Invalid credential request metadata: {}", err),
+            )
+        })?;
+
+        let rev_reg_def_json = match _parse_rev_reg_id_from_credential(credential)? {
+            Some(rev_reg_id) => {
+                let ledger = Arc::clone(profile).inject_anoncreds_ledger_read();
+                Some(ledger.get_rev_reg_def_json(&rev_reg_id).await?)
+            }
+            None => None,
+        };
+
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        let cred_id = anoncreds
+            .prover_store_credential(None, &req_meta, credential, &cred_def_json, rev_reg_def_json.as_deref())
+            .await?;
+        Ok(cred_id)
+    }
+}
@@ -0,0 +1,74 @@
+pub mod hyperledger_indy;
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::cred_issuance::v2::{
+    issue_credential::IssueCredentialAttachmentFormatType, offer_credential::OfferCredentialAttachmentFormatType,
+    request_credential::RequestCredentialAttachmentFormatType, CredentialPreviewV2,
+};
+
+use crate::{core::profile::profile::Profile, errors::error::VcxResult};
+
+/// The issuer side of a credential format (e.g. Hyperledger Indy, AnonCreds, JSON-LD) that can be
+/// issued through the issue credential 2.0 protocol.
+///
+/// The [`super::issuer::IssuerV2`] state machine offers a credential in every format it's given
+/// and then issues it in the format of the request it receives.
+#[async_trait]
+pub trait IssuerCredentialFormat: Debug + Send + Sync {
+    /// The format of the offer attachments created by [`IssuerCredentialFormat::create_offer`].
+    fn offer_format(&self) -> OfferCredentialAttachmentFormatType;
+
+    /// The format of the request attachments this format can issue a credential for.
+    fn request_format(&self) -> RequestCredentialAttachmentFormatType;
+
+    /// The format of the credential attachments created by [`IssuerCredentialFormat::create_credential`].
+    fn credential_format(&self) -> IssueCredentialAttachmentFormatType;
+
+    /// Creates the content of the offer attachment.
+    async fn create_offer(&self, profile: &Arc<dyn Profile>) -> VcxResult<String>;
+
+    /// Creates the content of the credential attachment, returning it along with
+    /// the revocation id of the credential, if it's revocable.
+    async fn create_credential(
+        &self,
+        profile: &Arc<dyn Profile>,
+        offer: &str,
+        request: &str,
+        preview: &CredentialPreviewV2,
+    ) -> VcxResult<(String, Option<String>)>;
+}
+
+/// The holder side of a credential format that can be issued through the issue credential 2.0 protocol.
+///
+/// The [`super::holder::HolderV2`] state machine requests the credential in the first offered format
+/// it has a [`HolderCredentialFormat`] for.
+#[async_trait]
+pub trait HolderCredentialFormat: Debug + Send + Sync {
+    /// The format of the offer attachments this format can request a credential for.
+    fn offer_format(&self) -> OfferCredentialAttachmentFormatType;
+
+    /// The format of the request attachments created by [`HolderCredentialFormat::create_request`].
+    fn request_format(&self) -> RequestCredentialAttachmentFormatType;
+
+    /// The format of the credential attachments this format can store.
+    fn credential_format(&self) -> IssueCredentialAttachmentFormatType;
+
+    /// Creates the content of the request attachment, returning it along with the
+    /// format specific metadata needed by [`HolderCredentialFormat::store_credential`].
+    async fn create_request(
+        &self,
+        profile: &Arc<dyn Profile>,
+        offer: &str,
+        my_pw_did: &str,
+    ) -> VcxResult<(String, String)>;
+
+    /// Stores the content of the credential attachment, returning the id of the stored credential.
+    async fn store_credential(
+        &self,
+        profile: &Arc<dyn Profile>,
+        credential: &str,
+        request_metadata: &str,
+    ) -> VcxResult<String>;
+}
@@ -0,0 +1,255 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        common::attachment_format_specifier::AttachmentFormatSpecifier,
+        cred_issuance::v2::{
+            ack::{AckCredentialV2, AckCredentialV2Content},
+            issue_credential::IssueCredentialV2,
+            offer_credential::OfferCredentialV2,
+            problem_report::CredIssuanceV2ProblemReport,
+            request_credential::{
+                RequestCredentialAttachmentFormatType, RequestCredentialV2, RequestCredentialV2Content,
+                RequestCredentialV2Decorators,
+            },
+        },
+        notification::ack::{AckDecorators, AckStatus},
+    },
+};
+use shared_vcx::maybe_known::MaybeKnown;
+use uuid::Uuid;
+
+use self::states::{CredentialReceived, Failed, OfferReceived, RequestSent};
+use super::{build_problem_report, formats::HolderCredentialFormat};
+use crate::{
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::{make_attach_from_str, verify_thread_id},
+    protocols::common::get_attachment_content,
+};
+
+/// A state machine for the holder role of the issue credential 2.0 protocol.
+///
+/// The credential is requested in the first offered format the holder has
+/// a [`HolderCredentialFormat`] for, as offers list their formats in order of preference.
+///
+/// The state machine only holds serializable state, so it can be stored between messages.
+/// The [`HolderCredentialFormat`]s are passed to the transitions that need them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HolderV2<S> {
+    thread_id: String,
+    state: S,
+}
+
+impl<S> HolderV2<S> {
+    pub fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Processes a [`CredIssuanceV2ProblemReport`] and transitions to [`HolderV2<Failed>`].
+    pub fn receive_problem_report(self, problem_report: CredIssuanceV2ProblemReport) -> VcxResult<HolderV2<Failed>> {
+        verify_thread_id(&self.thread_id, &problem_report.clone().into())?;
+        Ok(self.transit(Failed { problem_report }))
+    }
+
+    /// Abandons the protocol and transitions to [`HolderV2<Failed>`]. The problem report that
+    /// should be sent to the issuer can be retrieved through [`HolderV2<Failed>::problem_report`].
+    pub fn abandon(self, comment: Option<String>) -> HolderV2<Failed> {
+        let problem_report = build_problem_report(comment, &self.thread_id);
+        self.transit(Failed { problem_report })
+    }
+
+    fn transit<T>(self, state: T) -> HolderV2<T> {
+        HolderV2 {
+            thread_id: self.thread_id,
+            state,
+        }
+    }
+}
+
+impl HolderV2<OfferReceived> {
+    /// Creates a holder from an [`OfferCredentialV2`] received from the issuer.
+    pub fn from_offer(offer: OfferCredentialV2) -> Self {
+        let thread_id = offer
+            .decorators
+            .thread
+            .as_ref()
+            .map(|thread| thread.thid.clone())
+            .unwrap_or_else(|| offer.id.clone());
+
+        Self {
+            thread_id,
+            state: OfferReceived { offer },
+        }
+    }
+
+    pub fn offer(&self) -> &OfferCredentialV2 {
+        &self.state.offer
+    }
+
+    /// Returns whether any of the offered credential formats is supported by the given `formats`.
+    pub fn is_offer_supported(&self, formats: &[Arc<dyn HolderCredentialFormat>]) -> bool {
+        self.select_format(formats).is_some()
+    }
+
+    /// Creates a [`RequestCredentialV2`] in the preferred supported format and transitions
+    /// to [`HolderV2<RequestSent>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if none of the offered formats is supported or creating the request fails.
+    pub async fn create_request(
+        self,
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn HolderCredentialFormat>],
+        my_pw_did: &str,
+    ) -> VcxResult<HolderV2<RequestSent>> {
+        let format = self.select_format(formats).ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                format!(
+                    "None of the credential offer formats {:?} is supported",
+                    self.state.offer.content.formats
+                ),
+            )
+        })?;
+
+        let offer = &self.state.offer;
+        let offer_attach = get_attachment_content(
+            &offer.content.formats,
+            &offer.content.offers_attach,
+            format.offer_format(),
+        )?;
+        let (request, request_metadata) = format.create_request(profile, &offer_attach, my_pw_did).await?;
+
+        let attach_id = Uuid::new_v4().to_string();
+        let content = RequestCredentialV2Content::new(
+            vec![AttachmentFormatSpecifier::new(
+                attach_id.clone(),
+                MaybeKnown::Known(format.request_format()),
+            )],
+            vec![make_attach_from_str!(&request, attach_id)],
+        );
+
+        let mut decorators = RequestCredentialV2Decorators::default();
+        decorators.thread = Some(Thread::new(self.thread_id.clone()));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let request = RequestCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        let request_format = format.request_format();
+        Ok(self.transit(RequestSent {
+            request,
+            request_metadata,
+            request_format,
+        }))
+    }
+
+    fn select_format<'a>(
+        &self,
+        formats: &'a [Arc<dyn HolderCredentialFormat>],
+    ) -> Option<&'a Arc<dyn HolderCredentialFormat>> {
+        self.state.offer.content.formats.iter().find_map(|spec| {
+            formats
+                .iter()
+                .find(|format| spec.format == MaybeKnown::Known(format.offer_format()))
+        })
+    }
+}
+
+impl HolderV2<RequestSent> {
+    pub fn request(&self) -> &RequestCredentialV2 {
+        &self.state.request
+    }
+
+    /// Processes an [`IssueCredentialV2`], stores the credential and transitions to [`HolderV2<CredentialReceived>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if either:
+    ///     * the credential's thread ID does not match the one of the request
+    ///     * none of the `formats` is the one the credential was requested in
+    ///     * the credential is not in the requested format
+    ///     * storing the credential fails
+    pub async fn receive_credential(
+        self,
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn HolderCredentialFormat>],
+        credential: IssueCredentialV2,
+    ) -> VcxResult<HolderV2<CredentialReceived>> {
+        verify_thread_id(&self.thread_id, &credential.clone().into())?;
+
+        let RequestSent {
+            request_metadata,
+            request_format,
+            ..
+        } = &self.state;
+        let format = find_format(formats, *request_format)?;
+
+        let credential_attach = get_attachment_content(
+            &credential.content.formats,
+            &credential.content.credentials_attach,
+            format.credential_format(),
+        )?;
+        let cred_id = format
+            .store_credential(profile, &credential_attach, request_metadata)
+            .await?;
+
+        Ok(self.transit(CredentialReceived { credential, cred_id }))
+    }
+}
+
+impl HolderV2<CredentialReceived> {
+    pub fn credential(&self) -> &IssueCredentialV2 {
+        &self.state.credential
+    }
+
+    pub fn cred_id(&self) -> &str {
+        &self.state.cred_id
+    }
+
+    /// Creates the [`AckCredentialV2`] acknowledging the credential.
+    pub fn create_ack(&self) -> AckCredentialV2 {
+        let content = AckCredentialV2Content::new(AckStatus::Ok);
+        let mut decorators = AckDecorators::new(Thread::new(self.thread_id.clone()));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        AckCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+    }
+}
+
+impl HolderV2<Failed> {
+    pub fn problem_report(&self) -> &CredIssuanceV2ProblemReport {
+        &self.state.problem_report
+    }
+}
+
+fn find_format(
+    formats: &[Arc<dyn HolderCredentialFormat>],
+    request_format: RequestCredentialAttachmentFormatType,
+) -> VcxResult<&Arc<dyn HolderCredentialFormat>> {
+    formats
+        .iter()
+        .find(|format| format.request_format() == request_format)
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                format!(
+                    "No credential format was given for the requested format {:?}",
+                    request_format
+                ),
+            )
+        })
+}
@@ -0,0 +1,29 @@
+use messages::msg_fields::protocols::cred_issuance::v2::{
+    issue_credential::IssueCredentialV2,
+    offer_credential::OfferCredentialV2,
+    problem_report::CredIssuanceV2ProblemReport,
+    request_credential::{RequestCredentialAttachmentFormatType, RequestCredentialV2},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OfferReceived {
+    pub(crate) offer: OfferCredentialV2,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestSent {
+    pub(crate) request: RequestCredentialV2,
+    pub(crate) request_metadata: String,
+    pub(crate) request_format: RequestCredentialAttachmentFormatType,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CredentialReceived {
+    pub(crate) credential: IssueCredentialV2,
+    pub(crate) cred_id: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Failed {
+    pub(crate) problem_report: CredIssuanceV2ProblemReport,
+}
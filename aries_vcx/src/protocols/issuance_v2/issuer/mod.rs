@@ -0,0 +1,340 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        common::attachment_format_specifier::AttachmentFormatSpecifier,
+        cred_issuance::v2::{
+            ack::AckCredentialV2,
+            issue_credential::{IssueCredentialV2, IssueCredentialV2Content, IssueCredentialV2Decorators},
+            offer_credential::{OfferCredentialV2, OfferCredentialV2Content, OfferCredentialV2Decorators},
+            problem_report::CredIssuanceV2ProblemReport,
+            propose_credential::ProposeCredentialV2,
+            request_credential::{RequestCredentialAttachmentFormatType, RequestCredentialV2},
+            CredentialPreviewV2,
+        },
+    },
+};
+use shared_vcx::maybe_known::MaybeKnown;
+use uuid::Uuid;
+
+use self::states::{Completed, CredentialSent, Failed, OfferSent, ProposalReceived, RequestReceived};
+use super::{build_problem_report, formats::IssuerCredentialFormat};
+use crate::{
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::{make_attach_from_str, verify_thread_id},
+    protocols::common::get_attachment_content,
+};
+
+/// A state machine for the issuer role of the issue credential 2.0 protocol.
+///
+/// The credential is offered in every one of the [`IssuerCredentialFormat`]s the issuer
+/// is created with, and then issued in the one the holder requested.
+///
+/// The state machine only holds serializable state, so it can be stored between messages.
+/// The [`IssuerCredentialFormat`]s are passed to the transitions that need them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct IssuerV2<S> {
+    thread_id: String,
+    state: S,
+}
+
+impl<S> IssuerV2<S> {
+    pub fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Processes a [`CredIssuanceV2ProblemReport`] and transitions to [`IssuerV2<Failed>`].
+    pub fn receive_problem_report(self, problem_report: CredIssuanceV2ProblemReport) -> VcxResult<IssuerV2<Failed>> {
+        verify_thread_id(&self.thread_id, &problem_report.clone().into())?;
+        Ok(self.transit(Failed { problem_report }))
+    }
+
+    /// Abandons the protocol and transitions to [`IssuerV2<Failed>`]. The problem report that
+    /// should be sent to the holder can be retrieved through [`IssuerV2<Failed>::problem_report`].
+    pub fn abandon(self, comment: Option<String>) -> IssuerV2<Failed> {
+        let problem_report = build_problem_report(comment, &self.thread_id);
+        self.transit(Failed { problem_report })
+    }
+
+    fn transit<T>(self, state: T) -> IssuerV2<T> {
+        IssuerV2 {
+            thread_id: self.thread_id,
+            state,
+        }
+    }
+}
+
+impl IssuerV2<ProposalReceived> {
+    /// Creates an issuer from a [`ProposeCredentialV2`] received from the holder.
+    pub fn from_proposal(proposal: ProposeCredentialV2) -> Self {
+        let thread_id = proposal
+            .decorators
+            .thread
+            .as_ref()
+            .map(|thread| thread.thid.clone())
+            .unwrap_or_else(|| proposal.id.clone());
+
+        Self {
+            thread_id,
+            state: ProposalReceived { proposal },
+        }
+    }
+
+    pub fn proposal(&self) -> &ProposeCredentialV2 {
+        &self.state.proposal
+    }
+
+    /// Creates an [`OfferCredentialV2`] replying to the proposal, in every one of the given `formats`,
+    /// and transitions to [`IssuerV2<OfferSent>`].
+    pub async fn create_offer(
+        self,
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn IssuerCredentialFormat>],
+        preview: CredentialPreviewV2,
+        comment: Option<String>,
+    ) -> VcxResult<IssuerV2<OfferSent>> {
+        let mut offer = build_offer(profile, formats, &self.thread_id, preview, comment).await?;
+        offer.id = Uuid::new_v4().to_string();
+        offer.decorators.thread = Some(Thread::new(self.thread_id.clone()));
+
+        Ok(self.transit(OfferSent { offer }))
+    }
+}
+
+impl IssuerV2<OfferSent> {
+    /// Creates an [`OfferCredentialV2`] that starts the protocol, in every one of the given `formats`,
+    /// and transitions to [`IssuerV2<OfferSent>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if no formats are given or if creating any of the offer attachments fails.
+    pub async fn create_offer(
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn IssuerCredentialFormat>],
+        preview: CredentialPreviewV2,
+        comment: Option<String>,
+    ) -> VcxResult<Self> {
+        let thread_id = Uuid::new_v4().to_string();
+        let offer = build_offer(profile, formats, &thread_id, preview, comment).await?;
+
+        Ok(Self {
+            thread_id,
+            state: OfferSent { offer },
+        })
+    }
+
+    pub fn offer(&self) -> &OfferCredentialV2 {
+        &self.state.offer
+    }
+
+    /// Processes a [`RequestCredentialV2`] and transitions to [`IssuerV2<RequestReceived>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if either:
+    ///     * the request's thread ID does not match the one of the offer
+    ///     * the request is not in any of the offered formats, as supported by the given `formats`
+    pub fn receive_request(
+        self,
+        formats: &[Arc<dyn IssuerCredentialFormat>],
+        request: RequestCredentialV2,
+    ) -> VcxResult<IssuerV2<RequestReceived>> {
+        verify_thread_id(&self.thread_id, &request.clone().into())?;
+
+        let offered_formats = &self.state.offer.content.formats;
+        let request_format = request
+            .content
+            .formats
+            .iter()
+            .find_map(|spec| {
+                formats.iter().find(|format| {
+                    spec.format == MaybeKnown::Known(format.request_format())
+                        && offered_formats
+                            .iter()
+                            .any(|offered| offered.format == MaybeKnown::Known(format.offer_format()))
+                })
+            })
+            .map(|format| format.request_format())
+            .ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::ActionNotSupported,
+                    format!(
+                        "Credential request formats {:?} do not match any of the offered ones",
+                        request.content.formats
+                    ),
+                )
+            })?;
+
+        let offer = self.state.offer.clone();
+        Ok(self.transit(RequestReceived {
+            offer,
+            request,
+            request_format,
+        }))
+    }
+}
+
+impl IssuerV2<RequestReceived> {
+    pub fn request(&self) -> &RequestCredentialV2 {
+        &self.state.request
+    }
+
+    /// Creates the [`IssueCredentialV2`] in the requested format and transitions to [`IssuerV2<CredentialSent>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if none of the `formats` is the requested one or if creating the credential fails.
+    pub async fn create_credential(
+        self,
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn IssuerCredentialFormat>],
+    ) -> VcxResult<IssuerV2<CredentialSent>> {
+        let RequestReceived {
+            offer,
+            request,
+            request_format,
+        } = &self.state;
+        let format = find_format(formats, *request_format)?;
+
+        let offer_attach = get_attachment_content(
+            &offer.content.formats,
+            &offer.content.offers_attach,
+            format.offer_format(),
+        )?;
+        let request_attach = get_attachment_content(
+            &request.content.formats,
+            &request.content.requests_attach,
+            format.request_format(),
+        )?;
+
+        let (credential, cred_rev_id) = format
+            .create_credential(
+                profile,
+                &offer_attach,
+                &request_attach,
+                &offer.content.credential_preview,
+            )
+            .await?;
+
+        let attach_id = Uuid::new_v4().to_string();
+        let content = IssueCredentialV2Content::new(
+            vec![AttachmentFormatSpecifier::new(
+                attach_id.clone(),
+                MaybeKnown::Known(format.credential_format()),
+            )],
+            vec![make_attach_from_str!(&credential, attach_id)],
+        );
+
+        let mut decorators = IssueCredentialV2Decorators::new(Thread::new(self.thread_id.clone()));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let credential = IssueCredentialV2::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        Ok(self.transit(CredentialSent {
+            credential,
+            cred_rev_id,
+        }))
+    }
+}
+
+impl IssuerV2<CredentialSent> {
+    pub fn credential(&self) -> &IssueCredentialV2 {
+        &self.state.credential
+    }
+
+    pub fn cred_rev_id(&self) -> Option<&str> {
+        self.state.cred_rev_id.as_deref()
+    }
+
+    /// Processes the holder's [`AckCredentialV2`] and transitions to [`IssuerV2<Completed>`].
+    pub fn receive_ack(self, ack: AckCredentialV2) -> VcxResult<IssuerV2<Completed>> {
+        verify_thread_id(&self.thread_id, &ack.into())?;
+
+        let cred_rev_id = self.state.cred_rev_id.clone();
+        Ok(self.transit(Completed { cred_rev_id }))
+    }
+}
+
+impl IssuerV2<Completed> {
+    pub fn cred_rev_id(&self) -> Option<&str> {
+        self.state.cred_rev_id.as_deref()
+    }
+}
+
+impl IssuerV2<Failed> {
+    pub fn problem_report(&self) -> &CredIssuanceV2ProblemReport {
+        &self.state.problem_report
+    }
+}
+
+fn find_format(
+    formats: &[Arc<dyn IssuerCredentialFormat>],
+    request_format: RequestCredentialAttachmentFormatType,
+) -> VcxResult<&Arc<dyn IssuerCredentialFormat>> {
+    formats
+        .iter()
+        .find(|format| format.request_format() == request_format)
+        .ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                format!(
+                    "No credential format was given for the requested format {:?}",
+                    request_format
+                ),
+            )
+        })
+}
+
+async fn build_offer(
+    profile: &Arc<dyn Profile>,
+    formats: &[Arc<dyn IssuerCredentialFormat>],
+    thread_id: &str,
+    preview: CredentialPreviewV2,
+    comment: Option<String>,
+) -> VcxResult<OfferCredentialV2> {
+    if formats.is_empty() {
+        return Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidInput,
+            "Cannot create a credential offer without any credential format",
+        ));
+    }
+
+    let mut format_specifiers = Vec::with_capacity(formats.len());
+    let mut offers_attach = Vec::with_capacity(formats.len());
+
+    for format in formats {
+        let offer = format.create_offer(profile).await?;
+        let attach_id = Uuid::new_v4().to_string();
+
+        format_specifiers.push(AttachmentFormatSpecifier::new(
+            attach_id.clone(),
+            MaybeKnown::Known(format.offer_format()),
+        ));
+        offers_attach.push(make_attach_from_str!(&offer, attach_id));
+    }
+
+    let mut content = OfferCredentialV2Content::new(preview, format_specifiers, offers_attach);
+    content.comment = comment;
+
+    let mut decorators = OfferCredentialV2Decorators::default();
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    Ok(OfferCredentialV2::with_decorators(
+        thread_id.to_owned(),
+        content,
+        decorators,
+    ))
+}
@@ -0,0 +1,40 @@
+use messages::msg_fields::protocols::cred_issuance::v2::{
+    issue_credential::IssueCredentialV2,
+    offer_credential::OfferCredentialV2,
+    problem_report::CredIssuanceV2ProblemReport,
+    propose_credential::ProposeCredentialV2,
+    request_credential::{RequestCredentialAttachmentFormatType, RequestCredentialV2},
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProposalReceived {
+    pub(crate) proposal: ProposeCredentialV2,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OfferSent {
+    pub(crate) offer: OfferCredentialV2,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestReceived {
+    pub(crate) offer: OfferCredentialV2,
+    pub(crate) request: RequestCredentialV2,
+    pub(crate) request_format: RequestCredentialAttachmentFormatType,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CredentialSent {
+    pub(crate) credential: IssueCredentialV2,
+    pub(crate) cred_rev_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Completed {
+    pub(crate) cred_rev_id: Option<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Failed {
+    pub(crate) problem_report: CredIssuanceV2ProblemReport,
+}
@@ -0,0 +1,291 @@
+//! State machines for the [issue credential 2.0 protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0453-issue-credential-v2/README.md).
+//!
+//! Unlike the 1.0 protocol, messages can carry attachments in multiple credential formats,
+//! identified through their `formats` array. The transitions handling attachments are given a list of
+//! [`formats::IssuerCredentialFormat`] or [`formats::HolderCredentialFormat`] implementations
+//! and dispatch every attachment to the implementation handling its format.
+
+pub mod formats;
+pub mod holder;
+pub mod issuer;
+
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        cred_issuance::v2::problem_report::{CredIssuanceV2ProblemReport, CredIssuanceV2ProblemReportContent},
+        report_problem::ProblemReportDecorators,
+    },
+};
+use uuid::Uuid;
+
+fn build_problem_report(comment: Option<String>, thread_id: &str) -> CredIssuanceV2ProblemReport {
+    let content = CredIssuanceV2ProblemReportContent::new(comment.unwrap_or_default());
+
+    let mut decorators = ProblemReportDecorators::default();
+    decorators.thread = Some(Thread::new(thread_id.to_owned()));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    CredIssuanceV2ProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Arc;
+
+    use messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::cred_issuance::{
+            v2::{
+                propose_credential::{ProposeCredentialV2, ProposeCredentialV2Content, ProposeCredentialV2Decorators},
+                CredentialPreviewV2,
+            },
+            CredentialAttr,
+        },
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use super::{
+        formats::{
+            hyperledger_indy::{HyperledgerIndyHolderFormat, HyperledgerIndyIssuerFormat},
+            HolderCredentialFormat, IssuerCredentialFormat,
+        },
+        holder::{states::OfferReceived, HolderV2},
+        issuer::{states::OfferSent, IssuerV2},
+    };
+    use crate::{
+        core::profile::profile::Profile, errors::error::AriesVcxErrorKind,
+        utils::mockdata::profile::mock_profile::MockProfile,
+    };
+
+    fn issuer_formats() -> Vec<Arc<dyn IssuerCredentialFormat>> {
+        vec![Arc::new(HyperledgerIndyIssuerFormat::new(
+            "cred_def_id".to_owned(),
+            None,
+            None,
+        ))]
+    }
+
+    fn holder_formats() -> Vec<Arc<dyn HolderCredentialFormat>> {
+        vec![Arc::new(HyperledgerIndyHolderFormat)]
+    }
+
+    fn preview() -> CredentialPreviewV2 {
+        CredentialPreviewV2::new(vec![CredentialAttr::new("name".to_owned(), "alice".to_owned())])
+    }
+
+    // Stores and restores a state machine, as done between receiving messages.
+    fn reload<T: Serialize + DeserializeOwned>(sm: T) -> T {
+        serde_json::from_str(&serde_json::to_string(&sm).unwrap()).unwrap()
+    }
+
+    async fn offer_sent(profile: &Arc<dyn Profile>) -> (IssuerV2<OfferSent>, HolderV2<OfferReceived>) {
+        let issuer = IssuerV2::create_offer(profile, &issuer_formats(), preview(), None)
+            .await
+            .unwrap();
+        let holder = HolderV2::from_offer(issuer.offer().clone());
+        (issuer, holder)
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_flow() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (issuer, holder) = offer_sent(&profile).await;
+        let (issuer, holder) = (reload(issuer), reload(holder));
+        assert_eq!(holder.thread_id(), issuer.thread_id());
+        assert!(holder.is_offer_supported(&holder_formats()));
+
+        let holder = holder
+            .create_request(&profile, &holder_formats(), "pw_did")
+            .await
+            .unwrap();
+        let issuer = issuer
+            .receive_request(&issuer_formats(), holder.request().clone())
+            .unwrap();
+        let (issuer, holder) = (reload(issuer), reload(holder));
+
+        let issuer = issuer.create_credential(&profile, &issuer_formats()).await.unwrap();
+        let issuer = reload(issuer);
+
+        let holder = holder
+            .receive_credential(&profile, &holder_formats(), issuer.credential().clone())
+            .await
+            .unwrap();
+        let holder = reload(holder);
+        assert_eq!(holder.cred_id(), "cred_id");
+
+        let issuer = issuer.receive_ack(holder.create_ack()).unwrap();
+        let issuer = reload(issuer);
+        assert!(issuer.cred_rev_id().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_offer_replies_to_proposal() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let proposal = ProposeCredentialV2::with_decorators(
+            "proposal_id".to_owned(),
+            ProposeCredentialV2Content::new(vec![], vec![]),
+            ProposeCredentialV2Decorators::default(),
+        );
+        let issuer = reload(IssuerV2::from_proposal(proposal));
+        assert_eq!(issuer.thread_id(), "proposal_id");
+
+        let issuer = issuer
+            .create_offer(&profile, &issuer_formats(), preview(), None)
+            .await
+            .unwrap();
+        assert_ne!(issuer.offer().id, "proposal_id");
+
+        let holder = HolderV2::from_offer(issuer.offer().clone());
+        assert_eq!(holder.thread_id(), "proposal_id");
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_unsupported_offer_format() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (_, holder) = offer_sent(&profile).await;
+        assert!(!holder.is_offer_supported(&[]));
+
+        let err = holder.create_request(&profile, &[], "pw_did").await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::ActionNotSupported);
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_offer_without_formats() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let err = IssuerV2::create_offer(&profile, &[], preview(), None)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_request_with_other_thread_id() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (issuer, holder) = offer_sent(&profile).await;
+        let holder = holder
+            .create_request(&profile, &holder_formats(), "pw_did")
+            .await
+            .unwrap();
+
+        let mut request = holder.request().clone();
+        request.decorators.thread = Some(Thread::new("other_thread_id".to_owned()));
+        let err = issuer.receive_request(&issuer_formats(), request).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_request_in_unsupported_format() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (issuer, holder) = offer_sent(&profile).await;
+        let holder = holder
+            .create_request(&profile, &holder_formats(), "pw_did")
+            .await
+            .unwrap();
+
+        let err = issuer.receive_request(&[], holder.request().clone()).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::ActionNotSupported);
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_credential_without_requested_format() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (issuer, holder) = offer_sent(&profile).await;
+        let holder = holder
+            .create_request(&profile, &holder_formats(), "pw_did")
+            .await
+            .unwrap();
+        let issuer = issuer
+            .receive_request(&issuer_formats(), holder.request().clone())
+            .unwrap();
+
+        let err = issuer.create_credential(&profile, &[]).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::ActionNotSupported);
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_credential_and_ack_with_other_thread_id() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (issuer, holder) = offer_sent(&profile).await;
+        let holder = holder
+            .create_request(&profile, &holder_formats(), "pw_did")
+            .await
+            .unwrap();
+        let issuer = issuer
+            .receive_request(&issuer_formats(), holder.request().clone())
+            .unwrap();
+        let issuer = issuer.create_credential(&profile, &issuer_formats()).await.unwrap();
+
+        let mut credential = issuer.credential().clone();
+        credential.decorators.thread = Thread::new("other_thread_id".to_owned());
+        let err = holder
+            .clone()
+            .receive_credential(&profile, &holder_formats(), credential)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+
+        let holder = holder
+            .receive_credential(&profile, &holder_formats(), issuer.credential().clone())
+            .await
+            .unwrap();
+        let mut ack = holder.create_ack();
+        ack.decorators.thread = Thread::new("other_thread_id".to_owned());
+        let err = issuer.receive_ack(ack).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_holder_problem_report() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (issuer, holder) = offer_sent(&profile).await;
+        let holder = reload(holder.abandon(Some("rejected".to_owned())));
+
+        let issuer = issuer.receive_problem_report(holder.problem_report().clone()).unwrap();
+        let issuer = reload(issuer);
+        assert_eq!(issuer.problem_report().content.0.description.code, "rejected");
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_issuer_problem_report() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (issuer, holder) = offer_sent(&profile).await;
+        let holder = holder
+            .create_request(&profile, &holder_formats(), "pw_did")
+            .await
+            .unwrap();
+        let issuer = issuer
+            .receive_request(&issuer_formats(), holder.request().clone())
+            .unwrap();
+        let issuer = reload(issuer.abandon(Some("revoked".to_owned())));
+
+        let holder = holder.receive_problem_report(issuer.problem_report().clone()).unwrap();
+        let holder = reload(holder);
+        assert_eq!(holder.problem_report().content.0.description.code, "revoked");
+    }
+
+    #[tokio::test]
+    async fn test_issuance_v2_problem_report_with_other_thread_id() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (issuer, holder) = offer_sent(&profile).await;
+        let mut problem_report = holder.abandon(None).problem_report().clone();
+        problem_report.decorators.thread = Some(Thread::new("other_thread_id".to_owned()));
+
+        let err = issuer.receive_problem_report(problem_report).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+}
@@ -25,6 +25,7 @@ use messages::msg_fields::protocols::connection::request::{Request, RequestConte
 use messages::msg_fields::protocols::connection::response::Response;
 use messages::msg_fields::protocols::connection::{Connection, ConnectionData};
 use messages::msg_fields::protocols::discover_features::disclose::Disclose;
+use messages::msg_fields::protocols::discover_features::disclosures::Disclosures;
 use messages::msg_fields::protocols::discover_features::query::QueryContent;
 use messages::msg_fields::protocols::discover_features::ProtocolDescriptor;
 use messages::msg_fields::protocols::notification::ack::{Ack, AckContent, AckDecorators, AckStatus};
@@ -420,6 +421,16 @@ impl SmConnectionInvitee {
         Ok(Self { state, ..self })
     }
 
+    pub fn handle_disclosures(self, disclosures: Disclosures) -> VcxResult<Self> {
+        let state = match self.state {
+            InviteeFullState::Completed(state) => {
+                InviteeFullState::Completed((state, disclosures.content.protocols()).into())
+            }
+            _ => self.state,
+        };
+        Ok(Self { state, ..self })
+    }
+
     pub async fn handle_send_ack(self, send_message: SendClosureConnection) -> VcxResult<Self> {
         let state = match self.state {
             InviteeFullState::Responded(ref state) => {
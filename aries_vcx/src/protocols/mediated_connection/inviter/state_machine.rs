@@ -17,6 +17,7 @@ use messages::msg_fields::protocols::connection::request::Request;
 use messages::msg_fields::protocols::connection::response::{Response, ResponseContent, ResponseDecorators};
 use messages::msg_fields::protocols::connection::{Connection, ConnectionData};
 use messages::msg_fields::protocols::discover_features::disclose::Disclose;
+use messages::msg_fields::protocols::discover_features::disclosures::Disclosures;
 use messages::msg_fields::protocols::discover_features::query::QueryContent;
 use messages::msg_fields::protocols::discover_features::ProtocolDescriptor;
 use messages::msg_fields::protocols::trust_ping::TrustPing;
@@ -333,6 +334,16 @@ impl SmConnectionInviter {
         Ok(Self { state, ..self })
     }
 
+    pub fn handle_disclosures(self, disclosures: Disclosures) -> VcxResult<Self> {
+        let state = match self.state {
+            InviterFullState::Completed(state) => {
+                InviterFullState::Completed((state, disclosures.content.protocols()).into())
+            }
+            _ => self.state,
+        };
+        Ok(Self { state, ..self })
+    }
+
     pub async fn handle_confirmation_message(self, msg: &AriesMessage) -> VcxResult<Self> {
         verify_thread_id(&self.get_thread_id(), msg)?;
         match self.state {
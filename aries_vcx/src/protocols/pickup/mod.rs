@@ -0,0 +1,99 @@
+use ::uuid::Uuid;
+use chrono::Utc;
+use messages::{
+    decorators::{
+        timing::Timing,
+        transport::{ReturnRoute, Transport},
+    },
+    msg_fields::protocols::pickup::{
+        delivery_request::{DeliveryRequest, DeliveryRequestContent, DeliveryRequestDecorators},
+        live_delivery_change::{LiveDeliveryChange, LiveDeliveryChangeContent, LiveDeliveryChangeDecorators},
+        messages_received::{MessagesReceived, MessagesReceivedContent, MessagesReceivedDecorators},
+        status_request::{StatusRequest, StatusRequestContent, StatusRequestDecorators},
+    },
+};
+
+// The mediator answers pickup requests on the inbound transport, so every request
+// asks for the return route to be kept open.
+fn make_transport() -> Transport {
+    Transport::new(ReturnRoute::All)
+}
+
+fn make_timing() -> Timing {
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    timing
+}
+
+pub fn build_status_request(recipient_key: Option<String>) -> StatusRequest {
+    let mut content = StatusRequestContent::default();
+    content.recipient_key = recipient_key;
+
+    let mut decorators = StatusRequestDecorators::default();
+    decorators.transport = Some(make_transport());
+    decorators.timing = Some(make_timing());
+
+    StatusRequest::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_delivery_request(limit: u32, recipient_key: Option<String>) -> DeliveryRequest {
+    let mut content = DeliveryRequestContent::new(limit);
+    content.recipient_key = recipient_key;
+
+    let mut decorators = DeliveryRequestDecorators::default();
+    decorators.transport = Some(make_transport());
+    decorators.timing = Some(make_timing());
+
+    DeliveryRequest::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_messages_received(message_id_list: Vec<String>) -> MessagesReceived {
+    let content = MessagesReceivedContent::new(message_id_list);
+
+    let mut decorators = MessagesReceivedDecorators::default();
+    decorators.transport = Some(make_transport());
+    decorators.timing = Some(make_timing());
+
+    MessagesReceived::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+pub fn build_live_delivery_change(live_delivery: bool) -> LiveDeliveryChange {
+    let content = LiveDeliveryChangeContent::new(live_delivery);
+
+    let mut decorators = LiveDeliveryChangeDecorators::default();
+    decorators.transport = Some(make_transport());
+    decorators.timing = Some(make_timing());
+
+    LiveDeliveryChange::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_pickup_requests_keep_return_route_open() {
+        let status_request = build_status_request(None);
+        let delivery_request = build_delivery_request(10, Some("VsKV7grR1BUE29mG2Fm2kX".to_owned()));
+        let messages_received = build_messages_received(vec!["123456781".to_owned()]);
+        let live_delivery_change = build_live_delivery_change(true);
+
+        let expected = Some(make_transport());
+        assert_eq!(status_request.decorators.transport, expected);
+        assert_eq!(delivery_request.decorators.transport, expected);
+        assert_eq!(messages_received.decorators.transport, expected);
+        assert_eq!(live_delivery_change.decorators.transport, expected);
+    }
+
+    #[test]
+    fn test_build_delivery_request() {
+        let delivery_request = build_delivery_request(10, Some("VsKV7grR1BUE29mG2Fm2kX".to_owned()));
+
+        assert_eq!(delivery_request.content.limit, 10);
+        assert_eq!(
+            delivery_request.content.recipient_key.as_deref(),
+            Some("VsKV7grR1BUE29mG2Fm2kX")
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::present_proof::v2::{
+    present::PresentationAttachmentFormatType, request::RequestPresentationAttachmentFormatType,
+};
+
+use super::ProverPresentationFormat;
+use crate::{
+    common::{
+        credentials::w3c::{
+            credential::VerifiableCredential,
+            proof::{Proof, ProofPurpose, ProofType},
+            storage::get_credentials,
+            suites::sign_presentation,
+        },
+        proofs::presentation_exchange::{
+            definition::PresentationDefinition,
+            evaluator::{build_presentation, match_credentials, select_credentials, validate_selection},
+        },
+    },
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+};
+
+/// Content of a `dif/presentation-exchange/definitions@v1.0` request attachment.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PresentationExchangeRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub options: Option<PresentationExchangeOptions>,
+    pub presentation_definition: PresentationDefinition,
+}
+
+/// Values the proof of the presentation has to be created for.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct PresentationExchangeOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+/// A stored credential satisfying an input descriptor, as returned by
+/// [`DifPresentationExchangeProverFormat::retrieve_credentials`] keyed by input descriptor.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct MatchingCredential {
+    pub credential_id: String,
+    pub credential: VerifiableCredential,
+}
+
+/// The credentials selected for a presentation, passed as JSON to
+/// [`DifPresentationExchangeProverFormat::create_presentation`].
+///
+/// Credential identifiers are keyed by the input descriptor they satisfy. If no credential is
+/// selected, the first matching credentials satisfying the definition are presented.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct PresentationExchangeSelection {
+    #[serde(default)]
+    pub selected_credentials: HashMap<String, String>,
+}
+
+/// Creates verifiable presentations (`dif/presentation-exchange/submission@v1.0`) of the
+/// W3C credentials stored in the wallet, answering DIF Presentation Exchange definitions.
+///
+/// Presentations are signed by the holder with the wallet key of `verification_method`,
+/// which has to be an authentication method of the holder DID.
+#[derive(Clone, Debug)]
+pub struct DifPresentationExchangeProverFormat {
+    proof_type: ProofType,
+    verification_method: String,
+    signing_key: String,
+}
+
+impl DifPresentationExchangeProverFormat {
+    pub fn new(proof_type: ProofType, verification_method: String, signing_key: String) -> Self {
+        Self {
+            proof_type,
+            verification_method,
+            signing_key,
+        }
+    }
+
+    fn holder(&self) -> String {
+        let (did, _) = self
+            .verification_method
+            .split_once('#')
+            .unwrap_or((self.verification_method.as_str(), ""));
+        did.to_owned()
+    }
+}
+
+fn parse_request(request: &str) -> VcxResult<PresentationExchangeRequest> {
+    serde_json::from_str(request).map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidProofRequest,
+            format!("Invalid presentation exchange request: {}", err),
+        )
+    })
+}
+
+#[async_trait]
+impl ProverPresentationFormat for DifPresentationExchangeProverFormat {
+    fn request_format(&self) -> RequestPresentationAttachmentFormatType {
+        RequestPresentationAttachmentFormatType::DifPresentationExchangeDefinitions1_0
+    }
+
+    fn presentation_format(&self) -> PresentationAttachmentFormatType {
+        PresentationAttachmentFormatType::DifPresentationExchangeSubmission1_0
+    }
+
+    async fn retrieve_credentials(&self, profile: &Arc<dyn Profile>, request: &str) -> VcxResult<String> {
+        let request = parse_request(request)?;
+        let credentials = get_credentials(&profile.inject_wallet()).await?;
+        let candidates = match_credentials(&request.presentation_definition, &credentials)?;
+
+        let matching: HashMap<String, Vec<MatchingCredential>> = candidates
+            .into_iter()
+            .map(|(descriptor, credential_ids)| {
+                let matching = credentials
+                    .iter()
+                    .filter(|(id, _)| credential_ids.contains(id))
+                    .map(|(id, credential)| MatchingCredential {
+                        credential_id: id.clone(),
+                        credential: credential.clone(),
+                    })
+                    .collect();
+                (descriptor, matching)
+            })
+            .collect();
+        Ok(serde_json::to_string(&matching)?)
+    }
+
+    async fn create_presentation(
+        &self,
+        profile: &Arc<dyn Profile>,
+        request: &str,
+        credentials: &str,
+    ) -> VcxResult<String> {
+        let request = parse_request(request)?;
+        let PresentationExchangeSelection { selected_credentials } =
+            serde_json::from_str(credentials).map_err(|err| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::InvalidJson,
+                    format!("Invalid presentation exchange credentials selection: {}", err),
+                )
+            })?;
+
+        let wallet = profile.inject_wallet();
+        let definition = &request.presentation_definition;
+        let stored = get_credentials(&wallet).await?;
+        let candidates = match_credentials(definition, &stored)?;
+        let selection = if selected_credentials.is_empty() {
+            select_credentials(definition, &candidates)?
+        } else {
+            validate_selection(definition, &candidates, &selected_credentials)?
+        };
+
+        let mut presentation = build_presentation(definition, Some(self.holder()), &stored, &selection)?;
+        let mut proof = Proof::new(
+            self.proof_type,
+            self.verification_method.clone(),
+            ProofPurpose::Authentication,
+        );
+        let options = request.options.unwrap_or_default();
+        proof.challenge = options.challenge;
+        proof.domain = options.domain;
+        sign_presentation(&wallet, &mut presentation, proof, &self.signing_key).await?;
+
+        Ok(serde_json::to_string(&presentation)?)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    #[test]
+    fn test_presentation_exchange_request_serde() {
+        let json = json!({
+            "options": {
+                "challenge": "23516943-1d79-4ebd-8981-623f036365ef",
+                "domain": "example.org"
+            },
+            "presentation_definition": {
+                "id": "32f54163-7166-48f1-93d8-ff217bdb0653",
+                "input_descriptors": [{
+                    "id": "citizenship_input",
+                    "constraints": {
+                        "fields": [{ "path": ["$.credentialSubject.birth_date"] }]
+                    }
+                }]
+            }
+        });
+
+        let request = parse_request(&json.to_string()).unwrap();
+        assert_eq!(
+            request.options.as_ref().unwrap().challenge.as_deref(),
+            Some("23516943-1d79-4ebd-8981-623f036365ef")
+        );
+        assert_eq!(
+            request.presentation_definition.input_descriptors[0].id,
+            "citizenship_input"
+        );
+        assert_eq!(serde_json::to_value(&request).unwrap(), json);
+    }
+
+    #[test]
+    fn test_holder_is_did_of_verification_method() {
+        let format = DifPresentationExchangeProverFormat::new(
+            ProofType::Ed25519Signature2020,
+            "did:example:holder#key-1".to_owned(),
+            "3Emm6DnrzrUu4gtV3sW5RU7DvmZLZbC2sZGiGFEaUzci".to_owned(),
+        );
+        assert_eq!(format.holder(), "did:example:holder");
+    }
+}
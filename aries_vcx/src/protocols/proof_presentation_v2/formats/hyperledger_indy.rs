@@ -0,0 +1,107 @@
+use std::{collections::HashMap, sync::Arc};
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::present_proof::v2::{
+    present::PresentationAttachmentFormatType, request::RequestPresentationAttachmentFormatType,
+};
+
+use super::{ProverPresentationFormat, VerifierPresentationFormat};
+use crate::{
+    common::proofs::{
+        proof_request::PresentationRequestData, prover::prover::generate_indy_proof,
+        verifier::verifier::validate_indy_proof,
+    },
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::proof_presentation::types::SelectedCredentials,
+};
+
+/// Requests and verifies Hyperledger Indy proofs (`hlindy/proof@v2.0`).
+#[derive(Clone, Debug)]
+pub struct HyperledgerIndyVerifierFormat {
+    presentation_request: PresentationRequestData,
+}
+
+impl HyperledgerIndyVerifierFormat {
+    pub fn new(presentation_request: PresentationRequestData) -> Self {
+        Self { presentation_request }
+    }
+}
+
+#[async_trait]
+impl VerifierPresentationFormat for HyperledgerIndyVerifierFormat {
+    fn request_format(&self) -> RequestPresentationAttachmentFormatType {
+        RequestPresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0
+    }
+
+    fn presentation_format(&self) -> PresentationAttachmentFormatType {
+        PresentationAttachmentFormatType::HyperledgerIndyProof2_0
+    }
+
+    async fn create_request(&self, _profile: &Arc<dyn Profile>) -> VcxResult<String> {
+        serde_json::to_string(&self.presentation_request).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::SerializationError,
+                format!("Cannot serialize presentation request: {}", err),
+            )
+        })
+    }
+
+    async fn verify_presentation(
+        &self,
+        profile: &Arc<dyn Profile>,
+        request: &str,
+        presentation: &str,
+    ) -> VcxResult<bool> {
+        validate_indy_proof(profile, presentation, request).await
+    }
+}
+
+/// The credentials selected for a Hyperledger Indy proof, passed as JSON to
+/// [`HyperledgerIndyProverFormat::create_presentation`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct IndyCredentialsSelection {
+    pub selected_credentials: SelectedCredentials,
+    #[serde(default)]
+    pub self_attested_attrs: HashMap<String, String>,
+}
+
+/// Creates Hyperledger Indy proofs (`hlindy/proof@v2.0`).
+#[derive(Clone, Debug, Default)]
+pub struct HyperledgerIndyProverFormat;
+
+#[async_trait]
+impl ProverPresentationFormat for HyperledgerIndyProverFormat {
+    fn request_format(&self) -> RequestPresentationAttachmentFormatType {
+        RequestPresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0
+    }
+
+    fn presentation_format(&self) -> PresentationAttachmentFormatType {
+        PresentationAttachmentFormatType::HyperledgerIndyProof2_0
+    }
+
+    async fn retrieve_credentials(&self, profile: &Arc<dyn Profile>, request: &str) -> VcxResult<String> {
+        let anoncreds = Arc::clone(profile).inject_anoncreds();
+        let credentials = anoncreds.prover_get_credentials_for_proof_req(request).await?;
+        Ok(credentials)
+    }
+
+    async fn create_presentation(
+        &self,
+        profile: &Arc<dyn Profile>,
+        request: &str,
+        credentials: &str,
+    ) -> VcxResult<String> {
+        let IndyCredentialsSelection {
+            selected_credentials,
+            self_attested_attrs,
+        } = serde_json::from_str(credentials).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Invalid Indy credentials selection: {}", err),
+            )
+        })?;
+
+        generate_indy_proof(profile, &selected_credentials, &self_attested_attrs, request).await
+    }
+}
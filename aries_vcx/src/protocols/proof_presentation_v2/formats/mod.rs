@@ -0,0 +1,63 @@
+pub mod dif_presentation_exchange;
+pub mod hyperledger_indy;
+
+use std::{fmt::Debug, sync::Arc};
+
+use async_trait::async_trait;
+use messages::msg_fields::protocols::present_proof::v2::{
+    present::PresentationAttachmentFormatType, request::RequestPresentationAttachmentFormatType,
+};
+
+use crate::{core::profile::profile::Profile, errors::error::VcxResult};
+
+/// The verifier side of a proof format (e.g. Hyperledger Indy, AnonCreds, DIF Presentation Exchange)
+/// that can be requested through the present proof 2.0 protocol.
+///
+/// The [`super::verifier::VerifierV2`] state machine requests a presentation in every format it's given
+/// and then verifies it with the format the prover answered in.
+#[async_trait]
+pub trait VerifierPresentationFormat: Debug + Send + Sync {
+    /// The format of the request attachments created by [`VerifierPresentationFormat::create_request`].
+    fn request_format(&self) -> RequestPresentationAttachmentFormatType;
+
+    /// The format of the presentation attachments this format can verify.
+    fn presentation_format(&self) -> PresentationAttachmentFormatType;
+
+    /// Creates the content of the request attachment.
+    async fn create_request(&self, profile: &Arc<dyn Profile>) -> VcxResult<String>;
+
+    /// Verifies the content of the presentation attachment against the one of the request attachment,
+    /// returning whether the presentation is valid.
+    async fn verify_presentation(
+        &self,
+        profile: &Arc<dyn Profile>,
+        request: &str,
+        presentation: &str,
+    ) -> VcxResult<bool>;
+}
+
+/// The prover side of a proof format that can be presented through the present proof 2.0 protocol.
+///
+/// The [`super::prover::ProverV2`] state machine answers with a presentation in the first requested
+/// format it has a [`ProverPresentationFormat`] for.
+#[async_trait]
+pub trait ProverPresentationFormat: Debug + Send + Sync {
+    /// The format of the request attachments this format can create a presentation for.
+    fn request_format(&self) -> RequestPresentationAttachmentFormatType;
+
+    /// The format of the presentation attachments created by [`ProverPresentationFormat::create_presentation`].
+    fn presentation_format(&self) -> PresentationAttachmentFormatType;
+
+    /// Retrieves the credentials that can satisfy the content of the request attachment,
+    /// in a format specific JSON representation.
+    async fn retrieve_credentials(&self, profile: &Arc<dyn Profile>, request: &str) -> VcxResult<String>;
+
+    /// Creates the content of the presentation attachment from the format specific
+    /// JSON selection of the credentials to present.
+    async fn create_presentation(
+        &self,
+        profile: &Arc<dyn Profile>,
+        request: &str,
+        credentials: &str,
+    ) -> VcxResult<String>;
+}
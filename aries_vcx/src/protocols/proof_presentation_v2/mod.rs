@@ -0,0 +1,304 @@
+//! State machines for the [present proof 2.0 protocol](https://github.com/hyperledger/aries-rfcs/blob/main/features/0454-present-proof-v2/README.md).
+//!
+//! Like in the issue credential 2.0 protocol, messages can carry attachments in multiple proof formats,
+//! identified through their `formats` array. The transitions handling attachments are given a list of
+//! [`formats::VerifierPresentationFormat`] or [`formats::ProverPresentationFormat`] implementations
+//! and dispatch every attachment to the implementation handling its format.
+
+pub mod formats;
+pub mod prover;
+pub mod verifier;
+
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        present_proof::v2::problem_report::{PresentProofV2ProblemReport, PresentProofV2ProblemReportContent},
+        report_problem::ProblemReportDecorators,
+    },
+};
+use uuid::Uuid;
+
+fn build_problem_report(comment: Option<String>, thread_id: &str) -> PresentProofV2ProblemReport {
+    let content = PresentProofV2ProblemReportContent::new(comment.unwrap_or_default());
+
+    let mut decorators = ProblemReportDecorators::default();
+    decorators.thread = Some(Thread::new(thread_id.to_owned()));
+    let mut timing = Timing::default();
+    timing.out_time = Some(Utc::now());
+    decorators.timing = Some(timing);
+
+    PresentProofV2ProblemReport::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::sync::Arc;
+
+    use async_trait::async_trait;
+    use messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::present_proof::v2::{
+            present::PresentationAttachmentFormatType, request::RequestPresentationAttachmentFormatType,
+        },
+    };
+    use serde::{de::DeserializeOwned, Serialize};
+
+    use super::{
+        formats::{ProverPresentationFormat, VerifierPresentationFormat},
+        prover::{states::RequestReceived, ProverV2},
+        verifier::{states::RequestSent, VerifierV2},
+    };
+    use crate::{
+        core::profile::profile::Profile,
+        errors::error::{AriesVcxErrorKind, VcxResult},
+        protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus,
+        utils::mockdata::profile::mock_profile::MockProfile,
+    };
+
+    const REQUEST: &str = r#"{"requested":"name"}"#;
+    const CREDENTIALS: &str = r#"{"name":"cred_id"}"#;
+    const PRESENTATION: &str = r#"{"name":"alice"}"#;
+
+    #[derive(Debug)]
+    struct TestVerifierFormat {
+        valid: bool,
+    }
+
+    #[async_trait]
+    impl VerifierPresentationFormat for TestVerifierFormat {
+        fn request_format(&self) -> RequestPresentationAttachmentFormatType {
+            RequestPresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0
+        }
+
+        fn presentation_format(&self) -> PresentationAttachmentFormatType {
+            PresentationAttachmentFormatType::HyperledgerIndyProof2_0
+        }
+
+        async fn create_request(&self, _profile: &Arc<dyn Profile>) -> VcxResult<String> {
+            Ok(REQUEST.to_owned())
+        }
+
+        async fn verify_presentation(
+            &self,
+            _profile: &Arc<dyn Profile>,
+            request: &str,
+            presentation: &str,
+        ) -> VcxResult<bool> {
+            assert_eq!(request, REQUEST);
+            assert_eq!(presentation, PRESENTATION);
+            Ok(self.valid)
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestProverFormat;
+
+    #[async_trait]
+    impl ProverPresentationFormat for TestProverFormat {
+        fn request_format(&self) -> RequestPresentationAttachmentFormatType {
+            RequestPresentationAttachmentFormatType::HyperledgerIndyProofRequest2_0
+        }
+
+        fn presentation_format(&self) -> PresentationAttachmentFormatType {
+            PresentationAttachmentFormatType::HyperledgerIndyProof2_0
+        }
+
+        async fn retrieve_credentials(&self, _profile: &Arc<dyn Profile>, request: &str) -> VcxResult<String> {
+            assert_eq!(request, REQUEST);
+            Ok(CREDENTIALS.to_owned())
+        }
+
+        async fn create_presentation(
+            &self,
+            _profile: &Arc<dyn Profile>,
+            request: &str,
+            credentials: &str,
+        ) -> VcxResult<String> {
+            assert_eq!(request, REQUEST);
+            assert_eq!(credentials, CREDENTIALS);
+            Ok(PRESENTATION.to_owned())
+        }
+    }
+
+    fn verifier_formats(valid: bool) -> Vec<Arc<dyn VerifierPresentationFormat>> {
+        vec![Arc::new(TestVerifierFormat { valid })]
+    }
+
+    fn prover_formats() -> Vec<Arc<dyn ProverPresentationFormat>> {
+        vec![Arc::new(TestProverFormat)]
+    }
+
+    // Stores and restores a state machine, as done between receiving messages.
+    fn reload<T: Serialize + DeserializeOwned>(sm: T) -> T {
+        serde_json::from_str(&serde_json::to_string(&sm).unwrap()).unwrap()
+    }
+
+    async fn request_sent(profile: &Arc<dyn Profile>) -> (VerifierV2<RequestSent>, ProverV2<RequestReceived>) {
+        let verifier = VerifierV2::create_request(profile, &verifier_formats(true), None)
+            .await
+            .unwrap();
+        let prover = ProverV2::from_request(verifier.request().clone());
+        (verifier, prover)
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_flow() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (verifier, prover) = request_sent(&profile).await;
+        let (verifier, prover) = (reload(verifier), reload(prover));
+        assert_eq!(prover.thread_id(), verifier.thread_id());
+        assert!(prover.is_request_supported(&prover_formats()));
+
+        let credentials = prover.retrieve_credentials(&profile, &prover_formats()).await.unwrap();
+        let prover = prover
+            .create_presentation(&profile, &prover_formats(), &credentials)
+            .await
+            .unwrap();
+        let prover = reload(prover);
+
+        let verifier = verifier
+            .receive_presentation(&profile, &verifier_formats(true), prover.presentation().clone())
+            .await
+            .unwrap();
+        let verifier = reload(verifier);
+        assert_eq!(verifier.verification_status(), &PresentationVerificationStatus::Valid);
+
+        let prover = reload(prover.receive_ack(verifier.create_ack()).unwrap());
+        assert_eq!(prover.presentation().decorators.thread.thid, verifier.thread_id());
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_invalid_presentation() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (verifier, prover) = request_sent(&profile).await;
+        let prover = prover
+            .create_presentation(&profile, &prover_formats(), CREDENTIALS)
+            .await
+            .unwrap();
+
+        let verifier = verifier
+            .receive_presentation(&profile, &verifier_formats(false), prover.presentation().clone())
+            .await
+            .unwrap();
+        assert_eq!(verifier.verification_status(), &PresentationVerificationStatus::Invalid);
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_unsupported_request_format() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (_, prover) = request_sent(&profile).await;
+        assert!(!prover.is_request_supported(&[]));
+
+        let err = prover.retrieve_credentials(&profile, &[]).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::ActionNotSupported);
+
+        let err = prover
+            .create_presentation(&profile, &[], CREDENTIALS)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::ActionNotSupported);
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_request_without_formats() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let err = VerifierV2::create_request(&profile, &[], None).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_presentation_in_unsupported_format() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (verifier, prover) = request_sent(&profile).await;
+        let prover = prover
+            .create_presentation(&profile, &prover_formats(), CREDENTIALS)
+            .await
+            .unwrap();
+
+        let err = verifier
+            .receive_presentation(&profile, &[], prover.presentation().clone())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::ActionNotSupported);
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_presentation_and_ack_with_other_thread_id() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (verifier, prover) = request_sent(&profile).await;
+        let prover = prover
+            .create_presentation(&profile, &prover_formats(), CREDENTIALS)
+            .await
+            .unwrap();
+
+        let mut presentation = prover.presentation().clone();
+        presentation.decorators.thread = Thread::new("other_thread_id".to_owned());
+        let err = verifier
+            .clone()
+            .receive_presentation(&profile, &verifier_formats(true), presentation)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+
+        let verifier = verifier
+            .receive_presentation(&profile, &verifier_formats(true), prover.presentation().clone())
+            .await
+            .unwrap();
+        let mut ack = verifier.create_ack();
+        ack.decorators.thread = Thread::new("other_thread_id".to_owned());
+        let err = prover.receive_ack(ack).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_prover_problem_report() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (verifier, prover) = request_sent(&profile).await;
+        let prover = reload(prover.abandon(Some("rejected".to_owned())));
+
+        let verifier = verifier
+            .receive_problem_report(prover.problem_report().clone())
+            .unwrap();
+        let verifier = reload(verifier);
+        assert_eq!(verifier.problem_report().content.0.description.code, "rejected");
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_verifier_problem_report() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (verifier, prover) = request_sent(&profile).await;
+        let prover = prover
+            .create_presentation(&profile, &prover_formats(), CREDENTIALS)
+            .await
+            .unwrap();
+        let verifier = reload(verifier.abandon(Some("expired".to_owned())));
+
+        let prover = prover
+            .receive_problem_report(verifier.problem_report().clone())
+            .unwrap();
+        let prover = reload(prover);
+        assert_eq!(prover.problem_report().content.0.description.code, "expired");
+    }
+
+    #[tokio::test]
+    async fn test_presentation_v2_problem_report_with_other_thread_id() {
+        let profile: Arc<dyn Profile> = Arc::new(MockProfile);
+
+        let (verifier, prover) = request_sent(&profile).await;
+        let mut problem_report = prover.abandon(None).problem_report().clone();
+        problem_report.decorators.thread = Some(Thread::new("other_thread_id".to_owned()));
+
+        let err = verifier.receive_problem_report(problem_report).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+}
@@ -0,0 +1,207 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        common::attachment_format_specifier::AttachmentFormatSpecifier,
+        present_proof::v2::{
+            ack::AckPresentationV2,
+            present::{PresentationV2, PresentationV2Content, PresentationV2Decorators},
+            problem_report::PresentProofV2ProblemReport,
+            request::RequestPresentationV2,
+        },
+    },
+};
+use shared_vcx::maybe_known::MaybeKnown;
+use uuid::Uuid;
+
+use self::states::{Completed, Failed, PresentationSent, RequestReceived};
+use super::{build_problem_report, formats::ProverPresentationFormat};
+use crate::{
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::{make_attach_from_str, verify_thread_id},
+    protocols::common::get_attachment_content,
+};
+
+/// A state machine for the prover role of the present proof 2.0 protocol.
+///
+/// The presentation is created in the first requested format the prover has
+/// a [`ProverPresentationFormat`] for, as requests list their formats in order of preference.
+///
+/// The state machine only holds serializable state, so it can be stored between messages.
+/// The [`ProverPresentationFormat`]s are passed to the transitions that need them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProverV2<S> {
+    thread_id: String,
+    state: S,
+}
+
+impl<S> ProverV2<S> {
+    pub fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Processes a [`PresentProofV2ProblemReport`] and transitions to [`ProverV2<Failed>`].
+    pub fn receive_problem_report(self, problem_report: PresentProofV2ProblemReport) -> VcxResult<ProverV2<Failed>> {
+        verify_thread_id(&self.thread_id, &problem_report.clone().into())?;
+        Ok(self.transit(Failed { problem_report }))
+    }
+
+    /// Abandons the protocol and transitions to [`ProverV2<Failed>`]. The problem report that
+    /// should be sent to the verifier can be retrieved through [`ProverV2<Failed>::problem_report`].
+    pub fn abandon(self, comment: Option<String>) -> ProverV2<Failed> {
+        let problem_report = build_problem_report(comment, &self.thread_id);
+        self.transit(Failed { problem_report })
+    }
+
+    fn transit<T>(self, state: T) -> ProverV2<T> {
+        ProverV2 {
+            thread_id: self.thread_id,
+            state,
+        }
+    }
+}
+
+impl ProverV2<RequestReceived> {
+    /// Creates a prover from a [`RequestPresentationV2`] received from the verifier.
+    pub fn from_request(request: RequestPresentationV2) -> Self {
+        let thread_id = request
+            .decorators
+            .thread
+            .as_ref()
+            .map(|thread| thread.thid.clone())
+            .unwrap_or_else(|| request.id.clone());
+
+        Self {
+            thread_id,
+            state: RequestReceived { request },
+        }
+    }
+
+    pub fn request(&self) -> &RequestPresentationV2 {
+        &self.state.request
+    }
+
+    /// Returns whether any of the requested proof formats is supported by the given `formats`.
+    pub fn is_request_supported(&self, formats: &[Arc<dyn ProverPresentationFormat>]) -> bool {
+        self.select_format(formats).is_some()
+    }
+
+    /// Retrieves the credentials that can satisfy the request, in the JSON representation
+    /// of the preferred supported format.
+    pub async fn retrieve_credentials(
+        &self,
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn ProverPresentationFormat>],
+    ) -> VcxResult<String> {
+        let format = self.supported_format(formats)?;
+        let request_attach = self.request_attach(format)?;
+        format.retrieve_credentials(profile, &request_attach).await
+    }
+
+    /// Creates a [`PresentationV2`] in the preferred supported format from the given credentials
+    /// selection and transitions to [`ProverV2<PresentationSent>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if none of the requested formats is supported or creating the presentation fails.
+    pub async fn create_presentation(
+        self,
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn ProverPresentationFormat>],
+        credentials: &str,
+    ) -> VcxResult<ProverV2<PresentationSent>> {
+        let format = self.supported_format(formats)?;
+        let request_attach = self.request_attach(format)?;
+        let presentation = format
+            .create_presentation(profile, &request_attach, credentials)
+            .await?;
+
+        let attach_id = Uuid::new_v4().to_string();
+        let content = PresentationV2Content::new(
+            vec![AttachmentFormatSpecifier::new(
+                attach_id.clone(),
+                MaybeKnown::Known(format.presentation_format()),
+            )],
+            vec![make_attach_from_str!(&presentation, attach_id)],
+        );
+
+        let mut decorators = PresentationV2Decorators::new(Thread::new(self.thread_id.clone()));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let presentation = PresentationV2::with_decorators(Uuid::new_v4().to_string(), content, decorators);
+
+        Ok(self.transit(PresentationSent { presentation }))
+    }
+
+    fn request_attach(&self, format: &Arc<dyn ProverPresentationFormat>) -> VcxResult<String> {
+        let request = &self.state.request;
+        get_attachment_content(
+            &request.content.formats,
+            &request.content.request_presentations_attach,
+            format.request_format(),
+        )
+    }
+
+    fn supported_format<'a>(
+        &self,
+        formats: &'a [Arc<dyn ProverPresentationFormat>],
+    ) -> VcxResult<&'a Arc<dyn ProverPresentationFormat>> {
+        self.select_format(formats).ok_or_else(|| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::ActionNotSupported,
+                format!(
+                    "None of the presentation request formats {:?} is supported",
+                    self.state.request.content.formats
+                ),
+            )
+        })
+    }
+
+    fn select_format<'a>(
+        &self,
+        formats: &'a [Arc<dyn ProverPresentationFormat>],
+    ) -> Option<&'a Arc<dyn ProverPresentationFormat>> {
+        self.state.request.content.formats.iter().find_map(|spec| {
+            formats
+                .iter()
+                .find(|format| spec.format == MaybeKnown::Known(format.request_format()))
+        })
+    }
+}
+
+impl ProverV2<PresentationSent> {
+    pub fn presentation(&self) -> &PresentationV2 {
+        &self.state.presentation
+    }
+
+    /// Processes the verifier's [`AckPresentationV2`] and transitions to [`ProverV2<Completed>`].
+    pub fn receive_ack(self, ack: AckPresentationV2) -> VcxResult<ProverV2<Completed>> {
+        verify_thread_id(&self.thread_id, &ack.into())?;
+
+        let presentation = self.state.presentation.clone();
+        Ok(self.transit(Completed { presentation }))
+    }
+}
+
+impl ProverV2<Completed> {
+    pub fn presentation(&self) -> &PresentationV2 {
+        &self.state.presentation
+    }
+}
+
+impl ProverV2<Failed> {
+    pub fn problem_report(&self) -> &PresentProofV2ProblemReport {
+        &self.state.problem_report
+    }
+}
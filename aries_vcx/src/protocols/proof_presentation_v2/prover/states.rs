@@ -0,0 +1,23 @@
+use messages::msg_fields::protocols::present_proof::v2::{
+    present::PresentationV2, problem_report::PresentProofV2ProblemReport, request::RequestPresentationV2,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestReceived {
+    pub(crate) request: RequestPresentationV2,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresentationSent {
+    pub(crate) presentation: PresentationV2,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Completed {
+    pub(crate) presentation: PresentationV2,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Failed {
+    pub(crate) problem_report: PresentProofV2ProblemReport,
+}
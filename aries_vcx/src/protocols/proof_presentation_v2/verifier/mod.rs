@@ -0,0 +1,228 @@
+pub mod states;
+
+use std::sync::Arc;
+
+use chrono::Utc;
+use messages::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_fields::protocols::{
+        common::attachment_format_specifier::AttachmentFormatSpecifier,
+        notification::ack::{AckDecorators, AckStatus},
+        present_proof::v2::{
+            ack::{AckPresentationV2, AckPresentationV2Content},
+            present::PresentationV2,
+            problem_report::PresentProofV2ProblemReport,
+            request::{RequestPresentationV2, RequestPresentationV2Content, RequestPresentationV2Decorators},
+        },
+    },
+};
+use shared_vcx::maybe_known::MaybeKnown;
+use uuid::Uuid;
+
+use self::states::{Failed, PresentationReceived, RequestSent};
+use super::{build_problem_report, formats::VerifierPresentationFormat};
+use crate::{
+    core::profile::profile::Profile,
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::{make_attach_from_str, verify_thread_id},
+    protocols::{
+        common::get_attachment_content,
+        proof_presentation::verifier::verification_status::PresentationVerificationStatus,
+    },
+};
+
+/// A state machine for the verifier role of the present proof 2.0 protocol.
+///
+/// The presentation is requested in every one of the [`VerifierPresentationFormat`]s the verifier
+/// is created with, and then verified with the one the prover presented in.
+///
+/// The state machine only holds serializable state, so it can be stored between messages.
+/// The [`VerifierPresentationFormat`]s are passed to the transitions that need them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct VerifierV2<S> {
+    thread_id: String,
+    state: S,
+}
+
+impl<S> VerifierV2<S> {
+    pub fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// Processes a [`PresentProofV2ProblemReport`] and transitions to [`VerifierV2<Failed>`].
+    pub fn receive_problem_report(self, problem_report: PresentProofV2ProblemReport) -> VcxResult<VerifierV2<Failed>> {
+        verify_thread_id(&self.thread_id, &problem_report.clone().into())?;
+        Ok(self.transit(Failed { problem_report }))
+    }
+
+    /// Abandons the protocol and transitions to [`VerifierV2<Failed>`]. The problem report that
+    /// should be sent to the prover can be retrieved through [`VerifierV2<Failed>::problem_report`].
+    pub fn abandon(self, comment: Option<String>) -> VerifierV2<Failed> {
+        let problem_report = build_problem_report(comment, &self.thread_id);
+        self.transit(Failed { problem_report })
+    }
+
+    fn transit<T>(self, state: T) -> VerifierV2<T> {
+        VerifierV2 {
+            thread_id: self.thread_id,
+            state,
+        }
+    }
+}
+
+impl VerifierV2<RequestSent> {
+    /// Creates a [`RequestPresentationV2`] that starts the protocol, in every one of the given `formats`,
+    /// and transitions to [`VerifierV2<RequestSent>`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if no formats are given or if creating any of the request attachments fails.
+    pub async fn create_request(
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn VerifierPresentationFormat>],
+        comment: Option<String>,
+    ) -> VcxResult<Self> {
+        if formats.is_empty() {
+            return Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidInput,
+                "Cannot create a presentation request without any proof format",
+            ));
+        }
+
+        let mut format_specifiers = Vec::with_capacity(formats.len());
+        let mut request_presentations_attach = Vec::with_capacity(formats.len());
+
+        for format in formats {
+            let request = format.create_request(profile).await?;
+            let attach_id = Uuid::new_v4().to_string();
+
+            format_specifiers.push(AttachmentFormatSpecifier::new(
+                attach_id.clone(),
+                MaybeKnown::Known(format.request_format()),
+            ));
+            request_presentations_attach.push(make_attach_from_str!(&request, attach_id));
+        }
+
+        let mut content = RequestPresentationV2Content::new(format_specifiers, request_presentations_attach);
+        content.comment = comment;
+        content.will_confirm = Some(true);
+
+        let mut decorators = RequestPresentationV2Decorators::default();
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        let thread_id = Uuid::new_v4().to_string();
+        let request = RequestPresentationV2::with_decorators(thread_id.clone(), content, decorators);
+
+        Ok(Self {
+            thread_id,
+            state: RequestSent { request },
+        })
+    }
+
+    pub fn request(&self) -> &RequestPresentationV2 {
+        &self.state.request
+    }
+
+    /// Processes a [`PresentationV2`], verifies it and transitions to [`VerifierV2<PresentationReceived>`].
+    ///
+    /// A presentation that fails verification is not an error: its outcome is available
+    /// through [`VerifierV2<PresentationReceived>::verification_status`].
+    ///
+    /// # Errors
+    ///
+    /// Will return an error if either:
+    ///     * the presentation's thread ID does not match the one of the request
+    ///     * the presentation is not in any of the requested formats, as supported by the given `formats`
+    ///     * the verification itself could not be performed
+    pub async fn receive_presentation(
+        self,
+        profile: &Arc<dyn Profile>,
+        formats: &[Arc<dyn VerifierPresentationFormat>],
+        presentation: PresentationV2,
+    ) -> VcxResult<VerifierV2<PresentationReceived>> {
+        verify_thread_id(&self.thread_id, &presentation.clone().into())?;
+
+        let request = &self.state.request;
+        let format = presentation
+            .content
+            .formats
+            .iter()
+            .find_map(|spec| {
+                formats.iter().find(|format| {
+                    spec.format == MaybeKnown::Known(format.presentation_format())
+                        && request
+                            .content
+                            .formats
+                            .iter()
+                            .any(|requested| requested.format == MaybeKnown::Known(format.request_format()))
+                })
+            })
+            .ok_or_else(|| {
+                AriesVcxError::from_msg(
+                    AriesVcxErrorKind::ActionNotSupported,
+                    format!(
+                        "Presentation formats {:?} do not match any of the requested ones",
+                        presentation.content.formats
+                    ),
+                )
+            })?;
+
+        let request_attach = get_attachment_content(
+            &request.content.formats,
+            &request.content.request_presentations_attach,
+            format.request_format(),
+        )?;
+        let presentation_attach = get_attachment_content(
+            &presentation.content.formats,
+            &presentation.content.presentations_attach,
+            format.presentation_format(),
+        )?;
+
+        let valid = format
+            .verify_presentation(profile, &request_attach, &presentation_attach)
+            .await?;
+        let verification_status = if valid {
+            PresentationVerificationStatus::Valid
+        } else {
+            PresentationVerificationStatus::Invalid
+        };
+
+        Ok(self.transit(PresentationReceived {
+            presentation,
+            verification_status,
+        }))
+    }
+}
+
+impl VerifierV2<PresentationReceived> {
+    pub fn presentation(&self) -> &PresentationV2 {
+        &self.state.presentation
+    }
+
+    pub fn verification_status(&self) -> &PresentationVerificationStatus {
+        &self.state.verification_status
+    }
+
+    /// Creates the [`AckPresentationV2`] acknowledging the presentation.
+    pub fn create_ack(&self) -> AckPresentationV2 {
+        let content = AckPresentationV2Content::new(AckStatus::Ok);
+        let mut decorators = AckDecorators::new(Thread::new(self.thread_id.clone()));
+        let mut timing = Timing::default();
+        timing.out_time = Some(Utc::now());
+        decorators.timing = Some(timing);
+
+        AckPresentationV2::with_decorators(Uuid::new_v4().to_string(), content, decorators)
+    }
+}
+
+impl VerifierV2<Failed> {
+    pub fn problem_report(&self) -> &PresentProofV2ProblemReport {
+        &self.state.problem_report
+    }
+}
@@ -0,0 +1,21 @@
+use messages::msg_fields::protocols::present_proof::v2::{
+    present::PresentationV2, problem_report::PresentProofV2ProblemReport, request::RequestPresentationV2,
+};
+
+use crate::protocols::proof_presentation::verifier::verification_status::PresentationVerificationStatus;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestSent {
+    pub(crate) request: RequestPresentationV2,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PresentationReceived {
+    pub(crate) presentation: PresentationV2,
+    pub(crate) verification_status: PresentationVerificationStatus,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Failed {
+    pub(crate) problem_report: PresentProofV2ProblemReport,
+}
@@ -6,6 +6,7 @@ pub mod test_utils {
     use messages::msg_fields::protocols::revocation::revoke::{
         RevocationFormat, Revoke, RevokeContent, RevokeDecorators,
     };
+    use messages::msg_fields::protocols::revocation::v1::revoke::{RevokeV1, RevokeV1Content};
     use messages::AriesMessage;
     use shared_vcx::maybe_known::MaybeKnown;
     use uuid::Uuid;
@@ -25,6 +26,10 @@ pub mod test_utils {
         String::from("12")
     }
 
+    pub fn _thread_id() -> String {
+        String::from("7f8ab7ea-bc1f-4d17-bfa9-b0e4b4a8c5e2")
+    }
+
     pub fn _comment() -> Option<String> {
         Some("Comment.".to_string())
     }
@@ -45,6 +50,19 @@ pub mod test_utils {
         Revoke::with_decorators(id, content, decorators)
     }
 
+    pub fn _revocation_notification_v1(ack_on: Vec<AckOn>) -> RevokeV1 {
+        let id = Uuid::new_v4().to_string();
+
+        let mut content = RevokeV1Content::new(_thread_id());
+        content.comment = _comment();
+
+        let mut decorators = RevokeDecorators::default();
+        let please_ack = PleaseAck::new(ack_on);
+        decorators.please_ack = Some(please_ack);
+
+        RevokeV1::with_decorators(id, content, decorators)
+    }
+
     pub fn _revocation_notification_invalid_format() -> Revoke {
         let id = Uuid::new_v4().to_string();
 
@@ -1,2 +1,3 @@
+pub mod notification;
 pub mod state_machine;
 mod states;
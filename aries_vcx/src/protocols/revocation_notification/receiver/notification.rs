@@ -0,0 +1,93 @@
+use messages::msg_fields::protocols::revocation::revoke::{Revoke, RevokeDecorators};
+use messages::msg_fields::protocols::revocation::v1::revoke::RevokeV1;
+
+/// Revocation notification received by the holder, in either version of the protocol.
+///
+/// A 2.0 notification refers to the revoked credential by its `rev_reg_id::cred_rev_id`
+/// credential ID, whereas a 1.0 notification refers to it by the thread ID of its issuance.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum RevocationNotification {
+    V2(Revoke),
+    V1(RevokeV1),
+}
+
+impl RevocationNotification {
+    pub fn id(&self) -> &str {
+        match self {
+            Self::V2(notification) => &notification.id,
+            Self::V1(notification) => &notification.id,
+        }
+    }
+
+    pub fn decorators(&self) -> &RevokeDecorators {
+        match self {
+            Self::V2(notification) => &notification.decorators,
+            Self::V1(notification) => &notification.decorators,
+        }
+    }
+
+    pub fn comment(&self) -> Option<&str> {
+        match self {
+            Self::V2(notification) => notification.content.comment.as_deref(),
+            Self::V1(notification) => notification.content.comment.as_deref(),
+        }
+    }
+
+    /// Thread ID of the notification itself, which its ack is threaded with.
+    pub fn thread_id(&self) -> String {
+        self.decorators()
+            .thread
+            .as_ref()
+            .map(|t| t.thid.clone())
+            .unwrap_or(self.id().to_owned())
+    }
+}
+
+impl From<Revoke> for RevocationNotification {
+    fn from(value: Revoke) -> Self {
+        Self::V2(value)
+    }
+}
+
+impl From<RevokeV1> for RevocationNotification {
+    fn from(value: RevokeV1) -> Self {
+        Self::V1(value)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::decorators::please_ack::AckOn;
+
+    use super::*;
+    use crate::protocols::revocation_notification::test_utils::{
+        _revocation_notification, _revocation_notification_v1, _thread_id,
+    };
+
+    #[test]
+    fn test_revocation_notification_v2_serde() {
+        let revoke = _revocation_notification(vec![AckOn::Receipt]);
+        let serialized = serde_json::to_string(&revoke).unwrap();
+
+        let notification: RevocationNotification = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(notification, RevocationNotification::V2(revoke));
+    }
+
+    #[test]
+    fn test_revocation_notification_v1_serde() {
+        let revoke = _revocation_notification_v1(vec![AckOn::Receipt]);
+        let serialized = serde_json::to_string(&RevocationNotification::from(revoke.clone())).unwrap();
+
+        let notification: RevocationNotification = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(notification, RevocationNotification::V1(revoke));
+    }
+
+    #[test]
+    fn test_revocation_notification_thread_id() {
+        let notification = RevocationNotification::from(_revocation_notification_v1(vec![]));
+        assert_eq!(notification.thread_id(), notification.id());
+        assert_ne!(notification.thread_id(), _thread_id());
+    }
+}
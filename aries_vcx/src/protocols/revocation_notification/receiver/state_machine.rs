@@ -2,13 +2,16 @@ use chrono::Utc;
 use messages::decorators::please_ack::AckOn;
 use messages::decorators::thread::Thread;
 use messages::decorators::timing::Timing;
-use messages::msg_fields::protocols::notification::ack::{AckDecorators, AckStatus};
+use messages::msg_fields::protocols::notification::ack::{Ack, AckContent, AckDecorators, AckStatus};
 use messages::msg_fields::protocols::revocation::ack::{AckRevoke, AckRevokeContent};
 use messages::msg_fields::protocols::revocation::revoke::{RevocationFormat, Revoke};
+use messages::msg_fields::protocols::revocation::v1::revoke::RevokeV1;
+use messages::AriesMessage;
 use shared_vcx::maybe_known::MaybeKnown;
 use uuid::Uuid;
 
 use crate::errors::error::prelude::*;
+use crate::protocols::revocation_notification::receiver::notification::RevocationNotification;
 use crate::protocols::revocation_notification::receiver::states::finished::FinishedState;
 use crate::protocols::revocation_notification::receiver::states::initial::InitialState;
 use crate::protocols::revocation_notification::receiver::states::received::NotificationReceivedState;
@@ -19,6 +22,10 @@ pub struct RevocationNotificationReceiverSM {
     state: ReceiverFullState,
     rev_reg_id: String,
     cred_rev_id: String,
+    // Missing from receivers created before 1.0 notifications were handled, which can
+    // consequently only validate 2.0 notifications
+    #[serde(default)]
+    issuance_thread_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -29,15 +36,16 @@ pub enum ReceiverFullState {
 }
 
 impl RevocationNotificationReceiverSM {
-    pub fn create(rev_reg_id: String, cred_rev_id: String) -> Self {
+    pub fn create(rev_reg_id: String, cred_rev_id: String, issuance_thread_id: String) -> Self {
         Self {
             state: ReceiverFullState::Initial(InitialState::new()),
             rev_reg_id,
             cred_rev_id,
+            issuance_thread_id,
         }
     }
 
-    pub fn get_notification(&self) -> VcxResult<Revoke> {
+    pub fn get_notification(&self) -> VcxResult<RevocationNotification> {
         match &self.state {
             ReceiverFullState::NotificationReceived(state) => Ok(state.get_notification()),
             ReceiverFullState::Finished(state) => Ok(state.get_notification()),
@@ -61,14 +69,14 @@ impl RevocationNotificationReceiverSM {
 
     pub async fn handle_revocation_notification(
         self,
-        notification: Revoke,
+        notification: RevocationNotification,
         send_message: SendClosure,
     ) -> VcxResult<Self> {
         let state = match self.state {
             ReceiverFullState::Initial(_) => {
                 self.validate_revocation_notification(&notification)?;
                 if !notification
-                    .decorators
+                    .decorators()
                     .please_ack
                     .as_ref()
                     .map(|d| d.on.is_empty())
@@ -76,29 +84,13 @@ impl RevocationNotificationReceiverSM {
                 {
                     ReceiverFullState::Finished(FinishedState::new(notification))
                 } else if notification
-                    .decorators
+                    .decorators()
                     .please_ack
                     .as_ref()
                     .map(|d| d.on.contains(&AckOn::Receipt))
                     .unwrap_or(false)
                 {
-                    let id = Uuid::new_v4().to_string();
-                    let content = AckRevokeContent::new(AckStatus::Ok);
-
-                    let thread_id = notification
-                        .decorators
-                        .thread
-                        .as_ref()
-                        .map(|t| t.thid.clone())
-                        .unwrap_or(notification.id.clone());
-
-                    let mut decorators = AckDecorators::new(Thread::new(thread_id));
-                    let mut timing = Timing::default();
-                    timing.out_time = Some(Utc::now());
-                    decorators.timing = Some(timing);
-
-                    let ack = AckRevoke::with_decorators(id, content, decorators);
-                    send_message(ack.into()).await?;
+                    send_message(build_ack(&notification)).await?;
                     ReceiverFullState::Finished(FinishedState::new(notification))
                 } else {
                     ReceiverFullState::NotificationReceived(NotificationReceivedState::new(notification))
@@ -120,7 +112,7 @@ impl RevocationNotificationReceiverSM {
                 let notification = self.get_notification()?;
 
                 if !notification
-                    .decorators
+                    .decorators()
                     .please_ack
                     .as_ref()
                     .map(|d| d.on.contains(&AckOn::Outcome))
@@ -129,23 +121,7 @@ impl RevocationNotificationReceiverSM {
                     warn!("Revocation notification should have already been sent or not sent at all");
                 }
 
-                let id = Uuid::new_v4().to_string();
-                let content = AckRevokeContent::new(AckStatus::Ok);
-
-                let thread_id = notification
-                    .decorators
-                    .thread
-                    .as_ref()
-                    .map(|t| t.thid.clone())
-                    .unwrap_or(notification.id.clone());
-
-                let mut decorators = AckDecorators::new(Thread::new(thread_id));
-                let mut timing = Timing::default();
-                timing.out_time = Some(Utc::now());
-                decorators.timing = Some(timing);
-
-                let ack = AckRevoke::with_decorators(id, content, decorators);
-                send_message(ack.into()).await?;
+                send_message(build_ack(&notification)).await?;
                 ReceiverFullState::Finished(FinishedState::new(notification))
             }
             _ => {
@@ -158,7 +134,25 @@ impl RevocationNotificationReceiverSM {
         Ok(Self { state, ..self })
     }
 
-    fn validate_revocation_notification(&self, notification: &Revoke) -> VcxResult<()> {
+    fn validate_revocation_notification(&self, notification: &RevocationNotification) -> VcxResult<()> {
+        match notification {
+            RevocationNotification::V2(notification) => self.validate_revocation_notification_v2(notification),
+            RevocationNotification::V1(notification) => self.validate_revocation_notification_v1(notification),
+        }
+    }
+
+    fn validate_revocation_notification_v1(&self, notification: &RevokeV1) -> VcxResult<()> {
+        if notification.content.thread_id != self.issuance_thread_id {
+            Err(AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidRevocationDetails,
+                "Thread ID in received notification does not match thread ID of the issuance of this credential",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn validate_revocation_notification_v2(&self, notification: &Revoke) -> VcxResult<()> {
         let check_rev_format = || -> VcxResult<()> {
             if notification.content.revocation_format != MaybeKnown::Known(RevocationFormat::IndyAnoncreds) {
                 Err(AriesVcxError::from_msg(
@@ -207,15 +201,33 @@ impl RevocationNotificationReceiverSM {
     }
 }
 
+// Notifications of the 1.0 protocol are acknowledged with the generic notification ack.
+fn build_ack(notification: &RevocationNotification) -> AriesMessage {
+    let id = Uuid::new_v4().to_string();
+
+    let mut decorators = AckDecorators::new(Thread::new(notification.thread_id()));
+    decorators.timing = Some(Timing {
+        out_time: Some(Utc::now()),
+        ..Default::default()
+    });
+
+    match notification {
+        RevocationNotification::V2(_) => {
+            AckRevoke::with_decorators(id, AckRevokeContent::new(AckStatus::Ok), decorators).into()
+        }
+        RevocationNotification::V1(_) => Ack::with_decorators(id, AckContent::new(AckStatus::Ok), decorators).into(),
+    }
+}
+
 pub mod test_utils {
     use messages::AriesMessage;
 
-    use crate::protocols::revocation_notification::test_utils::{_cred_rev_id, _rev_reg_id};
+    use crate::protocols::revocation_notification::test_utils::{_cred_rev_id, _rev_reg_id, _thread_id};
 
     use super::*;
 
     pub fn _receiver() -> RevocationNotificationReceiverSM {
-        RevocationNotificationReceiverSM::create(_rev_reg_id(), _cred_rev_id())
+        RevocationNotificationReceiverSM::create(_rev_reg_id(), _cred_rev_id(), _thread_id())
     }
 
     pub fn _send_message_but_fail() -> SendClosure {
@@ -225,6 +237,54 @@ pub mod test_utils {
     }
 }
 
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::msg_fields::protocols::revocation::v1::revoke::RevokeV1Content;
+
+    use super::test_utils::_receiver;
+    use super::*;
+    use crate::protocols::revocation_notification::test_utils::{
+        _revocation_notification, _revocation_notification_v1, _send_message,
+    };
+
+    #[tokio::test]
+    async fn test_receiver_without_issuance_thread_id_deserializes() {
+        let mut serialized = serde_json::to_value(_receiver()).unwrap();
+        serialized.as_object_mut().unwrap().remove("issuance_thread_id");
+
+        let sm: RevocationNotificationReceiverSM = serde_json::from_value(serialized).unwrap();
+        assert_eq!(sm.issuance_thread_id, "");
+
+        let sm = sm
+            .handle_revocation_notification(_revocation_notification(vec![]).into(), _send_message())
+            .await
+            .unwrap();
+        assert!(matches!(sm.state, ReceiverFullState::Finished(_)));
+    }
+
+    #[tokio::test]
+    async fn test_handle_revocation_notification_v1() {
+        let sm = _receiver()
+            .handle_revocation_notification(_revocation_notification_v1(vec![]).into(), _send_message())
+            .await
+            .unwrap();
+        assert!(matches!(sm.state, ReceiverFullState::Finished(_)));
+    }
+
+    #[tokio::test]
+    async fn test_handle_revocation_notification_v1_fails_for_other_thread() {
+        let mut notification = _revocation_notification_v1(vec![]);
+        notification.content = RevokeV1Content::new("b8e0b9c5-5d5e-4f2c-9d7b-1a1a2c3e4f50".to_owned());
+
+        let err = _receiver()
+            .handle_revocation_notification(notification.into(), _send_message())
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidRevocationDetails);
+    }
+}
+
 // #[cfg(test)]
 // #[allow(clippy::unwrap_used)]
 // pub mod unit_tests {
@@ -1,25 +1,20 @@
-use messages::msg_fields::protocols::revocation::revoke::Revoke;
+use crate::protocols::revocation_notification::receiver::notification::RevocationNotification;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct NotificationReceivedState {
-    rev_msg: Revoke,
+    rev_msg: RevocationNotification,
 }
 
 impl NotificationReceivedState {
-    pub fn new(rev_msg: Revoke) -> Self {
+    pub fn new(rev_msg: RevocationNotification) -> Self {
         Self { rev_msg }
     }
 
-    pub fn get_notification(&self) -> Revoke {
+    pub fn get_notification(&self) -> RevocationNotification {
         self.rev_msg.clone()
     }
 
     pub fn get_thread_id(&self) -> String {
-        self.rev_msg
-            .decorators
-            .thread
-            .as_ref()
-            .map(|t| t.thid.clone())
-            .unwrap_or(self.rev_msg.id.clone())
+        self.rev_msg.thread_id()
     }
 }
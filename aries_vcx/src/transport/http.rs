@@ -0,0 +1,18 @@
+use async_trait::async_trait;
+use url::Url;
+
+use super::Transport;
+use crate::errors::error::VcxResult;
+
+/// [`Transport`] POSTing messages to the service endpoint over HTTP. Honours the agency mocks of
+/// [`agency_client::testing::mocking`], so messages sent in mocked tests don't reach the network.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HttpTransport;
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn send_message(&self, msg: Vec<u8>, service_endpoint: Url) -> VcxResult<()> {
+        agency_client::httpclient::post_message(msg, service_endpoint).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use url::Url;
+
+use super::{inbound::InboundTransport, Transport};
+use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+
+type Mailboxes = Arc<Mutex<HashMap<Url, VecDeque<Vec<u8>>>>>;
+
+/// In-process [`Transport`] that delivers messages into per-endpoint mailboxes
+/// instead of sending them over the network. The messages sent to an endpoint
+/// can be received through the [`InProcessEndpoint`] returned by [`InProcessTransport::endpoint`].
+///
+/// Cloning the transport shares the mailboxes, so agents living in the same process
+/// (e.g. in tests) can exchange messages through clones of the same instance.
+#[derive(Clone, Debug, Default)]
+pub struct InProcessTransport {
+    mailboxes: Mailboxes,
+}
+
+impl InProcessTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the [`InboundTransport`] receiving the messages sent to the given service endpoint.
+    pub fn endpoint(&self, service_endpoint: Url) -> InProcessEndpoint {
+        InProcessEndpoint {
+            service_endpoint,
+            mailboxes: self.mailboxes.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for InProcessTransport {
+    async fn send_message(&self, msg: Vec<u8>, service_endpoint: Url) -> VcxResult<()> {
+        lock_mailboxes(&self.mailboxes)?
+            .entry(service_endpoint)
+            .or_default()
+            .push_back(msg);
+        Ok(())
+    }
+}
+
+/// The receiving side of an [`InProcessTransport`] service endpoint.
+#[derive(Clone, Debug)]
+pub struct InProcessEndpoint {
+    service_endpoint: Url,
+    mailboxes: Mailboxes,
+}
+
+impl InProcessEndpoint {
+    pub fn service_endpoint(&self) -> &Url {
+        &self.service_endpoint
+    }
+}
+
+#[async_trait]
+impl InboundTransport for InProcessEndpoint {
+    async fn receive_message(&self) -> VcxResult<Option<Vec<u8>>> {
+        let msg = lock_mailboxes(&self.mailboxes)?
+            .get_mut(&self.service_endpoint)
+            .and_then(VecDeque::pop_front);
+        Ok(msg)
+    }
+}
+
+fn lock_mailboxes(mailboxes: &Mailboxes) -> VcxResult<MutexGuard<'_, HashMap<Url, VecDeque<Vec<u8>>>>> {
+    mailboxes.lock().map_err(|err| {
+        AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidState,
+            format!("In-process transport mailboxes are poisoned: {}", err),
+        )
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::{
+        msg_fields::protocols::trust_ping::ping::{Ping, PingContent, PingDecorators},
+        AriesMessage,
+    };
+
+    use super::*;
+    use crate::{
+        core::profile::profile::Profile,
+        protocols::connection::{pairwise_info::PairwiseInfo, Connection},
+        transport::inbound::InboundDispatcher,
+        utils::mockdata::profile::mock_profile::MockProfile,
+    };
+
+    #[tokio::test]
+    async fn test_in_process_transport_delivers_in_order() {
+        let transport = InProcessTransport::new();
+        let alice = transport.endpoint("http://alice.example".parse().unwrap());
+        let bob = transport.endpoint("http://bob.example".parse().unwrap());
+
+        transport
+            .send_message(b"first".to_vec(), alice.service_endpoint().clone())
+            .await
+            .unwrap();
+        transport
+            .send_message(b"second".to_vec(), alice.service_endpoint().clone())
+            .await
+            .unwrap();
+
+        assert_eq!(alice.receive_message().await.unwrap().unwrap(), b"first");
+        assert_eq!(alice.receive_message().await.unwrap().unwrap(), b"second");
+        assert!(alice.receive_message().await.unwrap().is_none());
+        assert!(bob.receive_message().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_in_process_transport_with_dispatcher() {
+        let transport = InProcessTransport::new();
+        let endpoint = transport.endpoint("http://alice.example".parse().unwrap());
+
+        let pairwise_info = PairwiseInfo {
+            pw_did: "did1".to_owned(),
+            pw_vk: "verkey1".to_owned(),
+        };
+        let mut dispatcher = InboundDispatcher::new(MockProfile.inject_wallet());
+        let con = Connection::new_inviter("con1".to_owned(), pairwise_info);
+        dispatcher.add_connection("con1".to_owned(), con.into());
+
+        let message: AriesMessage =
+            Ping::with_decorators("ping_id".to_owned(), PingContent::default(), PingDecorators::default()).into();
+        // The mock wallet returns the envelope as is when unpacking.
+        let envelope = json!({
+            "message": serde_json::to_string(&message).unwrap(),
+            "recipient_verkey": "verkey1",
+        });
+        transport
+            .send_message(envelope.to_string().into_bytes(), endpoint.service_endpoint().clone())
+            .await
+            .unwrap();
+
+        let inbound = dispatcher.receive(&endpoint).await.unwrap().unwrap();
+        assert_eq!(inbound.connection_id.as_deref(), Some("con1"));
+        assert_eq!(inbound.message, message);
+        assert!(inbound.sender_verkey.is_none());
+        assert!(dispatcher.receive(&endpoint).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,257 @@
+use std::{collections::HashMap, sync::Arc};
+
+use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use async_trait::async_trait;
+use messages::AriesMessage;
+
+use crate::{
+    errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult},
+    handlers::util::verify_thread_id,
+    protocols::connection::GenericConnection,
+};
+
+/// Trait used for implementing a mechanism to receive packed messages, the inbound counterpart
+/// of [`super::Transport`].
+#[async_trait]
+pub trait InboundTransport: Send + Sync {
+    /// Returns the next received envelope, or [`None`] if none is currently available.
+    async fn receive_message(&self) -> VcxResult<Option<Vec<u8>>>;
+}
+
+#[async_trait]
+impl<T: AsRef<dyn InboundTransport> + Send + Sync> InboundTransport for T {
+    async fn receive_message(&self) -> VcxResult<Option<Vec<u8>>> {
+        self.as_ref().receive_message().await
+    }
+}
+
+/// An unpacked inbound message, along with the id of the connection it was routed to.
+#[derive(Clone, Debug)]
+pub struct InboundMessage {
+    pub message: AriesMessage,
+    pub sender_verkey: Option<String>,
+    pub recipient_verkey: String,
+    pub connection_id: Option<String>,
+}
+
+/// The output of [`BaseWallet::unpack_message`].
+#[derive(Deserialize)]
+struct UnpackedEnvelope {
+    message: String,
+    recipient_verkey: String,
+    sender_verkey: Option<String>,
+}
+
+/// Unpacks inbound envelopes and routes the resulting [`AriesMessage`] to one of the
+/// registered [`GenericConnection`]s.
+///
+/// A message is routed to the connection whose pairwise verkey it was encrypted for.
+/// If there's no such connection, the message is routed by its thread id instead,
+/// which is needed for messages received on a key other than the pairwise one
+/// (e.g. a public invitation key).
+pub struct InboundDispatcher {
+    wallet: Arc<dyn BaseWallet>,
+    connections: HashMap<String, GenericConnection>,
+}
+
+impl InboundDispatcher {
+    pub fn new(wallet: Arc<dyn BaseWallet>) -> Self {
+        Self {
+            wallet,
+            connections: HashMap::new(),
+        }
+    }
+
+    /// Registers a connection under the given id, replacing and returning
+    /// the connection previously registered under it, if any.
+    pub fn add_connection(
+        &mut self,
+        connection_id: String,
+        connection: GenericConnection,
+    ) -> Option<GenericConnection> {
+        self.connections.insert(connection_id, connection)
+    }
+
+    pub fn remove_connection(&mut self, connection_id: &str) -> Option<GenericConnection> {
+        self.connections.remove(connection_id)
+    }
+
+    pub fn get_connection(&self, connection_id: &str) -> Option<&GenericConnection> {
+        self.connections.get(connection_id)
+    }
+
+    /// Receives the next envelope from the transport, if any, and dispatches it.
+    pub async fn receive<T>(&self, transport: &T) -> VcxResult<Option<InboundMessage>>
+    where
+        T: InboundTransport,
+    {
+        match transport.receive_message().await? {
+            Some(envelope) => self.dispatch(&envelope).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Unpacks the envelope and routes the contained message.
+    pub async fn dispatch(&self, envelope: &[u8]) -> VcxResult<InboundMessage> {
+        trace!(
+            "InboundDispatcher::dispatch >>> processing envelope of {} bytes",
+            envelope.len()
+        );
+
+        let unpacked = self.wallet.unpack_message(envelope).await?;
+        let UnpackedEnvelope {
+            message,
+            recipient_verkey,
+            sender_verkey,
+        } = serde_json::from_slice(&unpacked).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize unpacked envelope: {}", err),
+            )
+        })?;
+
+        let message: AriesMessage = serde_json::from_str(&message).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize A2A message: {}", err),
+            )
+        })?;
+
+        let connection_id = self.route(&recipient_verkey, &message).map(ToOwned::to_owned);
+
+        match &connection_id {
+            Some(id) => info!("InboundDispatcher::dispatch >>> message routed to connection {}", id),
+            None => warn!(
+                "InboundDispatcher::dispatch >>> no connection found for message received on verkey {}",
+                recipient_verkey
+            ),
+        }
+
+        Ok(InboundMessage {
+            message,
+            sender_verkey,
+            recipient_verkey,
+            connection_id,
+        })
+    }
+
+    fn route(&self, recipient_verkey: &str, message: &AriesMessage) -> Option<&str> {
+        self.connections
+            .iter()
+            .find(|(_, con)| con.pairwise_info().pw_vk == recipient_verkey)
+            .or_else(|| {
+                self.connections.iter().find(|(_, con)| {
+                    con.thread_id()
+                        .map(|thread_id| verify_thread_id(thread_id, message).is_ok())
+                        .unwrap_or_default()
+                })
+            })
+            .map(|(id, _)| id.as_str())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use messages::{
+        decorators::thread::Thread,
+        msg_fields::protocols::trust_ping::ping::{Ping, PingContent, PingDecorators},
+    };
+
+    use super::*;
+    use crate::{
+        core::profile::profile::Profile,
+        protocols::connection::{pairwise_info::PairwiseInfo, Connection},
+        utils::mockdata::profile::mock_profile::MockProfile,
+    };
+
+    const THREAD_ID: &str = "test_thread_id";
+    const SENDER_VK: &str = "7Z9ZajGKvb6BMsZ9TBEqxMHktxGdts3FvAbKSJT5XgzK";
+
+    fn make_dispatcher() -> InboundDispatcher {
+        let mut dispatcher = InboundDispatcher::new(MockProfile.inject_wallet());
+
+        let pairwise_info = PairwiseInfo {
+            pw_did: "did1".to_owned(),
+            pw_vk: "verkey1".to_owned(),
+        };
+        let con = Connection::new_inviter("con1".to_owned(), pairwise_info);
+        dispatcher.add_connection("con1".to_owned(), con.into());
+
+        let pairwise_info = PairwiseInfo {
+            pw_did: "did2".to_owned(),
+            pw_vk: "verkey2".to_owned(),
+        };
+        let con = Connection::new_inviter("con2".to_owned(), pairwise_info).into_invited(THREAD_ID);
+        dispatcher.add_connection("con2".to_owned(), con.into());
+
+        dispatcher
+    }
+
+    fn make_ping(thread_id: Option<&str>) -> AriesMessage {
+        let decorators = PingDecorators {
+            thread: thread_id.map(|thid| Thread::new(thid.to_owned())),
+            timing: None,
+        };
+        Ping::with_decorators("ping_id".to_owned(), PingContent::default(), decorators).into()
+    }
+
+    // The mock wallet returns the envelope as is when unpacking.
+    fn make_envelope(message: &AriesMessage, recipient_verkey: &str) -> Vec<u8> {
+        json!({
+            "message": serde_json::to_string(message).unwrap(),
+            "recipient_verkey": recipient_verkey,
+            "sender_verkey": SENDER_VK,
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_routes_by_verkey() {
+        let dispatcher = make_dispatcher();
+        let message = make_ping(Some(THREAD_ID));
+
+        let inbound = dispatcher.dispatch(&make_envelope(&message, "verkey1")).await.unwrap();
+
+        assert_eq!(inbound.connection_id.as_deref(), Some("con1"));
+        assert_eq!(inbound.sender_verkey.as_deref(), Some(SENDER_VK));
+        assert_eq!(inbound.recipient_verkey, "verkey1");
+        assert_eq!(inbound.message, message);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_routes_by_thread_id() {
+        let dispatcher = make_dispatcher();
+        let message = make_ping(Some(THREAD_ID));
+
+        let inbound = dispatcher
+            .dispatch(&make_envelope(&message, "unknown_verkey"))
+            .await
+            .unwrap();
+
+        assert_eq!(inbound.connection_id.as_deref(), Some("con2"));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_unrouted_message() {
+        let dispatcher = make_dispatcher();
+        let message = make_ping(None);
+
+        let inbound = dispatcher
+            .dispatch(&make_envelope(&message, "unknown_verkey"))
+            .await
+            .unwrap();
+
+        assert!(inbound.connection_id.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_invalid_envelope() {
+        let dispatcher = make_dispatcher();
+
+        let err = dispatcher.dispatch(b"not an envelope").await.unwrap_err();
+
+        assert_eq!(err.kind(), AriesVcxErrorKind::InvalidJson);
+    }
+}
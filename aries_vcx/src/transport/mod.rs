@@ -1,3 +1,7 @@
+pub mod http;
+pub mod in_process;
+pub mod inbound;
+
 use async_trait::async_trait;
 use url::Url;
 
@@ -111,6 +111,14 @@ impl EncryptionEnvelope {
             payload.len()
         );
 
+        let (msg_string, sender_vk, _) = Self::_unpack_a2a_message_with_recipient(wallet, payload).await?;
+        Ok((msg_string, sender_vk))
+    }
+
+    async fn _unpack_a2a_message_with_recipient(
+        wallet: &Arc<dyn BaseWallet>,
+        payload: Vec<u8>,
+    ) -> VcxResult<(String, Option<String>, Option<String>)> {
         let unpacked_msg = wallet.unpack_message(&payload).await?;
 
         let msg_value: serde_json::Value = serde_json::from_slice(unpacked_msg.as_slice()).map_err(|err| {
@@ -122,6 +130,8 @@ impl EncryptionEnvelope {
 
         let sender_vk = msg_value["sender_verkey"].as_str().map(String::from);
 
+        let recipient_vk = msg_value["recipient_verkey"].as_str().map(String::from);
+
         let msg_string = msg_value["message"]
             .as_str()
             .ok_or(AriesVcxError::from_msg(
@@ -130,7 +140,7 @@ impl EncryptionEnvelope {
             ))?
             .to_string();
 
-        Ok((msg_string, sender_vk))
+        Ok((msg_string, sender_vk, recipient_vk))
     }
 
     // todo: we should use auth_unpack wherever possible
@@ -160,6 +170,33 @@ impl EncryptionEnvelope {
         Ok((a2a_message, sender_vk))
     }
 
+    /// Unpacks a message without authenticating its sender, returning it along with the
+    /// verkey it was packed for and the sender verkey, if it was authcrypted.
+    ///
+    /// Used when messages for several connections arrive through a single channel, e.g.
+    /// when picking them up from a mediator, to find out which connection they belong to.
+    pub async fn unpack_with_recipient(
+        wallet: &Arc<dyn BaseWallet>,
+        payload: Vec<u8>,
+    ) -> VcxResult<(AriesMessage, String, Option<String>)> {
+        trace!(
+            "EncryptionEnvelope::unpack_with_recipient >>> processing payload of {} bytes",
+            payload.len()
+        );
+        let (message, sender_vk, recipient_vk) = Self::_unpack_a2a_message_with_recipient(wallet, payload).await?;
+        let recipient_vk = recipient_vk.ok_or(AriesVcxError::from_msg(
+            AriesVcxErrorKind::InvalidJson,
+            "Cannot find `recipient_verkey` field",
+        ))?;
+        let a2a_message = serde_json::from_str(&message).map_err(|err| {
+            AriesVcxError::from_msg(
+                AriesVcxErrorKind::InvalidJson,
+                format!("Cannot deserialize A2A message: {}", err),
+            )
+        })?;
+        Ok((a2a_message, recipient_vk, sender_vk))
+    }
+
     pub async fn auth_unpack(
         wallet: &Arc<dyn BaseWallet>,
         payload: Vec<u8>,
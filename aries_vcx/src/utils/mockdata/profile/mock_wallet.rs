@@ -1,6 +1,8 @@
 use aries_vcx_core::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
 use aries_vcx_core::utils::async_fn_iterator::AsyncFnIterator;
 use aries_vcx_core::wallet::base_wallet::BaseWallet;
+use aries_vcx_core::wallet::export_import::ArchiveDid;
+use aries_vcx_core::wallet::transaction::{TransactionLock, WalletTransaction};
 use async_trait::async_trait;
 
 use crate::utils::{self};
@@ -34,6 +36,18 @@ impl BaseWallet for MockWallet {
         Ok(())
     }
 
+    async fn export_dids(&self) -> VcxCoreResult<Vec<ArchiveDid>> {
+        Ok(vec![])
+    }
+
+    async fn import_did(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        Ok(())
+    }
+
+    async fn check_did_seed(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        Ok(())
+    }
+
     async fn add_wallet_record(
         &self,
         xtype: &str,
@@ -90,6 +104,14 @@ impl BaseWallet for MockWallet {
         ))
     }
 
+    async fn begin_transaction(&self) -> VcxCoreResult<WalletTransaction> {
+        Ok(TransactionLock::default().begin_transaction().await)
+    }
+
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        Ok(())
+    }
+
     async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         Ok(Vec::from(msg))
     }
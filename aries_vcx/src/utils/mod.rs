@@ -6,6 +6,7 @@ use aries_vcx_core::wallet::base_wallet::BaseWallet;
 use diddoc_legacy::aries::diddoc::AriesDidDoc;
 
 use crate::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
+use crate::transport::{http::HttpTransport, Transport};
 use crate::utils::encryption_envelope::EncryptionEnvelope;
 use messages::AriesMessage;
 
@@ -75,15 +76,14 @@ pub async fn send_message(
     let EncryptionEnvelope(envelope) =
         EncryptionEnvelope::create(&wallet, &message, Some(&sender_verkey), &did_doc).await?;
 
-    // TODO: Extract from agency client
-    agency_client::httpclient::post_message(
-        envelope,
-        did_doc
-            .get_endpoint()
-            .ok_or_else(|| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc"))?,
-    )
-    .await?;
-    Ok(())
+    HttpTransport
+        .send_message(
+            envelope,
+            did_doc
+                .get_endpoint()
+                .ok_or_else(|| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc"))?,
+        )
+        .await
 }
 
 pub async fn send_message_anonymously(
@@ -98,12 +98,12 @@ pub async fn send_message_anonymously(
     );
     let EncryptionEnvelope(envelope) = EncryptionEnvelope::create(&wallet, message, None, did_doc).await?;
 
-    agency_client::httpclient::post_message(
-        envelope,
-        did_doc
-            .get_endpoint()
-            .ok_or_else(|| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc"))?,
-    )
-    .await?;
-    Ok(())
+    HttpTransport
+        .send_message(
+            envelope,
+            did_doc
+                .get_endpoint()
+                .ok_or_else(|| AriesVcxError::from_msg(AriesVcxErrorKind::InvalidUrl, "No URL in DID Doc"))?,
+        )
+        .await
 }
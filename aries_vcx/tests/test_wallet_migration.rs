@@ -0,0 +1,80 @@
+#[macro_use]
+extern crate log;
+#[macro_use]
+extern crate serde_json;
+
+pub mod utils;
+
+#[cfg(test)]
+#[cfg(feature = "mixed_breed")]
+mod integration_tests {
+    use std::sync::Arc;
+
+    use aries_vcx::core::migration::{WalletMigrator, INDY_DID, INDY_KEY, INDY_MASTER_SECRET};
+    use aries_vcx::global::settings;
+    use aries_vcx::utils::devsetup::setup_issuer_wallet;
+    use aries_vcx_core::indy::wallet::{create_and_open_wallet, WalletConfig};
+    use aries_vcx_core::wallet::base_wallet::BaseWallet;
+    use aries_vcx_core::wallet::indy_wallet::IndySdkWallet;
+
+    async fn create_target_wallet() -> Arc<dyn BaseWallet> {
+        let config_wallet = WalletConfig {
+            wallet_name: format!("migration_target_wallet_{}", uuid::Uuid::new_v4()),
+            wallet_key: settings::DEFAULT_WALLET_KEY.into(),
+            wallet_key_derivation: settings::WALLET_KDF_RAW.into(),
+            wallet_type: None,
+            storage_config: None,
+            storage_credentials: None,
+            rekey: None,
+            rekey_derivation_method: None,
+        };
+        let wallet_handle = create_and_open_wallet(&config_wallet).await.unwrap();
+        Arc::new(IndySdkWallet::new(wallet_handle))
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_wallet_migration_is_resumable() {
+        let (institution_did, wallet_handle) = setup_issuer_wallet().await;
+        let source: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(wallet_handle));
+        let target = create_target_wallet().await;
+        let migrator = WalletMigrator::new(IndySdkWallet::new(wallet_handle), Arc::clone(&target));
+
+        let report = migrator.migrate().await.unwrap();
+        assert!(report.is_complete(), "{:?}", report.failed);
+        assert!(
+            report
+                .migrated
+                .iter()
+                .any(|record| record.record_type == INDY_MASTER_SECRET
+                    && record.id == settings::DEFAULT_LINK_SECRET_ALIAS)
+        );
+        assert!(report
+            .migrated
+            .iter()
+            .any(|record| record.record_type == INDY_DID && record.id == institution_did));
+        assert_eq!(
+            target.key_for_local_did(&institution_did).await.unwrap(),
+            source.key_for_local_did(&institution_did).await.unwrap()
+        );
+
+        let rerun_report = migrator.migrate().await.unwrap();
+        assert!(rerun_report.is_complete(), "{:?}", rerun_report.failed);
+        assert!(rerun_report.migrated.is_empty());
+        assert_eq!(rerun_report.skipped, report.migrated);
+    }
+
+    #[tokio::test]
+    #[ignore]
+    async fn test_indy_records_are_not_searchable_through_base_wallet() {
+        let (_institution_did, wallet_handle) = setup_issuer_wallet().await;
+        let wallet: Arc<dyn BaseWallet> = Arc::new(IndySdkWallet::new(wallet_handle));
+
+        assert!(wallet.iterate_wallet_records(INDY_KEY, "{}", "{}").await.is_err());
+        assert!(!IndySdkWallet::new(wallet_handle)
+            .get_all_records_for_migration(INDY_KEY)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+}
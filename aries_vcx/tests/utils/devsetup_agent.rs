@@ -740,8 +740,9 @@ pub mod test_utils {
             let rev_reg_id = self.credential.get_rev_reg_id().unwrap();
             let cred_rev_id = self.credential.get_cred_rev_id(&self.profile).await.unwrap();
             let send_message = self.connection.send_message_closure(&self.profile).await.unwrap();
-            let rev_not_receiver = RevocationNotificationReceiver::build(rev_reg_id, cred_rev_id)
-                .handle_revocation_notification(rev_not, send_message)
+            let thread_id = self.credential.get_thread_id().unwrap();
+            let rev_not_receiver = RevocationNotificationReceiver::build(rev_reg_id, cred_rev_id, thread_id)
+                .handle_revocation_notification(rev_not.into(), send_message)
                 .await
                 .unwrap();
             self.rev_not_receiver = Some(rev_not_receiver);
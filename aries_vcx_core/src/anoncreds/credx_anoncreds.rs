@@ -11,7 +11,7 @@ use crate::utils::{
 use crate::wallet::base_wallet::AsyncFnIteratorCollect;
 use crate::{
     errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult},
-    wallet::base_wallet::BaseWallet,
+    wallet::{base_wallet::BaseWallet, transaction::WalletTransaction},
 };
 
 use async_trait::async_trait;
@@ -36,9 +36,9 @@ use uuid::Uuid;
 
 use super::base_anoncreds::BaseAnonCreds;
 
-const CATEGORY_LINK_SECRET: &str = "VCX_LINK_SECRET";
+pub const CATEGORY_LINK_SECRET: &str = "VCX_LINK_SECRET";
 
-const CATEGORY_CREDENTIAL: &str = "VCX_CREDENTIAL";
+pub const CATEGORY_CREDENTIAL: &str = "VCX_CREDENTIAL";
 const CATEGORY_CRED_DEF: &str = "VCX_CRED_DEF";
 const CATEGORY_CRED_KEY_CORRECTNESS_PROOF: &str = "VCX_CRED_KEY_CORRECTNESS_PROOF";
 const CATEGORY_CRED_DEF_PRIV: &str = "VCX_CRED_DEF_PRIV";
@@ -53,6 +53,22 @@ const CATEGORY_REV_REG_INFO: &str = "VCX_REV_REG_INFO";
 const CATEGORY_REV_REG_DEF: &str = "VCX_REV_REG_DEF";
 const CATEGORY_REV_REG_DEF_PRIV: &str = "VCX_REV_REG_DEF_PRIV";
 
+/// All the record categories used by [`IndyCredxAnonCreds`], e.g. to export its records.
+pub const RECORD_CATEGORIES: &[&str] = &[
+    CATEGORY_LINK_SECRET,
+    CATEGORY_CREDENTIAL,
+    CATEGORY_CRED_DEF,
+    CATEGORY_CRED_KEY_CORRECTNESS_PROOF,
+    CATEGORY_CRED_DEF_PRIV,
+    CATEGORY_CRED_SCHEMA,
+    CATEGORY_CRED_MAP_SCHEMA_ID,
+    CATEGORY_REV_REG,
+    CATEGORY_REV_REG_DELTA,
+    CATEGORY_REV_REG_INFO,
+    CATEGORY_REV_REG_DEF,
+    CATEGORY_REV_REG_DEF_PRIV,
+];
+
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct RevocationRegistryInfo {
     pub id: RevocationRegistryId,
@@ -78,6 +94,21 @@ impl IndyCredxAnonCreds {
         serde_json::from_str(&str_record).map_err(From::from)
     }
 
+    async fn get_transaction_record_value<T>(
+        &self,
+        transaction: &WalletTransaction,
+        category: &str,
+        id: &str,
+    ) -> VcxCoreResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        let str_record = transaction
+            .get_wallet_record_value(self.wallet.as_ref(), category, id)
+            .await?;
+        serde_json::from_str(&str_record).map_err(From::from)
+    }
+
     async fn get_link_secret(&self, link_secret_id: &str) -> VcxCoreResult<MasterSecret> {
         let record = self
             .wallet
@@ -170,6 +201,94 @@ impl IndyCredxAnonCreds {
 
         self._get_credentials(&wql_query).await
     }
+
+    /// Stores an already processed credential under the given id, e.g. when importing it from another wallet.
+    pub async fn prover_import_credential(&self, cred_id: &str, cred_json: &str) -> VcxCoreResult<String> {
+        let credential: CredxCredential = serde_json::from_str(cred_json)?;
+        self.store_credential(Some(cred_id), &credential).await
+    }
+
+    /// Stores a link secret given as a decimal string, e.g. when importing it from another wallet.
+    pub async fn prover_import_link_secret(&self, link_secret_id: &str, ms_decimal: &str) -> VcxCoreResult<()> {
+        BigNumber::from_dec(ms_decimal).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::UrsaError,
+                format!("Failed to create BigNumber, UrsaErrorKind: {}", err.kind()),
+            )
+        })?;
+
+        self.store_link_secret(link_secret_id, ms_decimal).await
+    }
+
+    async fn store_credential(&self, cred_id: Option<&str>, credential: &CredxCredential) -> VcxCoreResult<String> {
+        let schema_id = &credential.schema_id;
+        let (_schema_method, schema_issuer_did, schema_name, schema_version) =
+            schema_id.parts().ok_or(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidSchema,
+                "Could not process credential.schema_id as parts.",
+            ))?;
+
+        let cred_def_id = &credential.cred_def_id;
+        let (_cred_def_method, issuer_did, _signature_type, _schema_id, _tag) =
+            cred_def_id.parts().ok_or(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidSchema,
+                "Could not process credential.cred_def_id as parts.",
+            ))?;
+
+        let mut tags = json!({
+            "schema_id": schema_id.0,
+            "schema_issuer_did": schema_issuer_did.0,
+            "schema_name": schema_name,
+            "schema_version": schema_version,
+            "issuer_did": issuer_did.0,
+            "cred_def_id": cred_def_id.0
+        });
+
+        if let Some(rev_reg_id) = &credential.rev_reg_id {
+            tags["rev_reg_id"] = serde_json::Value::String(rev_reg_id.0.to_string())
+        }
+
+        for (raw_attr_name, attr_value) in credential.values.0.iter() {
+            let attr_name = _normalize_attr_name(raw_attr_name);
+            // add attribute name and raw value pair
+            let value_tag_name = _format_attribute_as_value_tag_name(&attr_name);
+            tags[value_tag_name] = Value::String(attr_value.raw.to_string());
+
+            // add attribute name and marker (used for checking existent)
+            let marker_tag_name = _format_attribute_as_marker_tag_name(&attr_name);
+            tags[marker_tag_name] = Value::String("1".to_string());
+        }
+
+        let credential_id = cred_id.map_or(Uuid::new_v4().to_string(), String::from);
+
+        let record_value = serde_json::to_string(&credential)?;
+        let tags_json: HashMap<String, String> = serde_json::from_value(tags)?;
+
+        self.wallet
+            .add_wallet_record(CATEGORY_CREDENTIAL, &credential_id, &record_value, Some(tags_json))
+            .await?;
+
+        Ok(credential_id)
+    }
+
+    async fn store_link_secret(&self, link_secret_id: &str, ms_decimal: &str) -> VcxCoreResult<()> {
+        let existing_record = self
+            .wallet
+            .get_wallet_record(CATEGORY_LINK_SECRET, link_secret_id, "{}")
+            .await
+            .ok(); // ignore error, as we only care about whether it exists or not
+
+        if existing_record.is_some() {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::DuplicationMasterSecret,
+                format!("Master secret id: {} already exists in wallet.", link_secret_id),
+            ));
+        }
+
+        self.wallet
+            .add_wallet_record(CATEGORY_LINK_SECRET, link_secret_id, ms_decimal, None)
+            .await
+    }
 }
 
 #[async_trait]
@@ -256,28 +375,16 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
         };
 
         let str_rev_reg_info = serde_json::to_string(&rev_reg_info)?;
-
-        self.wallet
-            .add_wallet_record(CATEGORY_REV_REG_INFO, &rev_reg_id.0, &str_rev_reg_info, None)
-            .await?;
-
         let str_rev_reg_def = serde_json::to_string(&rev_reg_def)?;
-
-        self.wallet
-            .add_wallet_record(CATEGORY_REV_REG_DEF, &rev_reg_id.0, &str_rev_reg_def, None)
-            .await?;
-
         let str_rev_reg_def_priv = serde_json::to_string(&rev_reg_def_priv)?;
-
-        self.wallet
-            .add_wallet_record(CATEGORY_REV_REG_DEF_PRIV, &rev_reg_id.0, &str_rev_reg_def_priv, None)
-            .await?;
-
         let str_rev_reg = serde_json::to_string(&rev_reg)?;
 
-        self.wallet
-            .add_wallet_record(CATEGORY_REV_REG, &rev_reg_id.0, &str_rev_reg, None)
-            .await?;
+        let mut transaction = self.wallet.begin_transaction().await?;
+        transaction.add_wallet_record(CATEGORY_REV_REG_INFO, &rev_reg_id.0, &str_rev_reg_info, None);
+        transaction.add_wallet_record(CATEGORY_REV_REG_DEF, &rev_reg_id.0, &str_rev_reg_def, None);
+        transaction.add_wallet_record(CATEGORY_REV_REG_DEF_PRIV, &rev_reg_id.0, &str_rev_reg_def_priv, None);
+        transaction.add_wallet_record(CATEGORY_REV_REG, &rev_reg_id.0, &str_rev_reg, None);
+        self.wallet.commit_transaction(transaction).await?;
 
         Ok((rev_reg_id.0, str_rev_reg_def, str_rev_reg))
     }
@@ -312,43 +419,32 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
             credx::issuer::create_credential_definition(&issuer_did, &schema, tag, sig_type, config)?;
 
         let str_cred_def = serde_json::to_string(&cred_def)?;
-
-        // Store stuff in wallet
-        self.wallet
-            .add_wallet_record(CATEGORY_CRED_DEF, &cred_def_id.0, &str_cred_def, None)
-            .await?;
-
         let str_cred_def_priv = serde_json::to_string(&cred_def_priv)?;
-
-        self.wallet
-            .add_wallet_record(CATEGORY_CRED_DEF_PRIV, &cred_def_id.0, &str_cred_def_priv, None)
-            .await?;
-
         let str_cred_key_proof = serde_json::to_string(&cred_key_correctness_proof)?;
+        let str_schema_id = serde_json::to_string(schema.id())?;
 
-        self.wallet
-            .add_wallet_record(
-                CATEGORY_CRED_KEY_CORRECTNESS_PROOF,
-                &cred_def_id.0,
-                &str_cred_key_proof,
-                None,
-            )
-            .await?;
-
-        let store_schema_res = self
-            .wallet
-            .add_wallet_record(CATEGORY_CRED_SCHEMA, schema.id(), schema_json, None)
-            .await;
+        // Store stuff in wallet
+        let mut transaction = self.wallet.begin_transaction().await?;
+        transaction.add_wallet_record(CATEGORY_CRED_DEF, &cred_def_id.0, &str_cred_def, None);
+        transaction.add_wallet_record(CATEGORY_CRED_DEF_PRIV, &cred_def_id.0, &str_cred_def_priv, None);
+        transaction.add_wallet_record(
+            CATEGORY_CRED_KEY_CORRECTNESS_PROOF,
+            &cred_def_id.0,
+            &str_cred_key_proof,
+            None,
+        );
 
-        if let Err(e) = store_schema_res {
-            warn!("Storing schema {schema_json} failed - {e}. It's possible it is already stored.")
+        // The schema may already be stored by a previous cred def of the same schema
+        if transaction
+            .get_wallet_record_value(self.wallet.as_ref(), CATEGORY_CRED_SCHEMA, schema.id())
+            .await
+            .is_err()
+        {
+            transaction.add_wallet_record(CATEGORY_CRED_SCHEMA, schema.id(), schema_json, None);
         }
 
-        let str_schema_id = serde_json::to_string(schema.id())?;
-
-        self.wallet
-            .add_wallet_record(CATEGORY_CRED_MAP_SCHEMA_ID, &cred_def_id.0, &str_schema_id, None)
-            .await?;
+        transaction.add_wallet_record(CATEGORY_CRED_MAP_SCHEMA_ID, &cred_def_id.0, &str_schema_id, None);
+        self.wallet.commit_transaction(transaction).await?;
 
         // Return the ID and the cred def
         Ok((cred_def_id.0.to_owned(), str_cred_def))
@@ -391,6 +487,9 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
             .get_wallet_record_value(CATEGORY_CRED_DEF_PRIV, cred_def_id)
             .await?;
 
+        // The revocation registry is read and updated within the same transaction
+        let mut transaction = self.wallet.begin_transaction().await?;
+
         let mut revocation_config_parts = match (tails_dir, &rev_reg_id) {
             (Some(tails_dir), Some(rev_reg_id)) => {
                 let rev_reg_def = self.get_wallet_record_value(CATEGORY_REV_REG_DEF, rev_reg_id).await?;
@@ -399,9 +498,12 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
                     .get_wallet_record_value(CATEGORY_REV_REG_DEF_PRIV, rev_reg_id)
                     .await?;
 
-                let rev_reg = self.get_wallet_record_value(CATEGORY_REV_REG, rev_reg_id).await?;
-                let rev_reg_info: RevocationRegistryInfo =
-                    self.get_wallet_record_value(CATEGORY_REV_REG_INFO, rev_reg_id).await?;
+                let rev_reg = self
+                    .get_transaction_record_value(&transaction, CATEGORY_REV_REG, rev_reg_id)
+                    .await?;
+                let rev_reg_info: RevocationRegistryInfo = self
+                    .get_transaction_record_value(&transaction, CATEGORY_REV_REG_INFO, rev_reg_id)
+                    .await?;
 
                 Some((rev_reg_def, rev_reg_def_priv, rev_reg, rev_reg_info, tails_dir))
             }
@@ -477,16 +579,13 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
             let cred_rev_id = rev_reg_info.curr_id.to_string();
             let str_rev_reg_info = serde_json::to_string(&rev_reg_info)?;
 
-            self.wallet
-                .update_wallet_record_value(CATEGORY_REV_REG, &rev_reg_id, str_rev_reg)
-                .await?;
-
-            self.wallet
-                .update_wallet_record_value(CATEGORY_REV_REG_INFO, &rev_reg_id, &str_rev_reg_info)
-                .await?;
+            transaction.update_wallet_record_value(CATEGORY_REV_REG, &rev_reg_id, str_rev_reg);
+            transaction.update_wallet_record_value(CATEGORY_REV_REG_INFO, &rev_reg_id, &str_rev_reg_info);
+            self.wallet.commit_transaction(transaction).await?;
 
             Some(cred_rev_id)
         } else {
+            self.wallet.rollback_transaction(transaction).await?;
             None
         };
 
@@ -839,70 +938,10 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
             rev_reg_def.as_ref(),
         )?;
 
-        let schema_id = &credential.schema_id;
-        let (_schema_method, schema_issuer_did, schema_name, schema_version) =
-            schema_id.parts().ok_or(AriesVcxCoreError::from_msg(
-                AriesVcxCoreErrorKind::InvalidSchema,
-                "Could not process credential.schema_id as parts.",
-            ))?;
-
-        let cred_def_id = &credential.cred_def_id;
-        let (_cred_def_method, issuer_did, _signature_type, _schema_id, _tag) =
-            cred_def_id.parts().ok_or(AriesVcxCoreError::from_msg(
-                AriesVcxCoreErrorKind::InvalidSchema,
-                "Could not process credential.cred_def_id as parts.",
-            ))?;
-
-        let mut tags = json!({
-            "schema_id": schema_id.0,
-            "schema_issuer_did": schema_issuer_did.0,
-            "schema_name": schema_name,
-            "schema_version": schema_version,
-            "issuer_did": issuer_did.0,
-            "cred_def_id": cred_def_id.0
-        });
-
-        if let Some(rev_reg_id) = &credential.rev_reg_id {
-            tags["rev_reg_id"] = serde_json::Value::String(rev_reg_id.0.to_string())
-        }
-
-        for (raw_attr_name, attr_value) in credential.values.0.iter() {
-            let attr_name = _normalize_attr_name(raw_attr_name);
-            // add attribute name and raw value pair
-            let value_tag_name = _format_attribute_as_value_tag_name(&attr_name);
-            tags[value_tag_name] = Value::String(attr_value.raw.to_string());
-
-            // add attribute name and marker (used for checking existent)
-            let marker_tag_name = _format_attribute_as_marker_tag_name(&attr_name);
-            tags[marker_tag_name] = Value::String("1".to_string());
-        }
-
-        let credential_id = cred_id.map_or(Uuid::new_v4().to_string(), String::from);
-
-        let record_value = serde_json::to_string(&credential)?;
-        let tags_json: HashMap<String, String> = serde_json::from_value(tags)?;
-
-        self.wallet
-            .add_wallet_record(CATEGORY_CREDENTIAL, &credential_id, &record_value, Some(tags_json))
-            .await?;
-
-        Ok(credential_id)
+        self.store_credential(cred_id, &credential).await
     }
 
     async fn prover_create_link_secret(&self, link_secret_id: &str) -> VcxCoreResult<String> {
-        let existing_record = self
-            .wallet
-            .get_wallet_record(CATEGORY_LINK_SECRET, link_secret_id, "{}")
-            .await
-            .ok(); // ignore error, as we only care about whether it exists or not
-
-        if existing_record.is_some() {
-            return Err(AriesVcxCoreError::from_msg(
-                AriesVcxCoreErrorKind::DuplicationMasterSecret,
-                format!("Master secret id: {} already exists in wallet.", link_secret_id),
-            ));
-        }
-
         let secret = credx::prover::create_master_secret()?;
         let ms_decimal = secret
             .value
@@ -927,9 +966,7 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
                 )
             })?;
 
-        self.wallet
-            .add_wallet_record(CATEGORY_LINK_SECRET, link_secret_id, &ms_decimal, None)
-            .await?;
+        self.store_link_secret(link_secret_id, &ms_decimal).await?;
 
         return Ok(link_secret_id.to_string());
     }
@@ -964,7 +1001,11 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
             )
         })?;
 
-        let rev_reg = self.get_wallet_record_value(CATEGORY_REV_REG, rev_reg_id).await?;
+        let mut transaction = self.wallet.begin_transaction().await?;
+
+        let rev_reg = self
+            .get_transaction_record_value(&transaction, CATEGORY_REV_REG, rev_reg_id)
+            .await?;
 
         let rev_reg_def = self.get_wallet_record_value(CATEGORY_REV_REG_DEF, rev_reg_id).await?;
 
@@ -978,39 +1019,28 @@ impl BaseAnonCreds for IndyCredxAnonCreds {
         let (rev_reg, new_rev_reg_delta) =
             credx::issuer::revoke_credential(&rev_reg_def, &rev_reg, cred_rev_id, &tails_reader)?;
 
-        let old_str_rev_reg_delta = self.get_rev_reg_delta(rev_reg_id).await?;
+        let old_rev_reg_delta: Option<RevocationRegistryDelta> = self
+            .get_transaction_record_value(&transaction, CATEGORY_REV_REG_DELTA, rev_reg_id)
+            .await
+            .ok();
 
-        let rev_reg_delta = old_str_rev_reg_delta
+        let rev_reg_delta = old_rev_reg_delta
             .as_ref()
-            .map(|s| serde_json::from_str(s))
-            .transpose()?;
-
-        let rev_reg_delta = rev_reg_delta
-            .map(|rev_reg_delta| credx::issuer::merge_revocation_registry_deltas(&rev_reg_delta, &new_rev_reg_delta))
+            .map(|rev_reg_delta| credx::issuer::merge_revocation_registry_deltas(rev_reg_delta, &new_rev_reg_delta))
             .transpose()?
             .unwrap_or(new_rev_reg_delta);
 
         let str_rev_reg = serde_json::to_string(&rev_reg)?;
         let str_rev_reg_delta = serde_json::to_string(&rev_reg_delta)?;
 
-        self.wallet
-            .update_wallet_record_value(CATEGORY_REV_REG, rev_reg_id, &str_rev_reg)
-            .await?;
+        transaction.update_wallet_record_value(CATEGORY_REV_REG, rev_reg_id, &str_rev_reg);
 
-        match old_str_rev_reg_delta {
-            Some(_) => {
-                self.wallet
-                    .update_wallet_record_value(CATEGORY_REV_REG_DELTA, rev_reg_id, &str_rev_reg_delta)
-                    .await?
-            }
-            None => {
-                self.wallet
-                    .add_wallet_record(CATEGORY_REV_REG_DELTA, rev_reg_id, &str_rev_reg_delta, None)
-                    .await?
-            }
+        match old_rev_reg_delta {
+            Some(_) => transaction.update_wallet_record_value(CATEGORY_REV_REG_DELTA, rev_reg_id, &str_rev_reg_delta),
+            None => transaction.add_wallet_record(CATEGORY_REV_REG_DELTA, rev_reg_id, &str_rev_reg_delta, None),
         }
 
-        Ok(())
+        self.wallet.commit_transaction(transaction).await
     }
 
     async fn get_rev_reg_delta(&self, rev_reg_id: &str) -> VcxCoreResult<Option<String>> {
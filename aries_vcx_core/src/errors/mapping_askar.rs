@@ -0,0 +1,18 @@
+use aries_askar::{Error as AskarError, ErrorKind as AskarErrorKind};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind};
+
+impl From<AskarError> for AriesVcxCoreError {
+    fn from(err: AskarError) -> Self {
+        match err.kind() {
+            AskarErrorKind::Duplicate => {
+                AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::DuplicationWalletRecord, err)
+            }
+            AskarErrorKind::NotFound => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::WalletRecordNotFound, err),
+            AskarErrorKind::Input => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidInput, err),
+            AskarErrorKind::Encryption => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::WalletAccessFailed, err),
+            AskarErrorKind::Unsupported => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::ActionNotSupported, err),
+            _ => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::UnknownError, err),
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::io;
 use std::sync::PoisonError;
 
 use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind};
@@ -13,3 +14,9 @@ impl<T> From<PoisonError<T>> for AriesVcxCoreError {
         AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidState, err.to_string())
     }
 }
+
+impl From<io::Error> for AriesVcxCoreError {
+    fn from(err: io::Error) -> Self {
+        AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::IOError, err.to_string())
+    }
+}
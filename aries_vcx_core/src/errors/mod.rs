@@ -1,5 +1,7 @@
 pub mod error;
 mod mapping_agency_client;
+#[cfg(any(feature = "askar_wallet", feature = "in_memory_wallet"))]
+mod mapping_askar;
 #[cfg(any(feature = "modular_libs", feature = "vdr_proxy_ledger"))]
 mod mapping_credx;
 #[cfg(any(feature = "modular_libs", feature = "vdr_proxy_ledger"))]
@@ -1,10 +1,31 @@
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{PublicKey, SecretKey};
+use serde::{de::DeserializeOwned, Deserialize};
 use vdrtools::{DidMethod, DidValue, KeyInfo, Locator, MyDidInfo};
 
 use crate::errors::error::prelude::*;
 use crate::global::settings;
 use crate::indy::utils::mocks::did_mocks::{did_mocks_enabled, DidMocks};
+use crate::wallet::export_import::ArchiveDid;
 use crate::{utils, WalletHandle};
 
+/// Length of the Ed25519 seeds, making up the first half of the signing keys stored by vdrtools.
+const SEED_LENGTH: usize = 32;
+
+#[derive(Deserialize)]
+struct DidRecord {
+    did: String,
+    verkey: String,
+}
+
+#[derive(Deserialize)]
+struct KeyRecord {
+    verkey: String,
+    signkey: String,
+}
+
 pub async fn create_and_store_my_did(
     wallet_handle: WalletHandle,
     seed: Option<&str>,
@@ -76,3 +97,117 @@ pub async fn get_verkey_from_wallet(wallet_handle: WalletHandle, did: &str) -> V
 
     Ok(res)
 }
+
+pub async fn export_my_dids(wallet_handle: WalletHandle) -> VcxCoreResult<Vec<ArchiveDid>> {
+    trace!("export_my_dids >>>");
+
+    let mut seeds = HashMap::new();
+    for key in get_indy_records::<KeyRecord>(wallet_handle, "Indy::Key").await? {
+        let signkey = bs58::decode(&key.signkey).into_vec().map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidState,
+                format!("Signing key of verkey {} is not valid base58: {}", key.verkey, err),
+            )
+        })?;
+        if signkey.len() < SEED_LENGTH {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidState,
+                format!("Signing key of verkey {} is too short", key.verkey),
+            ));
+        }
+        seeds.insert(key.verkey, STANDARD.encode(&signkey[..SEED_LENGTH]));
+    }
+
+    get_indy_records::<DidRecord>(wallet_handle, "Indy::Did")
+        .await?
+        .into_iter()
+        .map(|did| {
+            let seed = seeds.remove(&did.verkey).ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::WalletRecordNotFound,
+                    format!("Key {} of DID {} not found in the wallet", did.verkey, did.did),
+                )
+            })?;
+            Ok(ArchiveDid {
+                did: did.did,
+                verkey: did.verkey,
+                seed,
+            })
+        })
+        .collect()
+}
+
+pub async fn import_my_did(wallet_handle: WalletHandle, did: &ArchiveDid) -> VcxCoreResult<()> {
+    trace!("import_my_did >>> did: {}", did.did);
+
+    let (_, verkey) = Locator::instance()
+        .did_controller
+        .create_and_store_my_did(
+            wallet_handle,
+            MyDidInfo {
+                did: Some(DidValue(did.did.clone())),
+                seed: Some(did.seed.clone()),
+                ..MyDidInfo::default()
+            },
+        )
+        .await?;
+
+    if verkey != did.verkey {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Seed of DID {} does not match its verkey {}", did.did, did.verkey),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Checks that the seed of an exported DID recreates its verkey, which [`import_my_did`] only
+/// notices once the DID is stored.
+pub fn check_archived_did_seed(did: &ArchiveDid) -> VcxCoreResult<()> {
+    // Seeds are accepted as by vdrtools: either raw or base64 encoded
+    let seed = if did.seed.len() == SEED_LENGTH {
+        did.seed.as_bytes().to_vec()
+    } else {
+        STANDARD.decode(&did.seed).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                format!("Seed of DID {} is not valid base64: {}", did.did, err),
+            )
+        })?
+    };
+    let secret = SecretKey::from_bytes(&seed).map_err(|_| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Seed of DID {} must be {} bytes long", did.did, SEED_LENGTH),
+        )
+    })?;
+
+    if bs58::encode(PublicKey::from(&secret).as_bytes()).into_string() != did.verkey {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Seed of DID {} does not match its verkey {}", did.did, did.verkey),
+        ));
+    }
+
+    Ok(())
+}
+
+// DIDs and keys are records managed by vdrtools itself, which the non-secrets API doesn't expose
+async fn get_indy_records<T: DeserializeOwned>(wallet_handle: WalletHandle, xtype: &str) -> VcxCoreResult<Vec<T>> {
+    Locator::instance()
+        .wallet_controller
+        .get_all_records_for_migration(wallet_handle, xtype)
+        .await?
+        .iter()
+        .map(|record| {
+            let value = record.get_value().ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidState,
+                    format!("Record {} of type {} has no value", record.get_id(), xtype),
+                )
+            })?;
+            Ok(serde_json::from_str(value)?)
+        })
+        .collect()
+}
@@ -1,11 +1,17 @@
 use std::sync::Arc;
 
-use crate::{secret, wallet::indy_wallet};
+use crate::{
+    secret,
+    wallet::{
+        indy_wallet,
+        transaction::{WalletOperation, WalletTransaction},
+    },
+};
 use serde::{Deserialize, Serialize};
 use vdrtools::{
     types::domain::wallet::{default_key_derivation_method, KeyDerivationMethod},
     types::errors::IndyErrorKind,
-    Locator, SecureEnclaveProvider,
+    Locator, SecureEnclaveProvider, WalletOperation as VdrtoolsWalletOperation,
 };
 
 use crate::{
@@ -447,6 +453,63 @@ pub(crate) async fn delete_wallet_record_tags(
     Ok(())
 }
 
+pub(crate) async fn apply_wallet_batch(
+    wallet_handle: WalletHandle,
+    transaction: WalletTransaction,
+) -> VcxCoreResult<()> {
+    trace!(
+        "apply_wallet_batch >>> operations: {:?}",
+        secret!(transaction.operations())
+    );
+
+    if settings::indy_mocks_enabled() {
+        return Ok(());
+    }
+
+    let operations = transaction
+        .into_operations()
+        .into_iter()
+        .map(|operation| match operation {
+            WalletOperation::AddRecord { xtype, id, value, tags } => VdrtoolsWalletOperation::AddRecord {
+                type_: xtype,
+                name: id,
+                value,
+                tags: tags.unwrap_or_default(),
+            },
+            WalletOperation::UpdateRecordValue { xtype, id, value } => VdrtoolsWalletOperation::UpdateRecordValue {
+                type_: xtype,
+                name: id,
+                value,
+            },
+            WalletOperation::AddRecordTags { xtype, id, tags } => VdrtoolsWalletOperation::AddRecordTags {
+                type_: xtype,
+                name: id,
+                tags,
+            },
+            WalletOperation::UpdateRecordTags { xtype, id, tags } => VdrtoolsWalletOperation::UpdateRecordTags {
+                type_: xtype,
+                name: id,
+                tags,
+            },
+            WalletOperation::DeleteRecordTags { xtype, id, tag_names } => VdrtoolsWalletOperation::DeleteRecordTags {
+                type_: xtype,
+                name: id,
+                tag_names,
+            },
+            WalletOperation::DeleteRecord { xtype, id } => {
+                VdrtoolsWalletOperation::DeleteRecord { type_: xtype, name: id }
+            }
+        })
+        .collect();
+
+    Locator::instance()
+        .non_secret_controller
+        .apply_batch(wallet_handle, operations)
+        .await?;
+
+    Ok(())
+}
+
 // TODO - FUTURE - revert to pub(crate) after libvcx dependency is fixed
 pub async fn open_search_wallet(
     wallet_handle: WalletHandle,
@@ -513,6 +576,25 @@ pub async fn close_search_wallet(search_handle: SearchHandle) -> VcxCoreResult<(
     Ok(())
 }
 
+// Unlike the search above, also returns the records managed by vdrtools itself (e.g. "Indy::Key"),
+// serialized the same way as the searched ones. Only used when migrating the wallet.
+pub(crate) async fn get_all_records_for_migration(
+    wallet_handle: WalletHandle,
+    xtype: &str,
+) -> VcxCoreResult<Vec<String>> {
+    trace!("get_all_records_for_migration >>> xtype: {}", secret!(&xtype));
+
+    let records = Locator::instance()
+        .wallet_controller
+        .get_all_records_for_migration(wallet_handle, xtype)
+        .await?;
+
+    records
+        .iter()
+        .map(|record| serde_json::to_string(record).map_err(AriesVcxCoreError::from))
+        .collect()
+}
+
 // TODO - FUTURE - can this be moved externally - move to a generic setup util?
 pub async fn wallet_configure_issuer(
     wallet_handle: WalletHandle,
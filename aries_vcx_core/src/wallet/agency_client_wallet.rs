@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::base_wallet::BaseWallet;
+use super::export_import::ArchiveDid;
+use super::transaction::WalletTransaction;
 use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
 use agency_client::errors::error::{AgencyClientError, AgencyClientErrorKind, AgencyClientResult};
 use agency_client::wallet::base_agency_client_wallet::BaseAgencyClientWallet;
@@ -38,6 +40,18 @@ impl BaseWallet for AgencyClientWallet {
         Err(unimplemented_agency_client_wallet_method("replace_did_key_apply"))
     }
 
+    async fn export_dids(&self) -> VcxCoreResult<Vec<ArchiveDid>> {
+        Err(unimplemented_agency_client_wallet_method("export_dids"))
+    }
+
+    async fn import_did(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        Err(unimplemented_agency_client_wallet_method("import_did"))
+    }
+
+    async fn check_did_seed(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        Err(unimplemented_agency_client_wallet_method("check_did_seed"))
+    }
+
     async fn add_wallet_record(
         &self,
         xtype: &str,
@@ -90,6 +104,14 @@ impl BaseWallet for AgencyClientWallet {
         Err(unimplemented_agency_client_wallet_method("iterate_wallet_records"))
     }
 
+    async fn begin_transaction(&self) -> VcxCoreResult<WalletTransaction> {
+        Err(unimplemented_agency_client_wallet_method("begin_transaction"))
+    }
+
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        Err(unimplemented_agency_client_wallet_method("commit_transaction"))
+    }
+
     async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         Err(unimplemented_agency_client_wallet_method("sign"))
     }
@@ -0,0 +1,798 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use aries_askar::{
+    crypto::alg::KeyAlg, kms::LocalKey, Entry, EntryTag, ErrorKind as AskarErrorKind, PassKey, Session, Store,
+    StoreKeyMethod, TagFilter,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::utils::async_fn_iterator::AsyncFnIterator;
+
+use super::{
+    base_wallet::BaseWallet,
+    export_import::ArchiveDid,
+    pack,
+    transaction::{TransactionLock, WalletOperation, WalletTransaction},
+};
+
+/// Category of the records holding the DIDs created in the wallet.
+const DID_CATEGORY: &str = "Indy::Did";
+/// Category of the records holding the verkeys of DID key rotations which have been started, but not applied yet.
+const TEMPORARY_DID_CATEGORY: &str = "Indy::TemporaryDid";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DidRecord {
+    did: String,
+    verkey: String,
+}
+
+/// Options for retrieving wallet records, using the same format as vdrtools.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordOptions {
+    #[serde(default, alias = "retrieve_type")]
+    retrieve_type: bool,
+    #[serde(default = "default_true", alias = "retrieve_value")]
+    retrieve_value: bool,
+    #[serde(default, alias = "retrieve_tags")]
+    retrieve_tags: bool,
+}
+
+impl Default for RecordOptions {
+    fn default() -> Self {
+        Self {
+            retrieve_type: false,
+            retrieve_value: true,
+            retrieve_tags: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// [`BaseWallet`] implementation backed by an [aries-askar](https://github.com/hyperledger/aries-askar) store.
+///
+/// Records and their tags are stored in the askar store as entries of the category matching the record type.
+/// Tag names prefixed with `~` are stored unencrypted, as with vdrtools wallets, so that
+/// WQL queries used with [`BaseWallet::iterate_wallet_records`] behave the same way.
+pub struct AskarWallet {
+    store: Store,
+    profile: Option<String>,
+    transaction_lock: TransactionLock,
+}
+
+impl fmt::Debug for AskarWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AskarWallet")
+            .field("profile", &self.profile)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AskarWallet {
+    /// Provisions a new store at `db_url` (e.g. `sqlite://path/to/wallet.db` or `sqlite://:memory:`),
+    /// protected by the given pass key.
+    pub async fn create(
+        db_url: &str,
+        key_method: StoreKeyMethod,
+        pass_key: &str,
+        profile: Option<String>,
+    ) -> VcxCoreResult<Self> {
+        let store = Store::provision(db_url, key_method, PassKey::from(pass_key), profile.as_deref(), false)
+            .await
+            .map_err(|err| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::WalletCreate, err))?;
+
+        Ok(Self {
+            store,
+            profile,
+            transaction_lock: TransactionLock::default(),
+        })
+    }
+
+    /// Opens an existing store at `db_url`.
+    pub async fn open(
+        db_url: &str,
+        key_method: Option<StoreKeyMethod>,
+        pass_key: &str,
+        profile: Option<String>,
+    ) -> VcxCoreResult<Self> {
+        let store = Store::open(db_url, key_method, PassKey::from(pass_key), profile.as_deref())
+            .await
+            .map_err(|err| match err.kind() {
+                AskarErrorKind::NotFound => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::WalletNotFound, err),
+                _ => AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::WalletAccessFailed, err),
+            })?;
+
+        Ok(Self {
+            store,
+            profile,
+            transaction_lock: TransactionLock::default(),
+        })
+    }
+
+    pub async fn close(self) -> VcxCoreResult<()> {
+        Ok(self.store.close().await?)
+    }
+
+    async fn session(&self) -> VcxCoreResult<Session> {
+        Ok(self.store.session(self.profile.clone()).await?)
+    }
+
+    async fn transaction(&self) -> VcxCoreResult<Session> {
+        Ok(self.store.transaction(self.profile.clone()).await?)
+    }
+}
+
+#[async_trait]
+impl BaseWallet for AskarWallet {
+    async fn create_and_store_my_did(
+        &self,
+        seed: Option<&str>,
+        method_name: Option<&str>,
+    ) -> VcxCoreResult<(String, String)> {
+        let key = match seed {
+            Some(seed) => LocalKey::from_secret_bytes(KeyAlg::Ed25519, &pack::seed_bytes(seed)?)?,
+            None => LocalKey::generate(KeyAlg::Ed25519, false)?,
+        };
+
+        let public_key = key.to_public_bytes()?;
+        let verkey = bs58::encode(&public_key[..]).into_string();
+        let did = bs58::encode(&public_key[..16]).into_string();
+        let did = match method_name {
+            Some(method_name) => format!("did:{}:{}", method_name, did),
+            None => did,
+        };
+
+        let mut session = self.transaction().await?;
+
+        insert_key(&mut session, &verkey, &key).await?;
+        insert_did(&mut session, &did, &verkey).await?;
+
+        session.commit().await?;
+
+        Ok((did, verkey))
+    }
+
+    async fn key_for_local_did(&self, did: &str) -> VcxCoreResult<String> {
+        let mut session = self.session().await?;
+        Ok(fetch_did(&mut session, did, false).await?.verkey)
+    }
+
+    async fn replace_did_keys_start(&self, target_did: &str) -> VcxCoreResult<String> {
+        let mut session = self.transaction().await?;
+
+        // Make sure the DID exists before generating a key for it
+        fetch_did(&mut session, target_did, false).await?;
+
+        let key = LocalKey::generate(KeyAlg::Ed25519, false)?;
+        let verkey = bs58::encode(&key.to_public_bytes()?[..]).into_string();
+        insert_key(&mut session, &verkey, &key).await?;
+
+        // Starting a new rotation discards a previously started one
+        let value = verkey.as_bytes();
+        if session.fetch(TEMPORARY_DID_CATEGORY, target_did, true).await?.is_some() {
+            session
+                .replace(TEMPORARY_DID_CATEGORY, target_did, value, None, None)
+                .await?;
+        } else {
+            session
+                .insert(TEMPORARY_DID_CATEGORY, target_did, value, None, None)
+                .await?;
+        }
+
+        session.commit().await?;
+
+        Ok(verkey)
+    }
+
+    async fn replace_did_keys_apply(&self, target_did: &str) -> VcxCoreResult<()> {
+        let mut session = self.transaction().await?;
+
+        let temporary = session
+            .fetch(TEMPORARY_DID_CATEGORY, target_did, true)
+            .await?
+            .ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::WalletRecordNotFound,
+                    format!("No key replacement was started for DID {}", target_did),
+                )
+            })?;
+        let verkey = entry_value(&temporary)?;
+
+        let mut record = fetch_did(&mut session, target_did, true).await?;
+        record.verkey = verkey;
+        session
+            .replace(DID_CATEGORY, target_did, &serde_json::to_vec(&record)?, None, None)
+            .await?;
+        session.remove(TEMPORARY_DID_CATEGORY, target_did).await?;
+
+        session.commit().await?;
+
+        Ok(())
+    }
+
+    async fn export_dids(&self) -> VcxCoreResult<Vec<ArchiveDid>> {
+        let mut session = self.session().await?;
+        let entries = session.fetch_all(DID_CATEGORY, None, None, false).await?;
+
+        let mut dids = Vec::with_capacity(entries.len());
+        for entry in entries.iter() {
+            let record: DidRecord = serde_json::from_slice(&entry.value)?;
+            let key = fetch_local_key(&mut session, &record.verkey).await?;
+            dids.push(pack::archive_did(&record.did, &record.verkey, &key)?);
+        }
+
+        Ok(dids)
+    }
+
+    async fn import_did(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        let key = pack::archived_did_key(did)?;
+        let mut session = self.transaction().await?;
+
+        insert_key(&mut session, &did.verkey, &key).await?;
+        insert_did(&mut session, &did.did, &did.verkey).await?;
+
+        Ok(session.commit().await?)
+    }
+
+    async fn check_did_seed(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        pack::archived_did_key(did).map(drop)
+    }
+
+    async fn add_wallet_record(
+        &self,
+        xtype: &str,
+        id: &str,
+        value: &str,
+        tags: Option<HashMap<String, String>>,
+    ) -> VcxCoreResult<()> {
+        let tags = tags.map(tags_from_map);
+        let mut session = self.session().await?;
+
+        Ok(session
+            .insert(xtype, id, value.as_bytes(), tags.as_deref(), None)
+            .await?)
+    }
+
+    async fn get_wallet_record(&self, xtype: &str, id: &str, options: &str) -> VcxCoreResult<String> {
+        let options = parse_options(options)?;
+        let mut session = self.session().await?;
+        let entry = fetch_record(&mut session, xtype, id, false).await?;
+
+        Ok(record_to_json(xtype, &entry, &options)?.to_string())
+    }
+
+    async fn get_wallet_record_value(&self, xtype: &str, id: &str) -> VcxCoreResult<String> {
+        let mut session = self.session().await?;
+        let entry = fetch_record(&mut session, xtype, id, false).await?;
+
+        entry_value(&entry)
+    }
+
+    async fn delete_wallet_record(&self, xtype: &str, id: &str) -> VcxCoreResult<()> {
+        let mut session = self.session().await?;
+
+        Ok(session.remove(xtype, id).await?)
+    }
+
+    async fn update_wallet_record_value(&self, xtype: &str, id: &str, value: &str) -> VcxCoreResult<()> {
+        let mut session = self.transaction().await?;
+        let entry = fetch_record(&mut session, xtype, id, true).await?;
+
+        session
+            .replace(xtype, id, value.as_bytes(), Some(&entry.tags), None)
+            .await?;
+
+        Ok(session.commit().await?)
+    }
+
+    async fn update_wallet_record_tags(
+        &self,
+        xtype: &str,
+        id: &str,
+        tags: HashMap<String, String>,
+    ) -> VcxCoreResult<()> {
+        let mut session = self.transaction().await?;
+        let entry = fetch_record(&mut session, xtype, id, true).await?;
+
+        session
+            .replace(xtype, id, &entry.value, Some(&tags_from_map(tags)), None)
+            .await?;
+
+        Ok(session.commit().await?)
+    }
+
+    async fn add_wallet_record_tags(&self, xtype: &str, id: &str, tags: HashMap<String, String>) -> VcxCoreResult<()> {
+        let mut session = self.transaction().await?;
+        let entry = fetch_record(&mut session, xtype, id, true).await?;
+
+        let mut merged = tags_to_map(&entry.tags);
+        merged.extend(tags);
+
+        session
+            .replace(xtype, id, &entry.value, Some(&tags_from_map(merged)), None)
+            .await?;
+
+        Ok(session.commit().await?)
+    }
+
+    async fn delete_wallet_record_tags(&self, xtype: &str, id: &str, tag_names: &str) -> VcxCoreResult<()> {
+        let tag_names: Vec<String> = serde_json::from_str(tag_names).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Tag names must be a JSON array of strings: {}", err),
+            )
+        })?;
+
+        let mut session = self.transaction().await?;
+        let entry = fetch_record(&mut session, xtype, id, true).await?;
+
+        let mut remaining = tags_to_map(&entry.tags);
+        remaining.retain(|name, _| !tag_names.contains(name));
+
+        session
+            .replace(xtype, id, &entry.value, Some(&tags_from_map(remaining)), None)
+            .await?;
+
+        Ok(session.commit().await?)
+    }
+
+    async fn iterate_wallet_records(
+        &self,
+        xtype: &str,
+        query: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        let options = parse_options(options)?;
+        let tag_filter = match query.trim() {
+            "" | "{}" => None,
+            query => Some(TagFilter::from_str(query).map_err(|err| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidInput,
+                    format!("Invalid WQL query: {}", err),
+                )
+            })?),
+        };
+
+        let mut session = self.session().await?;
+        let records = session
+            .fetch_all(xtype, tag_filter, None, false)
+            .await?
+            .iter()
+            .map(|entry| record_to_json(xtype, entry, &options).map(|record| record.to_string()))
+            .collect::<VcxCoreResult<Vec<_>>>()?;
+
+        Ok(Box::new(AskarWalletRecordIterator {
+            records: records.into_iter(),
+        }))
+    }
+
+    async fn begin_transaction(&self) -> VcxCoreResult<WalletTransaction> {
+        Ok(self.transaction_lock.begin_transaction().await)
+    }
+
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        // The askar transaction is rolled back when dropped without being committed
+        let mut session = self.transaction().await?;
+
+        for operation in transaction.into_operations() {
+            apply_operation(&mut session, operation).await?;
+        }
+
+        Ok(session.commit().await?)
+    }
+
+    async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let mut session = self.session().await?;
+        let key = fetch_local_key(&mut session, my_vk).await?;
+
+        Ok(key.sign_message(msg, None)?)
+    }
+
+    async fn verify(&self, vk: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<bool> {
+        let key = pack::public_key_from_verkey(vk)?;
+
+        Ok(key.verify_signature(msg, signature, None)?)
+    }
+
+    async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let receiver_keys: Vec<String> = serde_json::from_str(receiver_keys).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Receiver keys must be a JSON array of verkeys: {}", err),
+            )
+        })?;
+
+        match sender_vk {
+            Some(sender_vk) => {
+                let mut session = self.session().await?;
+                let sender_key = fetch_local_key(&mut session, sender_vk).await?;
+                pack::pack(Some((sender_vk, &sender_key)), &receiver_keys, msg)
+            }
+            None => pack::pack(None, &receiver_keys, msg),
+        }
+    }
+
+    async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let (envelope, header) = pack::parse_envelope(msg)?;
+        let mut session = self.session().await?;
+
+        for recipient in header.recipients.iter() {
+            if let Some(key_entry) = session.fetch_key(&recipient.header.kid, false).await? {
+                let key = key_entry.load_local_key()?;
+                return pack::unpack(&envelope, &header, recipient, &key);
+            }
+        }
+
+        Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::WalletRecordNotFound,
+            "None of the packed message recipient keys is stored in the wallet",
+        ))
+    }
+}
+
+/// Implementation of a generic [AsyncFnIterator] iterator for askar wallet record iteration.
+/// The records matching the query are fetched upfront.
+struct AskarWalletRecordIterator {
+    records: std::vec::IntoIter<String>,
+}
+
+#[async_trait]
+impl AsyncFnIterator for AskarWalletRecordIterator {
+    type Item = VcxCoreResult<String>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.records.next().map(Ok)
+    }
+}
+
+async fn insert_key(session: &mut Session, verkey: &str, key: &LocalKey) -> VcxCoreResult<()> {
+    match session.insert_key(verkey, key, None, None, None).await {
+        // The same key is stored when a seed is reused
+        Err(err) if err.kind() == AskarErrorKind::Duplicate => Ok(()),
+        res => Ok(res?),
+    }
+}
+
+async fn insert_did(session: &mut Session, did: &str, verkey: &str) -> VcxCoreResult<()> {
+    let record = serde_json::to_vec(&DidRecord {
+        did: did.to_owned(),
+        verkey: verkey.to_owned(),
+    })?;
+
+    session
+        .insert(DID_CATEGORY, did, &record, None, None)
+        .await
+        .map_err(|err| match err.kind() {
+            AskarErrorKind::Duplicate => AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::DuplicationDid,
+                format!("DID {} already exists in the wallet", did),
+            ),
+            _ => err.into(),
+        })
+}
+
+async fn fetch_local_key(session: &mut Session, verkey: &str) -> VcxCoreResult<LocalKey> {
+    let key_entry = session.fetch_key(verkey, false).await?.ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::WalletRecordNotFound,
+            format!("Key {} not found in the wallet", verkey),
+        )
+    })?;
+
+    Ok(key_entry.load_local_key()?)
+}
+
+async fn fetch_did(session: &mut Session, did: &str, for_update: bool) -> VcxCoreResult<DidRecord> {
+    let entry = session.fetch(DID_CATEGORY, did, for_update).await?.ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::WalletRecordNotFound,
+            format!("DID {} not found in the wallet", did),
+        )
+    })?;
+
+    Ok(serde_json::from_slice(&entry.value)?)
+}
+
+async fn fetch_record(session: &mut Session, xtype: &str, id: &str, for_update: bool) -> VcxCoreResult<Entry> {
+    session.fetch(xtype, id, for_update).await?.ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::WalletRecordNotFound,
+            format!("Wallet record of type {} and id {} not found", xtype, id),
+        )
+    })
+}
+
+async fn apply_operation(session: &mut Session, operation: WalletOperation) -> VcxCoreResult<()> {
+    match operation {
+        WalletOperation::AddRecord { xtype, id, value, tags } => {
+            let tags = tags.map(tags_from_map);
+            session
+                .insert(&xtype, &id, value.as_bytes(), tags.as_deref(), None)
+                .await?;
+        }
+        WalletOperation::UpdateRecordValue { xtype, id, value } => {
+            let entry = fetch_record(session, &xtype, &id, true).await?;
+            session
+                .replace(&xtype, &id, value.as_bytes(), Some(&entry.tags), None)
+                .await?;
+        }
+        WalletOperation::AddRecordTags { xtype, id, tags } => {
+            let entry = fetch_record(session, &xtype, &id, true).await?;
+            let mut merged = tags_to_map(&entry.tags);
+            merged.extend(tags);
+            session
+                .replace(&xtype, &id, &entry.value, Some(&tags_from_map(merged)), None)
+                .await?;
+        }
+        WalletOperation::UpdateRecordTags { xtype, id, tags } => {
+            let entry = fetch_record(session, &xtype, &id, true).await?;
+            session
+                .replace(&xtype, &id, &entry.value, Some(&tags_from_map(tags)), None)
+                .await?;
+        }
+        WalletOperation::DeleteRecordTags { xtype, id, tag_names } => {
+            let entry = fetch_record(session, &xtype, &id, true).await?;
+            let mut remaining = tags_to_map(&entry.tags);
+            remaining.retain(|name, _| !tag_names.contains(name));
+            session
+                .replace(&xtype, &id, &entry.value, Some(&tags_from_map(remaining)), None)
+                .await?;
+        }
+        WalletOperation::DeleteRecord { xtype, id } => session.remove(&xtype, &id).await?,
+    }
+
+    Ok(())
+}
+
+fn entry_value(entry: &Entry) -> VcxCoreResult<String> {
+    String::from_utf8(entry.value.to_vec()).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Wallet record value is not valid UTF-8: {}", err),
+        )
+    })
+}
+
+fn parse_options(options: &str) -> VcxCoreResult<RecordOptions> {
+    if options.trim().is_empty() {
+        return Ok(RecordOptions::default());
+    }
+
+    serde_json::from_str(options).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidJson,
+            format!("Invalid wallet record options: {}", err),
+        )
+    })
+}
+
+/// Serializes the entry into the record JSON returned by vdrtools.
+fn record_to_json(xtype: &str, entry: &Entry, options: &RecordOptions) -> VcxCoreResult<serde_json::Value> {
+    let value = options.retrieve_value.then(|| entry_value(entry)).transpose()?;
+    let tags = options.retrieve_tags.then(|| tags_to_map(&entry.tags));
+    let xtype = options.retrieve_type.then_some(xtype);
+
+    Ok(json!({
+        "id": entry.name,
+        "type": xtype,
+        "value": value,
+        "tags": tags,
+    }))
+}
+
+fn tags_from_map(tags: HashMap<String, String>) -> Vec<EntryTag> {
+    tags.into_iter()
+        .map(|(name, value)| match name.strip_prefix('~') {
+            Some(name) => EntryTag::Plaintext(name.to_owned(), value),
+            None => EntryTag::Encrypted(name, value),
+        })
+        .collect()
+}
+
+fn tags_to_map(tags: &[EntryTag]) -> HashMap<String, String> {
+    tags.iter()
+        .map(|tag| match tag {
+            EntryTag::Plaintext(name, value) => (format!("~{}", name), value.to_owned()),
+            EntryTag::Encrypted(name, value) => (name.to_owned(), value.to_owned()),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    async fn make_wallet() -> AskarWallet {
+        let key_method = StoreKeyMethod::parse_uri("kdf:argon2i:int").unwrap();
+        AskarWallet::create("sqlite://:memory:", key_method, "test_pass_key", None)
+            .await
+            .unwrap()
+    }
+
+    async fn collect_records(wallet: &AskarWallet, xtype: &str, query: &str) -> Vec<serde_json::Value> {
+        let mut iter = wallet.iterate_wallet_records(xtype, query, "{}").await.unwrap();
+        let mut records = Vec::new();
+        while let Some(record) = iter.next().await {
+            records.push(serde_json::from_str(&record.unwrap()).unwrap());
+        }
+        records
+    }
+
+    #[tokio::test]
+    async fn test_askar_wallet_did_from_seed() {
+        let wallet = make_wallet().await;
+        let seed = "000000000000000000000000Trustee1";
+
+        let (did, verkey) = wallet.create_and_store_my_did(Some(seed), None).await.unwrap();
+
+        assert_eq!(did, "V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(verkey, "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL");
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+
+        let err = wallet.create_and_store_my_did(Some(seed), None).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::DuplicationDid);
+    }
+
+    #[tokio::test]
+    async fn test_askar_wallet_sign_verify() {
+        let wallet = make_wallet().await;
+        let (_, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        let signature = wallet.sign(&verkey, b"message").await.unwrap();
+
+        assert!(wallet.verify(&verkey, b"message", &signature).await.unwrap());
+        assert!(!wallet.verify(&verkey, b"other message", &signature).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_askar_wallet_replace_did_keys() {
+        let wallet = make_wallet().await;
+        let (did, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        let new_verkey = wallet.replace_did_keys_start(&did).await.unwrap();
+        assert_ne!(new_verkey, verkey);
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+
+        wallet.replace_did_keys_apply(&did).await.unwrap();
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), new_verkey);
+
+        let err = wallet.replace_did_keys_apply(&did).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+    }
+
+    #[tokio::test]
+    async fn test_askar_wallet_records() {
+        let wallet = make_wallet().await;
+        let tags = HashMap::from([
+            ("tag1".to_owned(), "a".to_owned()),
+            ("~tag2".to_owned(), "b".to_owned()),
+        ]);
+
+        wallet
+            .add_wallet_record("type", "id", "value", Some(tags.clone()))
+            .await
+            .unwrap();
+        let err = wallet.add_wallet_record("type", "id", "value", None).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::DuplicationWalletRecord);
+
+        let options = r#"{"retrieveType": true, "retrieveValue": true, "retrieveTags": true}"#;
+        let record: serde_json::Value =
+            serde_json::from_str(&wallet.get_wallet_record("type", "id", options).await.unwrap()).unwrap();
+        assert_eq!(
+            record,
+            json!({"id": "id", "type": "type", "value": "value", "tags": {"tag1": "a", "~tag2": "b"}})
+        );
+
+        wallet
+            .update_wallet_record_value("type", "id", "new_value")
+            .await
+            .unwrap();
+        assert_eq!(wallet.get_wallet_record_value("type", "id").await.unwrap(), "new_value");
+
+        wallet
+            .add_wallet_record_tags("type", "id", HashMap::from([("tag3".to_owned(), "c".to_owned())]))
+            .await
+            .unwrap();
+        wallet
+            .delete_wallet_record_tags("type", "id", r#"["tag1"]"#)
+            .await
+            .unwrap();
+        let record: serde_json::Value =
+            serde_json::from_str(&wallet.get_wallet_record("type", "id", options).await.unwrap()).unwrap();
+        assert_eq!(record["tags"], json!({"~tag2": "b", "tag3": "c"}));
+
+        wallet.delete_wallet_record("type", "id").await.unwrap();
+        let err = wallet.get_wallet_record_value("type", "id").await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+    }
+
+    #[tokio::test]
+    async fn test_askar_wallet_iterate_records() {
+        let wallet = make_wallet().await;
+        for (id, tag) in [("id1", "a"), ("id2", "a"), ("id3", "b")] {
+            let tags = HashMap::from([("~tag".to_owned(), tag.to_owned())]);
+            wallet.add_wallet_record("type", id, "value", Some(tags)).await.unwrap();
+        }
+        wallet
+            .add_wallet_record("other_type", "id4", "value", None)
+            .await
+            .unwrap();
+
+        assert_eq!(collect_records(&wallet, "type", "{}").await.len(), 3);
+
+        let records = collect_records(&wallet, "type", r#"{"~tag": "b"}"#).await;
+        assert_eq!(
+            records,
+            vec![json!({"id": "id3", "type": null, "value": "value", "tags": null})]
+        );
+
+        let records = collect_records(&wallet, "type", r#"{"$not": {"~tag": "b"}}"#).await;
+        assert_eq!(records.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_askar_wallet_transaction() {
+        let wallet = make_wallet().await;
+        wallet.add_wallet_record("type", "id1", "value1", None).await.unwrap();
+
+        let mut transaction = wallet.begin_transaction().await.unwrap();
+        transaction.update_wallet_record_value("type", "id1", "new_value1");
+        transaction.add_wallet_record("type", "id2", "value2", None);
+        // Fails, as the record already exists
+        transaction.add_wallet_record("type", "id1", "value1", None);
+
+        assert!(wallet.commit_transaction(transaction).await.is_err());
+        assert_eq!(wallet.get_wallet_record_value("type", "id1").await.unwrap(), "value1");
+        assert!(wallet.get_wallet_record_value("type", "id2").await.is_err());
+
+        let mut transaction = wallet.begin_transaction().await.unwrap();
+        transaction.update_wallet_record_value("type", "id1", "new_value1");
+        transaction.add_wallet_record("type", "id2", "value2", None);
+
+        wallet.commit_transaction(transaction).await.unwrap();
+        assert_eq!(
+            wallet.get_wallet_record_value("type", "id1").await.unwrap(),
+            "new_value1"
+        );
+        assert_eq!(wallet.get_wallet_record_value("type", "id2").await.unwrap(), "value2");
+    }
+
+    #[tokio::test]
+    async fn test_askar_wallet_pack_unpack() {
+        let sender = make_wallet().await;
+        let recipient = make_wallet().await;
+        let (_, sender_vk) = sender.create_and_store_my_did(None, None).await.unwrap();
+        let (_, recipient_vk) = recipient.create_and_store_my_did(None, None).await.unwrap();
+        let receiver_keys = json!([recipient_vk]).to_string();
+
+        let packed = sender
+            .pack_message(Some(&sender_vk), &receiver_keys, b"authcrypted")
+            .await
+            .unwrap();
+        let unpacked: serde_json::Value =
+            serde_json::from_slice(&recipient.unpack_message(&packed).await.unwrap()).unwrap();
+        assert_eq!(
+            unpacked,
+            json!({"message": "authcrypted", "recipient_verkey": recipient_vk, "sender_verkey": sender_vk})
+        );
+
+        let packed = sender.pack_message(None, &receiver_keys, b"anoncrypted").await.unwrap();
+        let unpacked: serde_json::Value =
+            serde_json::from_slice(&recipient.unpack_message(&packed).await.unwrap()).unwrap();
+        assert_eq!(
+            unpacked,
+            json!({"message": "anoncrypted", "recipient_verkey": recipient_vk})
+        );
+
+        let err = sender.unpack_message(&packed).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+    }
+}
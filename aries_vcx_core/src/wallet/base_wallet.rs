@@ -3,7 +3,11 @@ use async_trait::async_trait;
 use crate::errors::error::VcxCoreResult;
 use crate::utils::async_fn_iterator::AsyncFnIterator;
 
+use super::export_import::{self, ArchiveDid, ExportConfig};
+use super::transaction::WalletTransaction;
+
 use std::collections::HashMap;
+use std::io::{Read, Write};
 
 /// Trait defining standard 'wallet' related functionality. The APIs, including
 /// input and output types are loosely based off the indy Wallet API:
@@ -26,6 +30,19 @@ pub trait BaseWallet: std::fmt::Debug + Send + Sync {
     // replaces the `target_did`'s current verkey with the one last generated by `replace_did_keys_start`
     async fn replace_did_keys_apply(&self, target_did: &str) -> VcxCoreResult<()>;
 
+    /// Returns the DIDs created in the wallet along with the seeds of their current keys, for
+    /// [`BaseWallet::export_wallet`]. Key replacements which were started but not applied are not
+    /// returned.
+    async fn export_dids(&self) -> VcxCoreResult<Vec<ArchiveDid>>;
+
+    /// Stores a DID returned by [`BaseWallet::export_dids`] (possibly by another implementation),
+    /// recreating its key from the seed.
+    async fn import_did(&self, did: &ArchiveDid) -> VcxCoreResult<()>;
+
+    /// Checks that the seed of a DID returned by [`BaseWallet::export_dids`] recreates its verkey,
+    /// without storing anything.
+    async fn check_did_seed(&self, did: &ArchiveDid) -> VcxCoreResult<()>;
+
     // ---- records
 
     async fn add_wallet_record(
@@ -71,6 +88,38 @@ pub trait BaseWallet: std::fmt::Debug + Send + Sync {
     async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>>;
 
     async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>>;
+
+    // ---- transactions
+
+    /// Starts a batch of record writes. Nothing is written until the returned transaction is passed to
+    /// [`BaseWallet::commit_transaction`], and no other transaction can begin on this wallet until then
+    /// or until it is rolled back. Implementations take the transaction from their own
+    /// [`TransactionLock`](super::transaction::TransactionLock).
+    async fn begin_transaction(&self) -> VcxCoreResult<WalletTransaction>;
+
+    /// Applies the record writes of `transaction` in order and atomically: if any of them fails,
+    /// none is persisted.
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()>;
+
+    /// Discards the record writes of `transaction` without applying them.
+    async fn rollback_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        drop(transaction);
+        Ok(())
+    }
+
+    // ---- export / import
+
+    /// Writes the DIDs and the records of the types listed in `config` into an encrypted archive,
+    /// returning the number of exported DIDs and records. See [`export_import`] for the archive format.
+    async fn export_wallet(&self, config: &ExportConfig, writer: Box<dyn Write + Send>) -> VcxCoreResult<usize> {
+        export_import::export_wallet(self, config, writer).await
+    }
+
+    /// Adds the DIDs and records of an archive written by [`BaseWallet::export_wallet`] (possibly by
+    /// another implementation) to the wallet, returning the number of imported DIDs and records.
+    async fn import_wallet(&self, key: &str, reader: Box<dyn Read + Send>) -> VcxCoreResult<usize> {
+        export_import::import_wallet(self, key, reader).await
+    }
 }
 
 #[async_trait]
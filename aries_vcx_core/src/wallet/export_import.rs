@@ -0,0 +1,768 @@
+//! Backend-neutral export and import of wallets.
+//!
+//! The DIDs and records of a wallet are exported into an encrypted archive which can be imported
+//! into any [`BaseWallet`] implementation, including one using another storage backend than the
+//! exported wallet.
+//!
+//! Archive format (version 1):
+//! * the 8 bytes [`MAGIC`],
+//! * the length of the header, as a little endian `u32`, followed by the JSON header,
+//! * chunks of entries, each consisting of the length of its ciphertext as a little endian `u32`,
+//!   a random 24 bytes nonce and the ciphertext.
+//!
+//! Chunks are encrypted with XChaCha20-Poly1305, using a key derived from the export key with
+//! Argon2i. The header, the index of the chunk and whether the chunk is the last one are
+//! authenticated along with each chunk, so a reordered or truncated archive fails to import.
+//! Once decrypted, a chunk holds newline separated JSON [`ArchiveEntry`]s: the DIDs of the wallet
+//! with the seeds of their keys (see [`BaseWallet::export_dids`]), followed by the records
+//! accessible through [`BaseWallet::iterate_wallet_records`].
+//!
+//! Key derivation, encryption and the reads and writes of the archive are blocking, so they run on
+//! the blocking thread pool of the tokio runtime.
+
+use std::collections::HashMap;
+use std::io::{ErrorKind as IoErrorKind, Read, Write};
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+use super::base_wallet::BaseWallet;
+
+/// Bytes starting every wallet archive.
+pub const MAGIC: &[u8; 8] = b"VCXWLTBK";
+/// Version of the archive format written by [`export_wallet`].
+pub const ARCHIVE_VERSION: u32 = 1;
+
+const CIPHER_XCHACHA20POLY1305: &str = "xchacha20poly1305";
+const KDF_ARGON2I: &str = "argon2i";
+
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 24;
+/// Entries are buffered until their serialization reaches this size, then encrypted as a chunk.
+const CHUNK_SIZE: usize = 64 * 1024;
+/// Upper bounds of the lengths read from an archive, so a corrupted one can't exhaust the memory.
+const MAX_HEADER_LENGTH: usize = 64 * 1024;
+const MAX_CHUNK_LENGTH: usize = 64 * 1024 * 1024;
+/// Upper bound of the Argon2 memory cost (in KiB) accepted from an archive header.
+const MAX_MEMORY_COST: u32 = 1024 * 1024;
+
+const RECORD_OPTIONS: &str = r#"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"#;
+
+/// Argon2i parameters used to derive the archive key, matching the `ARGON2I_MOD` and
+/// `ARGON2I_INT` key derivation methods of vdrtools.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyDerivationMethod {
+    /// Moderate cost (128 MiB, 6 iterations), for archives stored outside of the device.
+    #[default]
+    Argon2iMod,
+    /// Interactive cost (32 MiB, 4 iterations).
+    Argon2iInt,
+}
+
+impl KeyDerivationMethod {
+    fn params(&self) -> KdfParams {
+        let (memory_cost, time_cost) = match self {
+            Self::Argon2iMod => (128 * 1024, 6),
+            Self::Argon2iInt => (32 * 1024, 4),
+        };
+
+        KdfParams {
+            algorithm: KDF_ARGON2I.to_owned(),
+            memory_cost,
+            time_cost,
+            parallelism: 1,
+            salt: rand::random::<[u8; SALT_LENGTH]>().to_vec(),
+        }
+    }
+}
+
+/// Configuration of a wallet export.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportConfig {
+    /// Key the archive is encrypted with. The same key must be used to import the archive.
+    pub key: String,
+    pub key_derivation_method: KeyDerivationMethod,
+    /// Types of the records to export. Wallets can't list the types of their records, so the types
+    /// used by the application must be given, e.g. `credx_anoncreds::RECORD_CATEGORIES`.
+    pub record_types: Vec<String>,
+}
+
+impl ExportConfig {
+    pub fn new<I, T>(key: &str, record_types: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        Self {
+            key: key.to_owned(),
+            key_derivation_method: KeyDerivationMethod::default(),
+            record_types: record_types.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Header {
+    version: u32,
+    cipher: String,
+    kdf: KdfParams,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    memory_cost: u32,
+    time_cost: u32,
+    parallelism: u32,
+    salt: Vec<u8>,
+}
+
+impl KdfParams {
+    async fn derive_key(&self, key: &str) -> VcxCoreResult<[u8; KEY_LENGTH]> {
+        let params = self.clone();
+        let key = key.to_owned();
+        spawn_blocking(move || params.derive_key_blocking(&key)).await
+    }
+
+    fn derive_key_blocking(&self, key: &str) -> VcxCoreResult<[u8; KEY_LENGTH]> {
+        if self.algorithm != KDF_ARGON2I {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::UnimplementedFeature,
+                format!("Unsupported key derivation algorithm {}", self.algorithm),
+            ));
+        }
+        if self.memory_cost > MAX_MEMORY_COST {
+            return Err(invalid_archive(format!(
+                "key derivation memory cost {} KiB exceeds {} KiB",
+                self.memory_cost, MAX_MEMORY_COST
+            )));
+        }
+
+        let params = Params::new(self.memory_cost, self.time_cost, self.parallelism, Some(KEY_LENGTH))
+            .map_err(|err| invalid_archive(format!("invalid key derivation parameters: {}", err)))?;
+
+        let mut derived_key = [0; KEY_LENGTH];
+        Argon2::new(Algorithm::Argon2i, Version::V0x13, params)
+            .hash_password_into(key.as_bytes(), &self.salt, &mut derived_key)
+            .map_err(|err| invalid_archive(format!("failed to derive the archive key: {}", err)))?;
+
+        Ok(derived_key)
+    }
+}
+
+/// An entry of the archive.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum ArchiveEntry {
+    Did(ArchiveDid),
+    Record(ArchiveRecord),
+}
+
+/// A DID created in the wallet, as stored in the archive.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArchiveDid {
+    pub did: String,
+    pub verkey: String,
+    /// Base64 encoded seed of the Ed25519 key of the DID, from which the key is recreated.
+    pub seed: String,
+}
+
+/// A record, as stored in the archive.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ArchiveRecord {
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub id: String,
+    pub value: String,
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+}
+
+/// A record as returned by [`BaseWallet::iterate_wallet_records`].
+#[derive(Debug, Deserialize)]
+struct WalletRecord {
+    id: String,
+    value: Option<String>,
+    tags: Option<HashMap<String, String>>,
+}
+
+struct ChunkCipher {
+    cipher: XChaCha20Poly1305,
+    header: Vec<u8>,
+    index: u64,
+}
+
+impl ChunkCipher {
+    fn new(key: &[u8; KEY_LENGTH], header: Vec<u8>) -> Self {
+        Self {
+            cipher: XChaCha20Poly1305::new(Key::from_slice(key)),
+            header,
+            index: 0,
+        }
+    }
+
+    fn aad(&self, last: bool) -> Vec<u8> {
+        let mut aad = self.header.clone();
+        aad.extend_from_slice(&self.index.to_le_bytes());
+        aad.push(last as u8);
+        aad
+    }
+
+    fn write_chunk(&mut self, writer: &mut (dyn Write + Send), plaintext: &[u8], last: bool) -> VcxCoreResult<()> {
+        let nonce = rand::random::<[u8; NONCE_LENGTH]>();
+        let aad = self.aad(last);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                XNonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|_| {
+                AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::EncodeError, "Failed to encrypt wallet archive")
+            })?;
+
+        writer.write_all(&length_prefix(ciphertext.len())?)?;
+        writer.write_all(&nonce)?;
+        writer.write_all(&ciphertext)?;
+        self.index += 1;
+
+        Ok(())
+    }
+
+    /// Reads the next chunk, returning its plaintext and whether it is the last one.
+    fn read_chunk(&mut self, reader: &mut (dyn Read + Send)) -> VcxCoreResult<(Vec<u8>, bool)> {
+        let length = read_length(reader, MAX_CHUNK_LENGTH)?;
+        let mut nonce = [0; NONCE_LENGTH];
+        read_exact(reader, &mut nonce)?;
+        let mut ciphertext = vec![0; length];
+        read_exact(reader, &mut ciphertext)?;
+
+        // Whether the chunk is the last one is only known by authenticating it
+        for last in [false, true] {
+            let aad = self.aad(last);
+            let payload = Payload {
+                msg: &ciphertext,
+                aad: &aad,
+            };
+            if let Ok(plaintext) = self.cipher.decrypt(XNonce::from_slice(&nonce), payload) {
+                self.index += 1;
+                return Ok((plaintext, last));
+            }
+        }
+
+        Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::WalletAccessFailed,
+            "Failed to decrypt wallet archive, the key is invalid or the archive is corrupted",
+        ))
+    }
+}
+
+struct ChunkWriter {
+    writer: Box<dyn Write + Send>,
+    cipher: ChunkCipher,
+}
+
+/// Buffers the entries of an archive, writing them in encrypted chunks.
+struct ArchiveWriter {
+    inner: Arc<Mutex<ChunkWriter>>,
+    chunk: Vec<u8>,
+}
+
+impl ArchiveWriter {
+    async fn new(mut writer: Box<dyn Write + Send>, key: [u8; KEY_LENGTH], header: Vec<u8>) -> VcxCoreResult<Self> {
+        let inner = spawn_blocking(move || {
+            writer.write_all(MAGIC)?;
+            writer.write_all(&length_prefix(header.len())?)?;
+            writer.write_all(&header)?;
+
+            Ok(ChunkWriter {
+                writer,
+                cipher: ChunkCipher::new(&key, header),
+            })
+        })
+        .await?;
+
+        Ok(Self {
+            inner: Arc::new(Mutex::new(inner)),
+            chunk: Vec::new(),
+        })
+    }
+
+    async fn push(&mut self, entry: &ArchiveEntry) -> VcxCoreResult<()> {
+        serde_json::to_writer(&mut self.chunk, entry)?;
+        self.chunk.push(b'\n');
+
+        if self.chunk.len() >= CHUNK_SIZE {
+            self.write_chunk(false).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn finish(mut self) -> VcxCoreResult<()> {
+        self.write_chunk(true).await
+    }
+
+    async fn write_chunk(&mut self, last: bool) -> VcxCoreResult<()> {
+        let inner = self.inner.clone();
+        let chunk = mem::take(&mut self.chunk);
+
+        spawn_blocking(move || {
+            let mut inner = inner.lock()?;
+            let ChunkWriter { writer, cipher } = &mut *inner;
+            cipher.write_chunk(writer.as_mut(), &chunk, last)?;
+            if last {
+                writer.flush()?;
+            }
+            Ok(())
+        })
+        .await
+    }
+}
+
+/// Reads the chunks of an archive, decrypting them.
+struct ArchiveReader {
+    reader: Box<dyn Read + Send>,
+    cipher: ChunkCipher,
+}
+
+impl ArchiveReader {
+    /// Reads the next chunk, returning the reader along with the plaintext of the chunk and whether
+    /// it is the last one.
+    async fn read_chunk(mut self) -> VcxCoreResult<(Self, Vec<u8>, bool)> {
+        spawn_blocking(move || {
+            let (chunk, last) = self.cipher.read_chunk(self.reader.as_mut())?;
+            Ok((self, chunk, last))
+        })
+        .await
+    }
+}
+
+/// Writes the DIDs of the wallet and the records of the types listed in `config` into an encrypted
+/// archive, returning the number of exported DIDs and records.
+///
+/// Records are read through [`BaseWallet::iterate_wallet_records`], and written in chunks, so the
+/// whole wallet is never held in memory.
+pub async fn export_wallet<W>(wallet: &W, config: &ExportConfig, writer: Box<dyn Write + Send>) -> VcxCoreResult<usize>
+where
+    W: BaseWallet + ?Sized,
+{
+    let header = Header {
+        version: ARCHIVE_VERSION,
+        cipher: CIPHER_XCHACHA20POLY1305.to_owned(),
+        kdf: config.key_derivation_method.params(),
+    };
+    let key = header.kdf.derive_key(&config.key).await?;
+    let header = serde_json::to_vec(&header)?;
+
+    let mut archive = ArchiveWriter::new(writer, key, header).await?;
+    let mut exported = 0;
+
+    for did in wallet.export_dids().await? {
+        archive.push(&ArchiveEntry::Did(did)).await?;
+        exported += 1;
+    }
+
+    for record_type in config.record_types.iter() {
+        let mut records = wallet.iterate_wallet_records(record_type, "{}", RECORD_OPTIONS).await?;
+
+        while let Some(record) = records.next().await {
+            let record: WalletRecord = serde_json::from_str(&record?)?;
+            let value = record.value.ok_or_else(|| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::WalletRecordNotFound,
+                    format!(
+                        "Wallet record of type {} and id {} has no value",
+                        record_type, record.id
+                    ),
+                )
+            })?;
+            let record = ArchiveRecord {
+                record_type: record_type.to_owned(),
+                id: record.id,
+                value,
+                tags: record.tags.unwrap_or_default(),
+            };
+
+            archive.push(&ArchiveEntry::Record(record)).await?;
+            exported += 1;
+        }
+    }
+
+    archive.finish().await?;
+
+    Ok(exported)
+}
+
+/// Adds the DIDs and records of an archive written by [`export_wallet`] to the wallet, returning the
+/// number of imported DIDs and records.
+///
+/// The records are added within a single transaction, committed once the whole archive is read, so
+/// none of them is imported if the archive is invalid or if a record is already present in the
+/// wallet. The DIDs are checked before the records are committed, failing the import if one of
+/// them is already in the wallet or if its seed doesn't match its verkey, and stored once the
+/// records are committed.
+pub async fn import_wallet<W>(wallet: &W, key: &str, reader: Box<dyn Read + Send>) -> VcxCoreResult<usize>
+where
+    W: BaseWallet + ?Sized,
+{
+    let (reader, header, parsed_header) = spawn_blocking(move || {
+        let mut reader = reader;
+        let mut magic = [0; MAGIC.len()];
+        read_exact(reader.as_mut(), &mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_archive("missing archive magic bytes".to_owned()));
+        }
+
+        let mut header = vec![0; read_length(reader.as_mut(), MAX_HEADER_LENGTH)?];
+        read_exact(reader.as_mut(), &mut header)?;
+        let parsed_header: Header =
+            serde_json::from_slice(&header).map_err(|err| invalid_archive(format!("invalid header: {}", err)))?;
+
+        Ok((reader, header, parsed_header))
+    })
+    .await?;
+
+    if parsed_header.version != ARCHIVE_VERSION {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::UnimplementedFeature,
+            format!("Unsupported wallet archive version {}", parsed_header.version),
+        ));
+    }
+    if parsed_header.cipher != CIPHER_XCHACHA20POLY1305 {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::UnimplementedFeature,
+            format!("Unsupported wallet archive cipher {}", parsed_header.cipher),
+        ));
+    }
+
+    let key = parsed_header.kdf.derive_key(key).await?;
+    let mut archive = ArchiveReader {
+        reader,
+        cipher: ChunkCipher::new(&key, header),
+    };
+    let mut transaction = wallet.begin_transaction().await?;
+    let mut dids = Vec::new();
+    let mut imported = 0;
+
+    loop {
+        let (next, chunk, last) = archive.read_chunk().await?;
+        archive = next;
+
+        for line in chunk.split(|byte| *byte == b'\n').filter(|line| !line.is_empty()) {
+            let entry: ArchiveEntry =
+                serde_json::from_slice(line).map_err(|err| invalid_archive(format!("invalid entry: {}", err)))?;
+            match entry {
+                ArchiveEntry::Did(did) => dids.push(did),
+                ArchiveEntry::Record(record) => {
+                    transaction.add_wallet_record(&record.record_type, &record.id, &record.value, Some(record.tags))
+                }
+            }
+            imported += 1;
+        }
+
+        if last {
+            break;
+        }
+    }
+
+    for did in dids.iter() {
+        check_importable_did(wallet, did).await?;
+    }
+    wallet.commit_transaction(transaction).await?;
+    for did in dids.iter() {
+        wallet.import_did(did).await?;
+    }
+
+    Ok(imported)
+}
+
+/// Fails if [`BaseWallet::import_did`] would fail to store `did`, without storing anything.
+async fn check_importable_did<W>(wallet: &W, did: &ArchiveDid) -> VcxCoreResult<()>
+where
+    W: BaseWallet + ?Sized,
+{
+    match wallet.key_for_local_did(&did.did).await {
+        Ok(_) => Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::DuplicationDid,
+            format!("DID {} already exists in the wallet", did.did),
+        )),
+        Err(err) if err.kind() == AriesVcxCoreErrorKind::WalletRecordNotFound => wallet.check_did_seed(did).await,
+        Err(err) => Err(err),
+    }
+}
+
+/// Runs blocking code on the blocking thread pool of the tokio runtime.
+async fn spawn_blocking<F, T>(f: F) -> VcxCoreResult<T>
+where
+    F: FnOnce() -> VcxCoreResult<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::IOError,
+            format!("Wallet archive task failed: {}", err),
+        )
+    })?
+}
+
+fn length_prefix(length: usize) -> VcxCoreResult<[u8; 4]> {
+    u32::try_from(length).map(u32::to_le_bytes).map_err(|_| {
+        AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::EncodeError, "Wallet archive chunk is too large")
+    })
+}
+
+fn read_length(reader: &mut (dyn Read + Send), max_length: usize) -> VcxCoreResult<usize> {
+    let mut length = [0; 4];
+    read_exact(reader, &mut length)?;
+
+    let length = u32::from_le_bytes(length) as usize;
+    if length > max_length {
+        return Err(invalid_archive(format!("length {} exceeds {}", length, max_length)));
+    }
+
+    Ok(length)
+}
+
+fn read_exact(reader: &mut (dyn Read + Send), buf: &mut [u8]) -> VcxCoreResult<()> {
+    reader.read_exact(buf).map_err(|err| match err.kind() {
+        IoErrorKind::UnexpectedEof => invalid_archive("unexpected end of the archive".to_owned()),
+        _ => err.into(),
+    })
+}
+
+fn invalid_archive(msg: String) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::InvalidInput,
+        format!("Invalid wallet archive: {}", msg),
+    )
+}
+
+#[cfg(all(test, feature = "in_memory_wallet"))]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use std::io::Cursor;
+
+    use crate::wallet::base_wallet::AsyncFnIteratorCollect;
+    use crate::wallet::in_memory_wallet::InMemoryWallet;
+
+    use super::*;
+
+    const OPTIONS: &str = r#"{"retrieveType": true, "retrieveValue": true, "retrieveTags": true}"#;
+    const SEED: &str = "000000000000000000000000Trustee1";
+
+    /// Writer whose content remains readable once the export is done with it.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn _config(record_types: &[&str]) -> ExportConfig {
+        ExportConfig {
+            key_derivation_method: KeyDerivationMethod::Argon2iInt,
+            ..ExportConfig::new("export_key", record_types.iter().copied())
+        }
+    }
+
+    async fn _wallet_with_records() -> InMemoryWallet {
+        let wallet = InMemoryWallet::new();
+        let tags = HashMap::from([
+            ("tag".to_owned(), "a".to_owned()),
+            ("~plain".to_owned(), "b".to_owned()),
+        ]);
+        wallet
+            .add_wallet_record("type1", "id1", "value1", Some(tags))
+            .await
+            .unwrap();
+        wallet.add_wallet_record("type1", "id2", "value2", None).await.unwrap();
+        // Records larger than a chunk, so the archive holds several chunks
+        let large_value = "x".repeat(CHUNK_SIZE);
+        for id in ["id3", "id4"] {
+            wallet.add_wallet_record("type2", id, &large_value, None).await.unwrap();
+        }
+        wallet.add_wallet_record("type3", "id5", "value5", None).await.unwrap();
+        wallet
+    }
+
+    async fn _records(wallet: &dyn BaseWallet, record_type: &str) -> Vec<serde_json::Value> {
+        let records = wallet
+            .iterate_wallet_records(record_type, "{}", OPTIONS)
+            .await
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        records
+            .iter()
+            .map(|record| serde_json::from_str(record).unwrap())
+            .collect()
+    }
+
+    async fn _export(wallet: &dyn BaseWallet, config: &ExportConfig) -> Vec<u8> {
+        let archive = SharedBuffer::default();
+        wallet.export_wallet(config, Box::new(archive.clone())).await.unwrap();
+        let archive = archive.0.lock().unwrap().clone();
+        archive
+    }
+
+    async fn _import(wallet: &dyn BaseWallet, key: &str, archive: &[u8]) -> VcxCoreResult<usize> {
+        wallet.import_wallet(key, Box::new(Cursor::new(archive.to_vec()))).await
+    }
+
+    /// Checks that `target` holds the DID of `source`, with a key producing the same signatures.
+    async fn _assert_same_did(source: &dyn BaseWallet, target: &dyn BaseWallet, did: &str) {
+        let verkey = source.key_for_local_did(did).await.unwrap();
+        assert_eq!(target.key_for_local_did(did).await.unwrap(), verkey);
+
+        let signature = target.sign(&verkey, b"message").await.unwrap();
+        assert_eq!(signature, source.sign(&verkey, b"message").await.unwrap());
+        assert!(source.verify(&verkey, b"message", &signature).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_export_import_roundtrip() {
+        let source = _wallet_with_records().await;
+        let (did, _) = source.create_and_store_my_did(Some(SEED), None).await.unwrap();
+        let (rotated_did, _) = source.create_and_store_my_did(None, Some("sov")).await.unwrap();
+        source.replace_did_keys_start(&rotated_did).await.unwrap();
+        source.replace_did_keys_apply(&rotated_did).await.unwrap();
+
+        let archive = _export(&source, &_config(&["type1", "type2"])).await;
+        assert_eq!(&archive[..MAGIC.len()], MAGIC);
+
+        let target = InMemoryWallet::new();
+        let imported = _import(&target, "export_key", &archive).await.unwrap();
+
+        assert_eq!(imported, 6);
+        for record_type in ["type1", "type2"] {
+            assert_eq!(
+                _records(&target, record_type).await,
+                _records(&source, record_type).await
+            );
+        }
+        assert!(_records(&target, "type3").await.is_empty());
+        _assert_same_did(&source, &target, &did).await;
+        _assert_same_did(&source, &target, &rotated_did).await;
+    }
+
+    #[tokio::test]
+    async fn test_import_is_rolled_back_on_failure() {
+        let source = _wallet_with_records().await;
+        source.create_and_store_my_did(Some(SEED), None).await.unwrap();
+        let archive = _export(&source, &_config(&["type1", "type2"])).await;
+
+        // The last record of the archive is already present, failing the import once all the
+        // previous records are buffered
+        let target = InMemoryWallet::new();
+        target.add_wallet_record("type2", "id4", "value4", None).await.unwrap();
+
+        let err = _import(&target, "export_key", &archive).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::DuplicationWalletRecord);
+        assert!(_records(&target, "type1").await.is_empty());
+        assert_eq!(_records(&target, "type2").await.len(), 1);
+        assert!(target.export_dids().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_fails_with_existing_did() {
+        let source = _wallet_with_records().await;
+        let (did, verkey) = source.create_and_store_my_did(Some(SEED), None).await.unwrap();
+        let archive = _export(&source, &_config(&["type1"])).await;
+
+        let target = InMemoryWallet::new();
+        target.create_and_store_my_did(Some(SEED), None).await.unwrap();
+
+        let err = _import(&target, "export_key", &archive).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::DuplicationDid);
+        assert!(_records(&target, "type1").await.is_empty());
+        assert_eq!(target.key_for_local_did(&did).await.unwrap(), verkey);
+    }
+
+    #[tokio::test]
+    async fn test_import_fails_with_mismatched_did_key() {
+        let source = InMemoryWallet::new();
+        let (did, verkey) = source.create_and_store_my_did(Some(SEED), None).await.unwrap();
+        let mut exported = source.export_dids().await.unwrap().remove(0);
+        assert_eq!(exported.did, did);
+        assert_eq!(exported.verkey, verkey);
+
+        let (_, other_verkey) = source.create_and_store_my_did(None, None).await.unwrap();
+        exported.verkey = other_verkey;
+        let err = InMemoryWallet::new().import_did(&exported).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidInput);
+    }
+
+    #[cfg(feature = "askar_wallet")]
+    #[tokio::test]
+    async fn test_export_import_across_backends() {
+        use aries_askar::StoreKeyMethod;
+
+        use crate::wallet::askar_wallet::AskarWallet;
+
+        let key_method = StoreKeyMethod::parse_uri("kdf:argon2i:int").unwrap();
+        let source = AskarWallet::create("sqlite://:memory:", key_method, "test_pass_key", None)
+            .await
+            .unwrap();
+        let tags = HashMap::from([("tag".to_owned(), "a".to_owned())]);
+        source
+            .add_wallet_record("type1", "id1", "value1", Some(tags))
+            .await
+            .unwrap();
+        let (did, _) = source.create_and_store_my_did(Some(SEED), None).await.unwrap();
+
+        let archive = _export(&source, &_config(&["type1"])).await;
+        let target = InMemoryWallet::new();
+        _import(&target, "export_key", &archive).await.unwrap();
+
+        assert_eq!(_records(&target, "type1").await, _records(&source, "type1").await);
+        _assert_same_did(&source, &target, &did).await;
+    }
+
+    #[tokio::test]
+    async fn test_import_fails_with_invalid_key() {
+        let archive = _export(&_wallet_with_records().await, &_config(&["type1"])).await;
+
+        let err = _import(&InMemoryWallet::new(), "other_key", &archive)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletAccessFailed);
+    }
+
+    #[tokio::test]
+    async fn test_import_fails_with_truncated_archive() {
+        let archive = _export(&_wallet_with_records().await, &_config(&["type1", "type2"])).await;
+
+        // Dropping the last (empty) chunk leaves a valid archive prefix, which must not import silently
+        let last_chunk_length = 4 + NONCE_LENGTH + 16;
+        let truncated = &archive[..archive.len() - last_chunk_length];
+        let err = _import(&InMemoryWallet::new(), "export_key", truncated)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidInput);
+
+        let err = _import(&InMemoryWallet::new(), "export_key", b"not an archive")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,843 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+use aries_askar::{crypto::alg::KeyAlg, kms::LocalKey};
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::sync::RwLock;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+use crate::utils::async_fn_iterator::AsyncFnIterator;
+
+use self::wql::Query;
+use super::{
+    base_wallet::BaseWallet,
+    export_import::ArchiveDid,
+    pack,
+    transaction::{TransactionLock, WalletOperation, WalletTransaction},
+};
+
+mod wql;
+
+/// Options for retrieving wallet records, using the same format as vdrtools.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecordOptions {
+    #[serde(default, alias = "retrieve_type")]
+    retrieve_type: bool,
+    #[serde(default = "default_true", alias = "retrieve_value")]
+    retrieve_value: bool,
+    #[serde(default, alias = "retrieve_tags")]
+    retrieve_tags: bool,
+}
+
+impl Default for RecordOptions {
+    fn default() -> Self {
+        Self {
+            retrieve_type: false,
+            retrieve_value: true,
+            retrieve_tags: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone)]
+struct Record {
+    value: String,
+    tags: HashMap<String, String>,
+}
+
+#[derive(Default)]
+struct WalletState {
+    /// Private keys, by their base58 encoded verkey.
+    keys: HashMap<String, LocalKey>,
+    /// Verkeys of the DIDs created in the wallet.
+    dids: HashMap<String, String>,
+    /// Verkeys of the DID key rotations which have been started, but not applied yet.
+    temporary_dids: HashMap<String, String>,
+    /// Records by type, then by id.
+    records: Records,
+}
+
+type Records = HashMap<String, BTreeMap<String, Record>>;
+
+impl WalletState {
+    fn record(&self, xtype: &str, id: &str) -> VcxCoreResult<&Record> {
+        self.records
+            .get(xtype)
+            .and_then(|records| records.get(id))
+            .ok_or_else(|| record_not_found(xtype, id))
+    }
+
+    fn key(&self, verkey: &str) -> VcxCoreResult<&LocalKey> {
+        self.keys.get(verkey).ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::WalletRecordNotFound,
+                format!("Key {} not found in the wallet", verkey),
+            )
+        })
+    }
+
+    fn did_verkey(&self, did: &str) -> VcxCoreResult<&String> {
+        self.dids.get(did).ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::WalletRecordNotFound,
+                format!("DID {} not found in the wallet", did),
+            )
+        })
+    }
+}
+
+/// [`BaseWallet`] implementation keeping DIDs, keys and records in memory, meant for tests which
+/// should run without any storage.
+///
+/// Records, tags and WQL queries behave as with vdrtools wallets: tag names prefixed with `~` are
+/// unencrypted, and only those can be compared with the `$gt`, `$gte`, `$lt`, `$lte` and `$like`
+/// operators. Records matching a query are returned ordered by id. Signatures and packed messages
+/// are interoperable with the other wallet implementations.
+///
+/// Everything stored is lost when the wallet is dropped.
+#[derive(Default)]
+pub struct InMemoryWallet {
+    state: RwLock<WalletState>,
+    transaction_lock: TransactionLock,
+}
+
+impl fmt::Debug for InMemoryWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryWallet").finish_non_exhaustive()
+    }
+}
+
+impl InMemoryWallet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl BaseWallet for InMemoryWallet {
+    async fn create_and_store_my_did(
+        &self,
+        seed: Option<&str>,
+        method_name: Option<&str>,
+    ) -> VcxCoreResult<(String, String)> {
+        let key = match seed {
+            Some(seed) => LocalKey::from_secret_bytes(KeyAlg::Ed25519, &pack::seed_bytes(seed)?)?,
+            None => LocalKey::generate(KeyAlg::Ed25519, false)?,
+        };
+
+        let public_key = key.to_public_bytes()?;
+        let verkey = bs58::encode(&public_key[..]).into_string();
+        let did = bs58::encode(&public_key[..16]).into_string();
+        let did = match method_name {
+            Some(method_name) => format!("did:{}:{}", method_name, did),
+            None => did,
+        };
+
+        let mut state = self.state.write().await;
+        if state.dids.contains_key(&did) {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::DuplicationDid,
+                format!("DID {} already exists in the wallet", did),
+            ));
+        }
+        state.keys.insert(verkey.clone(), key);
+        state.dids.insert(did.clone(), verkey.clone());
+
+        Ok((did, verkey))
+    }
+
+    async fn key_for_local_did(&self, did: &str) -> VcxCoreResult<String> {
+        Ok(self.state.read().await.did_verkey(did)?.to_owned())
+    }
+
+    async fn replace_did_keys_start(&self, target_did: &str) -> VcxCoreResult<String> {
+        let mut state = self.state.write().await;
+
+        // Make sure the DID exists before generating a key for it
+        state.did_verkey(target_did)?;
+
+        let key = LocalKey::generate(KeyAlg::Ed25519, false)?;
+        let verkey = bs58::encode(&key.to_public_bytes()?[..]).into_string();
+        state.keys.insert(verkey.clone(), key);
+
+        // Starting a new rotation discards a previously started one
+        state.temporary_dids.insert(target_did.to_owned(), verkey.clone());
+
+        Ok(verkey)
+    }
+
+    async fn replace_did_keys_apply(&self, target_did: &str) -> VcxCoreResult<()> {
+        let mut state = self.state.write().await;
+
+        state.did_verkey(target_did)?;
+        let verkey = state.temporary_dids.remove(target_did).ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::WalletRecordNotFound,
+                format!("No key replacement was started for DID {}", target_did),
+            )
+        })?;
+        state.dids.insert(target_did.to_owned(), verkey);
+
+        Ok(())
+    }
+
+    async fn export_dids(&self) -> VcxCoreResult<Vec<ArchiveDid>> {
+        let state = self.state.read().await;
+
+        state
+            .dids
+            .iter()
+            .map(|(did, verkey)| pack::archive_did(did, verkey, state.key(verkey)?))
+            .collect()
+    }
+
+    async fn import_did(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        let key = pack::archived_did_key(did)?;
+
+        let mut state = self.state.write().await;
+        if state.dids.contains_key(&did.did) {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::DuplicationDid,
+                format!("DID {} already exists in the wallet", did.did),
+            ));
+        }
+        state.keys.insert(did.verkey.clone(), key);
+        state.dids.insert(did.did.clone(), did.verkey.clone());
+
+        Ok(())
+    }
+
+    async fn check_did_seed(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        pack::archived_did_key(did).map(drop)
+    }
+
+    async fn add_wallet_record(
+        &self,
+        xtype: &str,
+        id: &str,
+        value: &str,
+        tags: Option<HashMap<String, String>>,
+    ) -> VcxCoreResult<()> {
+        let record = Record {
+            value: value.to_owned(),
+            tags: tags.unwrap_or_default(),
+        };
+
+        insert_record(&mut self.state.write().await.records, xtype, id, record)
+    }
+
+    async fn get_wallet_record(&self, xtype: &str, id: &str, options: &str) -> VcxCoreResult<String> {
+        let options = parse_options(options)?;
+        let state = self.state.read().await;
+        let record = state.record(xtype, id)?;
+
+        Ok(record_to_json(xtype, id, record, &options).to_string())
+    }
+
+    async fn get_wallet_record_value(&self, xtype: &str, id: &str) -> VcxCoreResult<String> {
+        Ok(self.state.read().await.record(xtype, id)?.value.clone())
+    }
+
+    async fn delete_wallet_record(&self, xtype: &str, id: &str) -> VcxCoreResult<()> {
+        remove_record(&mut self.state.write().await.records, xtype, id)
+    }
+
+    async fn update_wallet_record_value(&self, xtype: &str, id: &str, value: &str) -> VcxCoreResult<()> {
+        record_mut(&mut self.state.write().await.records, xtype, id)?.value = value.to_owned();
+        Ok(())
+    }
+
+    async fn add_wallet_record_tags(&self, xtype: &str, id: &str, tags: HashMap<String, String>) -> VcxCoreResult<()> {
+        record_mut(&mut self.state.write().await.records, xtype, id)?
+            .tags
+            .extend(tags);
+        Ok(())
+    }
+
+    async fn update_wallet_record_tags(
+        &self,
+        xtype: &str,
+        id: &str,
+        tags: HashMap<String, String>,
+    ) -> VcxCoreResult<()> {
+        record_mut(&mut self.state.write().await.records, xtype, id)?.tags = tags;
+        Ok(())
+    }
+
+    async fn delete_wallet_record_tags(&self, xtype: &str, id: &str, tag_names: &str) -> VcxCoreResult<()> {
+        let tag_names: Vec<String> = serde_json::from_str(tag_names).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Tag names must be a JSON array of strings: {}", err),
+            )
+        })?;
+
+        let mut state = self.state.write().await;
+        let record = record_mut(&mut state.records, xtype, id)?;
+        record.tags.retain(|name, _| !tag_names.contains(name));
+
+        Ok(())
+    }
+
+    async fn iterate_wallet_records(
+        &self,
+        xtype: &str,
+        query: &str,
+        options: &str,
+    ) -> VcxCoreResult<Box<dyn AsyncFnIterator<Item = VcxCoreResult<String>>>> {
+        let options = parse_options(options)?;
+        let query = Query::parse(query)?;
+
+        let state = self.state.read().await;
+        let records = state
+            .records
+            .get(xtype)
+            .into_iter()
+            .flatten()
+            .filter(|(_, record)| query.matches(&record.tags))
+            .map(|(id, record)| record_to_json(xtype, id, record, &options).to_string())
+            .collect::<Vec<_>>();
+
+        Ok(Box::new(InMemoryWalletRecordIterator {
+            records: records.into_iter(),
+        }))
+    }
+
+    async fn begin_transaction(&self) -> VcxCoreResult<WalletTransaction> {
+        Ok(self.transaction_lock.begin_transaction().await)
+    }
+
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        let mut state = self.state.write().await;
+
+        // The writes are applied to a copy of the records, so that a failing one leaves the wallet untouched
+        let mut records = state.records.clone();
+        for operation in transaction.into_operations() {
+            apply_operation(&mut records, operation)?;
+        }
+        state.records = records;
+
+        Ok(())
+    }
+
+    async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        Ok(self.state.read().await.key(my_vk)?.sign_message(msg, None)?)
+    }
+
+    async fn verify(&self, vk: &str, msg: &[u8], signature: &[u8]) -> VcxCoreResult<bool> {
+        let key = pack::public_key_from_verkey(vk)?;
+
+        Ok(key.verify_signature(msg, signature, None)?)
+    }
+
+    async fn pack_message(&self, sender_vk: Option<&str>, receiver_keys: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let receiver_keys: Vec<String> = serde_json::from_str(receiver_keys).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Receiver keys must be a JSON array of verkeys: {}", err),
+            )
+        })?;
+
+        match sender_vk {
+            Some(sender_vk) => {
+                let state = self.state.read().await;
+                let sender_key = state.key(sender_vk)?;
+                pack::pack(Some((sender_vk, sender_key)), &receiver_keys, msg)
+            }
+            None => pack::pack(None, &receiver_keys, msg),
+        }
+    }
+
+    async fn unpack_message(&self, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+        let (envelope, header) = pack::parse_envelope(msg)?;
+        let state = self.state.read().await;
+
+        for recipient in header.recipients.iter() {
+            if let Some(key) = state.keys.get(&recipient.header.kid) {
+                return pack::unpack(&envelope, &header, recipient, key);
+            }
+        }
+
+        Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::WalletRecordNotFound,
+            "None of the packed message recipient keys is stored in the wallet",
+        ))
+    }
+}
+
+/// Implementation of a generic [AsyncFnIterator] iterator for in-memory wallet record iteration.
+/// The records matching the query are collected upfront.
+struct InMemoryWalletRecordIterator {
+    records: std::vec::IntoIter<String>,
+}
+
+#[async_trait]
+impl AsyncFnIterator for InMemoryWalletRecordIterator {
+    type Item = VcxCoreResult<String>;
+
+    async fn next(&mut self) -> Option<Self::Item> {
+        self.records.next().map(Ok)
+    }
+}
+
+fn insert_record(records: &mut Records, xtype: &str, id: &str, record: Record) -> VcxCoreResult<()> {
+    let records = records.entry(xtype.to_owned()).or_default();
+
+    if records.contains_key(id) {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::DuplicationWalletRecord,
+            format!("Wallet record of type {} and id {} already exists", xtype, id),
+        ));
+    }
+    records.insert(id.to_owned(), record);
+
+    Ok(())
+}
+
+fn record_mut<'a>(records: &'a mut Records, xtype: &str, id: &str) -> VcxCoreResult<&'a mut Record> {
+    records
+        .get_mut(xtype)
+        .and_then(|records| records.get_mut(id))
+        .ok_or_else(|| record_not_found(xtype, id))
+}
+
+fn remove_record(records: &mut Records, xtype: &str, id: &str) -> VcxCoreResult<()> {
+    records
+        .get_mut(xtype)
+        .and_then(|records| records.remove(id))
+        .map(|_| ())
+        .ok_or_else(|| record_not_found(xtype, id))
+}
+
+fn apply_operation(records: &mut Records, operation: WalletOperation) -> VcxCoreResult<()> {
+    match operation {
+        WalletOperation::AddRecord { xtype, id, value, tags } => {
+            let record = Record {
+                value,
+                tags: tags.unwrap_or_default(),
+            };
+            insert_record(records, &xtype, &id, record)
+        }
+        WalletOperation::UpdateRecordValue { xtype, id, value } => {
+            record_mut(records, &xtype, &id)?.value = value;
+            Ok(())
+        }
+        WalletOperation::AddRecordTags { xtype, id, tags } => {
+            record_mut(records, &xtype, &id)?.tags.extend(tags);
+            Ok(())
+        }
+        WalletOperation::UpdateRecordTags { xtype, id, tags } => {
+            record_mut(records, &xtype, &id)?.tags = tags;
+            Ok(())
+        }
+        WalletOperation::DeleteRecordTags { xtype, id, tag_names } => {
+            record_mut(records, &xtype, &id)?
+                .tags
+                .retain(|name, _| !tag_names.contains(name));
+            Ok(())
+        }
+        WalletOperation::DeleteRecord { xtype, id } => remove_record(records, &xtype, &id),
+    }
+}
+
+fn record_not_found(xtype: &str, id: &str) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::WalletRecordNotFound,
+        format!("Wallet record of type {} and id {} not found", xtype, id),
+    )
+}
+
+fn parse_options(options: &str) -> VcxCoreResult<RecordOptions> {
+    if options.trim().is_empty() {
+        return Ok(RecordOptions::default());
+    }
+
+    serde_json::from_str(options).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidJson,
+            format!("Invalid wallet record options: {}", err),
+        )
+    })
+}
+
+/// Serializes the record into the record JSON returned by vdrtools.
+fn record_to_json(xtype: &str, id: &str, record: &Record, options: &RecordOptions) -> serde_json::Value {
+    json!({
+        "id": id,
+        "type": options.retrieve_type.then_some(xtype),
+        "value": options.retrieve_value.then_some(&record.value),
+        "tags": options.retrieve_tags.then_some(&record.tags),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    async fn collect_records(wallet: &InMemoryWallet, xtype: &str, query: &str) -> Vec<serde_json::Value> {
+        let mut iter = wallet.iterate_wallet_records(xtype, query, "{}").await.unwrap();
+        let mut records = Vec::new();
+        while let Some(record) = iter.next().await {
+            records.push(serde_json::from_str(&record.unwrap()).unwrap());
+        }
+        records
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wallet_did_from_seed() {
+        let wallet = InMemoryWallet::new();
+        let seed = "000000000000000000000000Trustee1";
+
+        let (did, verkey) = wallet.create_and_store_my_did(Some(seed), None).await.unwrap();
+
+        assert_eq!(did, "V4SGRU86Z58d6TV7PBUe6f");
+        assert_eq!(verkey, "GJ1SzoWzavQYfNL9XkaJdrQejfztN4XqdsiV4ct3LXKL");
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+
+        let err = wallet.create_and_store_my_did(Some(seed), None).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::DuplicationDid);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wallet_replace_did_keys() {
+        let wallet = InMemoryWallet::new();
+        let (did, verkey) = wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        let new_verkey = wallet.replace_did_keys_start(&did).await.unwrap();
+        assert_ne!(new_verkey, verkey);
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), verkey);
+
+        wallet.replace_did_keys_apply(&did).await.unwrap();
+        assert_eq!(wallet.key_for_local_did(&did).await.unwrap(), new_verkey);
+
+        let signature = wallet.sign(&new_verkey, b"message").await.unwrap();
+        assert!(wallet.verify(&new_verkey, b"message", &signature).await.unwrap());
+        assert!(!wallet.verify(&verkey, b"message", &signature).await.unwrap());
+
+        let err = wallet.replace_did_keys_apply(&did).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wallet_records() {
+        let wallet = InMemoryWallet::new();
+        let tags = HashMap::from([
+            ("tag1".to_owned(), "a".to_owned()),
+            ("~tag2".to_owned(), "b".to_owned()),
+        ]);
+
+        wallet
+            .add_wallet_record("type", "id", "value", Some(tags.clone()))
+            .await
+            .unwrap();
+        let err = wallet.add_wallet_record("type", "id", "value", None).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::DuplicationWalletRecord);
+
+        let options = r#"{"retrieveType": true, "retrieveValue": true, "retrieveTags": true}"#;
+        let record: serde_json::Value =
+            serde_json::from_str(&wallet.get_wallet_record("type", "id", options).await.unwrap()).unwrap();
+        assert_eq!(
+            record,
+            json!({"id": "id", "type": "type", "value": "value", "tags": {"tag1": "a", "~tag2": "b"}})
+        );
+
+        wallet
+            .update_wallet_record_value("type", "id", "new_value")
+            .await
+            .unwrap();
+        assert_eq!(wallet.get_wallet_record_value("type", "id").await.unwrap(), "new_value");
+
+        wallet
+            .add_wallet_record_tags("type", "id", HashMap::from([("tag3".to_owned(), "c".to_owned())]))
+            .await
+            .unwrap();
+        wallet
+            .delete_wallet_record_tags("type", "id", r#"["tag1"]"#)
+            .await
+            .unwrap();
+        let record: serde_json::Value =
+            serde_json::from_str(&wallet.get_wallet_record("type", "id", options).await.unwrap()).unwrap();
+        assert_eq!(record["tags"], json!({"~tag2": "b", "tag3": "c"}));
+
+        wallet.delete_wallet_record("type", "id").await.unwrap();
+        let err = wallet.get_wallet_record_value("type", "id").await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+        let err = wallet.delete_wallet_record("type", "id").await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wallet_iterate_records() {
+        let wallet = InMemoryWallet::new();
+        for (id, tag) in [("id3", "b"), ("id1", "a"), ("id2", "a")] {
+            let tags = HashMap::from([("~tag".to_owned(), tag.to_owned())]);
+            wallet.add_wallet_record("type", id, "value", Some(tags)).await.unwrap();
+        }
+        wallet
+            .add_wallet_record("other_type", "id4", "value", None)
+            .await
+            .unwrap();
+
+        let ids: Vec<_> = collect_records(&wallet, "type", "{}")
+            .await
+            .into_iter()
+            .map(|record| record["id"].clone())
+            .collect();
+        assert_eq!(ids, vec![json!("id1"), json!("id2"), json!("id3")]);
+
+        let records = collect_records(&wallet, "type", r#"{"~tag": "b"}"#).await;
+        assert_eq!(
+            records,
+            vec![json!({"id": "id3", "type": null, "value": "value", "tags": null})]
+        );
+
+        let records = collect_records(&wallet, "type", r#"{"$not": {"~tag": "b"}}"#).await;
+        assert_eq!(records.len(), 2);
+
+        assert!(collect_records(&wallet, "unknown_type", "{}").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wallet_transaction() {
+        let wallet = InMemoryWallet::new();
+        wallet.add_wallet_record("type", "id1", "value1", None).await.unwrap();
+
+        let mut transaction = wallet.begin_transaction().await.unwrap();
+        transaction.update_wallet_record_value("type", "id1", "new_value1");
+        transaction.add_wallet_record("type", "id2", "value2", None);
+        // Fails, as the record does not exist
+        transaction.delete_wallet_record("type", "id3");
+
+        let err = wallet.commit_transaction(transaction).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+        assert_eq!(wallet.get_wallet_record_value("type", "id1").await.unwrap(), "value1");
+        assert!(wallet.get_wallet_record_value("type", "id2").await.is_err());
+
+        let mut transaction = wallet.begin_transaction().await.unwrap();
+        transaction.update_wallet_record_value("type", "id1", "new_value1");
+        transaction.add_wallet_record("type", "id2", "value2", None);
+        transaction.add_wallet_record_tags("type", "id2", HashMap::from([("tag".to_owned(), "a".to_owned())]));
+
+        wallet.commit_transaction(transaction).await.unwrap();
+        assert_eq!(
+            wallet.get_wallet_record_value("type", "id1").await.unwrap(),
+            "new_value1"
+        );
+        assert_eq!(collect_records(&wallet, "type", r#"{"tag": "a"}"#).await.len(), 1);
+
+        let mut transaction = wallet.begin_transaction().await.unwrap();
+        transaction.delete_wallet_record("type", "id1");
+        wallet.rollback_transaction(transaction).await.unwrap();
+        assert_eq!(
+            wallet.get_wallet_record_value("type", "id1").await.unwrap(),
+            "new_value1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wallet_transaction_reads() {
+        let wallet = InMemoryWallet::new();
+        wallet.add_wallet_record("type", "id1", "value1", None).await.unwrap();
+
+        let mut transaction = wallet.begin_transaction().await.unwrap();
+        assert_eq!(
+            transaction
+                .get_wallet_record_value(&wallet, "type", "id1")
+                .await
+                .unwrap(),
+            "value1"
+        );
+
+        transaction.update_wallet_record_value("type", "id1", "new_value1");
+        transaction.add_wallet_record("type", "id2", "value2", None);
+        assert_eq!(
+            transaction
+                .get_wallet_record_value(&wallet, "type", "id1")
+                .await
+                .unwrap(),
+            "new_value1"
+        );
+        assert_eq!(
+            transaction
+                .get_wallet_record_value(&wallet, "type", "id2")
+                .await
+                .unwrap(),
+            "value2"
+        );
+        assert_eq!(wallet.get_wallet_record_value("type", "id1").await.unwrap(), "value1");
+
+        transaction.delete_wallet_record("type", "id1");
+        let err = transaction
+            .get_wallet_record_value(&wallet, "type", "id1")
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+
+        // Another transaction can only begin once this one ends
+        let next_transaction = tokio::spawn(async move { wallet.begin_transaction().await.map(drop) });
+        tokio::task::yield_now().await;
+        assert!(!next_transaction.is_finished());
+
+        drop(transaction);
+        next_transaction.await.unwrap().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wallet_transactions_of_different_wallets_are_concurrent() {
+        use futures::FutureExt;
+
+        let first = InMemoryWallet::new();
+        let second = InMemoryWallet::new();
+
+        let mut first_transaction = first.begin_transaction().await.unwrap();
+        let mut second_transaction = second
+            .begin_transaction()
+            .now_or_never()
+            .expect("the transaction of another wallet should not be waited for")
+            .unwrap();
+        first_transaction.add_wallet_record("type", "id", "first", None);
+        second_transaction.add_wallet_record("type", "id", "second", None);
+
+        second.commit_transaction(second_transaction).await.unwrap();
+        first.commit_transaction(first_transaction).await.unwrap();
+        assert_eq!(first.get_wallet_record_value("type", "id").await.unwrap(), "first");
+        assert_eq!(second.get_wallet_record_value("type", "id").await.unwrap(), "second");
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wallet_pack_unpack() {
+        let sender = InMemoryWallet::new();
+        let recipient = InMemoryWallet::new();
+        let (_, sender_vk) = sender.create_and_store_my_did(None, None).await.unwrap();
+        let (_, recipient_vk) = recipient.create_and_store_my_did(None, None).await.unwrap();
+        let receiver_keys = json!([recipient_vk]).to_string();
+
+        let packed = sender
+            .pack_message(Some(&sender_vk), &receiver_keys, b"authcrypted")
+            .await
+            .unwrap();
+        let unpacked: serde_json::Value =
+            serde_json::from_slice(&recipient.unpack_message(&packed).await.unwrap()).unwrap();
+        assert_eq!(
+            unpacked,
+            json!({"message": "authcrypted", "recipient_verkey": recipient_vk, "sender_verkey": sender_vk})
+        );
+
+        let packed = sender.pack_message(None, &receiver_keys, b"anoncrypted").await.unwrap();
+        let unpacked: serde_json::Value =
+            serde_json::from_slice(&recipient.unpack_message(&packed).await.unwrap()).unwrap();
+        assert_eq!(
+            unpacked,
+            json!({"message": "anoncrypted", "recipient_verkey": recipient_vk})
+        );
+
+        let err = sender.unpack_message(&packed).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+    }
+
+    #[cfg(feature = "modular_libs")]
+    #[tokio::test]
+    async fn test_in_memory_wallet_credx_issuance_and_proof() {
+        use std::sync::Arc;
+
+        use crate::anoncreds::base_anoncreds::BaseAnonCreds;
+        use crate::anoncreds::credx_anoncreds::IndyCredxAnonCreds;
+
+        let issuer = IndyCredxAnonCreds::new(Arc::new(InMemoryWallet::new()));
+        let prover_wallet = Arc::new(InMemoryWallet::new());
+        let prover = IndyCredxAnonCreds::new(prover_wallet.clone());
+        let issuer_did = "V4SGRU86Z58d6TV7PBUe6f";
+        let (prover_did, _) = prover_wallet.create_and_store_my_did(None, None).await.unwrap();
+
+        let (schema_id, schema_json) = issuer
+            .issuer_create_schema(issuer_did, "test_schema", "1.0", r#"["name", "age"]"#)
+            .await
+            .unwrap();
+        let (cred_def_id, cred_def_json) = issuer
+            .issuer_create_and_store_credential_def(
+                issuer_did,
+                &schema_json,
+                "tag",
+                None,
+                r#"{"support_revocation": false}"#,
+            )
+            .await
+            .unwrap();
+
+        let link_secret_id = prover.prover_create_link_secret("main").await.unwrap();
+        let offer = issuer.issuer_create_credential_offer(&cred_def_id).await.unwrap();
+        let (cred_req, cred_req_metadata) = prover
+            .prover_create_credential_req(&prover_did, &offer, &cred_def_json, &link_secret_id)
+            .await
+            .unwrap();
+        let cred_values = json!({
+            "name": {"raw": "Alice", "encoded": "1139481716457488690172217916278103335"},
+            "age": {"raw": "27", "encoded": "27"},
+        });
+        let (credential, _, _) = issuer
+            .issuer_create_credential(&offer, &cred_req, &cred_values.to_string(), None, None)
+            .await
+            .unwrap();
+        let cred_id = prover
+            .prover_store_credential(None, &cred_req_metadata, &credential, &cred_def_json, None)
+            .await
+            .unwrap();
+
+        let proof_req = json!({
+            "nonce": issuer.generate_nonce().await.unwrap(),
+            "name": "proof_req",
+            "version": "1.0",
+            "requested_attributes": {
+                "attr_name": {"name": "name", "restrictions": {"cred_def_id": cred_def_id}}
+            },
+            "requested_predicates": {
+                "pred_age": {"name": "age", "p_type": ">=", "p_value": 18}
+            }
+        })
+        .to_string();
+        let matching: serde_json::Value =
+            serde_json::from_str(&prover.prover_get_credentials_for_proof_req(&proof_req).await.unwrap()).unwrap();
+        assert_eq!(
+            matching["attrs"]["attr_name"][0]["cred_info"]["referent"],
+            json!(cred_id)
+        );
+        assert_eq!(
+            matching["attrs"]["pred_age"][0]["cred_info"]["referent"],
+            json!(cred_id)
+        );
+
+        let requested_credentials = json!({
+            "self_attested_attributes": {},
+            "requested_attributes": {"attr_name": {"cred_id": cred_id, "revealed": true}},
+            "requested_predicates": {"pred_age": {"cred_id": cred_id}}
+        })
+        .to_string();
+        let schemas =
+            json!({ schema_id: serde_json::from_str::<serde_json::Value>(&schema_json).unwrap() }).to_string();
+        let cred_defs =
+            json!({ cred_def_id: serde_json::from_str::<serde_json::Value>(&cred_def_json).unwrap() }).to_string();
+        let proof = prover
+            .prover_create_proof(
+                &proof_req,
+                &requested_credentials,
+                &link_secret_id,
+                &schemas,
+                &cred_defs,
+                None,
+            )
+            .await
+            .unwrap();
+
+        assert!(issuer
+            .verifier_verify_proof(&proof_req, &proof, &schemas, &cred_defs, "null", "null")
+            .await
+            .unwrap());
+    }
+}
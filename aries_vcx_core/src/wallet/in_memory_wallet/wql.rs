@@ -0,0 +1,242 @@
+//! Evaluation of [WQL](https://github.com/hyperledger/indy-sdk/tree/main/docs/design/011-wallet-query-language)
+//! queries against the tags of in-memory records, with the same semantics as vdrtools wallets.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Query {
+    And(Vec<Query>),
+    Or(Vec<Query>),
+    Not(Box<Query>),
+    Eq(String, String),
+    Neq(String, String),
+    Gt(String, String),
+    Gte(String, String),
+    Lt(String, String),
+    Lte(String, String),
+    Like(String, String),
+    In(String, Vec<String>),
+}
+
+impl Query {
+    /// Parses a WQL query, an empty query matching all the records.
+    pub(super) fn parse(query: &str) -> VcxCoreResult<Self> {
+        if query.trim().is_empty() {
+            return Ok(Self::And(Vec::new()));
+        }
+
+        let query: Value = serde_json::from_str(query).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidJson,
+                format!("Invalid WQL query: {}", err),
+            )
+        })?;
+
+        parse_operator(&query)
+    }
+
+    pub(super) fn matches(&self, tags: &HashMap<String, String>) -> bool {
+        let tag = |name: &String| tags.get(name).map(String::as_str);
+
+        match self {
+            Self::And(queries) => queries.iter().all(|query| query.matches(tags)),
+            Self::Or(queries) => queries.iter().any(|query| query.matches(tags)),
+            Self::Not(query) => !query.matches(tags),
+            Self::Eq(name, value) => tag(name) == Some(value.as_str()),
+            Self::Neq(name, value) => tag(name).map_or(false, |tag| tag != value),
+            Self::Gt(name, value) => tag(name).map_or(false, |tag| tag > value.as_str()),
+            Self::Gte(name, value) => tag(name).map_or(false, |tag| tag >= value.as_str()),
+            Self::Lt(name, value) => tag(name).map_or(false, |tag| tag < value.as_str()),
+            Self::Lte(name, value) => tag(name).map_or(false, |tag| tag <= value.as_str()),
+            Self::Like(name, pattern) => tag(name).map_or(false, |tag| like(tag, pattern)),
+            Self::In(name, values) => tag(name).map_or(false, |tag| values.iter().any(|value| value == tag)),
+        }
+    }
+}
+
+fn parse_operator(query: &Value) -> VcxCoreResult<Query> {
+    let map = query
+        .as_object()
+        .ok_or_else(|| invalid_query(format!("expected an object, found {}", query)))?;
+
+    let mut operators = map
+        .iter()
+        .map(|(key, value)| match key.as_str() {
+            "$and" => parse_operators(value).map(Query::And),
+            "$or" => parse_operators(value).map(Query::Or),
+            "$not" => parse_operator(value).map(|query| Query::Not(Box::new(query))),
+            name => parse_tag_operator(name, value),
+        })
+        .collect::<VcxCoreResult<Vec<_>>>()?;
+
+    if operators.len() == 1 {
+        Ok(operators.remove(0))
+    } else {
+        Ok(Query::And(operators))
+    }
+}
+
+fn parse_operators(value: &Value) -> VcxCoreResult<Vec<Query>> {
+    value
+        .as_array()
+        .ok_or_else(|| invalid_query(format!("expected an array of queries, found {}", value)))?
+        .iter()
+        .map(parse_operator)
+        .collect()
+}
+
+fn parse_tag_operator(name: &str, value: &Value) -> VcxCoreResult<Query> {
+    let name = name.to_owned();
+
+    let operator = match value {
+        Value::String(value) => return Ok(Query::Eq(name, value.to_owned())),
+        Value::Object(operator) if operator.len() == 1 => operator,
+        _ => return Err(invalid_query(format!("invalid value of tag {}: {}", name, value))),
+    };
+
+    match operator.iter().next() {
+        Some((op, Value::Array(values))) if op == "$in" => {
+            let values = values
+                .iter()
+                .map(|value| tag_value(&name, value))
+                .collect::<VcxCoreResult<Vec<_>>>()?;
+            Ok(Query::In(name, values))
+        }
+        Some((op, value)) => {
+            let value = tag_value(&name, value)?;
+            match op.as_str() {
+                "$neq" => Ok(Query::Neq(name, value)),
+                "$gt" => plain_tag_operator(op, name, value, Query::Gt),
+                "$gte" => plain_tag_operator(op, name, value, Query::Gte),
+                "$lt" => plain_tag_operator(op, name, value, Query::Lt),
+                "$lte" => plain_tag_operator(op, name, value, Query::Lte),
+                "$like" => plain_tag_operator(op, name, value, Query::Like),
+                op => Err(invalid_query(format!("unsupported operator {}", op))),
+            }
+        }
+        None => Err(invalid_query(format!("missing operator of tag {}", name))),
+    }
+}
+
+/// Comparisons are only supported on unencrypted tags (prefixed with `~`), as vdrtools wallets can't
+/// compare encrypted values.
+fn plain_tag_operator(
+    op: &str,
+    name: String,
+    value: String,
+    build: fn(String, String) -> Query,
+) -> VcxCoreResult<Query> {
+    if name.starts_with('~') {
+        Ok(build(name, value))
+    } else {
+        Err(invalid_query(format!(
+            "operator {} can only be used with unencrypted tags, found {}",
+            op, name
+        )))
+    }
+}
+
+fn tag_value(name: &str, value: &Value) -> VcxCoreResult<String> {
+    value
+        .as_str()
+        .map(ToOwned::to_owned)
+        .ok_or_else(|| invalid_query(format!("value of tag {} must be a string, found {}", name, value)))
+}
+
+/// Matches a value against a SQL `LIKE` pattern, where `%` matches any sequence of characters
+/// and `_` matches a single character.
+fn like(value: &str, pattern: &str) -> bool {
+    let value: Vec<char> = value.chars().collect();
+    let pattern: Vec<char> = pattern.chars().collect();
+
+    // matches[j] is whether the value read so far matches the first j characters of the pattern
+    let mut matches = vec![false; pattern.len() + 1];
+    matches[0] = true;
+    for j in 1..=pattern.len() {
+        matches[j] = matches[j - 1] && pattern[j - 1] == '%';
+    }
+
+    for c in value {
+        let mut next = vec![false; pattern.len() + 1];
+        for j in 1..=pattern.len() {
+            next[j] = match pattern[j - 1] {
+                '%' => next[j - 1] || matches[j],
+                '_' => matches[j - 1],
+                p => matches[j - 1] && p == c,
+            };
+        }
+        matches = next;
+    }
+
+    matches[pattern.len()]
+}
+
+fn invalid_query(msg: String) -> AriesVcxCoreError {
+    AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::InvalidInput,
+        format!("Invalid WQL query: {}", msg),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use super::*;
+
+    fn _tags() -> HashMap<String, String> {
+        HashMap::from([
+            ("schema_id".to_owned(), "schema:1".to_owned()),
+            ("attr::name::marker".to_owned(), "1".to_owned()),
+            ("~age".to_owned(), "27".to_owned()),
+            ("~name".to_owned(), "Alice".to_owned()),
+        ])
+    }
+
+    fn _matches(query: Value) -> bool {
+        Query::parse(&query.to_string()).unwrap().matches(&_tags())
+    }
+
+    #[test]
+    fn test_wql_empty_query_matches_all() {
+        assert!(Query::parse("").unwrap().matches(&_tags()));
+        assert!(_matches(json!({})));
+    }
+
+    #[test]
+    fn test_wql_equality() {
+        assert!(_matches(json!({"schema_id": "schema:1", "attr::name::marker": "1"})));
+        assert!(!_matches(json!({"schema_id": "schema:1", "attr::age::marker": "1"})));
+        assert!(_matches(json!({"schema_id": {"$neq": "schema:2"}})));
+        assert!(!_matches(json!({"cred_def_id": {"$neq": "cred_def:1"}})));
+        assert!(_matches(json!({"schema_id": {"$in": ["schema:2", "schema:1"]}})));
+    }
+
+    #[test]
+    fn test_wql_combinations() {
+        assert!(_matches(json!({
+            "$and": [
+                {"attr::name::marker": "1"},
+                {"$or": [{"schema_id": "schema:2"}, {"~name": "Alice"}]}
+            ]
+        })));
+        assert!(!_matches(json!({"$not": {"schema_id": "schema:1"}})));
+        assert!(!_matches(json!({"$or": []})));
+    }
+
+    #[test]
+    fn test_wql_comparisons_of_plain_tags() {
+        assert!(_matches(json!({"~age": {"$gt": "26"}})));
+        assert!(_matches(json!({"~age": {"$lte": "27"}})));
+        assert!(!_matches(json!({"~age": {"$lt": "27"}})));
+        assert!(_matches(json!({"~name": {"$like": "A%e"}})));
+        assert!(_matches(json!({"~name": {"$like": "_lic_"}})));
+        assert!(!_matches(json!({"~name": {"$like": "Al"}})));
+
+        let err = Query::parse(&json!({"schema_id": {"$gt": "schema:0"}}).to_string()).unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::InvalidInput);
+    }
+}
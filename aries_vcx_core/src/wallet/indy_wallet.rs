@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Mutex;
 use std::thread;
 
 use async_trait::async_trait;
@@ -14,6 +15,14 @@ use crate::{
 use crate::{SearchHandle, WalletHandle};
 
 use super::base_wallet::BaseWallet;
+use super::export_import::ArchiveDid;
+use super::transaction::{TransactionLock, WalletTransaction};
+
+lazy_static! {
+    // IndySdkWallet only wraps a wallet handle and is created anew in many places, so the transaction
+    // locks are kept by wallet handle to be shared by all the instances of a wallet.
+    static ref TRANSACTION_LOCKS: Mutex<HashMap<WalletHandle, TransactionLock>> = Mutex::new(HashMap::new());
+}
 
 #[derive(Debug)]
 pub struct IndySdkWallet {
@@ -24,6 +33,17 @@ impl IndySdkWallet {
     pub fn new(wallet_handle: WalletHandle) -> Self {
         IndySdkWallet { wallet_handle }
     }
+
+    /// Returns all the records of the given type, including the ones managed by vdrtools itself
+    /// (e.g. "Indy::Key") which are not available through [`BaseWallet::iterate_wallet_records`].
+    /// Only meant for migrating the wallet to another storage.
+    pub async fn get_all_records_for_migration(&self, xtype: &str) -> VcxCoreResult<Vec<String>> {
+        indy::wallet::get_all_records_for_migration(self.wallet_handle, xtype).await
+    }
+
+    fn transaction_lock(&self) -> VcxCoreResult<TransactionLock> {
+        Ok(TRANSACTION_LOCKS.lock()?.entry(self.wallet_handle).or_default().clone())
+    }
 }
 
 #[allow(unused_variables)]
@@ -49,6 +69,18 @@ impl BaseWallet for IndySdkWallet {
         indy::keys::libindy_replace_keys_apply(self.wallet_handle, target_did).await
     }
 
+    async fn export_dids(&self) -> VcxCoreResult<Vec<ArchiveDid>> {
+        indy::keys::export_my_dids(self.wallet_handle).await
+    }
+
+    async fn import_did(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        indy::keys::import_my_did(self.wallet_handle, did).await
+    }
+
+    async fn check_did_seed(&self, did: &ArchiveDid) -> VcxCoreResult<()> {
+        indy::keys::check_archived_did_seed(did)
+    }
+
     async fn add_wallet_record(
         &self,
         xtype: &str,
@@ -121,6 +153,14 @@ impl BaseWallet for IndySdkWallet {
         Ok(Box::new(iter))
     }
 
+    async fn begin_transaction(&self) -> VcxCoreResult<WalletTransaction> {
+        Ok(self.transaction_lock()?.begin_transaction().await)
+    }
+
+    async fn commit_transaction(&self, transaction: WalletTransaction) -> VcxCoreResult<()> {
+        indy::wallet::apply_wallet_batch(self.wallet_handle, transaction).await
+    }
+
     async fn sign(&self, my_vk: &str, msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
         indy::signing::sign(self.wallet_handle, my_vk, msg).await
     }
@@ -190,3 +230,159 @@ impl Drop for IndyWalletRecordIterator {
         });
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod unit_tests {
+    use vdrtools::{
+        types::domain::wallet::{CacheConfig, CachingAlgorithm, Config, Credentials, KeyDerivationMethod},
+        Locator,
+    };
+
+    use super::*;
+    use crate::indy::{
+        utils::test_setup::generate_random_name,
+        wallet::{close_wallet, create_and_open_wallet, create_indy_wallet, delete_wallet, WalletConfig},
+    };
+
+    fn _wallet_config() -> WalletConfig {
+        WalletConfig {
+            wallet_name: generate_random_name(),
+            wallet_key: "8dvfYSt5d1taSd6yJdpjq4emkwsPDDLYxkNFysFD2cZY".to_owned(),
+            wallet_key_derivation: "RAW".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_indy_wallet_transaction() {
+        let wallet_config = _wallet_config();
+        create_indy_wallet(&wallet_config).await.unwrap();
+
+        // Records of "type" are cached, reading them back checks the cache as well as the storage
+        let wallet_handle = Locator::instance()
+            .wallet_controller
+            .open(
+                Config {
+                    id: wallet_config.wallet_name.clone(),
+                    storage_type: None,
+                    storage_config: None,
+                    cache: Some(CacheConfig {
+                        size: 10,
+                        entities: vec!["type".to_owned()],
+                        algorithm: CachingAlgorithm::LRU,
+                    }),
+                },
+                Credentials {
+                    key: wallet_config.wallet_key.clone(),
+                    key_derivation_method: KeyDerivationMethod::RAW,
+                    rekey: None,
+                    rekey_derivation_method: KeyDerivationMethod::RAW,
+                    storage_credentials: None,
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        let wallet = IndySdkWallet::new(wallet_handle);
+
+        wallet.add_wallet_record("type", "id1", "value1", None).await.unwrap();
+        assert_eq!(wallet.get_wallet_record_value("type", "id1").await.unwrap(), "value1");
+
+        let mut transaction = wallet.begin_transaction().await.unwrap();
+        transaction.update_wallet_record_value("type", "id1", "new_value1");
+        transaction.add_wallet_record("type", "id2", "value2", None);
+        // Fails, as the record does not exist
+        transaction.delete_wallet_record("type", "id3");
+
+        let err = wallet.commit_transaction(transaction).await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+        assert_eq!(wallet.get_wallet_record_value("type", "id1").await.unwrap(), "value1");
+        let err = wallet.get_wallet_record_value("type", "id2").await.unwrap_err();
+        assert_eq!(err.kind(), AriesVcxCoreErrorKind::WalletRecordNotFound);
+
+        let mut transaction = wallet.begin_transaction().await.unwrap();
+        transaction.update_wallet_record_value("type", "id1", "new_value1");
+        transaction.add_wallet_record("type", "id2", "value2", None);
+
+        wallet.commit_transaction(transaction).await.unwrap();
+        assert_eq!(
+            wallet.get_wallet_record_value("type", "id1").await.unwrap(),
+            "new_value1"
+        );
+        assert_eq!(wallet.get_wallet_record_value("type", "id2").await.unwrap(), "value2");
+
+        close_wallet(wallet_handle).await.unwrap();
+        delete_wallet(&wallet_config).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_indy_wallet_export_import_dids() {
+        let source_config = _wallet_config();
+        let source_handle = create_and_open_wallet(&source_config).await.unwrap();
+        let source = IndySdkWallet::new(source_handle);
+        let target_config = _wallet_config();
+        let target_handle = create_and_open_wallet(&target_config).await.unwrap();
+        let target = IndySdkWallet::new(target_handle);
+
+        let (did, _) = source
+            .create_and_store_my_did(Some("000000000000000000000000Trustee1"), None)
+            .await
+            .unwrap();
+        let (rotated_did, _) = source.create_and_store_my_did(None, None).await.unwrap();
+        source.replace_did_keys_start(&rotated_did).await.unwrap();
+        source.replace_did_keys_apply(&rotated_did).await.unwrap();
+
+        let dids = source.export_dids().await.unwrap();
+        assert_eq!(dids.len(), 2);
+        for exported in dids.iter() {
+            target.import_did(exported).await.unwrap();
+        }
+
+        for did in [did, rotated_did] {
+            let verkey = source.key_for_local_did(&did).await.unwrap();
+            assert_eq!(target.key_for_local_did(&did).await.unwrap(), verkey);
+            assert_eq!(
+                target.sign(&verkey, b"message").await.unwrap(),
+                source.sign(&verkey, b"message").await.unwrap()
+            );
+        }
+
+        close_wallet(source_handle).await.unwrap();
+        delete_wallet(&source_config).await.unwrap();
+        close_wallet(target_handle).await.unwrap();
+        delete_wallet(&target_config).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_indy_wallet_transactions_are_locked_per_wallet() {
+        use futures::FutureExt;
+
+        let first_config = _wallet_config();
+        let first_handle = create_and_open_wallet(&first_config).await.unwrap();
+        let second_config = _wallet_config();
+        let second_handle = create_and_open_wallet(&second_config).await.unwrap();
+
+        let transaction = IndySdkWallet::new(first_handle).begin_transaction().await.unwrap();
+        // Transactions of other wallets don't wait for this one
+        let other_transaction = IndySdkWallet::new(second_handle)
+            .begin_transaction()
+            .now_or_never()
+            .expect("the transaction of another wallet should not be waited for")
+            .unwrap();
+        // but the ones of the same wallet do, even through another instance
+        let next_transaction =
+            tokio::spawn(async move { IndySdkWallet::new(first_handle).begin_transaction().await.map(drop) });
+        tokio::task::yield_now().await;
+        assert!(!next_transaction.is_finished());
+
+        drop(transaction);
+        next_transaction.await.unwrap().unwrap();
+        drop(other_transaction);
+
+        close_wallet(first_handle).await.unwrap();
+        delete_wallet(&first_config).await.unwrap();
+        close_wallet(second_handle).await.unwrap();
+        delete_wallet(&second_config).await.unwrap();
+    }
+}
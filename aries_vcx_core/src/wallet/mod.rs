@@ -1,4 +1,12 @@
 pub mod agency_client_wallet;
+#[cfg(feature = "askar_wallet")]
+pub mod askar_wallet;
 pub mod base_wallet;
+pub mod export_import;
+#[cfg(feature = "in_memory_wallet")]
+pub mod in_memory_wallet;
 #[cfg(feature = "vdrtools")]
 pub mod indy_wallet;
+#[cfg(any(feature = "askar_wallet", feature = "in_memory_wallet"))]
+mod pack;
+pub mod transaction;
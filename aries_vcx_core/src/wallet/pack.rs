@@ -0,0 +1,321 @@
+//! DIDComm v1 envelope (`JWM/1.0`) packing, wire compatible with the vdrtools implementation
+//! of `pack_message` / `unpack_message`, and the Ed25519 key helpers shared by the wallets built on
+//! askar keys.
+
+use aries_askar::{
+    crypto::alg::{Chacha20Types, KeyAlg},
+    kms::{crypto_box, crypto_box_open, crypto_box_random_nonce, crypto_box_seal, crypto_box_seal_open, LocalKey},
+};
+use base64::{
+    alphabet,
+    engine::{
+        general_purpose::{GeneralPurpose, GeneralPurposeConfig, STANDARD, URL_SAFE},
+        DecodePaddingMode,
+    },
+    Engine,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+use super::export_import::ArchiveDid;
+
+const ENC_XCHACHA20POLY1305: &str = "xchacha20poly1305_ietf";
+const TYP_JWM: &str = "JWM/1.0";
+const ALG_AUTHCRYPT: &str = "Authcrypt";
+const ALG_ANONCRYPT: &str = "Anoncrypt";
+
+const CEK_ALG: KeyAlg = KeyAlg::Chacha20(Chacha20Types::XC20P);
+
+/// Length of the Ed25519 seeds accepted when creating DIDs.
+const SEED_LENGTH: usize = 32;
+
+/// Envelopes are encoded with padding (like vdrtools does), but other agents may omit it.
+const URL_SAFE_INDIFFERENT: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct Envelope {
+    protected: String,
+    iv: String,
+    ciphertext: String,
+    tag: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct ProtectedHeader {
+    enc: String,
+    typ: String,
+    alg: String,
+    pub(super) recipients: Vec<Recipient>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct Recipient {
+    encrypted_key: String,
+    pub(super) header: RecipientHeader,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(super) struct RecipientHeader {
+    pub(super) kid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sender: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    iv: Option<String>,
+}
+
+/// The output of `unpack_message`, as returned by vdrtools.
+#[derive(Debug, Serialize)]
+struct UnpackedMessage {
+    message: String,
+    recipient_verkey: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sender_verkey: Option<String>,
+}
+
+/// Packs the message for the given recipient verkeys. The message is authcrypted
+/// if the sender verkey and its private key are provided, and anoncrypted otherwise.
+pub(super) fn pack(sender: Option<(&str, &LocalKey)>, recipient_vks: &[String], msg: &[u8]) -> VcxCoreResult<Vec<u8>> {
+    if recipient_vks.is_empty() {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            "Cannot pack a message without any recipient",
+        ));
+    }
+
+    let cek = LocalKey::generate(CEK_ALG, true)?;
+    let cek_bytes = cek.to_secret_bytes()?;
+
+    let sender = sender
+        .map(|(vk, key)| key.convert_key(KeyAlg::X25519).map(|key| (vk, key)))
+        .transpose()?;
+
+    let recipients = recipient_vks
+        .iter()
+        .map(|recipient_vk| {
+            let recipient_key = public_key_from_verkey(recipient_vk)?.convert_key(KeyAlg::X25519)?;
+
+            let recipient = match &sender {
+                Some((sender_vk, sender_key)) => {
+                    let nonce = crypto_box_random_nonce()?;
+                    let encrypted_key = crypto_box(&recipient_key, sender_key, &cek_bytes, &nonce)?;
+                    let encrypted_sender = crypto_box_seal(&recipient_key, sender_vk.as_bytes())?;
+
+                    Recipient {
+                        encrypted_key: URL_SAFE.encode(encrypted_key),
+                        header: RecipientHeader {
+                            kid: recipient_vk.to_owned(),
+                            sender: Some(URL_SAFE.encode(encrypted_sender)),
+                            iv: Some(URL_SAFE.encode(nonce)),
+                        },
+                    }
+                }
+                None => Recipient {
+                    encrypted_key: URL_SAFE.encode(crypto_box_seal(&recipient_key, &cek_bytes)?),
+                    header: RecipientHeader {
+                        kid: recipient_vk.to_owned(),
+                        sender: None,
+                        iv: None,
+                    },
+                },
+            };
+
+            Ok(recipient)
+        })
+        .collect::<VcxCoreResult<Vec<_>>>()?;
+
+    let alg = if sender.is_some() { ALG_AUTHCRYPT } else { ALG_ANONCRYPT };
+    let header = ProtectedHeader {
+        enc: ENC_XCHACHA20POLY1305.to_owned(),
+        typ: TYP_JWM.to_owned(),
+        alg: alg.to_owned(),
+        recipients,
+    };
+    let protected = URL_SAFE.encode(serde_json::to_vec(&header)?);
+
+    let nonce = cek.aead_random_nonce()?;
+    let encrypted = cek.aead_encrypt(msg, &nonce, protected.as_bytes())?;
+
+    let envelope = Envelope {
+        protected,
+        iv: URL_SAFE.encode(nonce),
+        ciphertext: URL_SAFE.encode(encrypted.ciphertext()),
+        tag: URL_SAFE.encode(encrypted.tag()),
+    };
+
+    Ok(serde_json::to_vec(&envelope)?)
+}
+
+/// Parses an envelope along with its protected header.
+pub(super) fn parse_envelope(msg: &[u8]) -> VcxCoreResult<(Envelope, ProtectedHeader)> {
+    let envelope: Envelope = serde_json::from_slice(msg).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidJson,
+            format!("Cannot deserialize packed message: {}", err),
+        )
+    })?;
+
+    let header: ProtectedHeader = serde_json::from_slice(&decode(&envelope.protected)?).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidJson,
+            format!("Cannot deserialize packed message protected header: {}", err),
+        )
+    })?;
+
+    if header.enc != ENC_XCHACHA20POLY1305 {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Unsupported packed message encryption: {}", header.enc),
+        ));
+    }
+
+    Ok((envelope, header))
+}
+
+/// Decrypts the envelope using the private key of the given recipient,
+/// returning the same JSON output as vdrtools.
+pub(super) fn unpack(
+    envelope: &Envelope,
+    header: &ProtectedHeader,
+    recipient: &Recipient,
+    recipient_key: &LocalKey,
+) -> VcxCoreResult<Vec<u8>> {
+    let recipient_key = recipient_key.convert_key(KeyAlg::X25519)?;
+    let encrypted_key = decode(&recipient.encrypted_key)?;
+
+    let (cek_bytes, sender_verkey) = match header.alg.as_str() {
+        ALG_AUTHCRYPT => {
+            let encrypted_sender = decode(missing_header_field(recipient.header.sender.as_deref(), "sender")?)?;
+            let nonce = decode(missing_header_field(recipient.header.iv.as_deref(), "iv")?)?;
+
+            let sender_vk = crypto_box_seal_open(&recipient_key, &encrypted_sender)?;
+            let sender_vk = String::from_utf8(sender_vk.to_vec()).map_err(|err| {
+                AriesVcxCoreError::from_msg(
+                    AriesVcxCoreErrorKind::InvalidInput,
+                    format!("Packed message sender is not a valid verkey: {}", err),
+                )
+            })?;
+            let sender_key = public_key_from_verkey(&sender_vk)?.convert_key(KeyAlg::X25519)?;
+
+            let cek_bytes = crypto_box_open(&recipient_key, &sender_key, &encrypted_key, &nonce)?;
+            (cek_bytes, Some(sender_vk))
+        }
+        ALG_ANONCRYPT => (crypto_box_seal_open(&recipient_key, &encrypted_key)?, None),
+        alg => {
+            return Err(AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                format!("Unsupported packed message algorithm: {}", alg),
+            ))
+        }
+    };
+
+    let cek = LocalKey::from_secret_bytes(CEK_ALG, &cek_bytes)?;
+
+    // The AEAD ciphertext is expected to be followed by its authentication tag.
+    let mut ciphertext = decode(&envelope.ciphertext)?;
+    ciphertext.extend(decode(&envelope.tag)?);
+    let message = cek.aead_decrypt(
+        ciphertext.as_slice(),
+        &decode(&envelope.iv)?,
+        envelope.protected.as_bytes(),
+    )?;
+
+    let message = String::from_utf8(message.to_vec()).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Unpacked message is not valid UTF-8: {}", err),
+        )
+    })?;
+
+    let unpacked = UnpackedMessage {
+        message,
+        recipient_verkey: recipient.header.kid.clone(),
+        sender_verkey,
+    };
+
+    Ok(serde_json::to_vec(&unpacked)?)
+}
+
+/// Builds the public Ed25519 key corresponding to a base58 encoded verkey.
+pub(super) fn public_key_from_verkey(verkey: &str) -> VcxCoreResult<LocalKey> {
+    let bytes = bs58::decode(verkey).into_vec().map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidVerkey,
+            format!("Verkey {} is not valid base58: {}", verkey, err),
+        )
+    })?;
+
+    Ok(LocalKey::from_public_bytes(KeyAlg::Ed25519, &bytes)?)
+}
+
+/// Converts a seed the same way vdrtools does: the seed is either the raw
+/// [`SEED_LENGTH`] bytes long string, or its base64 encoding (ending with `=`).
+pub(super) fn seed_bytes(seed: &str) -> VcxCoreResult<Vec<u8>> {
+    let bytes = if seed.len() == SEED_LENGTH {
+        seed.as_bytes().to_vec()
+    } else if seed.ends_with('=') {
+        STANDARD.decode(seed).map_err(|err| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidInput,
+                format!("Seed is not valid base64: {}", err),
+            )
+        })?
+    } else {
+        Vec::new()
+    };
+
+    if bytes.len() != SEED_LENGTH {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Seed must be {} bytes long", SEED_LENGTH),
+        ));
+    }
+
+    Ok(bytes)
+}
+
+/// Exports a DID along with the base64 encoded seed of its key, as [`seed_bytes`] accepts it.
+pub(super) fn archive_did(did: &str, verkey: &str, key: &LocalKey) -> VcxCoreResult<ArchiveDid> {
+    Ok(ArchiveDid {
+        did: did.to_owned(),
+        verkey: verkey.to_owned(),
+        seed: STANDARD.encode(&key.to_secret_bytes()?[..]),
+    })
+}
+
+/// Recreates the key of an exported DID from its seed, checking that it matches the verkey.
+pub(super) fn archived_did_key(did: &ArchiveDid) -> VcxCoreResult<LocalKey> {
+    let key = LocalKey::from_secret_bytes(KeyAlg::Ed25519, &seed_bytes(&did.seed)?)?;
+    if bs58::encode(&key.to_public_bytes()?[..]).into_string() != did.verkey {
+        return Err(AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Seed of DID {} does not match its verkey {}", did.did, did.verkey),
+        ));
+    }
+
+    Ok(key)
+}
+
+fn decode(value: &str) -> VcxCoreResult<Vec<u8>> {
+    URL_SAFE_INDIFFERENT.decode(value).map_err(|err| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!("Packed message contains invalid base64: {}", err),
+        )
+    })
+}
+
+fn missing_header_field<'a>(value: Option<&'a str>, field: &str) -> VcxCoreResult<&'a str> {
+    value.ok_or_else(|| {
+        AriesVcxCoreError::from_msg(
+            AriesVcxCoreErrorKind::InvalidInput,
+            format!(
+                "Authcrypted message recipient header is missing the \"{}\" field",
+                field
+            ),
+        )
+    })
+}
@@ -0,0 +1,175 @@
+//! Batches of wallet record writes applied atomically by [`BaseWallet::commit_transaction`].
+//!
+//! Writes are buffered in the [`WalletTransaction`] and only reach the wallet on commit. Records
+//! read through the transaction observe the writes buffered so far, and as only one transaction is
+//! open at a time on a wallet, they are not modified by another transaction before this one ends.
+//!
+//! [`BaseWallet::commit_transaction`]: super::base_wallet::BaseWallet::commit_transaction
+
+use std::{collections::HashMap, sync::Arc};
+
+use tokio::sync::{Mutex, OwnedMutexGuard};
+
+use super::base_wallet::BaseWallet;
+use crate::errors::error::{AriesVcxCoreError, AriesVcxCoreErrorKind, VcxCoreResult};
+
+/// Single record write of a [`WalletTransaction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletOperation {
+    AddRecord {
+        xtype: String,
+        id: String,
+        value: String,
+        tags: Option<HashMap<String, String>>,
+    },
+    UpdateRecordValue {
+        xtype: String,
+        id: String,
+        value: String,
+    },
+    AddRecordTags {
+        xtype: String,
+        id: String,
+        tags: HashMap<String, String>,
+    },
+    UpdateRecordTags {
+        xtype: String,
+        id: String,
+        tags: HashMap<String, String>,
+    },
+    DeleteRecordTags {
+        xtype: String,
+        id: String,
+        tag_names: Vec<String>,
+    },
+    DeleteRecord {
+        xtype: String,
+        id: String,
+    },
+}
+
+/// Lock allowing a single open [`WalletTransaction`] on the wallet owning it, so that the
+/// transactions of different wallets don't wait for each other. Clones share the lock.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionLock(Arc<Mutex<()>>);
+
+impl TransactionLock {
+    /// Begins a transaction once the previous one begun through this lock is committed or rolled
+    /// back.
+    pub async fn begin_transaction(&self) -> WalletTransaction {
+        WalletTransaction {
+            operations: Vec::new(),
+            _lock: self.0.clone().lock_owned().await,
+        }
+    }
+}
+
+/// Ordered record writes started by [`BaseWallet::begin_transaction`], mirroring the record
+/// methods of [`BaseWallet`]. Beginning a transaction waits until the previous one on the same
+/// wallet is committed or rolled back.
+///
+/// [`BaseWallet::begin_transaction`]: super::base_wallet::BaseWallet::begin_transaction
+#[derive(Debug)]
+pub struct WalletTransaction {
+    operations: Vec<WalletOperation>,
+    _lock: OwnedMutexGuard<()>,
+}
+
+impl WalletTransaction {
+    /// Reads the value of a record stored in `wallet`, with the writes buffered in the transaction
+    /// applied.
+    pub async fn get_wallet_record_value(
+        &self,
+        wallet: &dyn BaseWallet,
+        xtype: &str,
+        id: &str,
+    ) -> VcxCoreResult<String> {
+        for operation in self.operations.iter().rev() {
+            match operation {
+                WalletOperation::AddRecord {
+                    xtype: op_xtype,
+                    id: op_id,
+                    value,
+                    ..
+                }
+                | WalletOperation::UpdateRecordValue {
+                    xtype: op_xtype,
+                    id: op_id,
+                    value,
+                } if op_xtype == xtype && op_id == id => return Ok(value.clone()),
+                WalletOperation::DeleteRecord {
+                    xtype: op_xtype,
+                    id: op_id,
+                } if op_xtype == xtype && op_id == id => {
+                    return Err(AriesVcxCoreError::from_msg(
+                        AriesVcxCoreErrorKind::WalletRecordNotFound,
+                        format!("Record {xtype} {id} is deleted by the transaction"),
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        wallet.get_wallet_record_value(xtype, id).await
+    }
+
+    pub fn add_wallet_record(&mut self, xtype: &str, id: &str, value: &str, tags: Option<HashMap<String, String>>) {
+        self.operations.push(WalletOperation::AddRecord {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            value: value.to_string(),
+            tags,
+        });
+    }
+
+    pub fn update_wallet_record_value(&mut self, xtype: &str, id: &str, value: &str) {
+        self.operations.push(WalletOperation::UpdateRecordValue {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    pub fn add_wallet_record_tags(&mut self, xtype: &str, id: &str, tags: HashMap<String, String>) {
+        self.operations.push(WalletOperation::AddRecordTags {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            tags,
+        });
+    }
+
+    pub fn update_wallet_record_tags(&mut self, xtype: &str, id: &str, tags: HashMap<String, String>) {
+        self.operations.push(WalletOperation::UpdateRecordTags {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            tags,
+        });
+    }
+
+    pub fn delete_wallet_record_tags(&mut self, xtype: &str, id: &str, tag_names: Vec<String>) {
+        self.operations.push(WalletOperation::DeleteRecordTags {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+            tag_names,
+        });
+    }
+
+    pub fn delete_wallet_record(&mut self, xtype: &str, id: &str) {
+        self.operations.push(WalletOperation::DeleteRecord {
+            xtype: xtype.to_string(),
+            id: id.to_string(),
+        });
+    }
+
+    pub fn operations(&self) -> &[WalletOperation] {
+        &self.operations
+    }
+
+    pub fn into_operations(self) -> Vec<WalletOperation> {
+        self.operations
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
@@ -37,7 +37,7 @@ impl Serialize for Multibase {
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&self.base.encode(&self.bytes))
+        serializer.serialize_str(&multibase::encode(self.base, &self.bytes))
     }
 }
 
@@ -103,6 +103,13 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_multibase_serde_roundtrip() {
+        let serialized = "\"zQmWvQxTqbG2Z9HPJgG57jjwR154cKhbtJenbyYTWkjgF3e\"";
+        let multibase: Multibase = serde_json::from_str(serialized).unwrap();
+        assert_eq!(serde_json::to_string(&multibase).unwrap(), serialized);
+    }
+
     #[test]
     fn test_multibase_deserialize_invalid() {
         let multibase: Result<Multibase, _> = serde_json::from_str("\"invalidmultibasekey\"");
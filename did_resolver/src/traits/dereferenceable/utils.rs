@@ -0,0 +1,195 @@
+use std::io::Cursor;
+
+use did_doc::schema::{did_doc::DidDocument, service::Service};
+use did_parser::DidUrl;
+use percent_encoding::percent_decode_str;
+use serde::Serialize;
+
+use crate::traits::resolvable::resolution_output::DidResolutionOutput;
+
+use super::{
+    dereferencing_error::DidDereferencingError, dereferencing_metadata::DidDereferencingMetadata,
+    dereferencing_output::DidDereferencingOutput,
+};
+
+fn matches_fragment(id: &str, did_url: &DidUrl, fragment: &str) -> bool {
+    id == did_url.did_url() || id.ends_with(&format!("#{}", fragment))
+}
+
+fn service_by_fragment<'a, E>(
+    did_document: &'a DidDocument<E>,
+    did_url: &DidUrl,
+    fragment: &str,
+) -> Option<&'a Service<E>> {
+    did_document
+        .service()
+        .iter()
+        .find(|service| matches_fragment(&service.id().to_string(), did_url, fragment))
+}
+
+fn fragment_content<E: Serialize>(
+    did_document: &DidDocument<E>,
+    did_url: &DidUrl,
+    fragment: &str,
+) -> Result<Vec<u8>, DidDereferencingError> {
+    let service = service_by_fragment(did_document, did_url, fragment);
+    let verification_method = did_document
+        .verification_method()
+        .iter()
+        .find(|vm| matches_fragment(vm.id().did_url(), did_url, fragment));
+
+    let value = match (service, verification_method) {
+        (Some(service), None) => serde_json::to_vec(service),
+        (None, Some(verification_method)) => serde_json::to_vec(verification_method),
+        (None, None) => return Err(DidDereferencingError::NotFound),
+        (Some(_), Some(_)) => return Err(DidDereferencingError::InvalidDid),
+    };
+    value.map_err(|_| DidDereferencingError::InvalidDid)
+}
+
+// https://www.w3.org/TR/did-core/#example-a-resource-external-to-a-did-document
+fn service_endpoint_content<E>(
+    did_document: &DidDocument<E>,
+    did_url: &DidUrl,
+    service_id: &str,
+) -> Result<Vec<u8>, DidDereferencingError> {
+    let service = service_by_fragment(did_document, did_url, service_id)
+        .ok_or(DidDereferencingError::NotFound)?;
+    let mut endpoint = service.service_endpoint().to_string();
+    if let Some(relative_ref) = did_url.queries().get("relativeRef") {
+        let relative_ref = percent_decode_str(relative_ref)
+            .decode_utf8()
+            .map_err(|_| DidDereferencingError::InvalidDid)?;
+        endpoint = format!(
+            "{}/{}",
+            endpoint.trim_end_matches('/'),
+            relative_ref.trim_start_matches('/')
+        );
+    }
+    Ok(endpoint.into_bytes())
+}
+
+/// Dereferences a DID URL against an already resolved DID document. Fragments select
+/// a verification method or a service, the `service` query (optionally combined with
+/// `relativeRef`) selects a service endpoint.
+pub fn dereference_did_document<E: Default + Serialize>(
+    resolution_output: &DidResolutionOutput<E>,
+    did_url: &DidUrl,
+) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, DidDereferencingError> {
+    let did_document = resolution_output.did_document();
+    let (content, content_type) = match (did_url.fragment(), did_url.queries().get("service")) {
+        (Some(fragment), None) => (
+            fragment_content(did_document, did_url, fragment)?,
+            "application/did+json",
+        ),
+        (None, Some(service_id)) => (
+            service_endpoint_content(did_document, did_url, service_id)?,
+            "text/uri-list",
+        ),
+        _ => return Err(DidDereferencingError::InvalidDid),
+    };
+
+    let dereferencing_metadata = DidDereferencingMetadata::builder()
+        .content_type(content_type.to_string())
+        .build();
+
+    Ok(DidDereferencingOutput::builder(Cursor::new(content))
+        .dereferencing_metadata(dereferencing_metadata)
+        .content_metadata(resolution_output.did_document_metadata().clone())
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use did_doc::schema::verification_method::VerificationMethod;
+    use did_parser::Did;
+    use serde_json::Value;
+
+    fn example_resolution_output() -> DidResolutionOutput<()> {
+        let did = "did:example:123456789abcdefghi";
+        let verification_method = VerificationMethod::builder(
+            DidUrl::parse(format!("{}#keys-1", did)).unwrap(),
+            Did::parse(did.to_string()).unwrap(),
+            "Ed25519VerificationKey2018".to_string(),
+        )
+        .add_extra_field(
+            "publicKeyBase58".to_string(),
+            Value::String("H3C2AVvLMv6gmMNam3uVAjZpfkcJCwDwnZn6z3wXmqPV".to_string()),
+        )
+        .build();
+        let service = Service::builder(
+            format!("{}#files", did).parse().unwrap(),
+            "https://example.com/files/".try_into().unwrap(),
+            Default::default(),
+        )
+        .add_service_type("LinkedDomains".to_string())
+        .unwrap()
+        .build();
+        let did_document = DidDocument::builder(Did::parse(did.to_string()).unwrap())
+            .add_verification_method(verification_method)
+            .add_service(service)
+            .build();
+        DidResolutionOutput::builder(did_document).build()
+    }
+
+    fn dereference(did_url: &str) -> Result<(Vec<u8>, String), DidDereferencingError> {
+        let did_url = DidUrl::parse(did_url.to_string()).unwrap();
+        let output = dereference_did_document(&example_resolution_output(), &did_url)?;
+        Ok((
+            output.content_stream().get_ref().clone(),
+            output
+                .dereferencing_metadata()
+                .content_type()
+                .unwrap()
+                .to_owned(),
+        ))
+    }
+
+    #[test]
+    fn test_dereference_verification_method() {
+        let (content, content_type) = dereference("did:example:123456789abcdefghi#keys-1").unwrap();
+        let content: Value = serde_json::from_slice(&content).unwrap();
+        assert_eq!(content["type"], "Ed25519VerificationKey2018");
+        assert_eq!(content_type, "application/did+json");
+    }
+
+    #[test]
+    fn test_dereference_service_fragment() {
+        let (content, _) = dereference("did:example:123456789abcdefghi#files").unwrap();
+        let content: Value = serde_json::from_slice(&content).unwrap();
+        assert_eq!(content["serviceEndpoint"], "https://example.com/files/");
+    }
+
+    #[test]
+    fn test_dereference_service_endpoint() {
+        let (content, content_type) =
+            dereference("did:example:123456789abcdefghi?service=files&relativeRef=%2Fresume.pdf")
+                .unwrap();
+        assert_eq!(
+            String::from_utf8(content).unwrap(),
+            "https://example.com/files/resume.pdf"
+        );
+        assert_eq!(content_type, "text/uri-list");
+    }
+
+    #[test]
+    fn test_dereference_not_found() {
+        assert_eq!(
+            dereference("did:example:123456789abcdefghi#keys-2").unwrap_err(),
+            DidDereferencingError::NotFound
+        );
+        assert_eq!(
+            dereference("did:example:123456789abcdefghi?service=unknown").unwrap_err(),
+            DidDereferencingError::NotFound
+        );
+    }
+
+    #[test]
+    fn test_dereference_without_fragment_or_service() {
+        assert_eq!(
+            dereference("did:example:123456789abcdefghi").unwrap_err(),
+            DidDereferencingError::InvalidDid
+        );
+    }
+}
@@ -3,11 +3,15 @@ use crate::shared_types::media_type::MediaType;
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct DidResolutionOptions {
     accept: Option<MediaType>,
+    no_cache: bool,
 }
 
 impl DidResolutionOptions {
     pub fn new() -> Self {
-        Self { accept: None }
+        Self {
+            accept: None,
+            no_cache: false,
+        }
     }
 
     pub fn set_accept(mut self, accept: MediaType) -> Self {
@@ -15,7 +19,17 @@ impl DidResolutionOptions {
         self
     }
 
+    // https://w3c-ccg.github.io/did-resolution/#caching
+    pub fn set_no_cache(mut self, no_cache: bool) -> Self {
+        self.no_cache = no_cache;
+        self
+    }
+
     pub fn accept(&self) -> Option<&MediaType> {
         self.accept.as_ref()
     }
+
+    pub fn no_cache(&self) -> bool {
+        self.no_cache
+    }
 }
@@ -0,0 +1,28 @@
+pub mod parsing;
+mod resolution;
+
+use did_resolver::did_doc::error::DidDocumentBuilderError;
+use thiserror::Error;
+
+use self::parsing::ParsingErrorSource;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidKeyError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Representation not supported: {0}")]
+    RepresentationNotSupported(String),
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+    #[error("Invalid public key: {0}")]
+    InvalidPublicKey(String),
+    #[error("Invalid public key length: expected {expected}, got {actual}")]
+    InvalidPublicKeyLength { expected: usize, actual: usize },
+    #[error("Unsupported public key type, multicodec prefix: {0:#x?}")]
+    UnsupportedPublicKeyType(Vec<u8>),
+    #[error("DID Document Builder Error: {0}")]
+    DidDocumentBuilderError(#[from] DidDocumentBuilderError),
+    #[error("Parsing error: {0}")]
+    ParsingError(#[from] ParsingErrorSource),
+}
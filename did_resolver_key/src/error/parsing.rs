@@ -0,0 +1,24 @@
+use did_resolver::did_parser;
+use thiserror::Error;
+
+use super::DidKeyError;
+
+#[derive(Error, Debug)]
+pub enum ParsingErrorSource {
+    #[error("DID parsing error: {0}")]
+    DidParsingError(#[from] did_parser::ParseError),
+    #[error("Multibase decoding error: {0}")]
+    MultibaseError(#[from] multibase::Error),
+}
+
+impl From<did_parser::ParseError> for DidKeyError {
+    fn from(error: did_parser::ParseError) -> Self {
+        DidKeyError::ParsingError(ParsingErrorSource::DidParsingError(error))
+    }
+}
+
+impl From<multibase::Error> for DidKeyError {
+    fn from(error: multibase::Error) -> Self {
+        DidKeyError::ParsingError(ParsingErrorSource::MultibaseError(error))
+    }
+}
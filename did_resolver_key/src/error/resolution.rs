@@ -0,0 +1,33 @@
+use did_resolver::traits::resolvable::{
+    resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+};
+
+use super::DidKeyError;
+
+impl From<&DidKeyError> for DidResolutionError {
+    fn from(err: &DidKeyError) -> Self {
+        match err {
+            DidKeyError::MethodNotSupported(_) => DidResolutionError::MethodNotSupported,
+            DidKeyError::RepresentationNotSupported(_) => {
+                DidResolutionError::RepresentationNotSupported
+            }
+            DidKeyError::InvalidDid(_) | DidKeyError::ParsingError(_) => {
+                DidResolutionError::InvalidDid
+            }
+            DidKeyError::InvalidPublicKey(_) => DidResolutionError::InvalidPublicKey,
+            DidKeyError::InvalidPublicKeyLength { .. } => {
+                DidResolutionError::InvalidPublicKeyLength
+            }
+            DidKeyError::UnsupportedPublicKeyType(_) => {
+                DidResolutionError::UnsupportedPublicKeyType
+            }
+            _ => DidResolutionError::InternalError,
+        }
+    }
+}
+
+impl From<&DidKeyError> for DidResolutionMetadata {
+    fn from(err: &DidKeyError) -> Self {
+        DidResolutionMetadata::builder().error(err.into()).build()
+    }
+}
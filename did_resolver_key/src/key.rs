@@ -0,0 +1,183 @@
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use multibase::Base;
+
+use crate::error::DidKeyError;
+
+const ED25519_MULTICODEC_PREFIX: [u8; 2] = [0xed, 0x01];
+const X25519_MULTICODEC_PREFIX: [u8; 2] = [0xec, 0x01];
+const KEY_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+    Ed25519,
+    X25519,
+}
+
+impl KeyType {
+    fn multicodec_prefix(&self) -> &'static [u8] {
+        match self {
+            KeyType::Ed25519 => &ED25519_MULTICODEC_PREFIX,
+            KeyType::X25519 => &X25519_MULTICODEC_PREFIX,
+        }
+    }
+
+    pub fn verification_method_type(&self) -> &'static str {
+        match self {
+            KeyType::Ed25519 => "Ed25519VerificationKey2020",
+            KeyType::X25519 => "X25519KeyAgreementKey2020",
+        }
+    }
+}
+
+// Public key prefixed with its multicodec identifier, as used in did:key and did:peer
+// https://w3c-ccg.github.io/did-method-key/#format
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MulticodecKey {
+    key_type: KeyType,
+    key: Vec<u8>,
+}
+
+impl MulticodecKey {
+    pub fn new(key_type: KeyType, key: Vec<u8>) -> Result<Self, DidKeyError> {
+        if key.len() != KEY_LENGTH {
+            return Err(DidKeyError::InvalidPublicKeyLength {
+                expected: KEY_LENGTH,
+                actual: key.len(),
+            });
+        }
+        Ok(Self { key_type, key })
+    }
+
+    pub fn from_ed25519_base58(verkey: &str) -> Result<Self, DidKeyError> {
+        let key = Base::Base58Btc.decode(verkey)?;
+        Self::new(KeyType::Ed25519, key)
+    }
+
+    pub fn from_fingerprint(fingerprint: &str) -> Result<Self, DidKeyError> {
+        let (base, bytes) = multibase::decode(fingerprint)?;
+        if base != Base::Base58Btc {
+            return Err(DidKeyError::InvalidPublicKey(format!(
+                "Expected base58btc multibase encoding, got {:?}",
+                base
+            )));
+        }
+        let key_type = if bytes.starts_with(&ED25519_MULTICODEC_PREFIX) {
+            KeyType::Ed25519
+        } else if bytes.starts_with(&X25519_MULTICODEC_PREFIX) {
+            KeyType::X25519
+        } else {
+            return Err(DidKeyError::UnsupportedPublicKeyType(
+                bytes.iter().take(2).copied().collect(),
+            ));
+        };
+        Self::new(
+            key_type,
+            bytes[key_type.multicodec_prefix().len()..].to_vec(),
+        )
+    }
+
+    pub fn key_type(&self) -> KeyType {
+        self.key_type
+    }
+
+    pub fn key(&self) -> &[u8] {
+        self.key.as_ref()
+    }
+
+    pub fn base58(&self) -> String {
+        Base::Base58Btc.encode(&self.key)
+    }
+
+    pub fn fingerprint(&self) -> String {
+        let prefixed = [self.key_type.multicodec_prefix(), self.key.as_slice()].concat();
+        multibase::encode(Base::Base58Btc, prefixed)
+    }
+
+    // Converts an Ed25519 public key to the birationally equivalent X25519 key
+    // https://w3c-ccg.github.io/did-method-key/#encryption-method-creation-algorithm
+    pub fn to_x25519(&self) -> Result<Self, DidKeyError> {
+        match self.key_type {
+            KeyType::X25519 => Ok(self.clone()),
+            KeyType::Ed25519 => {
+                let montgomery = CompressedEdwardsY::from_slice(&self.key)
+                    .decompress()
+                    .ok_or_else(|| {
+                        DidKeyError::InvalidPublicKey(
+                            "Ed25519 key is not a valid curve point".to_string(),
+                        )
+                    })?
+                    .to_montgomery();
+                Self::new(KeyType::X25519, montgomery.to_bytes().to_vec())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519_FINGERPRINT: &str = "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+    const ED25519_DERIVED_X25519_FINGERPRINT: &str =
+        "z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+
+    #[test]
+    fn test_from_fingerprint_ed25519() {
+        let key = MulticodecKey::from_fingerprint(ED25519_FINGERPRINT).unwrap();
+        assert_eq!(key.key_type(), KeyType::Ed25519);
+        assert_eq!(key.key().len(), KEY_LENGTH);
+        assert_eq!(key.fingerprint(), ED25519_FINGERPRINT);
+    }
+
+    #[test]
+    fn test_to_x25519() {
+        let key = MulticodecKey::from_fingerprint(ED25519_FINGERPRINT).unwrap();
+        let x25519 = key.to_x25519().unwrap();
+        assert_eq!(x25519.key_type(), KeyType::X25519);
+        assert_eq!(x25519.fingerprint(), ED25519_DERIVED_X25519_FINGERPRINT);
+    }
+
+    #[test]
+    fn test_from_ed25519_base58() {
+        let key = MulticodecKey::from_fingerprint(ED25519_FINGERPRINT).unwrap();
+        let from_verkey = MulticodecKey::from_ed25519_base58(&key.base58()).unwrap();
+        assert_eq!(from_verkey, key);
+    }
+
+    #[test]
+    fn test_from_fingerprint_unsupported_key_type() {
+        // secp256k1-pub multicodec
+        let key = multibase::encode(
+            Base::Base58Btc,
+            [[0xe7, 0x01].as_slice(), &[1; 33]].concat(),
+        );
+        assert!(matches!(
+            MulticodecKey::from_fingerprint(&key),
+            Err(DidKeyError::UnsupportedPublicKeyType(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_fingerprint_invalid_length() {
+        let key = multibase::encode(
+            Base::Base58Btc,
+            [ED25519_MULTICODEC_PREFIX.as_slice(), &[1; 16]].concat(),
+        );
+        assert!(matches!(
+            MulticodecKey::from_fingerprint(&key),
+            Err(DidKeyError::InvalidPublicKeyLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_fingerprint_invalid_base() {
+        let key = multibase::encode(
+            Base::Base64,
+            [ED25519_MULTICODEC_PREFIX.as_slice(), &[1; 32]].concat(),
+        );
+        assert!(matches!(
+            MulticodecKey::from_fingerprint(&key),
+            Err(DidKeyError::InvalidPublicKey(_))
+        ));
+    }
+}
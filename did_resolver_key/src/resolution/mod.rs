@@ -0,0 +1,5 @@
+mod resolver;
+mod utils;
+
+pub use resolver::DidKeyResolver;
+pub use utils::key_to_did_document;
@@ -0,0 +1,49 @@
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser::Did,
+    error::GenericError,
+    shared_types::media_type::MediaType,
+    traits::resolvable::{
+        resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+
+use crate::{error::DidKeyError, key::MulticodecKey};
+
+use super::utils::key_to_resolution_output;
+
+#[derive(Default)]
+pub struct DidKeyResolver;
+
+impl DidKeyResolver {
+    pub fn new() -> Self {
+        DidKeyResolver
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidKeyResolver {
+    type ExtraFields = ();
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &DidResolutionOptions,
+    ) -> Result<DidResolutionOutput<Self::ExtraFields>, GenericError> {
+        if let Some(accept) = options.accept() {
+            if accept != &MediaType::DidJson {
+                return Err(Box::new(DidKeyError::RepresentationNotSupported(
+                    accept.to_string(),
+                )));
+            }
+        }
+        if did.method() != "key" {
+            return Err(Box::new(DidKeyError::MethodNotSupported(
+                did.method().to_string(),
+            )));
+        }
+        let key = MulticodecKey::from_fingerprint(did.id())?;
+        key_to_resolution_output(did, &key).map_err(|err| err.into())
+    }
+}
@@ -0,0 +1,152 @@
+use did_resolver::{
+    did_doc::schema::{
+        did_doc::DidDocument, types::multibase::Multibase, verification_method::VerificationMethod,
+    },
+    did_parser::{Did, DidUrl},
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+    },
+};
+
+use crate::{
+    error::DidKeyError,
+    key::{KeyType, MulticodecKey},
+};
+
+fn verification_method_from_key(
+    did: &Did,
+    key: &MulticodecKey,
+) -> Result<VerificationMethod, DidKeyError> {
+    let fingerprint = key.fingerprint();
+    Ok(VerificationMethod::builder(
+        DidUrl::parse(format!("{}#{}", did.did(), fingerprint))?,
+        did.clone(),
+        key.key_type().verification_method_type().to_string(),
+    )
+    .add_public_key_multibase(Multibase::new(fingerprint)?)
+    .build())
+}
+
+// https://w3c-ccg.github.io/did-method-key/#document-creation-algorithm
+pub fn key_to_did_document<E>(
+    did: &Did,
+    key: &MulticodecKey,
+) -> Result<DidDocument<E>, DidKeyError> {
+    let verification_method = verification_method_from_key(did, key)?;
+    let builder = DidDocument::builder(did.clone());
+    let ddo = match key.key_type() {
+        KeyType::Ed25519 => {
+            let key_agreement = verification_method_from_key(did, &key.to_x25519()?)?;
+            let vm_id = verification_method.id().clone();
+            builder
+                .add_verification_method(verification_method)
+                .add_authentication_reference(vm_id.clone())
+                .add_assertion_method_reference(vm_id.clone())
+                .add_capability_invocation_refrence(vm_id.clone())
+                .add_capability_delegation_refrence(vm_id)
+                .add_key_agreement(key_agreement)
+                .build()
+        }
+        KeyType::X25519 => {
+            let vm_id = verification_method.id().clone();
+            builder
+                .add_verification_method(verification_method)
+                .add_key_agreement_refrence(vm_id)
+                .build()
+        }
+    };
+    Ok(ddo)
+}
+
+pub(super) fn key_to_resolution_output(
+    did: &Did,
+    key: &MulticodecKey,
+) -> Result<DidResolutionOutput<()>, DidKeyError> {
+    let ddo = key_to_did_document(did, key)?;
+
+    let resolution_metadata = DidResolutionMetadata::builder()
+        .content_type("application/did+json".to_string())
+        .build();
+
+    Ok(DidResolutionOutput::builder(ddo)
+        .did_resolution_metadata(resolution_metadata)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use did_resolver::did_doc::schema::verification_method::VerificationMethodKind;
+
+    const ED25519_DID: &str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+    const X25519_DID: &str = "did:key:z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p";
+
+    fn resolve(did: &str) -> DidDocument<()> {
+        let did = Did::parse(did.to_string()).unwrap();
+        let key = MulticodecKey::from_fingerprint(did.id()).unwrap();
+        key_to_did_document(&did, &key).unwrap()
+    }
+
+    #[test]
+    fn test_ed25519_did_document() {
+        let ddo = resolve(ED25519_DID);
+        let vm_id = format!(
+            "{}#z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK",
+            ED25519_DID
+        );
+
+        assert_eq!(ddo.id().to_string(), ED25519_DID);
+        assert_eq!(ddo.verification_method().len(), 1);
+        let vm = &ddo.verification_method()[0];
+        assert_eq!(vm.id().to_string(), vm_id);
+        assert_eq!(vm.verification_method_type(), "Ed25519VerificationKey2020");
+        assert_eq!(
+            serde_json::to_value(vm).unwrap()["publicKeyMultibase"],
+            "z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"
+        );
+
+        let vm_references = [VerificationMethodKind::Resolvable(
+            DidUrl::parse(vm_id).unwrap(),
+        )];
+        assert_eq!(ddo.authentication(), &vm_references);
+        assert_eq!(ddo.assertion_method(), &vm_references);
+        assert_eq!(ddo.capability_invocation(), &vm_references);
+        assert_eq!(ddo.capability_delegation(), &vm_references);
+
+        assert_eq!(ddo.key_agreement().len(), 1);
+        match &ddo.key_agreement()[0] {
+            VerificationMethodKind::Resolved(key_agreement) => {
+                assert_eq!(
+                    key_agreement.id().to_string(),
+                    format!(
+                        "{}#z6LSj72tK8brWgZja8NLRwPigth2T9QRiG1uH9oKZuKjdh9p",
+                        ED25519_DID
+                    )
+                );
+                assert_eq!(
+                    key_agreement.verification_method_type(),
+                    "X25519KeyAgreementKey2020"
+                );
+            }
+            VerificationMethodKind::Resolvable(_) => panic!("Expected embedded key agreement"),
+        }
+    }
+
+    #[test]
+    fn test_x25519_did_document() {
+        let ddo = resolve(X25519_DID);
+
+        assert_eq!(ddo.verification_method().len(), 1);
+        assert_eq!(
+            ddo.verification_method()[0].verification_method_type(),
+            "X25519KeyAgreementKey2020"
+        );
+        assert!(ddo.authentication().is_empty());
+        assert_eq!(
+            ddo.key_agreement(),
+            &[VerificationMethodKind::Resolvable(
+                ddo.verification_method()[0].id().clone()
+            )]
+        );
+    }
+}
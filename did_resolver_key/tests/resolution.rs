@@ -0,0 +1,79 @@
+use did_resolver::did_parser::Did;
+use did_resolver::traits::resolvable::{
+    resolution_error::DidResolutionError, resolution_options::DidResolutionOptions, DidResolvable,
+};
+use did_resolver_key::{error::DidKeyError, resolution::DidKeyResolver};
+use did_resolver_registry::ResolverRegistry;
+use tokio_test::assert_ok;
+
+const DID_KEY: &str = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+
+#[tokio::test]
+async fn test_did_key_resolver() {
+    let resolver = DidKeyResolver::new();
+    let did = Did::parse(DID_KEY.to_string()).unwrap();
+
+    let output = assert_ok!(
+        resolver
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+    );
+    let did_document = output.did_document();
+    assert_eq!(did_document.id().to_string(), DID_KEY);
+    assert_eq!(did_document.verification_method().len(), 1);
+    assert_eq!(did_document.authentication().len(), 1);
+    assert_eq!(did_document.key_agreement().len(), 1);
+    assert_eq!(
+        output.did_resolution_metadata().content_type(),
+        Some(&"application/did+json".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_did_key_resolver_through_registry() {
+    let mut registry = ResolverRegistry::new();
+    registry.register_resolver("key".to_string(), Box::new(DidKeyResolver::new()));
+
+    let did = Did::parse(DID_KEY.to_string()).unwrap();
+    let output = assert_ok!(
+        registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+    );
+    assert_eq!(output.did_document().id().to_string(), DID_KEY);
+}
+
+#[tokio::test]
+async fn test_did_key_resolver_unsupported_method() {
+    let resolver = DidKeyResolver::new();
+    let did = Did::parse("did:web:example.com".to_string()).unwrap();
+
+    let error = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    let error = error.downcast_ref::<DidKeyError>().unwrap();
+    assert!(matches!(error, DidKeyError::MethodNotSupported(_)));
+    assert_eq!(
+        DidResolutionError::from(error),
+        DidResolutionError::MethodNotSupported
+    );
+}
+
+#[tokio::test]
+async fn test_did_key_resolver_unsupported_key_type() {
+    let resolver = DidKeyResolver::new();
+    // secp256k1 did:key
+    let did = Did::parse("did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme".to_string())
+        .unwrap();
+
+    let error = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    let error = error.downcast_ref::<DidKeyError>().unwrap();
+    assert_eq!(
+        DidResolutionError::from(error),
+        DidResolutionError::UnsupportedPublicKeyType
+    );
+}
@@ -0,0 +1,29 @@
+pub mod parsing;
+mod resolution;
+
+use did_resolver::did_doc::error::DidDocumentBuilderError;
+use did_resolver_key::error::DidKeyError;
+use thiserror::Error;
+
+use self::parsing::ParsingErrorSource;
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum DidPeerError {
+    #[error("DID method not supported: {0}")]
+    MethodNotSupported(String),
+    #[error("Representation not supported: {0}")]
+    RepresentationNotSupported(String),
+    #[error("Invalid DID: {0}")]
+    InvalidDid(String),
+    #[error("Unsupported numalgo: {0}")]
+    UnsupportedNumalgo(char),
+    #[error("Unsupported purpose: {0}")]
+    UnsupportedPurpose(char),
+    #[error("Key error: {0}")]
+    KeyError(#[from] DidKeyError),
+    #[error("DID Document Builder Error: {0}")]
+    DidDocumentBuilderError(#[from] DidDocumentBuilderError),
+    #[error("Parsing error: {0}")]
+    ParsingError(#[from] ParsingErrorSource),
+}
@@ -0,0 +1,28 @@
+use did_resolver::traits::resolvable::{
+    resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+};
+
+use super::DidPeerError;
+
+impl From<&DidPeerError> for DidResolutionError {
+    fn from(err: &DidPeerError) -> Self {
+        match err {
+            DidPeerError::MethodNotSupported(_) => DidResolutionError::MethodNotSupported,
+            DidPeerError::RepresentationNotSupported(_) => {
+                DidResolutionError::RepresentationNotSupported
+            }
+            DidPeerError::InvalidDid(_)
+            | DidPeerError::UnsupportedNumalgo(_)
+            | DidPeerError::UnsupportedPurpose(_)
+            | DidPeerError::ParsingError(_) => DidResolutionError::InvalidDid,
+            DidPeerError::KeyError(err) => err.into(),
+            _ => DidResolutionError::InternalError,
+        }
+    }
+}
+
+impl From<&DidPeerError> for DidResolutionMetadata {
+    fn from(err: &DidPeerError) -> Self {
+        DidResolutionMetadata::builder().error(err.into()).build()
+    }
+}
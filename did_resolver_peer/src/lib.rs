@@ -0,0 +1,5 @@
+pub extern crate did_resolver;
+
+pub mod error;
+pub mod peer_did;
+pub mod resolution;
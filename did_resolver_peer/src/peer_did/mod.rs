@@ -0,0 +1,231 @@
+mod purpose;
+mod service;
+
+use did_resolver::did_parser::Did;
+use did_resolver_key::key::MulticodecKey;
+
+use crate::error::DidPeerError;
+
+pub use purpose::Purpose;
+pub use service::{ServiceAbbreviated, ServiceEndpointAbbreviated};
+
+const PEER_METHOD: &str = "peer";
+
+// https://identity.foundation/peer-did-method-spec/
+#[derive(Debug, Clone, PartialEq)]
+pub enum PeerDid {
+    Numalgo0(MulticodecKey),
+    Numalgo2 {
+        keys: Vec<(Purpose, MulticodecKey)>,
+        services: Vec<ServiceAbbreviated>,
+    },
+}
+
+impl PeerDid {
+    pub fn parse(did: &Did) -> Result<Self, DidPeerError> {
+        if did.method() != PEER_METHOD {
+            return Err(DidPeerError::MethodNotSupported(did.method().to_string()));
+        }
+        let mut chars = did.id().chars();
+        match chars.next() {
+            Some('0') => Ok(PeerDid::Numalgo0(MulticodecKey::from_fingerprint(
+                chars.as_str(),
+            )?)),
+            Some('2') => Self::parse_numalgo2(chars.as_str()),
+            Some(numalgo) => Err(DidPeerError::UnsupportedNumalgo(numalgo)),
+            None => Err(DidPeerError::InvalidDid(did.did().to_string())),
+        }
+    }
+
+    fn parse_numalgo2(elements: &str) -> Result<Self, DidPeerError> {
+        let mut keys = Vec::new();
+        let mut services = Vec::new();
+        for element in elements.split('.').filter(|element| !element.is_empty()) {
+            let mut chars = element.chars();
+            let purpose = match chars.next() {
+                Some(code) => Purpose::try_from(code)?,
+                None => continue,
+            };
+            match purpose {
+                Purpose::Service => services.extend(ServiceAbbreviated::decode(chars.as_str())?),
+                purpose => keys.push((purpose, MulticodecKey::from_fingerprint(chars.as_str())?)),
+            }
+        }
+        if keys.is_empty() {
+            return Err(DidPeerError::InvalidDid(
+                "Numalgo 2 peer DID contains no keys".to_string(),
+            ));
+        }
+        Ok(PeerDid::Numalgo2 { keys, services })
+    }
+
+    pub fn to_did(&self) -> Result<Did, DidPeerError> {
+        let id = match self {
+            PeerDid::Numalgo0(key) => format!("0{}", key.fingerprint()),
+            PeerDid::Numalgo2 { keys, services } => {
+                let mut id = "2".to_string();
+                for (purpose, key) in keys {
+                    id.push_str(&format!(".{}{}", purpose, key.fingerprint()));
+                }
+                for service in services {
+                    id.push_str(&format!(".{}{}", Purpose::Service, service.encode()?));
+                }
+                id
+            }
+        };
+        Ok(Did::parse(format!("did:{}:{}", PEER_METHOD, id))?)
+    }
+}
+
+pub fn generate_numalgo0(key: MulticodecKey) -> Result<Did, DidPeerError> {
+    PeerDid::Numalgo0(key).to_did()
+}
+
+pub fn generate_numalgo2(
+    keys: Vec<(Purpose, MulticodecKey)>,
+    services: Vec<ServiceAbbreviated>,
+) -> Result<Did, DidPeerError> {
+    if keys.iter().any(|(purpose, _)| *purpose == Purpose::Service) {
+        return Err(DidPeerError::UnsupportedPurpose(Purpose::Service.code()));
+    }
+    PeerDid::Numalgo2 { keys, services }.to_did()
+}
+
+// Builds a numalgo 2 peer DID for a DIDComm v1 agent identified by its Ed25519 verkey,
+// deriving the X25519 key agreement key from it
+pub fn generate_numalgo2_from_verkey(
+    verkey: &str,
+    service_endpoint: String,
+    routing_keys: Vec<String>,
+) -> Result<Did, DidPeerError> {
+    let verification_key = MulticodecKey::from_ed25519_base58(verkey)?;
+    let encryption_key = verification_key.to_x25519()?;
+    let routing_keys = routing_keys
+        .into_iter()
+        .map(|key| {
+            if key.starts_with("did:") {
+                Ok(key)
+            } else {
+                let fingerprint = MulticodecKey::from_ed25519_base58(&key)?.fingerprint();
+                Ok(format!("did:key:{}#{}", fingerprint, fingerprint))
+            }
+        })
+        .collect::<Result<Vec<_>, DidPeerError>>()?;
+    let service = ServiceAbbreviated::new("DIDCommMessaging".to_string(), service_endpoint)
+        .set_routing_keys(routing_keys)
+        .set_accept(vec!["didcomm/aip2;env=rfc19".to_string()]);
+    generate_numalgo2(
+        vec![
+            (Purpose::Encryption, encryption_key),
+            (Purpose::Verification, verification_key),
+        ],
+        vec![service],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use did_resolver_key::key::KeyType;
+
+    const DID_PEER_0: &str = "did:peer:0z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V";
+    const DID_PEER_2: &str = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+
+    #[test]
+    fn test_parse_numalgo0() {
+        let did = Did::parse(DID_PEER_0.to_string()).unwrap();
+        match PeerDid::parse(&did).unwrap() {
+            PeerDid::Numalgo0(key) => {
+                assert_eq!(key.key_type(), KeyType::Ed25519);
+                assert_eq!(
+                    key.fingerprint(),
+                    "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+                );
+            }
+            _ => panic!("Expected numalgo 0 peer DID"),
+        }
+    }
+
+    #[test]
+    fn test_parse_numalgo2() {
+        let did = Did::parse(DID_PEER_2.to_string()).unwrap();
+        match PeerDid::parse(&did).unwrap() {
+            PeerDid::Numalgo2 { keys, services } => {
+                assert_eq!(keys.len(), 3);
+                assert_eq!(keys[0].0, Purpose::Encryption);
+                assert_eq!(keys[0].1.key_type(), KeyType::X25519);
+                assert_eq!(keys[1].0, Purpose::Verification);
+                assert_eq!(keys[2].0, Purpose::Verification);
+                assert_eq!(services.len(), 1);
+                assert_eq!(services[0].service_type(), "DIDCommMessaging");
+                assert_eq!(
+                    services[0].service_endpoint(),
+                    "https://example.com/endpoint"
+                );
+            }
+            _ => panic!("Expected numalgo 2 peer DID"),
+        }
+    }
+
+    #[test]
+    fn test_numalgo2_roundtrip() {
+        let did = Did::parse(DID_PEER_2.to_string()).unwrap();
+        let peer_did = PeerDid::parse(&did).unwrap();
+        assert_eq!(peer_did.to_did().unwrap(), did);
+    }
+
+    #[test]
+    fn test_parse_unsupported_numalgo() {
+        let did =
+            Did::parse("did:peer:1zQmZMygzYqNwU6Uhmewx5Xepf2VLp5S4HLSwwgf2aiKZuwa".to_string())
+                .unwrap();
+        assert!(matches!(
+            PeerDid::parse(&did),
+            Err(DidPeerError::UnsupportedNumalgo('1'))
+        ));
+    }
+
+    #[test]
+    fn test_parse_unsupported_purpose() {
+        let did =
+            Did::parse("did:peer:2.Xz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V".to_string())
+                .unwrap();
+        assert!(matches!(
+            PeerDid::parse(&did),
+            Err(DidPeerError::UnsupportedPurpose('X'))
+        ));
+    }
+
+    #[test]
+    fn test_generate_numalgo2_from_verkey() {
+        let verkey =
+            MulticodecKey::from_fingerprint("z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V")
+                .unwrap()
+                .base58();
+        let routing_key =
+            MulticodecKey::from_fingerprint("z6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg")
+                .unwrap()
+                .base58();
+
+        let did = generate_numalgo2_from_verkey(
+            &verkey,
+            "https://example.com/endpoint".to_string(),
+            vec![routing_key],
+        )
+        .unwrap();
+
+        match PeerDid::parse(&did).unwrap() {
+            PeerDid::Numalgo2 { keys, services } => {
+                assert_eq!(keys.len(), 2);
+                assert_eq!(keys[0].0, Purpose::Encryption);
+                assert_eq!(keys[1].0, Purpose::Verification);
+                assert_eq!(keys[1].1.base58(), verkey);
+                assert_eq!(
+                    services[0].routing_keys(),
+                    &["did:key:z6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg#z6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg"]
+                );
+            }
+            _ => panic!("Expected numalgo 2 peer DID"),
+        }
+    }
+}
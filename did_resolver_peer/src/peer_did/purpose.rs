@@ -0,0 +1,50 @@
+use std::fmt::Display;
+
+use crate::error::DidPeerError;
+
+// Purpose codes prefixing each element of a numalgo 2 peer DID
+// https://identity.foundation/peer-did-method-spec/#method-2-multiple-inception-key-without-doc
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Purpose {
+    Assertion,
+    Encryption,
+    Verification,
+    CapabilityInvocation,
+    CapabilityDelegation,
+    Service,
+}
+
+impl Purpose {
+    pub fn code(&self) -> char {
+        match self {
+            Purpose::Assertion => 'A',
+            Purpose::Encryption => 'E',
+            Purpose::Verification => 'V',
+            Purpose::CapabilityInvocation => 'I',
+            Purpose::CapabilityDelegation => 'D',
+            Purpose::Service => 'S',
+        }
+    }
+}
+
+impl TryFrom<char> for Purpose {
+    type Error = DidPeerError;
+
+    fn try_from(code: char) -> Result<Self, Self::Error> {
+        match code {
+            'A' => Ok(Purpose::Assertion),
+            'E' => Ok(Purpose::Encryption),
+            'V' => Ok(Purpose::Verification),
+            'I' => Ok(Purpose::CapabilityInvocation),
+            'D' => Ok(Purpose::CapabilityDelegation),
+            'S' => Ok(Purpose::Service),
+            code => Err(DidPeerError::UnsupportedPurpose(code)),
+        }
+    }
+}
+
+impl Display for Purpose {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
@@ -0,0 +1,158 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+
+use crate::error::DidPeerError;
+
+const DIDCOMM_MESSAGING_TYPE: &str = "DIDCommMessaging";
+const DIDCOMM_MESSAGING_TYPE_ABBREVIATED: &str = "dm";
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum ServiceEndpointAbbreviated {
+    Uri(String),
+    Object {
+        uri: String,
+        #[serde(rename = "r", default, skip_serializing_if = "Vec::is_empty")]
+        routing_keys: Vec<String>,
+        #[serde(rename = "a", default, skip_serializing_if = "Vec::is_empty")]
+        accept: Vec<String>,
+    },
+}
+
+// Service block of a numalgo 2 peer DID with the abbreviated keys and values
+// https://identity.foundation/peer-did-method-spec/#generating-a-didpeer2
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ServiceAbbreviated {
+    #[serde(rename = "t")]
+    service_type: String,
+    #[serde(rename = "s")]
+    service_endpoint: ServiceEndpointAbbreviated,
+    #[serde(rename = "r", default, skip_serializing_if = "Vec::is_empty")]
+    routing_keys: Vec<String>,
+    #[serde(rename = "a", default, skip_serializing_if = "Vec::is_empty")]
+    accept: Vec<String>,
+}
+
+impl ServiceAbbreviated {
+    pub fn new(service_type: String, service_endpoint: String) -> Self {
+        let service_type = if service_type == DIDCOMM_MESSAGING_TYPE {
+            DIDCOMM_MESSAGING_TYPE_ABBREVIATED.to_string()
+        } else {
+            service_type
+        };
+        Self {
+            service_type,
+            service_endpoint: ServiceEndpointAbbreviated::Uri(service_endpoint),
+            routing_keys: Vec::new(),
+            accept: Vec::new(),
+        }
+    }
+
+    pub fn set_routing_keys(mut self, routing_keys: Vec<String>) -> Self {
+        self.routing_keys = routing_keys;
+        self
+    }
+
+    pub fn set_accept(mut self, accept: Vec<String>) -> Self {
+        self.accept = accept;
+        self
+    }
+
+    pub fn service_type(&self) -> &str {
+        if self.service_type == DIDCOMM_MESSAGING_TYPE_ABBREVIATED {
+            DIDCOMM_MESSAGING_TYPE
+        } else {
+            self.service_type.as_ref()
+        }
+    }
+
+    pub fn service_endpoint(&self) -> &str {
+        match &self.service_endpoint {
+            ServiceEndpointAbbreviated::Uri(uri) => uri.as_ref(),
+            ServiceEndpointAbbreviated::Object { uri, .. } => uri.as_ref(),
+        }
+    }
+
+    pub fn routing_keys(&self) -> &[String] {
+        match &self.service_endpoint {
+            ServiceEndpointAbbreviated::Object { routing_keys, .. } if !routing_keys.is_empty() => {
+                routing_keys.as_ref()
+            }
+            _ => self.routing_keys.as_ref(),
+        }
+    }
+
+    pub fn accept(&self) -> &[String] {
+        match &self.service_endpoint {
+            ServiceEndpointAbbreviated::Object { accept, .. } if !accept.is_empty() => {
+                accept.as_ref()
+            }
+            _ => self.accept.as_ref(),
+        }
+    }
+
+    pub(crate) fn encode(&self) -> Result<String, DidPeerError> {
+        Ok(URL_SAFE_NO_PAD.encode(serde_json::to_vec(self)?))
+    }
+
+    // A single encoded service element may contain either one service or a list of them
+    pub(crate) fn decode(encoded: &str) -> Result<Vec<Self>, DidPeerError> {
+        let decoded = URL_SAFE_NO_PAD.decode(encoded.trim_end_matches('='))?;
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrList {
+            One(ServiceAbbreviated),
+            List(Vec<ServiceAbbreviated>),
+        }
+        match serde_json::from_slice(&decoded)? {
+            OneOrList::One(service) => Ok(vec![service]),
+            OneOrList::List(services) => Ok(services),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_encode_decode() {
+        let service = ServiceAbbreviated::new(
+            "DIDCommMessaging".to_string(),
+            "https://example.com/endpoint".to_string(),
+        )
+        .set_routing_keys(vec!["did:example:somemediator#somekey".to_string()])
+        .set_accept(vec!["didcomm/v2".to_string()]);
+
+        let encoded = service.encode().unwrap();
+        assert_eq!(
+            encoded,
+            "eyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIl19"
+        );
+        assert_eq!(ServiceAbbreviated::decode(&encoded).unwrap(), vec![service]);
+    }
+
+    #[test]
+    fn test_service_decode_endpoint_object() {
+        let json = r#"{"t":"dm","s":{"uri":"https://example.com","r":["did:example:mediator#key-1"],"a":["didcomm/v2"]}}"#;
+        let encoded = URL_SAFE_NO_PAD.encode(json);
+
+        let services = ServiceAbbreviated::decode(&encoded).unwrap();
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].service_type(), "DIDCommMessaging");
+        assert_eq!(services[0].service_endpoint(), "https://example.com");
+        assert_eq!(services[0].routing_keys(), &["did:example:mediator#key-1"]);
+        assert_eq!(services[0].accept(), &["didcomm/v2"]);
+    }
+
+    #[test]
+    fn test_service_decode_list() {
+        let json = r#"[{"t":"dm","s":"https://example.com/1"},{"t":"did-communication","s":"https://example.com/2"}]"#;
+        let encoded = URL_SAFE_NO_PAD.encode(json);
+
+        let services = ServiceAbbreviated::decode(&encoded).unwrap();
+        assert_eq!(services.len(), 2);
+        assert_eq!(services[1].service_type(), "did-communication");
+        assert_eq!(services[1].service_endpoint(), "https://example.com/2");
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtraFieldsPeer {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    routing_keys: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    accept: Vec<String>,
+}
+
+impl ExtraFieldsPeer {
+    pub fn new(routing_keys: Vec<String>, accept: Vec<String>) -> Self {
+        Self {
+            routing_keys,
+            accept,
+        }
+    }
+
+    pub fn routing_keys(&self) -> &[String] {
+        self.routing_keys.as_ref()
+    }
+
+    pub fn accept(&self) -> &[String] {
+        self.accept.as_ref()
+    }
+}
@@ -0,0 +1,7 @@
+mod extra_fields;
+mod resolver;
+mod utils;
+
+pub use extra_fields::ExtraFieldsPeer;
+pub use resolver::DidPeerResolver;
+pub use utils::peer_did_to_did_document;
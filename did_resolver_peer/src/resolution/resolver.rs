@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser::Did,
+    error::GenericError,
+    shared_types::media_type::MediaType,
+    traits::resolvable::{
+        resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+
+use crate::error::DidPeerError;
+
+use super::{utils::did_to_resolution_output, ExtraFieldsPeer};
+
+#[derive(Default)]
+pub struct DidPeerResolver;
+
+impl DidPeerResolver {
+    pub fn new() -> Self {
+        DidPeerResolver
+    }
+}
+
+#[async_trait]
+impl DidResolvable for DidPeerResolver {
+    type ExtraFields = ExtraFieldsPeer;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &DidResolutionOptions,
+    ) -> Result<DidResolutionOutput<Self::ExtraFields>, GenericError> {
+        if let Some(accept) = options.accept() {
+            if accept != &MediaType::DidJson {
+                return Err(Box::new(DidPeerError::RepresentationNotSupported(
+                    accept.to_string(),
+                )));
+            }
+        }
+        did_to_resolution_output(did).map_err(|err| err.into())
+    }
+}
@@ -0,0 +1,184 @@
+use did_resolver::{
+    did_doc::schema::{
+        did_doc::{DidDocument, DidDocumentBuilder},
+        service::Service,
+        types::{multibase::Multibase, uri::Uri},
+        verification_method::VerificationMethod,
+    },
+    did_parser::{Did, DidUrl},
+    traits::resolvable::{
+        resolution_metadata::DidResolutionMetadata, resolution_output::DidResolutionOutput,
+    },
+};
+use did_resolver_key::resolution::key_to_did_document;
+
+use crate::{
+    error::DidPeerError,
+    peer_did::{PeerDid, Purpose, ServiceAbbreviated},
+};
+
+use super::ExtraFieldsPeer;
+
+fn service_id(did: &Did, index: usize) -> Result<Uri, DidPeerError> {
+    let id = if index == 0 {
+        format!("{}#service", did.did())
+    } else {
+        format!("{}#service-{}", did.did(), index)
+    };
+    Ok(Uri::new(&id)?)
+}
+
+fn expand_service(
+    did: &Did,
+    index: usize,
+    service: &ServiceAbbreviated,
+) -> Result<Service<ExtraFieldsPeer>, DidPeerError> {
+    let extra = ExtraFieldsPeer::new(service.routing_keys().to_vec(), service.accept().to_vec());
+    Ok(Service::builder(
+        service_id(did, index)?,
+        service.service_endpoint().try_into()?,
+        extra,
+    )
+    .add_service_type(service.service_type().to_string())?
+    .build())
+}
+
+fn add_key_reference(
+    builder: DidDocumentBuilder<ExtraFieldsPeer>,
+    purpose: Purpose,
+    reference: DidUrl,
+) -> DidDocumentBuilder<ExtraFieldsPeer> {
+    match purpose {
+        Purpose::Assertion => builder.add_assertion_method_reference(reference),
+        Purpose::Encryption => builder.add_key_agreement_refrence(reference),
+        Purpose::Verification => builder.add_authentication_reference(reference),
+        Purpose::CapabilityInvocation => builder.add_capability_invocation_refrence(reference),
+        Purpose::CapabilityDelegation => builder.add_capability_delegation_refrence(reference),
+        Purpose::Service => builder,
+    }
+}
+
+// https://identity.foundation/peer-did-method-spec/#resolving-a-didpeer2
+pub fn peer_did_to_did_document(
+    did: &Did,
+    peer_did: &PeerDid,
+) -> Result<DidDocument<ExtraFieldsPeer>, DidPeerError> {
+    let (keys, services) = match peer_did {
+        PeerDid::Numalgo0(key) => return Ok(key_to_did_document(did, key)?),
+        PeerDid::Numalgo2 { keys, services } => (keys, services),
+    };
+
+    let mut builder = DidDocument::builder(did.clone());
+    for (index, (purpose, key)) in keys.iter().enumerate() {
+        let id = DidUrl::parse(format!("{}#key-{}", did.did(), index + 1))?;
+        let verification_method = VerificationMethod::builder(
+            id.clone(),
+            did.clone(),
+            key.key_type().verification_method_type().to_string(),
+        )
+        .add_public_key_multibase(Multibase::new(key.fingerprint())?)
+        .build();
+        builder = add_key_reference(
+            builder.add_verification_method(verification_method),
+            *purpose,
+            id,
+        );
+    }
+    for (index, service) in services.iter().enumerate() {
+        builder = builder.add_service(expand_service(did, index, service)?);
+    }
+    Ok(builder.build())
+}
+
+pub(super) fn did_to_resolution_output(
+    did: &Did,
+) -> Result<DidResolutionOutput<ExtraFieldsPeer>, DidPeerError> {
+    let peer_did = PeerDid::parse(did)?;
+    let ddo = peer_did_to_did_document(did, &peer_did)?;
+
+    let resolution_metadata = DidResolutionMetadata::builder()
+        .content_type("application/did+json".to_string())
+        .build();
+
+    Ok(DidResolutionOutput::builder(ddo)
+        .did_resolution_metadata(resolution_metadata)
+        .build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use did_resolver::did_doc::schema::verification_method::VerificationMethodKind;
+
+    const DID_PEER_2: &str = "did:peer:2.Ez6LSbysY2xFMRpGMhb7tFTLMpeuPRaqaWM1yECx2AtzE3KCc.Vz6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V.Vz6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg.SeyJ0IjoiZG0iLCJzIjoiaHR0cHM6Ly9leGFtcGxlLmNvbS9lbmRwb2ludCIsInIiOlsiZGlkOmV4YW1wbGU6c29tZW1lZGlhdG9yI3NvbWVrZXkiXSwiYSI6WyJkaWRjb21tL3YyIiwiZGlkY29tbS9haXAyO2Vudj1yZmM1ODciXX0";
+
+    fn reference(did: &str, fragment: &str) -> VerificationMethodKind {
+        VerificationMethodKind::Resolvable(DidUrl::parse(format!("{}#{}", did, fragment)).unwrap())
+    }
+
+    #[test]
+    fn test_numalgo2_did_document() {
+        let did = Did::parse(DID_PEER_2.to_string()).unwrap();
+        let ddo = did_to_resolution_output(&did)
+            .unwrap()
+            .did_document()
+            .clone();
+
+        assert_eq!(ddo.id(), &did);
+        assert_eq!(ddo.verification_method().len(), 3);
+        assert_eq!(
+            ddo.verification_method()[0].verification_method_type(),
+            "X25519KeyAgreementKey2020"
+        );
+        assert_eq!(
+            ddo.verification_method()[1].verification_method_type(),
+            "Ed25519VerificationKey2020"
+        );
+        assert_eq!(ddo.key_agreement(), &[reference(DID_PEER_2, "key-1")]);
+        assert_eq!(
+            ddo.authentication(),
+            &[
+                reference(DID_PEER_2, "key-2"),
+                reference(DID_PEER_2, "key-3")
+            ]
+        );
+
+        assert_eq!(ddo.service().len(), 1);
+        let service = &ddo.service()[0];
+        assert_eq!(service.id().to_string(), format!("{}#service", DID_PEER_2));
+        assert_eq!(
+            service.service_endpoint().as_ref(),
+            "https://example.com/endpoint"
+        );
+        assert_eq!(
+            service.extra().routing_keys(),
+            &["did:example:somemediator#somekey"]
+        );
+        assert_eq!(
+            service.extra().accept(),
+            &["didcomm/v2", "didcomm/aip2;env=rfc587"]
+        );
+    }
+
+    #[test]
+    fn test_numalgo0_did_document() {
+        let did_string = "did:peer:0z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V";
+        let did = Did::parse(did_string.to_string()).unwrap();
+        let ddo = did_to_resolution_output(&did)
+            .unwrap()
+            .did_document()
+            .clone();
+
+        assert_eq!(ddo.id(), &did);
+        assert_eq!(ddo.verification_method().len(), 1);
+        assert_eq!(
+            ddo.authentication(),
+            &[reference(
+                did_string,
+                "z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V"
+            )]
+        );
+        assert_eq!(ddo.key_agreement().len(), 1);
+        assert!(ddo.service().is_empty());
+    }
+}
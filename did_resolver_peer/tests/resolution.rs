@@ -0,0 +1,72 @@
+use did_resolver::did_parser::Did;
+use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
+use did_resolver_peer::{
+    error::DidPeerError, peer_did::generate_numalgo2_from_verkey, resolution::DidPeerResolver,
+};
+use did_resolver_registry::ResolverRegistry;
+use tokio_test::assert_ok;
+
+const VERKEY: &str = "8HH5gYEeNc3z7PYXmd54d4x6qAfCNrqQqEB3nS7Zfu7K";
+
+#[tokio::test]
+async fn test_resolve_generated_numalgo2() {
+    let did =
+        generate_numalgo2_from_verkey(VERKEY, "https://example.com/".to_string(), vec![]).unwrap();
+
+    let resolver = DidPeerResolver::new();
+    let output = assert_ok!(
+        resolver
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+    );
+    let did_document = output.did_document();
+
+    assert_eq!(did_document.id(), &did);
+    assert_eq!(did_document.verification_method().len(), 2);
+    assert_eq!(did_document.authentication().len(), 1);
+    assert_eq!(did_document.key_agreement().len(), 1);
+    assert_eq!(did_document.service().len(), 1);
+    assert_eq!(
+        did_document.service()[0].service_endpoint().as_ref(),
+        "https://example.com/"
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_unsupported_method() {
+    let resolver = DidPeerResolver::new();
+    let did =
+        Did::parse("did:key:z6MkqRYqQiSgvZQdnBytw86Qbs2ZWUkGv22od935YF4s8M7V".to_string()).unwrap();
+
+    let error = resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<DidPeerError>(),
+        Some(DidPeerError::MethodNotSupported(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_resolve_through_registry() {
+    let routing_key = "did:key:z6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg#z6MkgoLTnTypo3tDRwCkZXSccTPHRLhF4ZnjhueYAFpEX6vg";
+    let did = generate_numalgo2_from_verkey(
+        VERKEY,
+        "https://example.com/".to_string(),
+        vec![routing_key.to_string()],
+    )
+    .unwrap();
+
+    let mut registry = ResolverRegistry::new();
+    registry.register_resolver("peer".to_string(), Box::new(DidPeerResolver::new()));
+
+    let output = assert_ok!(
+        registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+    );
+    let service = &output.did_document().service()[0];
+    assert_eq!(service.extra()["routingKeys"][0], routing_key);
+    assert_eq!(service.extra()["accept"][0], "didcomm/aip2;env=rfc19");
+}
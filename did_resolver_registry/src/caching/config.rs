@@ -0,0 +1,97 @@
+use std::{error::Error, num::NonZeroUsize, time::Duration};
+
+use did_resolver::traits::resolvable::resolution_error::DidResolutionError;
+
+pub type NotFoundPredicate = fn(&(dyn Error + Send + Sync + 'static)) -> bool;
+
+fn is_did_resolution_not_found(err: &(dyn Error + Send + Sync + 'static)) -> bool {
+    matches!(
+        err.downcast_ref::<DidResolutionError>(),
+        Some(DidResolutionError::NotFound)
+    )
+}
+
+pub struct CachingResolverConfig {
+    ttl: Duration,
+    not_found_ttl: Duration,
+    capacity: NonZeroUsize,
+    is_not_found: NotFoundPredicate,
+}
+
+impl CachingResolverConfig {
+    pub fn builder() -> CachingResolverConfigBuilder {
+        CachingResolverConfigBuilder::default()
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn not_found_ttl(&self) -> Duration {
+        self.not_found_ttl
+    }
+
+    pub fn capacity(&self) -> NonZeroUsize {
+        self.capacity
+    }
+
+    pub fn is_not_found(&self, err: &(dyn Error + Send + Sync + 'static)) -> bool {
+        (self.is_not_found)(err)
+    }
+}
+
+#[derive(Default)]
+pub struct CachingResolverConfigBuilder {}
+
+pub struct CachingResolverConfigBuilderTtlSet {
+    ttl: Duration,
+}
+
+pub struct CachingResolverConfigBuilderReady {
+    ttl: Duration,
+    not_found_ttl: Option<Duration>,
+    capacity: NonZeroUsize,
+    is_not_found: NotFoundPredicate,
+}
+
+impl CachingResolverConfigBuilder {
+    pub fn ttl(self, ttl: Duration) -> CachingResolverConfigBuilderTtlSet {
+        CachingResolverConfigBuilderTtlSet { ttl }
+    }
+}
+
+impl CachingResolverConfigBuilderTtlSet {
+    pub fn capacity(self, capacity: NonZeroUsize) -> CachingResolverConfigBuilderReady {
+        CachingResolverConfigBuilderReady {
+            ttl: self.ttl,
+            not_found_ttl: None,
+            capacity,
+            is_not_found: is_did_resolution_not_found,
+        }
+    }
+}
+
+impl CachingResolverConfigBuilderReady {
+    // Defaults to a tenth of the TTL of successful resolutions
+    pub fn not_found_ttl(mut self, not_found_ttl: Duration) -> Self {
+        self.not_found_ttl = Some(not_found_ttl);
+        self
+    }
+
+    // By default, only `DidResolutionError::NotFound` is recognized, which is what the built-in
+    // resolvers return for missing DIDs. Resolvers returning method specific errors should supply
+    // a predicate recognizing their own not found error.
+    pub fn not_found_predicate(mut self, is_not_found: NotFoundPredicate) -> Self {
+        self.is_not_found = is_not_found;
+        self
+    }
+
+    pub fn build(self) -> CachingResolverConfig {
+        CachingResolverConfig {
+            ttl: self.ttl,
+            not_found_ttl: self.not_found_ttl.unwrap_or(self.ttl / 10),
+            capacity: self.capacity,
+            is_not_found: self.is_not_found,
+        }
+    }
+}
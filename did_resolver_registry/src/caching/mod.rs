@@ -0,0 +1,308 @@
+mod config;
+
+pub use config::*;
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser::Did,
+    error::GenericError,
+    traits::resolvable::{
+        resolution_error::DidResolutionError, resolution_options::DidResolutionOptions,
+        resolution_output::DidResolutionOutput, DidResolvable,
+    },
+};
+use lru::LruCache;
+
+type CacheEntry<E> = (Option<DidResolutionOutput<E>>, Instant);
+
+// Caching decorator over any DID resolver. Successful resolutions are cached for the configured
+// TTL, not found results for the (typically shorter) not found TTL. A cached not found result is
+// returned as `DidResolutionError::NotFound`. Entries are keyed by the DID and the requested
+// media type. Resolution with the `noCache` option bypasses the cache and refreshes it.
+pub struct CachingResolver<T: DidResolvable> {
+    inner: T,
+    cache: Mutex<LruCache<String, CacheEntry<T::ExtraFields>>>,
+    config: CachingResolverConfig,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<T: DidResolvable> CachingResolver<T> {
+    pub fn new(inner: T, config: CachingResolverConfig) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(LruCache::new(config.capacity())),
+            config,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn ttl(&self, entry: &Option<DidResolutionOutput<T::ExtraFields>>) -> Duration {
+        match entry {
+            Some(_) => self.config.ttl(),
+            None => self.config.not_found_ttl(),
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<Option<DidResolutionOutput<T::ExtraFields>>>
+    where
+        T::ExtraFields: Clone,
+    {
+        let mut cache = self.cache.lock().ok()?;
+        let (entry, timestamp) = cache.get(key)?;
+        if timestamp.elapsed() > self.ttl(entry) {
+            cache.pop(key);
+            None
+        } else {
+            Some(entry.clone())
+        }
+    }
+
+    fn put(&self, key: String, entry: Option<DidResolutionOutput<T::ExtraFields>>) {
+        if let Ok(mut cache) = self.cache.lock() {
+            cache.put(key, (entry, Instant::now()));
+        }
+    }
+}
+
+#[async_trait]
+impl<T> DidResolvable for CachingResolver<T>
+where
+    T: DidResolvable + Send + Sync,
+    T::ExtraFields: Clone + Send + Sync,
+{
+    type ExtraFields = T::ExtraFields;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &DidResolutionOptions,
+    ) -> Result<DidResolutionOutput<Self::ExtraFields>, GenericError> {
+        let key = cache_key(did, options);
+        if !options.no_cache() {
+            if let Some(entry) = self.get(&key) {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return entry.ok_or_else(|| Box::new(DidResolutionError::NotFound) as GenericError);
+            }
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        match self.inner.resolve(did, options).await {
+            Ok(output) => {
+                self.put(key, Some(output.clone()));
+                Ok(output)
+            }
+            Err(err) => {
+                if self.config.is_not_found(err.as_ref()) {
+                    self.put(key, None);
+                }
+                Err(err)
+            }
+        }
+    }
+}
+
+fn cache_key(did: &Did, options: &DidResolutionOptions) -> String {
+    match options.accept() {
+        Some(accept) => format!("{} {}", did.did(), accept),
+        None => did.did().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        num::NonZeroUsize,
+        sync::{atomic::AtomicUsize, Arc},
+    };
+
+    use did_resolver::{
+        did_doc::schema::did_doc::DidDocumentBuilder, shared_types::media_type::MediaType,
+    };
+
+    use super::*;
+    use crate::ResolverRegistry;
+
+    struct CountingResolver {
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl DidResolvable for CountingResolver {
+        type ExtraFields = ();
+
+        async fn resolve(
+            &self,
+            did: &Did,
+            _options: &DidResolutionOptions,
+        ) -> Result<DidResolutionOutput<()>, GenericError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            if did.id() == "missing" {
+                return Err(Box::new(DidResolutionError::NotFound));
+            }
+            Ok(DidResolutionOutput::builder(DidDocumentBuilder::new(did.clone()).build()).build())
+        }
+    }
+
+    fn caching_resolver(
+        ttl: Duration,
+        not_found_ttl: Duration,
+    ) -> (CachingResolver<CountingResolver>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let config = CachingResolverConfig::builder()
+            .ttl(ttl)
+            .capacity(NonZeroUsize::new(2).unwrap())
+            .not_found_ttl(not_found_ttl)
+            .build();
+        let resolver = CachingResolver::new(
+            CountingResolver {
+                calls: calls.clone(),
+            },
+            config,
+        );
+        (resolver, calls)
+    }
+
+    fn did(id: &str) -> Did {
+        Did::parse(format!("did:example:{}", id)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit() {
+        let (resolver, calls) = caching_resolver(Duration::from_secs(10), Duration::from_secs(1));
+        let options = DidResolutionOptions::default();
+
+        let first = resolver.resolve(&did("1234"), &options).await.unwrap();
+        let second = resolver.resolve(&did("1234"), &options).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(resolver.hits(), 1);
+        assert_eq!(resolver.misses(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_cache_option_bypasses_cache() {
+        let (resolver, calls) = caching_resolver(Duration::from_secs(10), Duration::from_secs(1));
+
+        resolver
+            .resolve(&did("1234"), &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        resolver
+            .resolve(
+                &did("1234"),
+                &DidResolutionOptions::new().set_no_cache(true),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(resolver.hits(), 0);
+        assert_eq!(resolver.misses(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cache_keyed_by_accept() {
+        let (resolver, calls) = caching_resolver(Duration::from_secs(10), Duration::from_secs(1));
+        let json = DidResolutionOptions::new().set_accept(MediaType::DidJson);
+        let ld_json = DidResolutionOptions::new().set_accept(MediaType::DidLdJson);
+
+        resolver.resolve(&did("1234"), &json).await.unwrap();
+        resolver.resolve(&did("1234"), &ld_json).await.unwrap();
+        resolver.resolve(&did("1234"), &json).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(resolver.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_expiration() {
+        let (resolver, calls) =
+            caching_resolver(Duration::from_millis(1), Duration::from_millis(1));
+        let options = DidResolutionOptions::default();
+
+        resolver.resolve(&did("1234"), &options).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        resolver.resolve(&did("1234"), &options).await.unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_capacity() {
+        let (resolver, calls) = caching_resolver(Duration::from_secs(10), Duration::from_secs(1));
+        let options = DidResolutionOptions::default();
+
+        for id in ["1", "2", "3", "1"] {
+            resolver.resolve(&did(id), &options).await.unwrap();
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_cached_with_shorter_ttl() {
+        let (resolver, calls) = caching_resolver(Duration::from_secs(10), Duration::from_millis(1));
+        let options = DidResolutionOptions::default();
+
+        let err = resolver
+            .resolve(&did("missing"), &options)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DidResolutionError>(),
+            Some(DidResolutionError::NotFound)
+        ));
+        let err = resolver
+            .resolve(&did("missing"), &options)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<DidResolutionError>(),
+            Some(DidResolutionError::NotFound)
+        ));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(resolver.hits(), 1);
+
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        resolver
+            .resolve(&did("missing"), &options)
+            .await
+            .unwrap_err();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_caching_resolver_in_registry() {
+        let (resolver, calls) = caching_resolver(Duration::from_secs(10), Duration::from_secs(1));
+        let mut registry = ResolverRegistry::new();
+        registry.register_resolver("example".to_string(), Box::new(resolver));
+
+        for _ in 0..3 {
+            registry
+                .resolve(&did("1234"), &DidResolutionOptions::default())
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,43 @@
+use async_trait::async_trait;
+use did_resolver::{
+    did_parser::Did,
+    error::GenericError,
+    traits::resolvable::{
+        resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput,
+        DidResolvable,
+    },
+};
+use serde::Serialize;
+use serde_json::Value;
+
+// Erases the method specific extra fields of a resolver into a JSON value, so that resolvers
+// with different extra fields can be stored together in the registry
+pub(crate) struct GenericResolver<T> {
+    inner: Box<T>,
+}
+
+impl<T> GenericResolver<T> {
+    pub(crate) fn new(inner: Box<T>) -> Self {
+        Self { inner }
+    }
+}
+
+#[async_trait]
+impl<T> DidResolvable for GenericResolver<T>
+where
+    T: DidResolvable + Send + Sync,
+    T::ExtraFields: Serialize,
+{
+    type ExtraFields = Value;
+
+    async fn resolve(
+        &self,
+        did: &Did,
+        options: &DidResolutionOptions,
+    ) -> Result<DidResolutionOutput<Value>, GenericError> {
+        let resolution_output = self.inner.resolve(did, options).await?;
+        Ok(serde_json::from_value(serde_json::to_value(
+            resolution_output,
+        )?)?)
+    }
+}
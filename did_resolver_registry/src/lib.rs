@@ -1,19 +1,32 @@
+pub mod caching;
 pub mod error;
+mod generic_resolver;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, io::Cursor};
 
 use did_resolver::{
-    did_parser::Did,
+    did_parser::{Did, DidUrl},
     error::GenericError,
-    traits::resolvable::{
-        resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput,
-        DidResolvable,
+    traits::{
+        dereferenceable::{
+            dereferencing_options::DidDereferencingOptions,
+            dereferencing_output::DidDereferencingOutput, utils::dereference_did_document,
+        },
+        resolvable::{
+            resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput,
+            DidResolvable,
+        },
     },
 };
 use error::DidResolverRegistryError;
+use generic_resolver::GenericResolver;
+use serde::Serialize;
+use serde_json::Value;
 
+// Method specific extra fields are type-erased into `serde_json::Value`, so that resolvers
+// of different methods can be registered side by side
 pub struct ResolverRegistry {
-    resolvers: HashMap<String, Box<dyn DidResolvable<ExtraFields = ()>>>, // TODO: Use e.g. hashmap
+    resolvers: HashMap<String, Box<dyn DidResolvable<ExtraFields = Value> + Send + Sync>>,
 }
 
 impl ResolverRegistry {
@@ -23,12 +36,13 @@ impl ResolverRegistry {
         }
     }
 
-    pub fn register_resolver(
-        &mut self,
-        method: String,
-        resolver: Box<dyn DidResolvable<ExtraFields = ()>>,
-    ) {
-        self.resolvers.insert(method, resolver);
+    pub fn register_resolver<T>(&mut self, method: String, resolver: Box<T>)
+    where
+        T: DidResolvable + Send + Sync + 'static,
+        T::ExtraFields: Serialize,
+    {
+        self.resolvers
+            .insert(method, Box::new(GenericResolver::new(resolver)));
     }
 
     pub fn unregister_resolver(&mut self, method: &str) {
@@ -39,21 +53,37 @@ impl ResolverRegistry {
         &self,
         did: &Did,
         options: &DidResolutionOptions,
-    ) -> Result<DidResolutionOutput<()>, GenericError> {
+    ) -> Result<DidResolutionOutput<Value>, GenericError> {
         let method = did.method();
         match self.resolvers.get(method) {
             Some(resolver) => resolver.resolve(did, options).await,
             None => Err(Box::new(DidResolverRegistryError::UnsupportedMethod)),
         }
     }
+
+    pub async fn dereference(
+        &self,
+        did_url: &DidUrl,
+        _options: &DidDereferencingOptions,
+    ) -> Result<DidDereferencingOutput<Cursor<Vec<u8>>>, GenericError> {
+        let did: Did = did_url.try_into()?;
+        let resolution_output = self.resolve(&did, &DidResolutionOptions::default()).await?;
+        dereference_did_document(&resolution_output, did_url).map_err(|err| err.into())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use async_trait::async_trait;
-    use did_resolver::did_doc::schema::did_doc::DidDocumentBuilder;
+    use did_resolver::{
+        did_doc::schema::{
+            did_doc::DidDocumentBuilder, service::Service, verification_method::VerificationMethod,
+        },
+        traits::dereferenceable::dereferencing_error::DidDereferencingError,
+    };
     use mockall::{automock, predicate::eq};
+    use serde::Serialize;
     use std::{error::Error, pin::Pin};
 
     struct DummyDidResolver;
@@ -229,4 +259,149 @@ mod tests {
             .await;
         assert!(result_after.is_ok());
     }
+
+    fn mock_resolver_with_verification_method(did: &'static str) -> MockDummyDidResolver {
+        let mut mock_resolver = MockDummyDidResolver::new();
+        mock_resolver
+            .expect_resolve()
+            .times(1)
+            .return_once(move |_, _| {
+                let future = async move {
+                    let verification_method = VerificationMethod::builder(
+                        DidUrl::parse(format!("{}#key-1", did)).unwrap(),
+                        Did::parse(did.to_string()).unwrap(),
+                        "Ed25519VerificationKey2020".to_string(),
+                    )
+                    .build();
+                    Ok::<DidResolutionOutput<()>, GenericError>(
+                        DidResolutionOutput::builder(
+                            DidDocumentBuilder::new(Did::parse(did.to_string()).unwrap())
+                                .add_verification_method(verification_method)
+                                .build(),
+                        )
+                        .build(),
+                    )
+                };
+                Pin::from(Box::new(future))
+            });
+        mock_resolver
+    }
+
+    #[tokio::test]
+    async fn test_dereference_success() {
+        let did = "did:example:1234";
+        let did_url = DidUrl::parse(format!("{}#key-1", did)).unwrap();
+
+        let mut registry = ResolverRegistry::new();
+        registry.register_resolver(
+            "example".to_string(),
+            Box::new(mock_resolver_with_verification_method(did)),
+        );
+
+        let output = registry
+            .dereference(&did_url, &DidDereferencingOptions::default())
+            .await
+            .unwrap();
+        let verification_method: VerificationMethod =
+            serde_json::from_slice(output.content_stream().get_ref()).unwrap();
+        assert_eq!(verification_method.id(), &did_url);
+        assert_eq!(
+            output.dereferencing_metadata().content_type(),
+            Some(&"application/did+json".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dereference_not_found() {
+        let did = "did:example:1234";
+        let did_url = DidUrl::parse(format!("{}#key-2", did)).unwrap();
+
+        let mut registry = ResolverRegistry::new();
+        registry.register_resolver(
+            "example".to_string(),
+            Box::new(mock_resolver_with_verification_method(did)),
+        );
+
+        let error = registry
+            .dereference(&did_url, &DidDereferencingOptions::default())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(
+            error.downcast_ref::<DidDereferencingError>(),
+            Some(&DidDereferencingError::NotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dereference_unsupported_method() {
+        let did_url = DidUrl::parse("did:unknown:1234#key-1".to_string()).unwrap();
+
+        let registry = ResolverRegistry::new();
+        let error = registry
+            .dereference(&did_url, &DidDereferencingOptions::default())
+            .await
+            .err()
+            .unwrap();
+        assert!(matches!(
+            error.downcast_ref::<DidResolverRegistryError>(),
+            Some(DidResolverRegistryError::UnsupportedMethod)
+        ));
+    }
+
+    #[derive(Serialize, Default)]
+    #[serde(rename_all = "camelCase")]
+    struct ExtraFieldsDummy {
+        routing_keys: Vec<String>,
+    }
+
+    struct DummyResolverWithExtraFields;
+
+    #[async_trait]
+    impl DidResolvable for DummyResolverWithExtraFields {
+        type ExtraFields = ExtraFieldsDummy;
+
+        async fn resolve(
+            &self,
+            did: &Did,
+            _options: &DidResolutionOptions,
+        ) -> Result<DidResolutionOutput<ExtraFieldsDummy>, GenericError> {
+            let service = Service::builder(
+                format!("{}#service", did.did()).parse().unwrap(),
+                "https://example.com".try_into().unwrap(),
+                ExtraFieldsDummy {
+                    routing_keys: vec!["routing-key".to_string()],
+                },
+            )
+            .add_service_type("DIDCommMessaging".to_string())
+            .unwrap()
+            .build();
+            Ok(DidResolutionOutput::builder(
+                DidDocumentBuilder::new(did.clone())
+                    .add_service(service)
+                    .build(),
+            )
+            .build())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_preserves_extra_fields() {
+        let did = Did::parse("did:dummy:1234".to_string()).unwrap();
+
+        let mut registry = ResolverRegistry::new();
+        registry.register_resolver("dummy".to_string(), Box::new(DummyResolverWithExtraFields));
+        registry.register_resolver("example".to_string(), Box::new(MockDummyDidResolver::new()));
+
+        let output = registry
+            .resolve(&did, &DidResolutionOptions::default())
+            .await
+            .unwrap();
+        let service = &output.did_document().service()[0];
+        assert_eq!(
+            service.extra()["routingKeys"],
+            serde_json::json!(["routing-key"])
+        );
+        assert_eq!(service.service_endpoint().as_ref(), "https://example.com/");
+    }
 }
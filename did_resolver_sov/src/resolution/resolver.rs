@@ -6,8 +6,8 @@ use did_resolver::{
     error::GenericError,
     shared_types::media_type::MediaType,
     traits::resolvable::{
-        resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput,
-        DidResolvable,
+        resolution_error::DidResolutionError, resolution_options::DidResolutionOptions,
+        resolution_output::DidResolutionOutput, DidResolvable,
     },
 };
 use serde_json::Value;
@@ -34,8 +34,7 @@ impl DidSovResolver {
 
 #[async_trait]
 impl DidResolvable for DidSovResolver {
-    // TODO: Change to ExtraFields from Sovrin-specific DDO wrapper
-    type ExtraFields = ();
+    type ExtraFields = ExtraFieldsSov;
 
     async fn resolve(
         &self,
@@ -61,10 +60,19 @@ impl DidResolvable for DidSovResolver {
         }
         let did = parsed_did.did();
         let ledger_response = self.ledger.get_attr(did, "endpoint").await?;
-        let verkey = self.get_verkey(did).await?;
+        let verkey = self.get_verkey(did).await.map_err(to_generic_error)?;
         ledger_response_to_ddo(did, &ledger_response, verkey)
             .await
-            .map_err(|err| err.into())
+            .map_err(to_generic_error)
+    }
+}
+
+// A DID missing on the ledger is reported as the method independent `DidResolutionError::NotFound`,
+// so that callers (e.g. the caching resolver) can recognize it without knowing about `DidSovError`
+fn to_generic_error(err: DidSovError) -> GenericError {
+    match err {
+        DidSovError::NotFound(_) => Box::new(DidResolutionError::NotFound),
+        err => Box::new(err),
     }
 }
 
@@ -72,13 +80,17 @@ impl DidSovResolver {
     async fn get_verkey(&self, did: &str) -> Result<String, DidSovError> {
         let nym_response = self.ledger.get_nym(did).await?;
         let nym_json: Value = serde_json::from_str(&nym_response)?;
-        let nym_data = nym_json["result"]["data"]
-            .as_str()
-            .ok_or(DidSovError::ParsingError(
-                ParsingErrorSource::LedgerResponseParsingError(
-                    "Failed to parse nym data".to_string(),
-                ),
-            ))?;
+        let nym_data = match &nym_json["result"]["data"] {
+            Value::String(nym_data) => nym_data,
+            Value::Null => return Err(DidSovError::NotFound("DID not found".to_string())),
+            _ => {
+                return Err(DidSovError::ParsingError(
+                    ParsingErrorSource::LedgerResponseParsingError(
+                        "Failed to parse nym data".to_string(),
+                    ),
+                ))
+            }
+        };
         let nym_data: Value = serde_json::from_str(nym_data)?;
         let verkey = nym_data["verkey"]
             .as_str()
@@ -90,3 +102,43 @@ impl DidSovResolver {
         Ok(verkey.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct MissingDidReader;
+
+    #[async_trait]
+    impl AttrReader for MissingDidReader {
+        async fn get_attr(
+            &self,
+            _target_did: &str,
+            _attr_name: &str,
+        ) -> Result<String, DidSovError> {
+            Ok(r#"{"result":{"data":null}}"#.to_string())
+        }
+
+        async fn get_nym(&self, _did: &str) -> Result<String, DidSovError> {
+            Ok(r#"{"result":{"data":null}}"#.to_string())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resolve_missing_did_returns_not_found() {
+        let resolver = DidSovResolver::new(Arc::new(MissingDidReader));
+
+        let err = resolver
+            .resolve(
+                &Did::parse("did:sov:2wJPyULfLLnYTEFYzByfUR".to_string()).unwrap(),
+                &DidResolutionOptions::default(),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.downcast_ref::<DidResolutionError>(),
+            Some(&DidResolutionError::NotFound)
+        );
+    }
+}
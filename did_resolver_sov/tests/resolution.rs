@@ -12,8 +12,10 @@ use aries_vcx::{
 };
 use did_resolver::did_parser::Did;
 use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
+use did_resolver_registry::ResolverRegistry;
 use did_resolver_sov::reader::ConcreteAttrReader;
 use did_resolver_sov::resolution::DidSovResolver;
+use serde_json::json;
 
 async fn write_test_endpoint(profile: &Arc<dyn Profile>, did: &str) {
     let endpoint = EndpointDidSov::create()
@@ -44,6 +46,31 @@ async fn write_service_on_ledger_and_resolve_did_doc() {
     .await;
 }
 
+#[tokio::test]
+async fn write_service_on_ledger_and_resolve_through_registry() {
+    SetupProfile::run(|init| async move {
+        let did = format!("did:sov:{}", init.institution_did);
+        write_test_endpoint(&init.profile, &init.institution_did).await;
+        let resolver = DidSovResolver::new(Arc::<ConcreteAttrReader>::new(
+            init.profile.inject_indy_ledger_read().into(),
+        ));
+        let mut registry = ResolverRegistry::new();
+        registry.register_resolver("sov".to_string(), Box::new(resolver));
+
+        let output = registry
+            .resolve(
+                &Did::parse(did.clone()).unwrap(),
+                &DidResolutionOptions::default(),
+            )
+            .await
+            .unwrap();
+        let service = serde_json::to_value(&output.did_document().service()[0]).unwrap();
+        assert_eq!(service["routingKeys"], json!(["key1", "key2"]));
+        assert_eq!(service["type"], json!(["endpoint"]));
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn test_error_handling_during_resolution() {
     SetupProfile::run(|init| async move {
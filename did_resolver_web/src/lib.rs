@@ -1,2 +1,3 @@
+pub mod dereferencing;
 pub mod error;
 pub mod resolution;
@@ -4,14 +4,14 @@ use did_resolver::{
     error::GenericError,
     shared_types::{did_document_metadata::DidDocumentMetadata, media_type::MediaType},
     traits::resolvable::{
-        resolution_metadata::DidResolutionMetadata, resolution_options::DidResolutionOptions,
-        resolution_output::DidResolutionOutput, DidResolvable,
+        resolution_error::DidResolutionError, resolution_metadata::DidResolutionMetadata,
+        resolution_options::DidResolutionOptions, resolution_output::DidResolutionOutput, DidResolvable,
     },
 };
 use hyper::{
     client::{connect::Connect, HttpConnector},
     http::uri::{self, Scheme},
-    Body, Client, Uri,
+    Body, Client, StatusCode, Uri,
 };
 use hyper_tls::HttpsConnector;
 
@@ -103,7 +103,13 @@ where
             .path_and_query(path_and_query.as_str())
             .build()?;
 
-        let did_document = serde_json::from_str(&self.fetch_did_document(url).await?)?;
+        let did_document = match self.fetch_did_document(url).await {
+            Ok(did_document) => serde_json::from_str(&did_document)?,
+            Err(DidWebError::NonSuccessResponse(StatusCode::NOT_FOUND)) => {
+                return Err(Box::new(DidResolutionError::NotFound));
+            }
+            Err(err) => return Err(Box::new(err)),
+        };
 
         let did_resolution_output = DidResolutionOutput::builder(did_document)
             .did_resolution_metadata(DidResolutionMetadata::default())
@@ -0,0 +1,111 @@
+use did_resolver::did_doc::schema::verification_method::VerificationMethod;
+use did_resolver::did_parser::DidUrl;
+use did_resolver::traits::dereferenceable::{
+    dereferencing_error::DidDereferencingError, dereferencing_options::DidDereferencingOptions, DidDereferenceable,
+};
+use did_resolver_web::resolution::resolver::DidWebResolver;
+use hyper::{
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server,
+};
+use std::convert::Infallible;
+use std::io::Read;
+use std::net::SocketAddr;
+use tokio_test::assert_ok;
+
+const DID_DOCUMENT: &str = r##"
+{
+  "@context": [
+    "https://www.w3.org/ns/did/v1",
+    "https://w3id.org/security/suites/jws-2020/v1"
+  ],
+  "id": "did:web:example.com",
+  "verificationMethod": [
+    {
+      "id": "did:web:example.com#key-0",
+      "type": "JsonWebKey2020",
+      "controller": "did:web:example.com",
+      "publicKeyJwk": {
+        "kty": "OKP",
+        "crv": "Ed25519",
+        "x": "0-e2i2_Ua1S5HbTYnVB0lj2Z2ytXu2-tYmDFf8f5NjU"
+      }
+    }
+  ],
+  "authentication": [
+    "did:web:example.com#key-0"
+  ],
+  "service": [
+    {
+      "id": "did:web:example.com#files",
+      "type": "LinkedDomains",
+      "serviceEndpoint": "https://example.com/files/"
+    }
+  ]
+}"##;
+
+async fn mock_server_handler(req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let response = match req.uri().path() {
+        "/.well-known/did.json" => Response::new(Body::from(DID_DOCUMENT)),
+        _ => Response::builder().status(404).body(Body::from("Not Found")).unwrap(),
+    };
+
+    Ok(response)
+}
+
+async fn create_mock_server(port: u16) -> String {
+    let make_svc = make_service_fn(|_conn| async { Ok::<_, Infallible>(service_fn(mock_server_handler)) });
+
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    let server = Server::bind(&addr).serve(make_svc);
+
+    tokio::spawn(async move {
+        server.await.unwrap();
+    });
+
+    "localhost".to_string()
+}
+
+async fn dereference_to_string(resolver: &DidWebResolver<hyper::client::HttpConnector>, did_url: &str) -> String {
+    let did_url = DidUrl::parse(did_url.to_string()).unwrap();
+    let output = assert_ok!(
+        resolver
+            .dereference(&did_url, &DidDereferencingOptions::default())
+            .await
+    );
+    let mut content = String::new();
+    output.content_stream().clone().read_to_string(&mut content).unwrap();
+    content
+}
+
+#[tokio::test]
+async fn test_did_web_dereferencer() {
+    let port = 3001;
+    let host = create_mock_server(port).await;
+    let did = format!("did:web:{}%3A{}", host, port);
+
+    let did_web_resolver = DidWebResolver::http();
+
+    let verification_method: VerificationMethod =
+        serde_json::from_str(&dereference_to_string(&did_web_resolver, &format!("{}#key-0", did)).await).unwrap();
+    assert_eq!(verification_method.id().to_string(), "did:web:example.com#key-0");
+    assert_eq!(verification_method.verification_method_type(), "JsonWebKey2020");
+
+    let service_endpoint = dereference_to_string(
+        &did_web_resolver,
+        &format!("{}?service=files&relativeRef=%2Fresume.pdf", did),
+    )
+    .await;
+    assert_eq!(service_endpoint, "https://example.com/files/resume.pdf");
+
+    let did_url = DidUrl::parse(format!("{}#key-1", did)).unwrap();
+    let error = did_web_resolver
+        .dereference(&did_url, &DidDereferencingOptions::default())
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(
+        error.downcast_ref::<DidDereferencingError>(),
+        Some(&DidDereferencingError::NotFound)
+    );
+}
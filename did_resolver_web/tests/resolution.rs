@@ -1,6 +1,8 @@
 use did_resolver::did_doc::schema::did_doc::DidDocument;
 use did_resolver::did_parser::Did;
-use did_resolver::traits::resolvable::{resolution_options::DidResolutionOptions, DidResolvable};
+use did_resolver::traits::resolvable::{
+    resolution_error::DidResolutionError, resolution_options::DidResolutionOptions, DidResolvable,
+};
 use did_resolver_web::resolution::resolver::DidWebResolver;
 use hyper::{
     service::{make_service_fn, service_fn},
@@ -118,3 +120,21 @@ async fn test_did_web_resolver() {
     );
     verify_did_document(result_2.did_document());
 }
+
+#[tokio::test]
+async fn test_did_web_resolver_not_found() {
+    let port = 3002;
+    let host = create_mock_server(port).await;
+
+    let did_web_resolver = DidWebResolver::http();
+
+    let did = Did::parse(format!("did:web:{}%3A{}:user:bob", host, port)).unwrap();
+    let error = did_web_resolver
+        .resolve(&did, &DidResolutionOptions::default())
+        .await
+        .unwrap_err();
+    assert_eq!(
+        error.downcast_ref::<DidResolutionError>(),
+        Some(&DidResolutionError::NotFound)
+    );
+}
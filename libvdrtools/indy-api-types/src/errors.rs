@@ -326,6 +326,11 @@ impl From<sqlx::Error> for IndyError {
                         IndyErrorKind::WalletItemAlreadyExists,
                         "Wallet item already exists",
                     ),
+                    // Unique violation - postgres (23505)
+                    "23505" => err.to_indy(
+                        IndyErrorKind::WalletItemAlreadyExists,
+                        "Wallet item already exists",
+                    ),
                     _ => err.to_indy(IndyErrorKind::InvalidState, "Unexpected database error"),
                 },
                 None => err.to_indy(IndyErrorKind::InvalidState, "Unexpected database error"),
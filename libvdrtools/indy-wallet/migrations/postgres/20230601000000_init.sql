@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS wallets (
+  id BIGSERIAL PRIMARY KEY,
+  name VARCHAR(64) NOT NULL UNIQUE,
+  metadata TEXT DEFAULT NULL
+);
+
+CREATE TABLE IF NOT EXISTS items (
+  id BIGSERIAL PRIMARY KEY,
+  wallet_id BIGINT NOT NULL REFERENCES wallets (id) ON DELETE CASCADE,
+  type VARCHAR(256) NOT NULL,
+  name VARCHAR(256) NOT NULL,
+  value BYTEA NOT NULL,
+  tags JSONB NOT NULL DEFAULT '{}',
+  UNIQUE (wallet_id, type, name)
+);
+
+CREATE INDEX IF NOT EXISTS ix_items_tags ON items USING GIN (tags jsonb_path_ops);
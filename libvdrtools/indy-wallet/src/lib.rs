@@ -30,7 +30,8 @@ use crate::{
     cache::wallet_cache::{WalletCache, WalletCacheHitData, WalletCacheHitMetrics},
     export_import::{export_continue, finish_import, preparse_file_to_import},
     storage::{
-        default::SQLiteStorageType, mysql::MySqlStorageType, WalletStorage, WalletStorageType,
+        default::SQLiteStorageType, mysql::MySqlStorageType, postgres::PostgresStorageType,
+        WalletStorage, WalletStorageType,
     },
     wallet::{Keys, Wallet},
 };
@@ -83,10 +84,12 @@ impl WalletService {
         let storage_types = {
             let s1: Arc<dyn WalletStorageType> = Arc::new(SQLiteStorageType::new());
             let s2: Arc<dyn WalletStorageType> = Arc::new(MySqlStorageType::new());
+            let s3: Arc<dyn WalletStorageType> = Arc::new(PostgresStorageType::new());
 
             Mutex::new(HashMap::from([
                 ("default".to_string(), s1),
                 ("mysql".to_string(), s2),
+                ("postgres".to_string(), s3),
             ]))
         };
 
@@ -502,6 +505,15 @@ impl WalletService {
             .map_err(|err| WalletService::_map_wallet_storage_error(err, type_, name))
     }
 
+    pub async fn apply_batch(
+        &self,
+        wallet_handle: WalletHandle,
+        operations: &[WalletOperation],
+    ) -> IndyResult<()> {
+        let wallet = self.get_wallet(wallet_handle).await?;
+        wallet.apply_batch(operations).await
+    }
+
     pub async fn delete_indy_record<T>(
         &self,
         wallet_handle: WalletHandle,
@@ -1016,6 +1028,54 @@ pub struct MetadataRaw {
     pub keys: Vec<u8>,
 }
 
+/// Record write applied as part of a batch by `WalletService::apply_batch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalletOperation {
+    AddRecord {
+        type_: String,
+        name: String,
+        value: String,
+        tags: Tags,
+    },
+    UpdateRecordValue {
+        type_: String,
+        name: String,
+        value: String,
+    },
+    AddRecordTags {
+        type_: String,
+        name: String,
+        tags: Tags,
+    },
+    UpdateRecordTags {
+        type_: String,
+        name: String,
+        tags: Tags,
+    },
+    DeleteRecordTags {
+        type_: String,
+        name: String,
+        tag_names: Vec<String>,
+    },
+    DeleteRecord {
+        type_: String,
+        name: String,
+    },
+}
+
+impl WalletOperation {
+    pub fn type_(&self) -> &str {
+        match self {
+            WalletOperation::AddRecord { type_, .. }
+            | WalletOperation::UpdateRecordValue { type_, .. }
+            | WalletOperation::AddRecordTags { type_, .. }
+            | WalletOperation::UpdateRecordTags { type_, .. }
+            | WalletOperation::DeleteRecordTags { type_, .. }
+            | WalletOperation::DeleteRecord { type_, .. } => type_,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct WalletRecord {
     #[serde(rename = "type")]
@@ -8,7 +8,7 @@ use indy_utils::environment;
 use serde::Deserialize;
 use sqlx::{
     sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
-    ConnectOptions, SqlitePool,
+    ConnectOptions, Sqlite, SqlitePool, Transaction,
 };
 
 use async_trait::async_trait;
@@ -16,7 +16,10 @@ use log::LevelFilter;
 
 use crate::{
     language,
-    storage::{StorageIterator, StorageRecord, Tag, TagName, WalletStorage, WalletStorageType},
+    storage::{
+        StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage,
+        WalletStorageType,
+    },
     wallet::EncryptedValue,
     RecordOptions, SearchOptions,
 };
@@ -88,141 +91,22 @@ impl SQLiteStorageType {
     }
 }
 
-#[async_trait]
-impl WalletStorage for SQLiteStorage {
-    ///
-    /// Tries to fetch values and/or tags from the storage.
-    /// Returns Result with StorageEntity object which holds requested data in case of success or
-    /// Result with IndyError in case of failure.
-    ///
-    ///
-    /// # Arguments
-    ///
-    ///  * `type_` - type_ of the item in storage
-    ///  * `id` - id of the item in storage
-    ///  * `options` - JSon containing what needs to be fetched.
-    ///  Example: {"retrieveValue": true, "retrieveTags": true}
-    ///
-    /// # Returns
-    ///
-    /// Result that can be either:
-    ///
-    ///  * `StorageEntity` - Contains name, optional value and optional tags
-    ///  * `IndyError`
-    ///
-    /// # Errors
-    ///
-    /// Any of the following `IndyError` type_ of errors can be throw by this method:
-    ///
-    ///  * `IndyError::Closed` - Storage is closed
-    ///  * `IndyError::ItemNotFound` - Item is not found in database
-    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
-    ///
-    async fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
-        let options: RecordOptions = serde_json::from_str(options).to_indy(
-            IndyErrorKind::InvalidStructure,
-            "RecordOptions is malformed json",
-        )?;
-
-        let mut conn = self.pool.acquire().await?;
-
-        let (item_id, value, key, with_biometrics): (i64, Vec<u8>, Vec<u8>, bool) =
-            sqlx::query_as("SELECT id, value, key, with_biometrics FROM items where type = ?1 AND name = ?2")
-                .bind(type_)
-                .bind(id)
-                .fetch_one(&mut conn)
-                .await?;
-
-        let value = if options.retrieve_value {
-            Some(EncryptedValue::new(value, key, with_biometrics))
-        } else {
-            None
-        };
-
-        let type_ = if options.retrieve_type {
-            Some(type_.to_vec())
-        } else {
-            None
-        };
-
-        let tags = if options.retrieve_tags {
-            let mut tags = Vec::new();
-
-            tags.extend(
-                sqlx::query_as::<_, (Vec<u8>, String)>(
-                    "SELECT name, value from tags_plaintext where item_id = ?",
-                )
-                .bind(item_id)
-                .fetch_all(&mut conn)
-                .await?
-                .drain(..)
-                .map(|r| Tag::PlainText(r.0, r.1)),
-            );
-
-            tags.extend(
-                sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(
-                    "SELECT name, value from tags_encrypted where item_id = ?",
-                )
-                .bind(item_id)
-                .fetch_all(&mut conn)
-                .await?
-                .drain(..)
-                .map(|r| Tag::Encrypted(r.0, r.1)),
-            );
-
-            Some(tags)
-        } else {
-            None
-        };
-
-        Ok(StorageRecord::new(id.to_vec(), value, type_, tags))
-    }
-
-    ///
-    /// inserts value and tags into storage.
-    /// Returns Result with () on success or
-    /// Result with IndyError in case of failure.
-    ///
-    ///
-    /// # Arguments
-    ///
-    ///  * `type_` - type of the item in storage
-    ///  * `id` - id of the item in storage
-    ///  * `value` - value of the item in storage
-    ///  * `value_key` - key used to encrypt the value
-    ///  * `tags` - tags assigned to the value
-    ///
-    /// # Returns
-    ///
-    /// Result that can be either:
-    ///
-    ///  * `()`
-    ///  * `IndyError`
-    ///
-    /// # Errors
-    ///
-    /// Any of the following `IndyError` class of errors can be throw by this method:
-    ///
-    ///  * `IndyError::Closed` - Storage is closed
-    ///  * `IndyError::ItemAlreadyExists` - Item is already present in database
-    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
-    ///
-    async fn add(
+impl SQLiteStorage {
+    async fn _add(
         &self,
+        tx: &mut Transaction<'_, Sqlite>,
         type_: &[u8],
         id: &[u8],
         value: &EncryptedValue,
         tags: &[Tag],
     ) -> IndyResult<()> {
-        let mut tx = self.pool.begin().await?;
-
         let id = sqlx::query("INSERT INTO items (type, name, value, key, with_biometrics) VALUES (?1, ?2, ?3, ?4, ?5)")
             .bind(type_)
             .bind(id)
             .bind(&value.data)
             .bind(&value.key)
             .bind(&value.with_biometrics)
-            .execute(&mut tx)
+            .execute(&mut *tx)
             .await?
             .last_insert_rowid();
 
@@ -235,7 +119,7 @@ impl WalletStorage for SQLiteStorage {
                     .bind(id)
                     .bind(tag_name)
                     .bind(tag_data)
-                    .execute(&mut tx)
+                    .execute(&mut *tx)
                     .await?
                 }
                 Tag::PlainText(ref tag_name, ref tag_data) => {
@@ -245,19 +129,22 @@ impl WalletStorage for SQLiteStorage {
                     .bind(id)
                     .bind(tag_name)
                     .bind(tag_data)
-                    .execute(&mut tx)
+                    .execute(&mut *tx)
                     .await?
                 }
             };
         }
 
-        tx.commit().await?;
         Ok(())
     }
 
-    async fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
-        let mut tx = self.pool.begin().await?;
-
+    async fn _update(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        type_: &[u8],
+        id: &[u8],
+        value: &EncryptedValue,
+    ) -> IndyResult<()> {
         let row_updated =
             sqlx::query("UPDATE items SET value = ?1, key = ?2, with_biometrics = ?3 WHERE type = ?4 AND name = ?5")
                 .bind(&value.data)
@@ -265,15 +152,12 @@ impl WalletStorage for SQLiteStorage {
                 .bind(value.with_biometrics)
                 .bind(type_)
                 .bind(id)
-                .execute(&mut tx)
+                .execute(&mut *tx)
                 .await?
                 .rows_affected();
 
         match row_updated {
-            1 => {
-                tx.commit().await?;
-                Ok(())
-            }
+            1 => Ok(()),
             0 => Err(err_msg(
                 IndyErrorKind::WalletItemNotFound,
                 "Item to update not found",
@@ -285,14 +169,18 @@ impl WalletStorage for SQLiteStorage {
         }
     }
 
-    async fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
-        let mut tx = self.pool.begin().await?;
-
+    async fn _add_tags(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        type_: &[u8],
+        id: &[u8],
+        tags: &[Tag],
+    ) -> IndyResult<()> {
         let (item_id,): (i64,) =
             sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
                 .bind(type_)
                 .bind(id)
-                .fetch_one(&mut tx)
+                .fetch_one(&mut *tx)
                 .await?;
 
         for tag in tags {
@@ -304,7 +192,7 @@ impl WalletStorage for SQLiteStorage {
                     .bind(item_id)
                     .bind(tag_name)
                     .bind(tag_data)
-                    .execute(&mut tx)
+                    .execute(&mut *tx)
                     .await?
                 }
                 Tag::PlainText(ref tag_name, ref tag_data) => {
@@ -314,34 +202,37 @@ impl WalletStorage for SQLiteStorage {
                     .bind(item_id)
                     .bind(tag_name)
                     .bind(tag_data)
-                    .execute(&mut tx)
+                    .execute(&mut *tx)
                     .await?
                 }
             };
         }
 
-        tx.commit().await?;
         Ok(())
     }
 
-    async fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
-        let mut tx = self.pool.begin().await?;
-
+    async fn _update_tags(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        type_: &[u8],
+        id: &[u8],
+        tags: &[Tag],
+    ) -> IndyResult<()> {
         let (item_id,): (i64,) =
             sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
                 .bind(type_)
                 .bind(&id)
-                .fetch_one(&mut tx)
+                .fetch_one(&mut *tx)
                 .await?;
 
         sqlx::query("DELETE FROM tags_encrypted WHERE item_id = ?1")
             .bind(item_id)
-            .execute(&mut tx)
+            .execute(&mut *tx)
             .await?;
 
         sqlx::query("DELETE FROM tags_plaintext WHERE item_id = ?1")
             .bind(item_id)
-            .execute(&mut tx)
+            .execute(&mut *tx)
             .await?;
 
         for tag in tags {
@@ -353,7 +244,7 @@ impl WalletStorage for SQLiteStorage {
                     .bind(item_id)
                     .bind(tag_name)
                     .bind(tag_data)
-                    .execute(&mut tx)
+                    .execute(&mut *tx)
                     .await?
                 }
                 Tag::PlainText(ref tag_name, ref tag_data) => {
@@ -363,25 +254,27 @@ impl WalletStorage for SQLiteStorage {
                     .bind(item_id)
                     .bind(tag_name)
                     .bind(tag_data)
-                    .execute(&mut tx)
+                    .execute(&mut *tx)
                     .await?
                 }
             };
         }
 
-        tx.commit().await?;
-
         Ok(())
     }
 
-    async fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
-        let mut tx = self.pool.begin().await?;
-
+    async fn _delete_tags(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        type_: &[u8],
+        id: &[u8],
+        tag_names: &[TagName],
+    ) -> IndyResult<()> {
         let (item_id,): (i64,) =
             sqlx::query_as("SELECT id FROM items WHERE type = ?1 AND name = ?2")
                 .bind(type_)
                 .bind(id)
-                .fetch_one(&mut tx)
+                .fetch_one(&mut *tx)
                 .await?;
 
         for tag_name in tag_names {
@@ -390,19 +283,206 @@ impl WalletStorage for SQLiteStorage {
                     sqlx::query("DELETE FROM tags_encrypted WHERE item_id = ?1 AND name = ?2")
                         .bind(item_id)
                         .bind(tag_name)
-                        .execute(&mut tx)
+                        .execute(&mut *tx)
                         .await?
                 }
                 TagName::OfPlain(ref tag_name) => {
                     sqlx::query("DELETE FROM tags_plaintext WHERE item_id = ?1 AND name = ?2")
                         .bind(item_id)
                         .bind(tag_name)
-                        .execute(&mut tx)
+                        .execute(&mut *tx)
                         .await?
                 }
             };
         }
 
+        Ok(())
+    }
+
+    async fn _delete(
+        &self,
+        tx: &mut Transaction<'_, Sqlite>,
+        type_: &[u8],
+        id: &[u8],
+    ) -> IndyResult<()> {
+        let rows_affected = sqlx::query("DELETE FROM items where type = ?1 AND name = ?2")
+            .bind(type_)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+
+        match rows_affected {
+            1 => Ok(()),
+            0 => Err(err_msg(
+                IndyErrorKind::WalletItemNotFound,
+                "Item to delete not found",
+            )),
+            _ => Err(err_msg(
+                IndyErrorKind::InvalidState,
+                "More than one row deleted. Seems wallet structure is inconsistent",
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl WalletStorage for SQLiteStorage {
+    ///
+    /// Tries to fetch values and/or tags from the storage.
+    /// Returns Result with StorageEntity object which holds requested data in case of success or
+    /// Result with IndyError in case of failure.
+    ///
+    ///
+    /// # Arguments
+    ///
+    ///  * `type_` - type_ of the item in storage
+    ///  * `id` - id of the item in storage
+    ///  * `options` - JSon containing what needs to be fetched.
+    ///  Example: {"retrieveValue": true, "retrieveTags": true}
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `StorageEntity` - Contains name, optional value and optional tags
+    ///  * `IndyError`
+    ///
+    /// # Errors
+    ///
+    /// Any of the following `IndyError` type_ of errors can be throw by this method:
+    ///
+    ///  * `IndyError::Closed` - Storage is closed
+    ///  * `IndyError::ItemNotFound` - Item is not found in database
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    async fn get(&self, type_: &[u8], id: &[u8], options: &str) -> IndyResult<StorageRecord> {
+        let options: RecordOptions = serde_json::from_str(options).to_indy(
+            IndyErrorKind::InvalidStructure,
+            "RecordOptions is malformed json",
+        )?;
+
+        let mut conn = self.pool.acquire().await?;
+
+        let (item_id, value, key, with_biometrics): (i64, Vec<u8>, Vec<u8>, bool) = sqlx::query_as(
+            "SELECT id, value, key, with_biometrics FROM items where type = ?1 AND name = ?2",
+        )
+        .bind(type_)
+        .bind(id)
+        .fetch_one(&mut conn)
+        .await?;
+
+        let value = if options.retrieve_value {
+            Some(EncryptedValue::new(value, key, with_biometrics))
+        } else {
+            None
+        };
+
+        let type_ = if options.retrieve_type {
+            Some(type_.to_vec())
+        } else {
+            None
+        };
+
+        let tags = if options.retrieve_tags {
+            let mut tags = Vec::new();
+
+            tags.extend(
+                sqlx::query_as::<_, (Vec<u8>, String)>(
+                    "SELECT name, value from tags_plaintext where item_id = ?",
+                )
+                .bind(item_id)
+                .fetch_all(&mut conn)
+                .await?
+                .drain(..)
+                .map(|r| Tag::PlainText(r.0, r.1)),
+            );
+
+            tags.extend(
+                sqlx::query_as::<_, (Vec<u8>, Vec<u8>)>(
+                    "SELECT name, value from tags_encrypted where item_id = ?",
+                )
+                .bind(item_id)
+                .fetch_all(&mut conn)
+                .await?
+                .drain(..)
+                .map(|r| Tag::Encrypted(r.0, r.1)),
+            );
+
+            Some(tags)
+        } else {
+            None
+        };
+
+        Ok(StorageRecord::new(id.to_vec(), value, type_, tags))
+    }
+
+    ///
+    /// inserts value and tags into storage.
+    /// Returns Result with () on success or
+    /// Result with IndyError in case of failure.
+    ///
+    ///
+    /// # Arguments
+    ///
+    ///  * `type_` - type of the item in storage
+    ///  * `id` - id of the item in storage
+    ///  * `value` - value of the item in storage
+    ///  * `value_key` - key used to encrypt the value
+    ///  * `tags` - tags assigned to the value
+    ///
+    /// # Returns
+    ///
+    /// Result that can be either:
+    ///
+    ///  * `()`
+    ///  * `IndyError`
+    ///
+    /// # Errors
+    ///
+    /// Any of the following `IndyError` class of errors can be throw by this method:
+    ///
+    ///  * `IndyError::Closed` - Storage is closed
+    ///  * `IndyError::ItemAlreadyExists` - Item is already present in database
+    ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+    ///
+    async fn add(
+        &self,
+        type_: &[u8],
+        id: &[u8],
+        value: &EncryptedValue,
+        tags: &[Tag],
+    ) -> IndyResult<()> {
+        let mut tx = self.pool.begin().await?;
+        self._add(&mut tx, type_, id, value, tags).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update(&self, type_: &[u8], id: &[u8], value: &EncryptedValue) -> IndyResult<()> {
+        let mut tx = self.pool.begin().await?;
+        self._update(&mut tx, type_, id, value).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let mut tx = self.pool.begin().await?;
+        self._add_tags(&mut tx, type_, id, tags).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+        let mut tx = self.pool.begin().await?;
+        self._update_tags(&mut tx, type_, id, tags).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn delete_tags(&self, type_: &[u8], id: &[u8], tag_names: &[TagName]) -> IndyResult<()> {
+        let mut tx = self.pool.begin().await?;
+        self._delete_tags(&mut tx, type_, id, tag_names).await?;
         tx.commit().await?;
         Ok(())
     }
@@ -435,28 +515,42 @@ impl WalletStorage for SQLiteStorage {
     ///
     async fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
         let mut tx = self.pool.begin().await?;
+        self._delete(&mut tx, type_, id).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
-        let rows_affected = sqlx::query("DELETE FROM items where type = ?1 AND name = ?2")
-            .bind(type_)
-            .bind(id)
-            .execute(&mut tx)
-            .await?
-            .rows_affected();
+    async fn apply_batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+        let mut tx = self.pool.begin().await?;
 
-        match rows_affected {
-            1 => {
-                tx.commit().await?;
-                Ok(())
+        for operation in operations {
+            match operation {
+                StorageOperation::Add {
+                    type_,
+                    id,
+                    value,
+                    tags,
+                } => self._add(&mut tx, type_, id, value, tags).await?,
+                StorageOperation::Update { type_, id, value } => {
+                    self._update(&mut tx, type_, id, value).await?
+                }
+                StorageOperation::AddTags { type_, id, tags } => {
+                    self._add_tags(&mut tx, type_, id, tags).await?
+                }
+                StorageOperation::UpdateTags { type_, id, tags } => {
+                    self._update_tags(&mut tx, type_, id, tags).await?
+                }
+                StorageOperation::DeleteTags {
+                    type_,
+                    id,
+                    tag_names,
+                } => self._delete_tags(&mut tx, type_, id, tag_names).await?,
+                StorageOperation::Delete { type_, id } => self._delete(&mut tx, type_, id).await?,
             }
-            0 => Err(err_msg(
-                IndyErrorKind::WalletItemNotFound,
-                "Item to delete not found",
-            )),
-            _ => Err(err_msg(
-                IndyErrorKind::InvalidState,
-                "More than one row deleted. Seems wallet structure is inconsistent",
-            )),
         }
+
+        tx.commit().await?;
+        Ok(())
     }
 
     async fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
@@ -945,3 +1039,21 @@ impl WalletStorageType for SQLiteStorageType {
         }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::SQLiteStorageType;
+    use crate::storage::sql::tests::TestBackend;
+
+    fn backend() -> TestBackend {
+        TestBackend {
+            storage_type: Arc::new(SQLiteStorageType::new()),
+            config: "{}",
+            credentials: "{}",
+        }
+    }
+
+    sql_storage_tests!(sqlite_storage, backend());
+}
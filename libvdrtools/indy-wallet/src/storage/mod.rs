@@ -3,8 +3,12 @@ use indy_api_types::errors::prelude::*;
 
 use crate::{language, wallet::EncryptedValue};
 
+#[macro_use]
+mod sql;
+
 pub mod default;
 pub mod mysql;
+pub mod postgres;
 
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
 pub enum Tag {
@@ -42,6 +46,42 @@ impl StorageRecord {
     }
 }
 
+/// Single write operation of a storage batch. Names, values and tags are already
+/// encrypted by the wallet layer.
+#[derive(Debug)]
+pub enum StorageOperation {
+    Add {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        value: EncryptedValue,
+        tags: Vec<Tag>,
+    },
+    Update {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        value: EncryptedValue,
+    },
+    AddTags {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        tags: Vec<Tag>,
+    },
+    UpdateTags {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        tags: Vec<Tag>,
+    },
+    DeleteTags {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+        tag_names: Vec<TagName>,
+    },
+    Delete {
+        type_: Vec<u8>,
+        id: Vec<u8>,
+    },
+}
+
 #[async_trait]
 pub trait StorageIterator: Send + Sync {
     async fn next(&mut self) -> Result<Option<StorageRecord>, IndyError>;
@@ -74,6 +114,9 @@ pub trait WalletStorage: Send + Sync {
         tag_names: &[TagName],
     ) -> Result<(), IndyError>;
     async fn delete(&self, type_: &[u8], id: &[u8]) -> Result<(), IndyError>;
+    /// Applies all operations within a single storage transaction: either every
+    /// operation is persisted or none of them is.
+    async fn apply_batch(&self, operations: &[StorageOperation]) -> Result<(), IndyError>;
     async fn get_storage_metadata(&self) -> Result<Vec<u8>, IndyError>;
     async fn set_storage_metadata(&self, metadata: &[u8]) -> Result<(), IndyError>;
     async fn get_all(&self) -> Result<Box<dyn StorageIterator>, IndyError>;
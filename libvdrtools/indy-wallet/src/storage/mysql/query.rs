@@ -1,319 +1,77 @@
-use indy_api_types::errors::prelude::*;
-use indy_utils::crypto::base64;
-use serde_json::Value;
+use crate::storage::sql::SqlDialect;
 
-use crate::{
-    language::{Operator, TagName, TargetValue},
-    SearchOptions,
-};
+pub(super) struct MySqlDialect;
 
-pub fn wql_to_sql(
-    wallet_id: i64,
-    type_: &[u8],
-    wql: &Operator,
-    options: &SearchOptions,
-) -> IndyResult<(String, Vec<Value>)> {
-    let mut arguments: Vec<Value> = Vec::new();
+impl SqlDialect for MySqlDialect {
+    const UNIQUE_VIOLATION: &'static str = "23000";
 
-    let query_condition = match operator_to_sql(wql, &mut arguments) {
-        Ok(query_condition) => query_condition,
-        Err(err) => return Err(err),
-    };
-
-    let query_string = format!(
-        "SELECT {}, name, {}, {} FROM items WHERE {} type = ? AND wallet_id = ?",
-        if options.retrieve_type {
-            "type"
-        } else {
-            "NULL"
-        },
-        if options.retrieve_value {
-            "value"
-        } else {
-            "NULL"
-        },
-        if options.retrieve_tags {
-            "tags"
-        } else {
-            "NULL"
-        },
-        if !query_condition.is_empty() {
-            query_condition + " AND"
-        } else {
-            "".to_string()
-        }
-    );
+    const NULL_TYPE: &'static str = "NULL";
 
-    arguments.push(base64::encode(type_).into());
-    arguments.push(wallet_id.into());
-
-    Ok((query_string, arguments))
-}
-
-pub fn wql_to_sql_count(
-    wallet_id: i64,
-    type_: &[u8],
-    wql: &Operator,
-) -> IndyResult<(String, Vec<Value>)> {
-    let mut arguments: Vec<Value> = Vec::new();
-
-    let query_condition = match operator_to_sql(wql, &mut arguments) {
-        Ok(query_condition) => query_condition,
-        Err(err) => return Err(err),
-    };
+    const NULL_VALUE: &'static str = "NULL";
 
-    let query_string = format!(
-        "SELECT count(*) FROM items i WHERE {} i.type = ? AND i.wallet_id = ?",
-        if !query_condition.is_empty() {
-            query_condition + " AND"
-        } else {
-            "".to_string()
-        }
-    );
+    const NULL_TAGS: &'static str = "NULL";
 
-    arguments.push(base64::encode(type_).into());
-    arguments.push(wallet_id.into());
-
-    Ok((query_string, arguments))
-}
-
-fn operator_to_sql(op: &Operator, arguments: &mut Vec<Value>) -> IndyResult<String> {
-    match *op {
-        Operator::Eq(ref tag_name, ref target_value) => {
-            Ok(eq_to_sql(tag_name, target_value, arguments))
-        }
-        Operator::Neq(ref tag_name, ref target_value) => {
-            Ok(neq_to_sql(tag_name, target_value, arguments))
-        }
-        Operator::Gt(ref tag_name, ref target_value) => {
-            gt_to_sql(tag_name, target_value, arguments)
-        }
-        Operator::Gte(ref tag_name, ref target_value) => {
-            gte_to_sql(tag_name, target_value, arguments)
-        }
-        Operator::Lt(ref tag_name, ref target_value) => {
-            lt_to_sql(tag_name, target_value, arguments)
-        }
-        Operator::Lte(ref tag_name, ref target_value) => {
-            lte_to_sql(tag_name, target_value, arguments)
-        }
-        Operator::Like(ref tag_name, ref target_value) => {
-            like_to_sql(tag_name, target_value, arguments)
-        }
-        Operator::In(ref tag_name, ref target_values) => {
-            Ok(in_to_sql(tag_name, target_values, arguments))
-        }
-        Operator::And(ref suboperators) => and_to_sql(suboperators, arguments),
-        Operator::Or(ref suboperators) => or_to_sql(suboperators, arguments),
-        Operator::Not(ref suboperator) => not_to_sql(suboperator, arguments),
+    fn placeholder(_position: usize) -> String {
+        "?".to_string()
     }
-}
-
-fn eq_to_sql(tag_name: &TagName, tag_value: &TargetValue, arguments: &mut Vec<Value>) -> String {
-    let tag_path = format!(r#"'$."{}"'"#, tag_name.to_plain());
-
-    arguments.push(tag_value.to_plain().into());
-    format!("(JSON_UNQUOTE(JSON_EXTRACT(tags, {})) = ?)", tag_path)
-}
-
-fn neq_to_sql(tag_name: &TagName, tag_value: &TargetValue, arguments: &mut Vec<Value>) -> String {
-    let tag_path = format!(r#"'$."{}"'"#, tag_name.to_plain());
-
-    arguments.push(tag_value.to_plain().into());
-    format!("(JSON_UNQUOTE(JSON_EXTRACT(tags, {})) != ?)", tag_path)
-}
-
-fn gt_to_sql(
-    tag_name: &TagName,
-    tag_value: &TargetValue,
-    arguments: &mut Vec<Value>,
-) -> IndyResult<String> {
-    match (tag_name, tag_value) {
-        (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) => {
-            let tag_path = format!(r#"'$."{}"'"#, tag_name.to_plain());
-            arguments.push(tag_value.to_plain().into());
-
-            Ok(format!(
-                "(JSON_UNQUOTE(JSON_EXTRACT(tags, {})) > ?)",
-                tag_path
-            ))
-        }
-        _ => Err(err_msg(
-            IndyErrorKind::WalletQueryError,
-            "Invalid combination of tag name and value for $gt operator",
-        )),
-    }
-}
-
-fn gte_to_sql(
-    tag_name: &TagName,
-    tag_value: &TargetValue,
-    arguments: &mut Vec<Value>,
-) -> IndyResult<String> {
-    match (tag_name, tag_value) {
-        (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) => {
-            let tag_path = format!(r#"'$."{}"'"#, tag_name.to_plain());
-            arguments.push(tag_value.to_plain().into());
-
-            Ok(format!(
-                "(JSON_UNQUOTE(JSON_EXTRACT(tags, {})) >= ?)",
-                tag_path
-            ))
-        }
-        _ => Err(err_msg(
-            IndyErrorKind::WalletQueryError,
-            "Invalid combination of tag name and value for $gt operator",
-        )),
-    }
-}
 
-fn lt_to_sql(
-    tag_name: &TagName,
-    tag_value: &TargetValue,
-    arguments: &mut Vec<Value>,
-) -> IndyResult<String> {
-    match (tag_name, tag_value) {
-        (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) => {
-            let tag_path = format!(r#"'$."{}"'"#, tag_name.to_plain());
-            arguments.push(tag_value.to_plain().into());
-
-            Ok(format!(
-                "(JSON_UNQUOTE(JSON_EXTRACT(tags, {})) < ?)",
-                tag_path
-            ))
-        }
-        _ => Err(err_msg(
-            IndyErrorKind::WalletQueryError,
-            "Invalid combination of tag name and value for $lt operator",
-        )),
+    fn tags_json(placeholder: &str) -> String {
+        placeholder.to_string()
     }
-}
 
-fn lte_to_sql(
-    tag_name: &TagName,
-    tag_value: &TargetValue,
-    arguments: &mut Vec<Value>,
-) -> IndyResult<String> {
-    match (tag_name, tag_value) {
-        (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) => {
-            let tag_path = format!(r#"'$."{}"'"#, tag_name.to_plain());
-            arguments.push(tag_value.to_plain().into());
-
-            Ok(format!(
-                "(JSON_UNQUOTE(JSON_EXTRACT(tags, {})) <= ?)",
-                tag_path
-            ))
-        }
-        _ => Err(err_msg(
-            IndyErrorKind::WalletQueryError,
-            "Invalid combination of tag name and value for $lt operator",
-        )),
+    fn merge_tags(tags_json: &str) -> String {
+        format!("JSON_MERGE_PATCH(tags, {})", tags_json)
     }
-}
 
-fn like_to_sql(
-    tag_name: &TagName,
-    tag_value: &TargetValue,
-    arguments: &mut Vec<Value>,
-) -> IndyResult<String> {
-    match (tag_name, tag_value) {
-        (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) => {
-            let tag_path = format!(r#"'$."{}"'"#, tag_name.to_plain());
-            arguments.push(tag_value.to_plain().into());
-
-            Ok(format!(
-                "(JSON_UNQUOTE(JSON_EXTRACT(tags, {})) LIKE ?)",
-                tag_path
-            ))
-        }
-        _ => Err(err_msg(
-            IndyErrorKind::WalletQueryError,
-            "Invalid combination of tag name and value for $lt operator",
-        )),
+    fn remove_tags(tag_placeholders: &[String]) -> String {
+        format!("JSON_REMOVE(tags, {})", tag_placeholders.join(", "))
     }
-}
-
-fn in_to_sql(
-    tag_name: &TagName,
-    tag_values: &Vec<TargetValue>,
-    arguments: &mut Vec<Value>,
-) -> String {
-    let tag_path = format!(r#"'$."{}"'"#, tag_name.to_plain());
-    let mut in_string = format!("JSON_UNQUOTE(JSON_EXTRACT(tags, {})) IN (", tag_path);
 
-    for (index, tag_value) in tag_values.iter().enumerate() {
-        in_string.push('?');
-        if index < tag_values.len() - 1 {
-            in_string.push(',');
-        } else {
-            in_string.push(')');
-        }
-
-        arguments.push(tag_value.to_plain().into());
+    // Tags are referred to by their json path.
+    fn tag_argument(tag_name: String) -> String {
+        format!(r#"$."{}""#, tag_name)
     }
 
-    in_string
-}
-
-fn and_to_sql(suboperators: &[Operator], arguments: &mut Vec<Value>) -> IndyResult<String> {
-    join_operators(suboperators, " AND ", arguments)
-}
-
-fn or_to_sql(suboperators: &[Operator], arguments: &mut Vec<Value>) -> IndyResult<String> {
-    join_operators(suboperators, " OR ", arguments)
-}
-
-fn not_to_sql(suboperator: &Operator, arguments: &mut Vec<Value>) -> IndyResult<String> {
-    let suboperator_string = operator_to_sql(suboperator, arguments)?;
-    Ok("NOT (".to_string() + &suboperator_string + ")")
-}
-
-fn join_operators(
-    operators: &[Operator],
-    join_str: &str,
-    arguments: &mut Vec<Value>,
-) -> IndyResult<String> {
-    let mut s = String::new();
-
-    if !operators.is_empty() {
-        s.push('(');
-        for (index, operator) in operators.iter().enumerate() {
-            let operator_string = operator_to_sql(operator, arguments)?;
-
-            s.push_str(&operator_string);
-
-            if index < operators.len() - 1 {
-                s.push_str(join_str);
-            }
-        }
-
-        s.push(')');
+    fn tag_value(tag_placeholder: &str) -> String {
+        format!("JSON_UNQUOTE(JSON_EXTRACT(tags, {}))", tag_placeholder)
     }
-
-    Ok(s)
 }
 
-// FIXME: It is quite smilar for to_string method of tag and value, but for some reason
-// to_string uses "". It is added to avoid potential damage as i have no time
-// for investigation.
-trait ToPlain {
-    fn to_plain(&self) -> String;
-}
+#[cfg(test)]
+mod tests {
+    use indy_utils::crypto::base64;
+    use serde_json::Value;
 
-impl ToPlain for TagName {
-    fn to_plain(&self) -> String {
-        match *self {
-            TagName::EncryptedTagName(ref v) => base64::encode(v),
-            TagName::PlainTagName(ref v) => format!("~{}", base64::encode(v)),
-        }
-    }
-}
+    use super::*;
+    use crate::{
+        language::{Operator, TagName, TargetValue},
+        storage::sql::query::wql_to_sql,
+        SearchOptions,
+    };
 
-impl ToPlain for TargetValue {
-    fn to_plain(&self) -> String {
-        match *self {
-            TargetValue::Unencrypted(ref s) => s.to_owned(),
-            TargetValue::Encrypted(ref v) => base64::encode(v),
-        }
+    #[test]
+    fn wql_to_sql_binds_tag_paths() {
+        let wql = Operator::Gt(
+            TagName::PlainTagName(vec![7]),
+            TargetValue::Unencrypted("a".to_string()),
+        );
+
+        let (query, arguments) =
+            wql_to_sql::<MySqlDialect>(42, &[9], &wql, &SearchOptions::default()).unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT NULL, name, value, NULL FROM items WHERE \
+             (JSON_UNQUOTE(JSON_EXTRACT(tags, ?)) > ?) AND type = ? AND wallet_id = ? ORDER BY id"
+        );
+        assert_eq!(
+            arguments,
+            vec![
+                Value::from(format!(r#"$."~{}""#, base64::encode(&[7]))),
+                Value::from("a"),
+                Value::from(base64::encode(&[9])),
+                Value::from(42),
+            ]
+        );
     }
 }
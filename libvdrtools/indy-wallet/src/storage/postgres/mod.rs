@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use futures::lock::Mutex;
+use indy_api_types::errors::prelude::*;
+use log::LevelFilter;
+use query::PostgresDialect;
+use serde::Deserialize;
+use sqlx::{
+    migrate::Migrator,
+    postgres::{PgConnectOptions, PgPoolOptions, PgRow},
+    ConnectOptions, PgPool, Postgres,
+};
+
+mod query;
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations/postgres");
+
+#[derive(Deserialize, Debug)]
+struct Config {
+    pub read_host: String,
+    pub write_host: String,
+    pub port: u16,
+    pub db_name: String,
+    #[serde(default = "default_connection_limit")]
+    pub connection_limit: u32,
+}
+
+fn default_connection_limit() -> u32 {
+    100
+}
+
+#[derive(Deserialize)]
+pub struct Credentials {
+    pub user: String,
+    pub pass: String,
+}
+
+#[derive(Debug)]
+struct PostgresStorage {
+    wallet_id: i64,
+    read_pool: PgPool,
+    write_pool: PgPool,
+}
+
+pub struct PostgresStorageType {
+    connections: Mutex<HashMap<String, PgPool>>,
+}
+
+impl PostgresStorageType {
+    pub fn new() -> PostgresStorageType {
+        PostgresStorageType {
+            connections: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn _connect(
+        &self,
+        read_only: bool,
+        config: Option<&str>,
+        credentials: Option<&str>,
+    ) -> IndyResult<PgPool> {
+        let config = config
+            .map(serde_json::from_str::<Config>)
+            .transpose()
+            .to_indy(IndyErrorKind::InvalidStructure, "Malformed config json")?
+            .ok_or(err_msg(
+                IndyErrorKind::InvalidStructure,
+                "Absent config json",
+            ))?;
+
+        let credentials = credentials
+            .map(serde_json::from_str::<Credentials>)
+            .transpose()
+            .to_indy(
+                IndyErrorKind::InvalidStructure,
+                "Malformed credentials json",
+            )?
+            .ok_or(err_msg(
+                IndyErrorKind::InvalidStructure,
+                "Absent credentials json",
+            ))?;
+
+        let host_addr = if read_only {
+            &config.read_host
+        } else {
+            &config.write_host
+        };
+
+        let connection_string = format!(
+            "{}:{}@{}:{}/{}",
+            credentials.user, credentials.pass, host_addr, config.port, config.db_name
+        );
+
+        let mut connref = self.connections.lock().await;
+
+        if let Some(connection) = connref.get(&connection_string) {
+            return Ok(connection.clone());
+        }
+
+        let mut pg_connect_options = PgConnectOptions::new()
+            .host(host_addr)
+            .port(config.port)
+            .database(&config.db_name)
+            .username(&credentials.user)
+            .password(&credentials.pass);
+        pg_connect_options.log_statements(LevelFilter::Debug);
+
+        let connection = PgPoolOptions::default()
+            .max_connections(config.connection_limit)
+            .test_before_acquire(false)
+            .connect_with(pg_connect_options)
+            .await?;
+
+        // Read hosts are expected to be replicas of the write host, so the schema is only
+        // migrated through the write pool.
+        if !read_only {
+            MIGRATOR.run(&connection).await.to_indy(
+                IndyErrorKind::IOError,
+                "Unable to migrate wallet database schema",
+            )?;
+        }
+
+        connref.insert(connection_string, connection.clone());
+        Ok(connection)
+    }
+}
+
+impl_sql_storage!(
+    PostgresStorage,
+    PostgresStorageType,
+    Postgres,
+    PgRow,
+    PostgresDialect
+);
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use std::sync::Arc;
+
+    use super::PostgresStorageType;
+    use crate::storage::sql::tests::TestBackend;
+
+    // docker run --name indy-postgres -e POSTGRES_PASSWORD=pass@word1 -e POSTGRES_DB=indy -p 5432:5432 -d postgres:latest
+
+    fn backend() -> TestBackend {
+        TestBackend {
+            storage_type: Arc::new(PostgresStorageType::new()),
+            config: r#"
+            {
+                "read_host": "127.0.0.1",
+                "write_host": "127.0.0.1",
+                "port": 5432,
+                "db_name": "indy"
+            }
+            "#,
+            credentials: r#"
+            {
+                "user": "postgres",
+                "pass": "pass@word1"
+            }
+            "#,
+        }
+    }
+
+    sql_storage_tests!(postgres_storage, backend());
+}
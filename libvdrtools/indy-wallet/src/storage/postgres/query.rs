@@ -0,0 +1,134 @@
+use crate::storage::sql::SqlDialect;
+
+pub(super) struct PostgresDialect;
+
+impl SqlDialect for PostgresDialect {
+    const UNIQUE_VIOLATION: &'static str = "23505";
+
+    const NULL_TYPE: &'static str = "NULL::text";
+
+    const NULL_VALUE: &'static str = "NULL::bytea";
+
+    const NULL_TAGS: &'static str = "NULL::jsonb";
+
+    // Postgres placeholders are numbered, so each argument is referred to by its position.
+    fn placeholder(position: usize) -> String {
+        format!("${}", position)
+    }
+
+    fn tags_json(placeholder: &str) -> String {
+        format!("{}::jsonb", placeholder)
+    }
+
+    fn merge_tags(tags_json: &str) -> String {
+        format!("tags || {}", tags_json)
+    }
+
+    fn remove_tags(tag_placeholders: &[String]) -> String {
+        format!("tags - ARRAY[{}]::text[]", tag_placeholders.join(", "))
+    }
+
+    fn tag_argument(tag_name: String) -> String {
+        tag_name
+    }
+
+    fn tag_value(tag_placeholder: &str) -> String {
+        format!("(tags ->> {})", tag_placeholder)
+    }
+
+    // Containment is used for equality as it is served by the GIN index on tags.
+    fn tag_equals(name_placeholder: &str, value_placeholder: &str) -> String {
+        format!(
+            "(tags @> jsonb_build_object({}::text, {}::text))",
+            name_placeholder, value_placeholder
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indy_api_types::errors::prelude::*;
+    use indy_utils::crypto::base64;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::{
+        language::{Operator, TagName, TargetValue},
+        storage::sql::query::{wql_to_sql, wql_to_sql_count},
+        SearchOptions,
+    };
+
+    #[test]
+    fn wql_to_sql_numbers_placeholders() {
+        let wql = Operator::And(vec![
+            Operator::Eq(
+                TagName::EncryptedTagName(vec![1, 2, 3]),
+                TargetValue::Encrypted(vec![4, 5, 6]),
+            ),
+            Operator::In(
+                TagName::PlainTagName(vec![7]),
+                vec![
+                    TargetValue::Unencrypted("a".to_string()),
+                    TargetValue::Unencrypted("b".to_string()),
+                ],
+            ),
+        ]);
+
+        let (query, arguments) =
+            wql_to_sql::<PostgresDialect>(42, &[9], &wql, &SearchOptions::default()).unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT NULL::text, name, value, NULL::jsonb FROM items WHERE \
+             ((tags @> jsonb_build_object($1::text, $2::text)) AND ((tags ->> $3) IN ($4,$5))) AND \
+             type = $6 AND wallet_id = $7 ORDER BY id"
+        );
+        assert_eq!(
+            arguments,
+            vec![
+                Value::from(base64::encode(&[1, 2, 3])),
+                Value::from(base64::encode(&[4, 5, 6])),
+                Value::from(format!("~{}", base64::encode(&[7]))),
+                Value::from("a"),
+                Value::from("b"),
+                Value::from(base64::encode(&[9])),
+                Value::from(42),
+            ]
+        );
+    }
+
+    #[test]
+    fn wql_to_sql_count_works_for_empty_query() {
+        let (query, arguments) =
+            wql_to_sql_count::<PostgresDialect>(42, &[9], &Operator::And(vec![])).unwrap();
+
+        assert_eq!(
+            query,
+            "SELECT count(*) FROM items i WHERE  i.type = $1 AND i.wallet_id = $2"
+        );
+        assert_eq!(
+            arguments,
+            vec![Value::from(base64::encode(&[9])), Value::from(42)]
+        );
+    }
+
+    #[test]
+    fn wql_to_sql_fails_for_comparison_of_encrypted_tag() {
+        let wql = Operator::Gt(
+            TagName::EncryptedTagName(vec![1, 2, 3]),
+            TargetValue::Encrypted(vec![4, 5, 6]),
+        );
+
+        let res = wql_to_sql::<PostgresDialect>(42, &[9], &wql, &SearchOptions::default());
+        assert_eq!(IndyErrorKind::WalletQueryError, res.unwrap_err().kind());
+    }
+
+    #[test]
+    fn delete_tags_numbers_placeholders() {
+        assert_eq!(
+            PostgresDialect::delete_tags(2),
+            "UPDATE items SET tags = tags - ARRAY[$1, $2]::text[] \
+             WHERE type = $3 AND name = $4 AND wallet_id = $5"
+        );
+    }
+}
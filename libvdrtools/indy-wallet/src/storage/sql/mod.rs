@@ -0,0 +1,1050 @@
+//! Storage of wallet items in a SQL database, shared by the MySQL and Postgres backends.
+//!
+//! Items are kept in an `items` table with their tags stored as a json object, next to a
+//! `wallets` table holding the metadata of every wallet. A backend only provides its
+//! [`SqlDialect`] and connection handling, while the statements are executed by the code
+//! generated with `impl_sql_storage!`.
+
+use std::collections::{HashMap, VecDeque};
+
+use async_trait::async_trait;
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::base64;
+
+use crate::{
+    storage::{StorageIterator, StorageRecord, Tag, TagName},
+    wallet::EncryptedValue,
+};
+
+pub(super) mod query;
+#[cfg(test)]
+#[macro_use]
+pub(super) mod tests;
+
+/// SQL specific to a database. The provided methods build the statements shared by all
+/// databases out of the required ones.
+pub(super) trait SqlDialect {
+    /// SQLSTATE returned when a unique constraint is violated.
+    const UNIQUE_VIOLATION: &'static str;
+
+    /// Selected instead of the `type` column when it is not retrieved.
+    const NULL_TYPE: &'static str;
+
+    /// Selected instead of the `value` column when it is not retrieved.
+    const NULL_VALUE: &'static str;
+
+    /// Selected instead of the `tags` column when it is not retrieved.
+    const NULL_TAGS: &'static str;
+
+    /// Placeholder of the statement argument at the given 1-based position.
+    fn placeholder(position: usize) -> String;
+
+    /// Reads the json object bound to the placeholder as a value of the `tags` column.
+    fn tags_json(placeholder: &str) -> String;
+
+    /// Expression adding the tags of the json object to the `tags` column, replacing the
+    /// values of the tags already present.
+    fn merge_tags(tags_json: &str) -> String;
+
+    /// Expression removing the tags bound to the placeholders from the `tags` column.
+    fn remove_tags(tag_placeholders: &[String]) -> String;
+
+    /// Statement argument referring to the tag with the given name.
+    fn tag_argument(tag_name: String) -> String;
+
+    /// Expression extracting the value of the tag bound to the placeholder from the `tags`
+    /// column.
+    fn tag_value(tag_placeholder: &str) -> String;
+
+    /// Condition matching the items with the tag bound to the name placeholder set to the
+    /// value bound to the value placeholder.
+    fn tag_equals(name_placeholder: &str, value_placeholder: &str) -> String {
+        format!(
+            "({} = {})",
+            Self::tag_value(name_placeholder),
+            value_placeholder
+        )
+    }
+
+    fn item_condition(first_position: usize) -> String {
+        format!(
+            "type = {} AND name = {} AND wallet_id = {}",
+            Self::placeholder(first_position),
+            Self::placeholder(first_position + 1),
+            Self::placeholder(first_position + 2)
+        )
+    }
+
+    fn select_item(retrieve_value: bool, retrieve_tags: bool) -> String {
+        format!(
+            "SELECT {}, {} FROM items WHERE {}",
+            if retrieve_value {
+                "value"
+            } else {
+                Self::NULL_VALUE
+            },
+            if retrieve_tags {
+                "tags"
+            } else {
+                Self::NULL_TAGS
+            },
+            Self::item_condition(1)
+        )
+    }
+
+    fn select_all_items() -> String {
+        format!(
+            "SELECT type, name, value, tags FROM items WHERE wallet_id = {} ORDER BY id",
+            Self::placeholder(1)
+        )
+    }
+
+    fn insert_item() -> String {
+        format!(
+            "INSERT INTO items (type, name, value, tags, wallet_id) VALUES ({}, {}, {}, {}, {})",
+            Self::placeholder(1),
+            Self::placeholder(2),
+            Self::placeholder(3),
+            Self::tags_json(&Self::placeholder(4)),
+            Self::placeholder(5)
+        )
+    }
+
+    fn update_value() -> String {
+        format!(
+            "UPDATE items SET value = {} WHERE {}",
+            Self::placeholder(1),
+            Self::item_condition(2)
+        )
+    }
+
+    fn add_tags() -> String {
+        format!(
+            "UPDATE items SET tags = {} WHERE {}",
+            Self::merge_tags(&Self::tags_json(&Self::placeholder(1))),
+            Self::item_condition(2)
+        )
+    }
+
+    fn update_tags() -> String {
+        format!(
+            "UPDATE items SET tags = {} WHERE {}",
+            Self::tags_json(&Self::placeholder(1)),
+            Self::item_condition(2)
+        )
+    }
+
+    fn delete_tags(tag_count: usize) -> String {
+        let tag_placeholders = (1..=tag_count).map(Self::placeholder).collect::<Vec<_>>();
+
+        format!(
+            "UPDATE items SET tags = {} WHERE {}",
+            Self::remove_tags(&tag_placeholders),
+            Self::item_condition(tag_count + 1)
+        )
+    }
+
+    fn delete_item() -> String {
+        format!("DELETE FROM items WHERE {}", Self::item_condition(1))
+    }
+
+    fn select_metadata() -> String {
+        format!(
+            "SELECT metadata FROM wallets WHERE id = {}",
+            Self::placeholder(1)
+        )
+    }
+
+    fn update_metadata() -> String {
+        format!(
+            "UPDATE wallets SET metadata = {} WHERE id = {}",
+            Self::placeholder(1),
+            Self::placeholder(2)
+        )
+    }
+
+    fn select_wallet_id() -> String {
+        format!(
+            "SELECT id FROM wallets WHERE name = {}",
+            Self::placeholder(1)
+        )
+    }
+
+    fn insert_wallet() -> String {
+        format!(
+            "INSERT INTO wallets (name, metadata) VALUES ({}, {})",
+            Self::placeholder(1),
+            Self::placeholder(2)
+        )
+    }
+
+    fn delete_wallet() -> String {
+        format!("DELETE FROM wallets WHERE name = {}", Self::placeholder(1))
+    }
+}
+
+pub(super) struct SqlStorageIterator {
+    records: Option<VecDeque<IndyResult<StorageRecord>>>,
+    total_count: Option<usize>,
+}
+
+impl SqlStorageIterator {
+    pub(super) fn new(
+        records: Option<VecDeque<IndyResult<StorageRecord>>>,
+        total_count: Option<usize>,
+    ) -> SqlStorageIterator {
+        SqlStorageIterator {
+            records,
+            total_count,
+        }
+    }
+}
+
+#[async_trait]
+impl StorageIterator for SqlStorageIterator {
+    async fn next(&mut self) -> IndyResult<Option<StorageRecord>> {
+        match self.records.as_mut().and_then(VecDeque::pop_front) {
+            Some(record) => Ok(Some(record?)),
+            None => Ok(None),
+        }
+    }
+
+    fn get_total_count(&self) -> IndyResult<Option<usize>> {
+        Ok(self.total_count)
+    }
+}
+
+pub(super) fn record_from_columns(
+    type_: Option<String>,
+    id: String,
+    value: Option<Vec<u8>>,
+    tags: Option<serde_json::Value>,
+) -> IndyResult<StorageRecord> {
+    Ok(StorageRecord::new(
+        base64::decode(&id)?,
+        value
+            .map(|value| EncryptedValue::from_bytes(&value))
+            .transpose()?,
+        type_.map(|type_| base64::decode(&type_)).transpose()?,
+        tags.map(tags_from_json).transpose()?,
+    ))
+}
+
+pub(super) fn check_item_updated(rows_affected: u64) -> IndyResult<()> {
+    match rows_affected {
+        1 => Ok(()),
+        0 => Err(err_msg(
+            IndyErrorKind::WalletItemNotFound,
+            "Item to update not found",
+        )),
+        _ => Err(err_msg(
+            IndyErrorKind::InvalidState,
+            "More than one row update. Seems wallet structure is inconsistent",
+        )),
+    }
+}
+
+pub(super) fn check_item_deleted(rows_affected: u64) -> IndyResult<()> {
+    match rows_affected {
+        1 => Ok(()),
+        0 => Err(err_msg(
+            IndyErrorKind::WalletItemNotFound,
+            "Item to delete not found",
+        )),
+        _ => Err(err_msg(
+            IndyErrorKind::InvalidState,
+            "More than one row deleted. Seems wallet structure is inconsistent",
+        )),
+    }
+}
+
+fn tags_to_plain(tags: &[Tag]) -> HashMap<String, String> {
+    let mut map = HashMap::with_capacity(tags.len());
+
+    for tag in tags {
+        match *tag {
+            Tag::Encrypted(ref name, ref value) => {
+                map.insert(base64::encode(name), base64::encode(value))
+            }
+            Tag::PlainText(ref name, ref value) => {
+                map.insert(format!("~{}", &base64::encode(name)), value.to_string())
+            }
+        };
+    }
+
+    map
+}
+
+pub(super) fn tags_to_json(tags: &[Tag]) -> IndyResult<String> {
+    serde_json::to_string(&tags_to_plain(tags)).to_indy(
+        IndyErrorKind::InvalidState,
+        "Unable to serialize tags as json",
+    )
+}
+
+pub(super) fn tags_from_json(json: serde_json::Value) -> IndyResult<Vec<Tag>> {
+    let string_tags: HashMap<String, String> = serde_json::from_value(json).to_indy(
+        IndyErrorKind::InvalidState,
+        "Unable to deserialize tags from json",
+    )?;
+
+    let mut tags = Vec::with_capacity(string_tags.len());
+
+    for (k, v) in string_tags {
+        if k.starts_with('~') {
+            let mut key = k;
+            key.remove(0);
+            tags.push(Tag::PlainText(
+                base64::decode(&key).to_indy(
+                    IndyErrorKind::InvalidState,
+                    "Unable to decode tag key from base64",
+                )?,
+                v,
+            ));
+        } else {
+            tags.push(Tag::Encrypted(
+                base64::decode(&k).to_indy(
+                    IndyErrorKind::InvalidState,
+                    "Unable to decode tag key from base64",
+                )?,
+                base64::decode(&v).to_indy(
+                    IndyErrorKind::InvalidState,
+                    "Unable to decode tag value from base64",
+                )?,
+            ));
+        }
+    }
+    Ok(tags)
+}
+
+pub(super) fn tag_names_to_plain(tag_names: &[TagName]) -> Vec<String> {
+    tag_names
+        .iter()
+        .map(|tag_name| match *tag_name {
+            TagName::OfEncrypted(ref tag_name) => base64::encode(tag_name),
+            TagName::OfPlain(ref tag_name) => format!("~{}", base64::encode(tag_name)),
+        })
+        .collect()
+}
+
+/// Implements [`WalletStorage`](crate::storage::WalletStorage) for `$storage` and
+/// [`WalletStorageType`](crate::storage::WalletStorageType) for `$storage_type` of a SQL
+/// backend, executing the statements of `$dialect` on the `$database` of sqlx.
+///
+/// `$storage` must have the `wallet_id`, `read_pool` and `write_pool` fields, and
+/// `$storage_type` must provide the `_connect` method returning the pool of the database.
+macro_rules! impl_sql_storage {
+    ($storage:ident, $storage_type:ident, $database:ty, $row:ty, $dialect:ty) => {
+        mod sql_storage {
+            use std::collections::VecDeque;
+
+            use async_trait::async_trait;
+            use indy_api_types::errors::prelude::*;
+            use indy_utils::crypto::base64;
+            use sqlx::{Row, Transaction};
+
+            use super::*;
+            use $crate::{
+                language,
+                storage::{
+                    sql::{
+                        check_item_deleted, check_item_updated,
+                        query::{wql_to_sql, wql_to_sql_count},
+                        record_from_columns, tag_names_to_plain, tags_from_json, tags_to_json,
+                        SqlDialect, SqlStorageIterator,
+                    },
+                    StorageIterator, StorageOperation, StorageRecord, Tag, TagName, WalletStorage,
+                    WalletStorageType,
+                },
+                wallet::EncryptedValue,
+                RecordOptions, SearchOptions,
+            };
+
+            type Dialect = $dialect;
+
+            impl $storage {
+                async fn _add(
+                    &self,
+                    tx: &mut Transaction<'_, $database>,
+                    type_: &[u8],
+                    id: &[u8],
+                    value: &EncryptedValue,
+                    tags: &[Tag],
+                ) -> IndyResult<()> {
+                    sqlx::query(&Dialect::insert_item())
+                        .bind(&base64::encode(type_))
+                        .bind(&base64::encode(id))
+                        .bind(&value.to_bytes())
+                        .bind(&tags_to_json(tags)?)
+                        .bind(&self.wallet_id)
+                        .execute(&mut *tx)
+                        .await?;
+
+                    Ok(())
+                }
+
+                async fn _update(
+                    &self,
+                    tx: &mut Transaction<'_, $database>,
+                    type_: &[u8],
+                    id: &[u8],
+                    value: &EncryptedValue,
+                ) -> IndyResult<()> {
+                    let rows_affected = sqlx::query(&Dialect::update_value())
+                        .bind(&value.to_bytes())
+                        .bind(&base64::encode(type_))
+                        .bind(&base64::encode(id))
+                        .bind(&self.wallet_id)
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+
+                    check_item_updated(rows_affected)
+                }
+
+                async fn _add_tags(
+                    &self,
+                    tx: &mut Transaction<'_, $database>,
+                    type_: &[u8],
+                    id: &[u8],
+                    tags: &[Tag],
+                ) -> IndyResult<()> {
+                    if tags.is_empty() {
+                        // FIXME: Think about checking item exists
+                        return Ok(());
+                    }
+
+                    let rows_affected = sqlx::query(&Dialect::add_tags())
+                        .bind(&tags_to_json(tags)?)
+                        .bind(&base64::encode(type_))
+                        .bind(&base64::encode(id))
+                        .bind(&self.wallet_id)
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+
+                    check_item_updated(rows_affected)
+                }
+
+                async fn _update_tags(
+                    &self,
+                    tx: &mut Transaction<'_, $database>,
+                    type_: &[u8],
+                    id: &[u8],
+                    tags: &[Tag],
+                ) -> IndyResult<()> {
+                    let rows_affected = sqlx::query(&Dialect::update_tags())
+                        .bind(&tags_to_json(tags)?)
+                        .bind(&base64::encode(type_))
+                        .bind(&base64::encode(id))
+                        .bind(&self.wallet_id)
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+
+                    check_item_updated(rows_affected)
+                }
+
+                async fn _delete_tags(
+                    &self,
+                    tx: &mut Transaction<'_, $database>,
+                    type_: &[u8],
+                    id: &[u8],
+                    tag_names: &[TagName],
+                ) -> IndyResult<()> {
+                    if tag_names.is_empty() {
+                        // FIXME: Think about checking item exists
+                        return Ok(());
+                    }
+
+                    let statement = Dialect::delete_tags(tag_names.len());
+                    let mut query = sqlx::query(&statement);
+
+                    for tag_name in tag_names_to_plain(tag_names) {
+                        query = query.bind(Dialect::tag_argument(tag_name));
+                    }
+
+                    let rows_affected = query
+                        .bind(base64::encode(type_))
+                        .bind(base64::encode(id))
+                        .bind(self.wallet_id)
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+
+                    check_item_updated(rows_affected)
+                }
+
+                async fn _delete(
+                    &self,
+                    tx: &mut Transaction<'_, $database>,
+                    type_: &[u8],
+                    id: &[u8],
+                ) -> IndyResult<()> {
+                    let rows_affected = sqlx::query(&Dialect::delete_item())
+                        .bind(&base64::encode(type_))
+                        .bind(&base64::encode(id))
+                        .bind(&self.wallet_id)
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+
+                    check_item_deleted(rows_affected)
+                }
+            }
+
+            #[async_trait]
+            impl WalletStorage for $storage {
+                ///
+                /// Tries to fetch values and/or tags from the storage.
+                /// Returns Result with StorageEntity object which holds requested data in case of success or
+                /// Result with IndyError in case of failure.
+                ///
+                ///
+                /// # Arguments
+                ///
+                ///  * `type_` - type_ of the item in storage
+                ///  * `id` - id of the item in storage
+                ///  * `options` - JSon containing what needs to be fetched.
+                ///  Example: {"retrieveValue": true, "retrieveTags": true}
+                ///
+                /// # Returns
+                ///
+                /// Result that can be either:
+                ///
+                ///  * `StorageEntity` - Contains name, optional value and optional tags
+                ///  * `IndyError`
+                ///
+                /// # Errors
+                ///
+                /// Any of the following `IndyError` type_ of errors can be throw by this method:
+                ///
+                ///  * `IndyError::Closed` - Storage is closed
+                ///  * `IndyError::ItemNotFound` - Item is not found in database
+                ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+                ///
+                async fn get(
+                    &self,
+                    type_: &[u8],
+                    id: &[u8],
+                    options: &str,
+                ) -> IndyResult<StorageRecord> {
+                    let options: RecordOptions = serde_json::from_str(options).to_indy(
+                        IndyErrorKind::InvalidStructure,
+                        "RecordOptions is malformed json",
+                    )?;
+
+                    let mut conn = self.read_pool.acquire().await?;
+
+                    let (value, tags): (Option<Vec<u8>>, Option<serde_json::Value>) =
+                        sqlx::query_as(&Dialect::select_item(
+                            options.retrieve_value,
+                            options.retrieve_tags,
+                        ))
+                        .bind(&base64::encode(type_))
+                        .bind(&base64::encode(id))
+                        .bind(self.wallet_id)
+                        .fetch_one(&mut conn)
+                        .await?;
+
+                    let value = if let Some(value) = value {
+                        Some(EncryptedValue::from_bytes(&value)?)
+                    } else {
+                        None
+                    };
+
+                    let type_ = if options.retrieve_type {
+                        Some(type_.to_vec())
+                    } else {
+                        None
+                    };
+
+                    let tags = if let Some(tags) = tags {
+                        Some(tags_from_json(tags)?)
+                    } else {
+                        None
+                    };
+
+                    Ok(StorageRecord::new(id.to_vec(), value, type_, tags))
+                }
+
+                ///
+                /// inserts value and tags into storage.
+                /// Returns Result with () on success or
+                /// Result with IndyError in case of failure.
+                ///
+                ///
+                /// # Arguments
+                ///
+                ///  * `type_` - type of the item in storage
+                ///  * `id` - id of the item in storage
+                ///  * `value` - value of the item in storage
+                ///  * `value_key` - key used to encrypt the value
+                ///  * `tags` - tags assigned to the value
+                ///
+                /// # Returns
+                ///
+                /// Result that can be either:
+                ///
+                ///  * `()`
+                ///  * `IndyError`
+                ///
+                /// # Errors
+                ///
+                /// Any of the following `IndyError` class of errors can be throw by this method:
+                ///
+                ///  * `IndyError::Closed` - Storage is closed
+                ///  * `IndyError::ItemAlreadyExists` - Item is already present in database
+                ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+                ///
+                async fn add(
+                    &self,
+                    type_: &[u8],
+                    id: &[u8],
+                    value: &EncryptedValue,
+                    tags: &[Tag],
+                ) -> IndyResult<()> {
+                    let mut tx = self.write_pool.begin().await?;
+                    self._add(&mut tx, type_, id, value, tags).await?;
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn update(
+                    &self,
+                    type_: &[u8],
+                    id: &[u8],
+                    value: &EncryptedValue,
+                ) -> IndyResult<()> {
+                    let mut tx = self.write_pool.begin().await?;
+                    self._update(&mut tx, type_, id, value).await?;
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn add_tags(&self, type_: &[u8], id: &[u8], tags: &[Tag]) -> IndyResult<()> {
+                    let mut tx = self.write_pool.begin().await?;
+                    self._add_tags(&mut tx, type_, id, tags).await?;
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn update_tags(
+                    &self,
+                    type_: &[u8],
+                    id: &[u8],
+                    tags: &[Tag],
+                ) -> IndyResult<()> {
+                    let mut tx = self.write_pool.begin().await?;
+                    self._update_tags(&mut tx, type_, id, tags).await?;
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn delete_tags(
+                    &self,
+                    type_: &[u8],
+                    id: &[u8],
+                    tag_names: &[TagName],
+                ) -> IndyResult<()> {
+                    let mut tx = self.write_pool.begin().await?;
+                    self._delete_tags(&mut tx, type_, id, tag_names).await?;
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                ///
+                /// deletes value and tags into storage.
+                /// Returns Result with () on success or
+                /// Result with IndyError in case of failure.
+                ///
+                ///
+                /// # Arguments
+                ///
+                ///  * `type_` - type of the item in storage
+                ///  * `id` - id of the item in storage
+                ///
+                /// # Returns
+                ///
+                /// Result that can be either:
+                ///
+                ///  * `()`
+                ///  * `IndyError`
+                ///
+                /// # Errors
+                ///
+                /// Any of the following `IndyError` type_ of errors can be throw by this method:
+                ///
+                ///  * `IndyError::Closed` - Storage is closed
+                ///  * `IndyError::ItemNotFound` - Item is not found in database
+                ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+                ///
+                async fn delete(&self, type_: &[u8], id: &[u8]) -> IndyResult<()> {
+                    let mut tx = self.write_pool.begin().await?;
+                    self._delete(&mut tx, type_, id).await?;
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn apply_batch(&self, operations: &[StorageOperation]) -> IndyResult<()> {
+                    let mut tx = self.write_pool.begin().await?;
+
+                    for operation in operations {
+                        match operation {
+                            StorageOperation::Add {
+                                type_,
+                                id,
+                                value,
+                                tags,
+                            } => self._add(&mut tx, type_, id, value, tags).await?,
+                            StorageOperation::Update { type_, id, value } => {
+                                self._update(&mut tx, type_, id, value).await?
+                            }
+                            StorageOperation::AddTags { type_, id, tags } => {
+                                self._add_tags(&mut tx, type_, id, tags).await?
+                            }
+                            StorageOperation::UpdateTags { type_, id, tags } => {
+                                self._update_tags(&mut tx, type_, id, tags).await?
+                            }
+                            StorageOperation::DeleteTags {
+                                type_,
+                                id,
+                                tag_names,
+                            } => self._delete_tags(&mut tx, type_, id, tag_names).await?,
+                            StorageOperation::Delete { type_, id } => {
+                                self._delete(&mut tx, type_, id).await?
+                            }
+                        }
+                    }
+
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn get_storage_metadata(&self) -> IndyResult<Vec<u8>> {
+                    let mut conn = self.read_pool.acquire().await?;
+
+                    let (metadata,): (String,) =
+                        sqlx::query_as::<_, (String,)>(&Dialect::select_metadata())
+                            .bind(&self.wallet_id)
+                            .fetch_one(&mut conn)
+                            .await?;
+
+                    base64::decode(&metadata)
+                }
+
+                async fn set_storage_metadata(&self, metadata: &[u8]) -> IndyResult<()> {
+                    let mut tx = self.write_pool.begin().await?;
+
+                    sqlx::query(&Dialect::update_metadata())
+                        .bind(base64::encode(metadata))
+                        .bind(&self.wallet_id)
+                        .execute(&mut tx)
+                        .await?;
+
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                async fn get_all(&self) -> IndyResult<Box<dyn StorageIterator>> {
+                    let records: VecDeque<_> = sqlx::query(&Dialect::select_all_items())
+                        .bind(self.wallet_id)
+                        .map(|r: $row| -> IndyResult<StorageRecord> {
+                            record_from_columns(
+                                Some(r.get(0)),
+                                r.get(1),
+                                Some(r.get(2)),
+                                Some(r.get(3)),
+                            )
+                        })
+                        .fetch_all(&self.read_pool)
+                        .await?
+                        .into_iter()
+                        .collect();
+
+                    let total_len = records.len();
+
+                    // FIXME: Fetch total count
+                    Ok(Box::new(SqlStorageIterator::new(
+                        Some(records),
+                        Some(total_len),
+                    )))
+                }
+
+                async fn search(
+                    &self,
+                    type_: &[u8],
+                    query: &language::Operator,
+                    options: Option<&str>,
+                ) -> IndyResult<Box<dyn StorageIterator>> {
+                    let options = if let Some(options) = options {
+                        serde_json::from_str(options).to_indy(
+                            IndyErrorKind::InvalidStructure,
+                            "Search options is malformed json",
+                        )?
+                    } else {
+                        SearchOptions::default()
+                    };
+
+                    let mut conn = self.read_pool.acquire().await?;
+
+                    let total_count = if options.retrieve_total_count {
+                        let (query, args) =
+                            wql_to_sql_count::<Dialect>(self.wallet_id, type_, query)?;
+                        let mut query = sqlx::query_as::<$database, (i64,)>(&query);
+
+                        for arg in args.iter() {
+                            query = if arg.is_i64() {
+                                query.bind(arg.as_i64().unwrap())
+                            } else if arg.is_string() {
+                                query.bind(arg.as_str().unwrap())
+                            } else {
+                                return Err(err_msg(
+                                    IndyErrorKind::InvalidState,
+                                    "Unexpected sql parameter type.",
+                                ));
+                            }
+                        }
+
+                        let (total_count,) = query.fetch_one(&mut conn).await?;
+                        Some(total_count as usize)
+                    } else {
+                        None
+                    };
+
+                    let records = if options.retrieve_records {
+                        let (query, args) =
+                            wql_to_sql::<Dialect>(self.wallet_id, type_, query, &options)?;
+
+                        let mut query = sqlx::query::<$database>(&query);
+
+                        for arg in args.iter() {
+                            query = if arg.is_i64() {
+                                query.bind(arg.as_i64().unwrap())
+                            } else if arg.is_string() {
+                                query.bind(arg.as_str().unwrap())
+                            } else {
+                                return Err(err_msg(
+                                    IndyErrorKind::InvalidState,
+                                    "Unexpected sql parameter type.",
+                                ));
+                            }
+                        }
+
+                        let records: VecDeque<_> = query
+                            .map(|r: $row| -> IndyResult<StorageRecord> {
+                                record_from_columns(
+                                    if options.retrieve_type {
+                                        Some(r.get(0))
+                                    } else {
+                                        None
+                                    },
+                                    r.get(1),
+                                    if options.retrieve_value {
+                                        Some(r.get(2))
+                                    } else {
+                                        None
+                                    },
+                                    if options.retrieve_tags {
+                                        Some(r.get(3))
+                                    } else {
+                                        None
+                                    },
+                                )
+                            })
+                            .fetch_all(&self.read_pool)
+                            .await?
+                            .into_iter()
+                            .collect();
+
+                        Some(records)
+                    } else {
+                        None
+                    };
+
+                    Ok(Box::new(SqlStorageIterator::new(records, total_count)))
+                }
+
+                fn close(&mut self) -> IndyResult<()> {
+                    Ok(())
+                }
+            }
+
+            #[async_trait]
+            impl WalletStorageType for $storage_type {
+                ///
+                /// Deletes the wallet with the provided id, together with all its items, from the
+                /// database specified in the config.
+                ///
+                /// # Arguments
+                ///
+                ///  * `id` - name of the wallet
+                ///  * `storage_config` - config containing the location of the database
+                ///  * `storage_credentials` - DB credentials
+                ///
+                /// # Returns
+                ///
+                /// Result that can be either:
+                ///
+                ///  * `()`
+                ///  * `IndyError`
+                ///
+                /// # Errors
+                ///
+                /// Any of the following `IndyError` type_ of errors can be throw by this method:
+                ///
+                ///  * `IndyError::NotFound` - Wallet with the provided id not found
+                ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+                ///
+                async fn delete_storage(
+                    &self,
+                    id: &str,
+                    config: Option<&str>,
+                    credentials: Option<&str>,
+                ) -> IndyResult<()> {
+                    let mut tx = self
+                        ._connect(false, config, credentials)
+                        .await?
+                        .begin()
+                        .await?;
+
+                    let rows_affected = sqlx::query(&Dialect::delete_wallet())
+                        .bind(id)
+                        .execute(&mut tx)
+                        .await?
+                        .rows_affected();
+
+                    match rows_affected {
+                        1 => {
+                            tx.commit().await?;
+                            Ok(())
+                        }
+                        0 => Err(err_msg(
+                            IndyErrorKind::WalletNotFound,
+                            "Item to delete not found",
+                        )),
+                        _ => Err(err_msg(
+                            IndyErrorKind::InvalidState,
+                            "More than one row deleted. Seems wallet structure is inconsistent",
+                        )),
+                    }
+                }
+
+                ///
+                /// Creates the wallet with the provided name in the database specified in the
+                /// config, and stores the encryption keys needed for encryption and decryption
+                /// of data.
+                ///
+                /// # Arguments
+                ///
+                ///  * `id` - name of the wallet
+                ///  * `config` - config containing the location of the database
+                ///  * `credentials` - DB credentials
+                ///  * `metadata` - encryption keys that need to be stored in the newly created DB
+                ///
+                /// # Returns
+                ///
+                /// Result that can be either:
+                ///
+                ///  * `()`
+                ///  * `IndyError`
+                ///
+                /// # Errors
+                ///
+                /// Any of the following `IndyError` type_ of errors can be throw by this method:
+                ///
+                ///  * `AlreadyExists` - Wallet with a given name already exists in the database
+                ///  * `IOError("IO error during storage operation:...")` - Connection to the DB failed
+                ///
+                async fn create_storage(
+                    &self,
+                    id: &str,
+                    config: Option<&str>,
+                    credentials: Option<&str>,
+                    metadata: &[u8],
+                ) -> IndyResult<()> {
+                    let mut tx = self
+                        ._connect(false, config, credentials)
+                        .await?
+                        .begin()
+                        .await?;
+
+                    let res = sqlx::query(&Dialect::insert_wallet())
+                        .bind(id)
+                        .bind(base64::encode(metadata))
+                        .execute(&mut tx)
+                        .await;
+
+                    match res {
+                        Err(sqlx::Error::Database(e))
+                            if e.code().as_deref() == Some(Dialect::UNIQUE_VIOLATION) =>
+                        {
+                            return Err(err_msg(
+                                IndyErrorKind::WalletAlreadyExists,
+                                "Wallet already exists",
+                            ))
+                        }
+                        e => e?,
+                    };
+
+                    tx.commit().await?;
+                    Ok(())
+                }
+
+                ///
+                /// Establishes a connection to the database specified in the config and looks up
+                /// the wallet with the provided id. In case of success returns a Storage object
+                /// embedding the read and write connection pools of the wallet.
+                ///
+                ///
+                /// # Arguments
+                ///
+                ///  * `id` - name of the wallet
+                ///  * `config` - config containing the location of the database
+                ///  * `credentials` - DB credentials
+                ///
+                /// # Returns
+                ///
+                /// Result that can be either:
+                ///
+                ///  * `Box<Storage>` - Storage of the wallet
+                ///  * `IndyError`
+                ///
+                /// # Errors
+                ///
+                /// Any of the following `IndyError` type_ of errors can be throw by this method:
+                ///
+                ///  * `IndyError::NotFound` - Wallet with the provided id not found
+                ///  * `IOError("IO error during storage operation:...")` - Failed connection or SQL query
+                ///
+                async fn open_storage(
+                    &self,
+                    id: &str,
+                    config: Option<&str>,
+                    credentials: Option<&str>,
+                ) -> IndyResult<Box<dyn WalletStorage>> {
+                    let read_pool = self._connect(true, config, credentials).await?;
+                    let write_pool = self._connect(false, config, credentials).await?;
+
+                    let res = sqlx::query_as::<_, (i64,)>(&Dialect::select_wallet_id())
+                        .bind(id)
+                        .fetch_one(&read_pool)
+                        .await;
+
+                    let (wallet_id,) = match res {
+                        Err(sqlx::Error::RowNotFound) => {
+                            return Err(err_msg(IndyErrorKind::WalletNotFound, "Wallet not found"));
+                        }
+                        e => e?,
+                    };
+
+                    Ok(Box::new($storage {
+                        read_pool,
+                        write_pool,
+                        wallet_id,
+                    }))
+                }
+            }
+        }
+    };
+}
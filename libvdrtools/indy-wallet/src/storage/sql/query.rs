@@ -0,0 +1,256 @@
+use indy_api_types::errors::prelude::*;
+use indy_utils::crypto::base64;
+use serde_json::Value;
+
+use super::SqlDialect;
+use crate::{
+    language::{Operator, TagName, TargetValue},
+    SearchOptions,
+};
+
+pub(in crate::storage) fn wql_to_sql<D: SqlDialect>(
+    wallet_id: i64,
+    type_: &[u8],
+    wql: &Operator,
+    options: &SearchOptions,
+) -> IndyResult<(String, Vec<Value>)> {
+    let mut arguments: Vec<Value> = Vec::new();
+
+    let query_condition = operator_to_sql::<D>(wql, &mut arguments)?;
+    let type_arg = push_argument::<D>(base64::encode(type_).into(), &mut arguments);
+    let wallet_id_arg = push_argument::<D>(wallet_id.into(), &mut arguments);
+
+    let query_string = format!(
+        "SELECT {}, name, {}, {} FROM items WHERE {} type = {} AND wallet_id = {} ORDER BY id",
+        if options.retrieve_type {
+            "type"
+        } else {
+            D::NULL_TYPE
+        },
+        if options.retrieve_value {
+            "value"
+        } else {
+            D::NULL_VALUE
+        },
+        if options.retrieve_tags {
+            "tags"
+        } else {
+            D::NULL_TAGS
+        },
+        if !query_condition.is_empty() {
+            query_condition + " AND"
+        } else {
+            "".to_string()
+        },
+        type_arg,
+        wallet_id_arg
+    );
+
+    Ok((query_string, arguments))
+}
+
+pub(in crate::storage) fn wql_to_sql_count<D: SqlDialect>(
+    wallet_id: i64,
+    type_: &[u8],
+    wql: &Operator,
+) -> IndyResult<(String, Vec<Value>)> {
+    let mut arguments: Vec<Value> = Vec::new();
+
+    let query_condition = operator_to_sql::<D>(wql, &mut arguments)?;
+    let type_arg = push_argument::<D>(base64::encode(type_).into(), &mut arguments);
+    let wallet_id_arg = push_argument::<D>(wallet_id.into(), &mut arguments);
+
+    let query_string = format!(
+        "SELECT count(*) FROM items i WHERE {} i.type = {} AND i.wallet_id = {}",
+        if !query_condition.is_empty() {
+            query_condition + " AND"
+        } else {
+            "".to_string()
+        },
+        type_arg,
+        wallet_id_arg
+    );
+
+    Ok((query_string, arguments))
+}
+
+fn push_argument<D: SqlDialect>(argument: Value, arguments: &mut Vec<Value>) -> String {
+    arguments.push(argument);
+    D::placeholder(arguments.len())
+}
+
+fn push_tag_arguments<D: SqlDialect>(
+    tag_name: &TagName,
+    tag_value: &TargetValue,
+    arguments: &mut Vec<Value>,
+) -> (String, String) {
+    let name_arg = push_argument::<D>(D::tag_argument(tag_name.to_plain()).into(), arguments);
+    let value_arg = push_argument::<D>(tag_value.to_plain().into(), arguments);
+    (name_arg, value_arg)
+}
+
+fn operator_to_sql<D: SqlDialect>(op: &Operator, arguments: &mut Vec<Value>) -> IndyResult<String> {
+    match *op {
+        Operator::Eq(ref tag_name, ref target_value) => {
+            Ok(eq_to_sql::<D>(tag_name, target_value, arguments))
+        }
+        Operator::Neq(ref tag_name, ref target_value) => {
+            Ok(neq_to_sql::<D>(tag_name, target_value, arguments))
+        }
+        Operator::Gt(ref tag_name, ref target_value) => {
+            plain_cmp_to_sql::<D>(tag_name, target_value, ">", "$gt", arguments)
+        }
+        Operator::Gte(ref tag_name, ref target_value) => {
+            plain_cmp_to_sql::<D>(tag_name, target_value, ">=", "$gte", arguments)
+        }
+        Operator::Lt(ref tag_name, ref target_value) => {
+            plain_cmp_to_sql::<D>(tag_name, target_value, "<", "$lt", arguments)
+        }
+        Operator::Lte(ref tag_name, ref target_value) => {
+            plain_cmp_to_sql::<D>(tag_name, target_value, "<=", "$lte", arguments)
+        }
+        Operator::Like(ref tag_name, ref target_value) => {
+            plain_cmp_to_sql::<D>(tag_name, target_value, "LIKE", "$like", arguments)
+        }
+        Operator::In(ref tag_name, ref target_values) => {
+            Ok(in_to_sql::<D>(tag_name, target_values, arguments))
+        }
+        Operator::And(ref suboperators) => and_to_sql::<D>(suboperators, arguments),
+        Operator::Or(ref suboperators) => or_to_sql::<D>(suboperators, arguments),
+        Operator::Not(ref suboperator) => not_to_sql::<D>(suboperator, arguments),
+    }
+}
+
+fn eq_to_sql<D: SqlDialect>(
+    tag_name: &TagName,
+    tag_value: &TargetValue,
+    arguments: &mut Vec<Value>,
+) -> String {
+    let (name_arg, value_arg) = push_tag_arguments::<D>(tag_name, tag_value, arguments);
+    D::tag_equals(&name_arg, &value_arg)
+}
+
+fn neq_to_sql<D: SqlDialect>(
+    tag_name: &TagName,
+    tag_value: &TargetValue,
+    arguments: &mut Vec<Value>,
+) -> String {
+    let (name_arg, value_arg) = push_tag_arguments::<D>(tag_name, tag_value, arguments);
+    format!("({} != {})", D::tag_value(&name_arg), value_arg)
+}
+
+fn plain_cmp_to_sql<D: SqlDialect>(
+    tag_name: &TagName,
+    tag_value: &TargetValue,
+    sql_operator: &str,
+    wql_operator: &str,
+    arguments: &mut Vec<Value>,
+) -> IndyResult<String> {
+    match (tag_name, tag_value) {
+        (&TagName::PlainTagName(_), &TargetValue::Unencrypted(_)) => {
+            let (name_arg, value_arg) = push_tag_arguments::<D>(tag_name, tag_value, arguments);
+
+            Ok(format!(
+                "({} {} {})",
+                D::tag_value(&name_arg),
+                sql_operator,
+                value_arg
+            ))
+        }
+        _ => Err(err_msg(
+            IndyErrorKind::WalletQueryError,
+            format!(
+                "Invalid combination of tag name and value for {} operator",
+                wql_operator
+            ),
+        )),
+    }
+}
+
+fn in_to_sql<D: SqlDialect>(
+    tag_name: &TagName,
+    tag_values: &[TargetValue],
+    arguments: &mut Vec<Value>,
+) -> String {
+    if tag_values.is_empty() {
+        return "FALSE".to_string();
+    }
+
+    let name_arg = push_argument::<D>(D::tag_argument(tag_name.to_plain()).into(), arguments);
+    let value_args = tag_values
+        .iter()
+        .map(|tag_value| push_argument::<D>(tag_value.to_plain().into(), arguments))
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!("({} IN ({}))", D::tag_value(&name_arg), value_args)
+}
+
+fn and_to_sql<D: SqlDialect>(
+    suboperators: &[Operator],
+    arguments: &mut Vec<Value>,
+) -> IndyResult<String> {
+    join_operators::<D>(suboperators, " AND ", arguments)
+}
+
+fn or_to_sql<D: SqlDialect>(
+    suboperators: &[Operator],
+    arguments: &mut Vec<Value>,
+) -> IndyResult<String> {
+    join_operators::<D>(suboperators, " OR ", arguments)
+}
+
+fn not_to_sql<D: SqlDialect>(
+    suboperator: &Operator,
+    arguments: &mut Vec<Value>,
+) -> IndyResult<String> {
+    let suboperator_string = operator_to_sql::<D>(suboperator, arguments)?;
+    Ok("NOT (".to_string() + &suboperator_string + ")")
+}
+
+fn join_operators<D: SqlDialect>(
+    operators: &[Operator],
+    join_str: &str,
+    arguments: &mut Vec<Value>,
+) -> IndyResult<String> {
+    let mut s = String::new();
+
+    if !operators.is_empty() {
+        s.push('(');
+        for (index, operator) in operators.iter().enumerate() {
+            let operator_string = operator_to_sql::<D>(operator, arguments)?;
+
+            s.push_str(&operator_string);
+
+            if index < operators.len() - 1 {
+                s.push_str(join_str);
+            }
+        }
+
+        s.push(')');
+    }
+
+    Ok(s)
+}
+
+trait ToPlain {
+    fn to_plain(&self) -> String;
+}
+
+impl ToPlain for TagName {
+    fn to_plain(&self) -> String {
+        match *self {
+            TagName::EncryptedTagName(ref v) => base64::encode(v),
+            TagName::PlainTagName(ref v) => format!("~{}", base64::encode(v)),
+        }
+    }
+}
+
+impl ToPlain for TargetValue {
+    fn to_plain(&self) -> String {
+        match *self {
+            TargetValue::Unencrypted(ref s) => s.to_owned(),
+            TargetValue::Encrypted(ref v) => base64::encode(v),
+        }
+    }
+}
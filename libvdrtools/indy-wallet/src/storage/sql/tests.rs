@@ -0,0 +1,1281 @@
+//! Tests shared by the SQLite, MySQL and Postgres storage backends, run against a database by
+//! the tests of every backend with `sql_storage_tests!`.
+
+use std::sync::Arc;
+
+use indy_api_types::errors::prelude::*;
+use indy_utils::assert_kind;
+
+use crate::{
+    storage::{StorageOperation, Tag, TagName, WalletStorage, WalletStorageType},
+    wallet::EncryptedValue,
+};
+
+/// Defines a test for every shared test, running it against the backend returned by
+/// `$backend`. The tests are named after the shared ones, prefixed with `$prefix`.
+macro_rules! sql_storage_tests {
+    ($prefix:ident, $backend:expr) => {
+        sql_storage_tests!(
+            @tests $prefix, $backend,
+            [
+                type_create_works,
+                type_create_works_for_twice,
+                get_storage_metadata_works,
+                type_delete_works,
+                type_delete_works_for_non_existing,
+                type_open_works,
+                type_open_works_for_not_created,
+                add_works_for_is_802,
+                set_get_works,
+                set_get_works_for_twice,
+                set_get_works_for_reopen,
+                get_works_for_wrong_key,
+                delete_works,
+                delete_works_for_non_existing,
+                delete_returns_error_item_not_found_if_no_such_type,
+                get_all_works,
+                get_all_works_for_empty,
+                update_works,
+                update_works_for_non_existing_id,
+                update_works_for_non_existing_type,
+                add_tags_works,
+                add_tags_works_for_non_existing_id,
+                add_tags_works_for_non_existing_type,
+                add_tags_works_for_already_existing,
+                update_tags_works,
+                update_tags_works_for_non_existing_id,
+                update_tags_works_for_non_existing_type,
+                update_tags_works_for_already_existing,
+                delete_tags_works,
+                delete_tags_works_for_non_existing_type,
+                delete_tags_works_for_non_existing_id,
+                apply_batch_works,
+                apply_batch_works_for_failed_operation
+            ]
+        );
+
+        paste::paste! {
+            #[tokio::test]
+            #[cfg(feature = "benchmark")]
+            async fn [<$prefix _sync_send>]() {
+                $crate::storage::sql::tests::sync_send(&$backend).await;
+            }
+        }
+    };
+    (@tests $prefix:ident, $backend:expr, [$($test:ident),*]) => {
+        paste::paste! {
+            $(
+                #[tokio::test]
+                async fn [<$prefix _ $test>]() {
+                    $crate::storage::sql::tests::$test(&$backend).await;
+                }
+            )*
+        }
+    };
+}
+
+/// Database the shared tests are run against.
+pub(in crate::storage) struct TestBackend {
+    pub(in crate::storage) storage_type: Arc<dyn WalletStorageType>,
+    pub(in crate::storage) config: &'static str,
+    pub(in crate::storage) credentials: &'static str,
+}
+
+impl TestBackend {
+    fn config(&self) -> Option<&'static str> {
+        Some(self.config)
+    }
+
+    fn credentials(&self) -> Option<&'static str> {
+        Some(self.credentials)
+    }
+
+    async fn cleanup(&self, name: &str) {
+        self.storage_type
+            .delete_storage(name, self.config(), self.credentials())
+            .await
+            .ok();
+    }
+
+    async fn storage(&self, name: &str) -> Box<dyn WalletStorage> {
+        self.storage_type
+            .create_storage(name, self.config(), self.credentials(), &_metadata())
+            .await
+            .unwrap();
+
+        self.storage_type
+            .open_storage(name, self.config(), self.credentials())
+            .await
+            .unwrap()
+    }
+}
+
+#[cfg(feature = "benchmark")]
+pub(in crate::storage) async fn sync_send(backend: &TestBackend) {
+    use futures::{channel::oneshot, executor::ThreadPool, future::join_all};
+
+    let count = 1000;
+    let executor = ThreadPool::new().expect("Failed to new ThreadPool");
+
+    let waiters: Vec<_> = (0..count)
+        .map(|id| {
+            let storage_type = backend.storage_type.clone();
+            let (config, credentials) = (backend.config(), backend.credentials());
+            let (tx, rx) = oneshot::channel::<IndyResult<()>>();
+
+            let future = async move {
+                let res = storage_type
+                    .delete_storage(&format!("sync_send_{}", id), config, credentials)
+                    .await;
+
+                tx.send(res).unwrap();
+            };
+
+            executor.spawn_ok(future);
+            rx
+        })
+        .collect();
+
+    join_all(waiters).await;
+
+    let waiters: Vec<_> = (0..count)
+        .map(|id| {
+            let storage_type = backend.storage_type.clone();
+            let (config, credentials) = (backend.config(), backend.credentials());
+            let (tx, rx) = oneshot::channel::<IndyResult<()>>();
+
+            let future = async move {
+                let res = storage_type
+                    .create_storage(
+                        &format!("sync_send_{}", id),
+                        config,
+                        credentials,
+                        &_metadata(),
+                    )
+                    .await;
+
+                tx.send(res).unwrap();
+            };
+
+            executor.spawn_ok(future);
+            rx
+        })
+        .collect();
+
+    join_all(waiters).await;
+
+    let waiters: Vec<_> = (0..count)
+        .map(|id| {
+            let storage_type = backend.storage_type.clone();
+            let (config, credentials) = (backend.config(), backend.credentials());
+            let (tx, rx) = oneshot::channel::<IndyResult<()>>();
+
+            let future = async move {
+                let res = storage_type
+                    .delete_storage(&format!("sync_send_{}", id), config, credentials)
+                    .await;
+
+                tx.send(res).unwrap();
+            };
+
+            executor.spawn_ok(future);
+            rx
+        })
+        .collect();
+
+    join_all(waiters).await;
+}
+pub(in crate::storage) async fn type_create_works(backend: &TestBackend) {
+    backend.cleanup("type_create_works").await;
+
+    let storage_type = &backend.storage_type;
+
+    storage_type
+        .create_storage(
+            "type_create_works",
+            backend.config(),
+            backend.credentials(),
+            &_metadata(),
+        )
+        .await
+        .unwrap();
+
+    backend.cleanup("type_create_works").await;
+}
+
+pub(in crate::storage) async fn type_create_works_for_twice(backend: &TestBackend) {
+    backend.cleanup("type_create_works_for_twice").await;
+
+    let storage_type = &backend.storage_type;
+    storage_type
+        .create_storage(
+            "type_create_works_for_twice",
+            backend.config(),
+            backend.credentials(),
+            &_metadata(),
+        )
+        .await
+        .unwrap();
+
+    let res = storage_type
+        .create_storage(
+            "type_create_works_for_twice",
+            backend.config(),
+            backend.credentials(),
+            &_metadata(),
+        )
+        .await;
+
+    assert_kind!(IndyErrorKind::WalletAlreadyExists, res);
+
+    storage_type
+        .delete_storage(
+            "type_create_works_for_twice",
+            backend.config(),
+            backend.credentials(),
+        )
+        .await
+        .unwrap();
+}
+
+pub(in crate::storage) async fn get_storage_metadata_works(backend: &TestBackend) {
+    backend.cleanup("get_storage_metadata_works").await;
+
+    {
+        let storage = backend.storage("get_storage_metadata_works").await;
+        let metadata = storage.get_storage_metadata().await.unwrap();
+
+        assert_eq!(metadata, _metadata());
+    }
+
+    backend.cleanup("get_storage_metadata_works").await;
+}
+
+pub(in crate::storage) async fn type_delete_works(backend: &TestBackend) {
+    backend.cleanup("type_delete_works").await;
+
+    let storage_type = &backend.storage_type;
+    storage_type
+        .create_storage(
+            "type_delete_works",
+            backend.config(),
+            backend.credentials(),
+            &_metadata(),
+        )
+        .await
+        .unwrap();
+
+    storage_type
+        .delete_storage("type_delete_works", backend.config(), backend.credentials())
+        .await
+        .unwrap();
+}
+
+pub(in crate::storage) async fn type_delete_works_for_non_existing(backend: &TestBackend) {
+    backend.cleanup("type_delete_works_for_non_existing").await;
+
+    let storage_type = &backend.storage_type;
+
+    storage_type
+        .create_storage(
+            "type_delete_works_for_non_existing",
+            backend.config(),
+            backend.credentials(),
+            &_metadata(),
+        )
+        .await
+        .unwrap();
+
+    let res = storage_type
+        .delete_storage("unknown", backend.config(), backend.credentials())
+        .await;
+    assert_kind!(IndyErrorKind::WalletNotFound, res);
+
+    storage_type
+        .delete_storage(
+            "type_delete_works_for_non_existing",
+            backend.config(),
+            backend.credentials(),
+        )
+        .await
+        .unwrap();
+}
+
+pub(in crate::storage) async fn type_open_works(backend: &TestBackend) {
+    backend.cleanup("type_open_works").await;
+    backend.storage("type_open_works").await;
+    backend.cleanup("type_open_works").await;
+}
+
+pub(in crate::storage) async fn type_open_works_for_not_created(backend: &TestBackend) {
+    backend.cleanup("type_open_works_for_not_created").await;
+
+    let storage_type = &backend.storage_type;
+
+    let res = storage_type
+        .open_storage("unknown", backend.config(), backend.credentials())
+        .await;
+
+    assert_kind!(IndyErrorKind::WalletNotFound, res);
+}
+
+pub(in crate::storage) async fn add_works_for_is_802(backend: &TestBackend) {
+    backend.cleanup("add_works_for_is_802").await;
+
+    {
+        let storage = backend.storage("add_works_for_is_802").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage.add(&_type1(), &_id1(), &_value1(), &_tags()).await;
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+
+        let res = storage.add(&_type1(), &_id1(), &_value1(), &_tags()).await;
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+    }
+
+    backend.cleanup("add_works_for_is_802").await;
+}
+
+pub(in crate::storage) async fn set_get_works(backend: &TestBackend) {
+    backend.cleanup("set_get_works").await;
+
+    {
+        let storage = backend.storage("set_get_works").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+    }
+
+    backend.cleanup("set_get_works").await;
+}
+
+pub(in crate::storage) async fn set_get_works_for_twice(backend: &TestBackend) {
+    backend.cleanup("set_get_works_for_twice").await;
+
+    {
+        let storage = backend.storage("set_get_works_for_twice").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage.add(&_type1(), &_id1(), &_value2(), &_tags()).await;
+        assert_kind!(IndyErrorKind::WalletItemAlreadyExists, res);
+    }
+
+    backend.cleanup("set_get_works_for_twice").await;
+}
+
+pub(in crate::storage) async fn set_get_works_for_reopen(backend: &TestBackend) {
+    backend.cleanup("set_get_works_for_reopen").await;
+
+    backend
+        .storage("set_get_works_for_reopen")
+        .await
+        .add(&_type1(), &_id1(), &_value1(), &_tags())
+        .await
+        .unwrap();
+
+    let record = backend
+        .storage_type
+        .open_storage(
+            "set_get_works_for_reopen",
+            backend.config(),
+            backend.credentials(),
+        )
+        .await
+        .unwrap()
+        .get(
+            &_type1(),
+            &_id1(),
+            r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(record.value.unwrap(), _value1());
+    assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+    backend.cleanup("set_get_works_for_reopen").await;
+}
+
+pub(in crate::storage) async fn get_works_for_wrong_key(backend: &TestBackend) {
+    backend.cleanup("get_works_for_wrong_key").await;
+
+    {
+        let storage = backend.storage("get_works_for_wrong_key").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage
+            .get(
+                &_type1(),
+                &_id2(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await;
+
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend.cleanup("get_works_for_wrong_key").await;
+}
+
+pub(in crate::storage) async fn delete_works(backend: &TestBackend) {
+    backend.cleanup("delete_works").await;
+
+    {
+        let storage = backend.storage("delete_works").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+        storage.delete(&_type1(), &_id1()).await.unwrap();
+
+        let res = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await;
+
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend.cleanup("delete_works").await;
+}
+
+pub(in crate::storage) async fn delete_works_for_non_existing(backend: &TestBackend) {
+    backend.cleanup("delete_works_for_non_existing").await;
+
+    {
+        let storage = backend.storage("delete_works_for_non_existing").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage.delete(&_type1(), &_id2()).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend.cleanup("delete_works_for_non_existing").await;
+}
+
+pub(in crate::storage) async fn delete_returns_error_item_not_found_if_no_such_type(
+    backend: &TestBackend,
+) {
+    backend
+        .cleanup("delete_returns_error_item_not_found_if_no_such_type")
+        .await;
+
+    {
+        let storage = backend
+            .storage("delete_returns_error_item_not_found_if_no_such_type")
+            .await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage.delete(&_type2(), &_id2()).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend
+        .cleanup("delete_returns_error_item_not_found_if_no_such_type")
+        .await;
+}
+
+pub(in crate::storage) async fn get_all_works(backend: &TestBackend) {
+    backend.cleanup("get_all_works").await;
+
+    {
+        let storage = backend.storage("get_all_works").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        storage
+            .add(&_type2(), &_id2(), &_value2(), &_tags())
+            .await
+            .unwrap();
+
+        let mut storage_iterator = storage.get_all().await.unwrap();
+
+        let record = storage_iterator.next().await.unwrap().unwrap();
+        assert_eq!(record.type_.unwrap(), _type1());
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+        let record = storage_iterator.next().await.unwrap().unwrap();
+        assert_eq!(record.type_.unwrap(), _type2());
+        assert_eq!(record.value.unwrap(), _value2());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_tags()));
+
+        let record = storage_iterator.next().await.unwrap();
+        assert!(record.is_none());
+    }
+
+    backend.cleanup("get_all_works").await;
+}
+
+pub(in crate::storage) async fn get_all_works_for_empty(backend: &TestBackend) {
+    backend.cleanup("get_all_works_for_empty").await;
+
+    {
+        let storage = backend.storage("get_all_works_for_empty").await;
+        let mut storage_iterator = storage.get_all().await.unwrap();
+
+        let record = storage_iterator.next().await.unwrap();
+        assert!(record.is_none());
+    }
+
+    backend.cleanup("get_all_works_for_empty").await;
+}
+
+pub(in crate::storage) async fn update_works(backend: &TestBackend) {
+    backend.cleanup("update_works").await;
+
+    {
+        let storage = backend.storage("update_works").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+
+        storage
+            .update(&_type1(), &_id1(), &_value2())
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value2());
+    }
+
+    backend.cleanup("update_works").await;
+}
+
+pub(in crate::storage) async fn update_works_for_non_existing_id(backend: &TestBackend) {
+    backend.cleanup("update_works_for_non_existing_id").await;
+
+    {
+        let storage = backend.storage("update_works_for_non_existing_id").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+
+        let res = storage.update(&_type1(), &_id2(), &_value2()).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend.cleanup("update_works_for_non_existing_id").await;
+}
+
+pub(in crate::storage) async fn update_works_for_non_existing_type(backend: &TestBackend) {
+    backend.cleanup("update_works_for_non_existing_type").await;
+
+    {
+        let storage = backend.storage("update_works_for_non_existing_type").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+
+        let res = storage.update(&_type2(), &_id1(), &_value2()).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend.cleanup("update_works_for_non_existing_type").await;
+}
+
+pub(in crate::storage) async fn add_tags_works(backend: &TestBackend) {
+    backend.cleanup("add_tags_works").await;
+
+    {
+        let storage = backend.storage("add_tags_works").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        storage
+            .add_tags(&_type1(), &_id1(), &_new_tags())
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+
+        let expected_tags = {
+            let mut tags = _tags();
+            tags.extend(_new_tags());
+            _sort(tags)
+        };
+
+        assert_eq!(_sort(record.tags.unwrap()), expected_tags);
+    }
+
+    backend.cleanup("add_tags_works").await;
+}
+
+pub(in crate::storage) async fn add_tags_works_for_non_existing_id(backend: &TestBackend) {
+    backend.cleanup("add_tags_works_for_non_existing_id").await;
+
+    {
+        let storage = backend.storage("add_tags_works_for_non_existing_id").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage.add_tags(&_type1(), &_id2(), &_new_tags()).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend.cleanup("add_tags_works_for_non_existing_id").await;
+}
+
+pub(in crate::storage) async fn add_tags_works_for_non_existing_type(backend: &TestBackend) {
+    backend
+        .cleanup("add_tags_works_for_non_existing_type")
+        .await;
+
+    {
+        let storage = backend
+            .storage("add_tags_works_for_non_existing_type")
+            .await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage.add_tags(&_type2(), &_id1(), &_new_tags()).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend
+        .cleanup("add_tags_works_for_non_existing_type")
+        .await;
+}
+
+pub(in crate::storage) async fn add_tags_works_for_already_existing(backend: &TestBackend) {
+    backend.cleanup("add_tags_works_for_already_existing").await;
+
+    {
+        let storage = backend.storage("add_tags_works_for_already_existing").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let tags_with_existing = {
+            let mut tags = _tags();
+            tags.extend(_new_tags());
+            tags
+        };
+
+        storage
+            .add_tags(&_type1(), &_id1(), &tags_with_existing)
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+
+        let expected_tags = {
+            let mut tags = _tags();
+            tags.extend(_new_tags());
+            _sort(tags)
+        };
+
+        assert_eq!(_sort(record.tags.unwrap()), expected_tags);
+    }
+
+    backend.cleanup("add_tags_works_for_already_existing").await;
+}
+
+pub(in crate::storage) async fn update_tags_works(backend: &TestBackend) {
+    backend.cleanup("update_tags_works").await;
+
+    {
+        let storage = backend.storage("update_tags_works").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        storage
+            .update_tags(&_type1(), &_id1(), &_new_tags())
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+    }
+
+    backend.cleanup("update_tags_works").await;
+}
+
+pub(in crate::storage) async fn update_tags_works_for_non_existing_id(backend: &TestBackend) {
+    backend
+        .cleanup("update_tags_works_for_non_existing_id")
+        .await;
+
+    {
+        let storage = backend
+            .storage("update_tags_works_for_non_existing_id")
+            .await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage.update_tags(&_type1(), &_id2(), &_new_tags()).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend
+        .cleanup("update_tags_works_for_non_existing_id")
+        .await;
+}
+
+pub(in crate::storage) async fn update_tags_works_for_non_existing_type(backend: &TestBackend) {
+    backend
+        .cleanup("update_tags_works_for_non_existing_type")
+        .await;
+
+    {
+        let storage = backend
+            .storage("update_tags_works_for_non_existing_type")
+            .await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage.update_tags(&_type1(), &_id2(), &_new_tags()).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend
+        .cleanup("update_tags_works_for_non_existing_type")
+        .await;
+}
+
+pub(in crate::storage) async fn update_tags_works_for_already_existing(backend: &TestBackend) {
+    backend
+        .cleanup("update_tags_works_for_already_existing")
+        .await;
+    {
+        let storage = backend
+            .storage("update_tags_works_for_already_existing")
+            .await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let tags_with_existing = {
+            let mut tags = _tags();
+            tags.extend(_new_tags());
+            tags
+        };
+
+        storage
+            .update_tags(&_type1(), &_id1(), &tags_with_existing)
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+
+        let expected_tags = {
+            let mut tags = _tags();
+            tags.extend(_new_tags());
+            _sort(tags)
+        };
+
+        assert_eq!(_sort(record.tags.unwrap()), expected_tags);
+    }
+    backend
+        .cleanup("update_tags_works_for_already_existing")
+        .await;
+}
+
+pub(in crate::storage) async fn delete_tags_works(backend: &TestBackend) {
+    backend.cleanup("delete_tags_works").await;
+
+    {
+        let storage = backend.storage("delete_tags_works").await;
+
+        let tag_name1 = vec![0, 0, 0];
+        let tag_name2 = vec![1, 1, 1];
+        let tag_name3 = vec![2, 2, 2];
+        let tag1 = Tag::Encrypted(tag_name1.clone(), vec![0, 0, 0]);
+        let tag2 = Tag::PlainText(tag_name2.clone(), "tag_value_2".to_string());
+        let tag3 = Tag::Encrypted(tag_name3.clone(), vec![2, 2, 2]);
+        let tags = vec![tag1.clone(), tag2.clone(), tag3.clone()];
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &tags)
+            .await
+            .unwrap();
+
+        let tag_names = vec![
+            TagName::OfEncrypted(tag_name1.clone()),
+            TagName::OfPlain(tag_name2.clone()),
+        ];
+
+        storage
+            .delete_tags(&_type1(), &_id1(), &tag_names)
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.tags.unwrap(), vec![tag3]);
+    }
+
+    backend.cleanup("delete_tags_works").await;
+}
+
+pub(in crate::storage) async fn delete_tags_works_for_non_existing_type(backend: &TestBackend) {
+    backend
+        .cleanup("delete_tags_works_for_non_existing_type")
+        .await;
+
+    {
+        let storage = backend
+            .storage("delete_tags_works_for_non_existing_type")
+            .await;
+
+        let tag_name1 = vec![0, 0, 0];
+        let tag_name2 = vec![1, 1, 1];
+        let tag_name3 = vec![2, 2, 2];
+        let tag1 = Tag::Encrypted(tag_name1.clone(), vec![0, 0, 0]);
+        let tag2 = Tag::PlainText(tag_name2.clone(), "tag_value_2".to_string());
+        let tag3 = Tag::Encrypted(tag_name3.clone(), vec![2, 2, 2]);
+        let tags = vec![tag1.clone(), tag2.clone(), tag3.clone()];
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &tags)
+            .await
+            .unwrap();
+
+        let tag_names = vec![
+            TagName::OfEncrypted(tag_name1.clone()),
+            TagName::OfPlain(tag_name2.clone()),
+        ];
+
+        let res = storage.delete_tags(&_type2(), &_id1(), &tag_names).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend
+        .cleanup("delete_tags_works_for_non_existing_type")
+        .await;
+}
+
+pub(in crate::storage) async fn delete_tags_works_for_non_existing_id(backend: &TestBackend) {
+    backend
+        .cleanup("delete_tags_works_for_non_existing_id")
+        .await;
+
+    {
+        let storage = backend
+            .storage("delete_tags_works_for_non_existing_id")
+            .await;
+
+        let tag_name1 = vec![0, 0, 0];
+        let tag_name2 = vec![1, 1, 1];
+        let tag_name3 = vec![2, 2, 2];
+        let tag1 = Tag::Encrypted(tag_name1.clone(), vec![0, 0, 0]);
+        let tag2 = Tag::PlainText(tag_name2.clone(), "tag_value_2".to_string());
+        let tag3 = Tag::Encrypted(tag_name3.clone(), vec![2, 2, 2]);
+        let tags = vec![tag1.clone(), tag2.clone(), tag3.clone()];
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &tags)
+            .await
+            .unwrap();
+
+        let tag_names = vec![
+            TagName::OfEncrypted(tag_name1.clone()),
+            TagName::OfPlain(tag_name2.clone()),
+        ];
+
+        let res = storage.delete_tags(&_type1(), &_id2(), &tag_names).await;
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend
+        .cleanup("delete_tags_works_for_non_existing_id")
+        .await;
+}
+
+pub(in crate::storage) async fn apply_batch_works(backend: &TestBackend) {
+    backend.cleanup("apply_batch_works").await;
+
+    {
+        let storage = backend.storage("apply_batch_works").await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        storage
+            .apply_batch(&[
+                StorageOperation::Update {
+                    type_: _type1(),
+                    id: _id1(),
+                    value: _value2(),
+                },
+                StorageOperation::Add {
+                    type_: _type1(),
+                    id: _id2(),
+                    value: _value1(),
+                    tags: _new_tags(),
+                },
+            ])
+            .await
+            .unwrap();
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id1(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": false}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value2());
+
+        let record = storage
+            .get(
+                &_type1(),
+                &_id2(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.value.unwrap(), _value1());
+        assert_eq!(_sort(record.tags.unwrap()), _sort(_new_tags()));
+    }
+
+    backend.cleanup("apply_batch_works").await;
+}
+
+pub(in crate::storage) async fn apply_batch_works_for_failed_operation(backend: &TestBackend) {
+    backend
+        .cleanup("apply_batch_works_for_failed_operation")
+        .await;
+
+    {
+        let storage = backend
+            .storage("apply_batch_works_for_failed_operation")
+            .await;
+
+        storage
+            .add(&_type1(), &_id1(), &_value1(), &_tags())
+            .await
+            .unwrap();
+
+        let res = storage
+            .apply_batch(&[
+                StorageOperation::Add {
+                    type_: _type1(),
+                    id: _id2(),
+                    value: _value2(),
+                    tags: _tags(),
+                },
+                StorageOperation::Update {
+                    type_: _type2(),
+                    id: _id1(),
+                    value: _value2(),
+                },
+            ])
+            .await;
+
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+
+        let res = storage
+            .get(
+                &_type1(),
+                &_id2(),
+                r##"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"##,
+            )
+            .await;
+
+        assert_kind!(IndyErrorKind::WalletItemNotFound, res);
+    }
+
+    backend
+        .cleanup("apply_batch_works_for_failed_operation")
+        .await;
+}
+
+fn _metadata() -> Vec<u8> {
+    vec![
+        1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6,
+        7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4, 5, 6, 7, 8, 1, 2, 3, 4,
+        5, 6, 7, 8,
+    ]
+}
+
+fn _type(i: u8) -> Vec<u8> {
+    vec![i, 1 + i, 2 + i]
+}
+
+fn _type1() -> Vec<u8> {
+    _type(1)
+}
+
+fn _type2() -> Vec<u8> {
+    _type(2)
+}
+
+fn _id(i: u8) -> Vec<u8> {
+    vec![3 + i, 4 + i, 5 + i]
+}
+
+fn _id1() -> Vec<u8> {
+    _id(1)
+}
+
+fn _id2() -> Vec<u8> {
+    _id(2)
+}
+
+fn _value(i: u8) -> EncryptedValue {
+    EncryptedValue {
+        data: vec![6 + i, 7 + i, 8 + i],
+        key: vec![
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+            9 + i,
+            10 + i,
+            11 + i,
+        ],
+        with_biometrics: false,
+    }
+}
+
+fn _value1() -> EncryptedValue {
+    _value(1)
+}
+
+fn _value2() -> EncryptedValue {
+    _value(2)
+}
+
+fn _tags() -> Vec<Tag> {
+    vec![
+        Tag::Encrypted(vec![1, 5, 8], vec![3, 5, 6]),
+        Tag::PlainText(vec![1, 5, 8, 1], "Plain value".to_string()),
+    ]
+}
+
+fn _new_tags() -> Vec<Tag> {
+    vec![
+        Tag::Encrypted(vec![1, 1, 1], vec![2, 2, 2]),
+        Tag::PlainText(vec![1, 1, 1], String::from("tag_value_3")),
+    ]
+}
+
+fn _sort(mut v: Vec<Tag>) -> Vec<Tag> {
+    v.sort();
+    v
+}
@@ -19,8 +19,8 @@ use crate::{
     iterator::WalletIterator,
     query_encryption::encrypt_query,
     storage,
-    storage::StorageRecord,
-    RecordOptions, WalletRecord,
+    storage::{StorageOperation, StorageRecord},
+    RecordOptions, WalletOperation, WalletRecord,
 };
 use futures::future::join;
 
@@ -172,7 +172,7 @@ impl EncryptedValue {
         }
 
         let value_key = joined_data[..ENCRYPTED_KEY_LEN].to_owned();
-        let value = joined_data[ENCRYPTED_KEY_LEN..joined_data.len() - 2].to_owned();
+        let value = joined_data[ENCRYPTED_KEY_LEN..joined_data.len() - 1].to_owned();
         let with_biometrics = joined_data[joined_data.len() - 1] == 1;
         Ok(EncryptedValue {
             data: value,
@@ -509,6 +509,174 @@ impl Wallet {
         Ok(())
     }
 
+    pub async fn apply_batch(&self, operations: &[WalletOperation]) -> IndyResult<()> {
+        let mut storage_operations = Vec::with_capacity(operations.len());
+
+        for operation in operations {
+            let storage_operation = match operation {
+                WalletOperation::AddRecord {
+                    type_,
+                    name,
+                    value,
+                    tags,
+                } => {
+                    let (etype, ename) = self._encrypt_type_and_name(type_, name);
+                    let evalue = self._encrypt_value(type_, name, &etype, &ename, value)?;
+
+                    StorageOperation::Add {
+                        type_: etype,
+                        id: ename,
+                        value: evalue,
+                        tags: self._encrypt_tags(tags),
+                    }
+                }
+                WalletOperation::UpdateRecordValue { type_, name, value } => {
+                    let (etype, ename) = self._encrypt_type_and_name(type_, name);
+                    let evalue = self._encrypt_value(type_, name, &etype, &ename, value)?;
+
+                    StorageOperation::Update {
+                        type_: etype,
+                        id: ename,
+                        value: evalue,
+                    }
+                }
+                WalletOperation::AddRecordTags { type_, name, tags } => {
+                    let (etype, ename) = self._encrypt_type_and_name(type_, name);
+
+                    StorageOperation::AddTags {
+                        type_: etype,
+                        id: ename,
+                        tags: self._encrypt_tags(tags),
+                    }
+                }
+                WalletOperation::UpdateRecordTags { type_, name, tags } => {
+                    let (etype, ename) = self._encrypt_type_and_name(type_, name);
+
+                    StorageOperation::UpdateTags {
+                        type_: etype,
+                        id: ename,
+                        tags: self._encrypt_tags(tags),
+                    }
+                }
+                WalletOperation::DeleteRecordTags {
+                    type_,
+                    name,
+                    tag_names,
+                } => {
+                    let (etype, ename) = self._encrypt_type_and_name(type_, name);
+                    let tag_names = tag_names.iter().map(String::as_str).collect::<Vec<_>>();
+
+                    StorageOperation::DeleteTags {
+                        type_: etype,
+                        id: ename,
+                        tag_names: encrypt_tag_names(
+                            &tag_names,
+                            &self.keys.tag_name_key,
+                            &self.keys.tags_hmac_key,
+                        ),
+                    }
+                }
+                WalletOperation::DeleteRecord { type_, name } => {
+                    let (etype, ename) = self._encrypt_type_and_name(type_, name);
+
+                    StorageOperation::Delete {
+                        type_: etype,
+                        id: ename,
+                    }
+                }
+            };
+
+            storage_operations.push(storage_operation);
+        }
+
+        self.storage.apply_batch(&storage_operations).await?;
+
+        // the cache is only touched once the whole batch is persisted
+        for (operation, storage_operation) in operations.iter().zip(&storage_operations) {
+            let type_ = operation.type_();
+
+            match storage_operation {
+                StorageOperation::Add {
+                    type_: etype,
+                    id,
+                    value,
+                    tags,
+                } => self.cache.add(type_, etype, id, value, tags),
+                StorageOperation::Update {
+                    type_: etype,
+                    id,
+                    value,
+                } => self.cache.update(type_, etype, id, value).await,
+                StorageOperation::AddTags {
+                    type_: etype,
+                    id,
+                    tags,
+                } => self.cache.add_tags(type_, etype, id, tags).await,
+                StorageOperation::UpdateTags {
+                    type_: etype,
+                    id,
+                    tags,
+                } => self.cache.update_tags(type_, etype, id, tags).await,
+                StorageOperation::DeleteTags {
+                    type_: etype,
+                    id,
+                    tag_names,
+                } => self.cache.delete_tags(type_, etype, id, tag_names).await,
+                StorageOperation::Delete { type_: etype, id } => {
+                    self.cache.delete(type_, etype, id).await
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _encrypt_type_and_name(&self, type_: &str, name: &str) -> (Vec<u8>, Vec<u8>) {
+        let etype = encrypt_as_searchable(
+            type_.as_bytes(),
+            &self.keys.type_key,
+            &self.keys.item_hmac_key,
+        );
+
+        let ename = encrypt_as_searchable(
+            name.as_bytes(),
+            &self.keys.name_key,
+            &self.keys.item_hmac_key,
+        );
+
+        (etype, ename)
+    }
+
+    fn _encrypt_value(
+        &self,
+        type_: &str,
+        name: &str,
+        etype: &[u8],
+        ename: &[u8],
+        value: &str,
+    ) -> IndyResult<EncryptedValue> {
+        let evalue = if let Some(secure_enclave_provider) = self.secure_enclave_provider.as_ref() {
+            EncryptedValue::encrypt_with_biometrics(
+                value,
+                &secure_enclave_provider.get_handle(type_, name, etype, ename)?,
+                secure_enclave_provider,
+            )?
+        } else {
+            EncryptedValue::encrypt(value, &self.keys.value_key)
+        };
+
+        Ok(evalue)
+    }
+
+    fn _encrypt_tags(&self, tags: &HashMap<String, String>) -> Vec<storage::Tag> {
+        encrypt_tags(
+            tags,
+            &self.keys.tag_name_key,
+            &self.keys.tag_value_key,
+            &self.keys.tags_hmac_key,
+        )
+    }
+
     pub async fn search(
         &self,
         type_: &str,
@@ -557,3 +725,26 @@ impl Drop for Wallet {
         self.close().unwrap(); //FIXME pass the error to the API cb
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypted_value_to_bytes_from_bytes_works() {
+        let key = chacha20poly1305_ietf::gen_key();
+        let value = EncryptedValue::encrypt("value", &key);
+
+        let restored = EncryptedValue::from_bytes(&value.to_bytes()).unwrap();
+
+        assert_eq!(restored, value);
+        assert_eq!(restored.decrypt(&key).unwrap(), "value");
+    }
+
+    #[test]
+    fn encrypted_value_to_bytes_from_bytes_works_for_biometrics() {
+        let value = EncryptedValue::new(vec![1, 2, 3], vec![4; ENCRYPTED_KEY_LEN], true);
+
+        assert_eq!(EncryptedValue::from_bytes(&value.to_bytes()).unwrap(), value);
+    }
+}
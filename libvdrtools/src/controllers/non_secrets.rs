@@ -3,7 +3,9 @@ use std::{collections::HashMap, sync::Arc};
 use futures::lock::Mutex;
 use indy_api_types::{domain::wallet::Tags, errors::prelude::*, SearchHandle, WalletHandle};
 use indy_utils::next_search_handle;
-use indy_wallet::{RecordOptions, SearchOptions, WalletRecord, WalletSearch, WalletService};
+use indy_wallet::{
+    RecordOptions, SearchOptions, WalletOperation, WalletRecord, WalletSearch, WalletService,
+};
 
 pub struct NonSecretsController {
     wallet_service: Arc<WalletService>,
@@ -269,6 +271,37 @@ impl NonSecretsController {
         res
     }
 
+    /// Apply a batch of non-secret record writes atomically: either all
+    /// operations are persisted or none of them is
+    ///
+    /// #Params
+
+    /// wallet_handle: wallet handle (created by open_wallet)
+    /// operations: record writes to apply, in order
+    pub async fn apply_batch(
+        &self,
+        wallet_handle: WalletHandle,
+        operations: Vec<WalletOperation>,
+    ) -> IndyResult<()> {
+        trace!(
+            "apply_batch > wallet_handle {:?} operations {:?}",
+            wallet_handle,
+            operations
+        );
+
+        for operation in &operations {
+            self._check_type(operation.type_())?;
+        }
+
+        self.wallet_service
+            .apply_batch(wallet_handle, &operations)
+            .await?;
+
+        let res = Ok(());
+        trace!("apply_batch < {:?}", res);
+        res
+    }
+
     /// Get an wallet record by id
     ///
     /// #Params
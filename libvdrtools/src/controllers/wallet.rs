@@ -13,7 +13,7 @@ use indy_utils::crypto::{
 };
 
 use crate::utils::crypto::base58::ToBase58;
-use indy_wallet::{KeyDerivationData, WalletService};
+use indy_wallet::{KeyDerivationData, WalletRecord, WalletService};
 
 pub use indy_wallet::SecureEnclaveProvider;
 
@@ -325,6 +325,40 @@ impl WalletController {
         res
     }
 
+    /// Fetches all the records of the given type, with their values and tags, including the records
+    /// managed by libindy itself (e.g. "Indy::Key") which the non-secrets API refuses to expose.
+    ///
+    /// Only meant for migrating the wallet content to another storage.
+    pub async fn get_all_records_for_migration(
+        &self,
+        wallet_handle: WalletHandle,
+        type_: &str,
+    ) -> IndyResult<Vec<WalletRecord>> {
+        trace!(
+            "get_all_records_for_migration > handle: {:?} type_: {:?}",
+            wallet_handle,
+            type_
+        );
+
+        let mut search = self
+            .wallet_service
+            .search_records(
+                wallet_handle,
+                type_,
+                "{}",
+                r#"{"retrieveType": false, "retrieveValue": true, "retrieveTags": true}"#,
+            )
+            .await?;
+
+        let mut records = Vec::new();
+        while let Some(record) = search.fetch_next_record().await? {
+            records.push(record);
+        }
+
+        trace!("get_all_records_for_migration < count: {}", records.len());
+        Ok(records)
+    }
+
     /// Creates a new secure wallet and then imports its content
     /// according to fields provided in import_config
     /// This can be seen as an indy_create_wallet call with additional content import
@@ -46,7 +46,7 @@ use crate::{
 
 pub use controllers::CredentialDefinitionId;
 
-pub use indy_wallet::SecureEnclaveProvider;
+pub use indy_wallet::{SecureEnclaveProvider, WalletOperation};
 pub use domain::{
     anoncreds::{
         credential::{Credential, CredentialValues},
@@ -42,9 +42,8 @@ impl Attachment {
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct AttachmentData {
-    // There probably is a better type for this???
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub jws: Option<String>,
+    pub jws: Option<AttachmentJws>,
     // Better type for this as well?
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sha256: Option<String>,
@@ -62,6 +61,41 @@ impl AttachmentData {
     }
 }
 
+/// A JWS signing the `base64` content of an attachment, as described in the
+/// [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/concepts/0017-attachments/README.md#signing-attachments>).
+///
+/// The payload is detached: the signing input is the `protected` header followed by the
+/// unpadded base64url encoding of the attachment content.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct AttachmentJws {
+    pub header: JwsHeader,
+    pub protected: String,
+    pub signature: String,
+}
+
+impl AttachmentJws {
+    pub fn new(header: JwsHeader, protected: String, signature: String) -> Self {
+        Self {
+            header,
+            protected,
+            signature,
+        }
+    }
+}
+
+/// The unprotected header of an [`AttachmentJws`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct JwsHeader {
+    /// The `did:key` of the signing key.
+    pub kid: String,
+}
+
+impl JwsHeader {
+    pub fn new(kid: String) -> Self {
+        Self { kid }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum AttachmentType {
@@ -163,9 +197,32 @@ pub mod tests {
         test_utils::test_serde(attach_data, expected);
     }
 
+    #[test]
+    fn test_attachment_jws() {
+        let jws = AttachmentJws::new(
+            JwsHeader::new("test_kid".to_owned()),
+            "test_protected".to_owned(),
+            "test_signature".to_owned(),
+        );
+
+        let expected = json!({
+            "header": {
+                "kid": "test_kid"
+            },
+            "protected": "test_protected",
+            "signature": "test_signature"
+        });
+
+        test_utils::test_serde(jws, expected);
+    }
+
     #[test]
     fn test_extended_attach_data() {
-        let jws = "test_jws".to_owned();
+        let jws = AttachmentJws::new(
+            JwsHeader::new("test_kid".to_owned()),
+            "test_protected".to_owned(),
+            "test_signature".to_owned(),
+        );
         let sha256 = "test_sha256".to_owned();
 
         let data = json!({
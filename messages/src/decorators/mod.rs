@@ -3,3 +3,4 @@ pub mod localization;
 pub mod please_ack;
 pub mod thread;
 pub mod timing;
+pub mod transport;
@@ -23,8 +23,9 @@ use crate::{
     misc::utils::MSG_TYPE,
     msg_fields::{
         protocols::{
-            basic_message::BasicMessage, connection::Connection, cred_issuance::CredentialIssuance,
-            discover_features::DiscoverFeatures, notification::Notification, out_of_band::OutOfBand,
+            action_menu::ActionMenu, basic_message::BasicMessage, connection::Connection,
+            coordinate_mediation::CoordinateMediation, cred_issuance::CredentialIssuance, did_exchange::DidExchange,
+            discover_features::DiscoverFeatures, notification::Notification, out_of_band::OutOfBand, pickup::Pickup,
             present_proof::PresentProof, report_problem::ProblemReport, revocation::Revocation, routing::Forward,
             trust_ping::TrustPing,
         },
@@ -61,6 +62,10 @@ pub enum AriesMessage {
     BasicMessage(BasicMessage),
     OutOfBand(OutOfBand),
     Notification(Notification),
+    DidExchange(DidExchange),
+    CoordinateMediation(CoordinateMediation),
+    Pickup(Pickup),
+    ActionMenu(ActionMenu),
 }
 
 impl DelayedSerde for AriesMessage {
@@ -135,6 +140,18 @@ impl DelayedSerde for AriesMessage {
             Protocol::NotificationType(msg_type) => {
                 Notification::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
             }
+            Protocol::DidExchangeType(msg_type) => {
+                DidExchange::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
+            Protocol::CoordinateMediationType(msg_type) => {
+                CoordinateMediation::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
+            Protocol::PickupType(msg_type) => {
+                Pickup::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
+            Protocol::ActionMenuType(msg_type) => {
+                ActionMenu::delayed_deserialize((msg_type, kind_str), deserializer).map(From::from)
+            }
         }
     }
 
@@ -154,6 +171,10 @@ impl DelayedSerde for AriesMessage {
             Self::BasicMessage(v) => MsgWithType::from(v).serialize(serializer),
             Self::OutOfBand(v) => v.delayed_serialize(serializer),
             Self::Notification(v) => v.delayed_serialize(serializer),
+            Self::DidExchange(v) => v.delayed_serialize(serializer),
+            Self::CoordinateMediation(v) => v.delayed_serialize(serializer),
+            Self::Pickup(v) => v.delayed_serialize(serializer),
+            Self::ActionMenu(v) => v.delayed_serialize(serializer),
         }
     }
 }
@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Menu = MsgParts<MenuContent, MenuDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MenuContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errormsg: Option<String>,
+    pub options: Vec<MenuOption>,
+}
+
+impl MenuContent {
+    pub fn new(options: Vec<MenuOption>) -> Self {
+        Self {
+            title: None,
+            description: None,
+            errormsg: None,
+            options,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MenuOption {
+    pub name: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub disabled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub form: Option<MenuForm>,
+}
+
+impl MenuOption {
+    pub fn new(name: String, title: String) -> Self {
+        Self {
+            name,
+            title,
+            description: None,
+            disabled: false,
+            form: None,
+        }
+    }
+}
+
+/// Form whose parameters have to be filled in when performing a menu option.
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct MenuForm {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub params: Vec<MenuFormParam>,
+    #[serde(rename = "submit-label")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub submit_label: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MenuFormParam {
+    pub name: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub param_type: Option<MaybeKnown<MenuFormParamType>>,
+}
+
+impl MenuFormParam {
+    pub fn new(name: String, title: String) -> Self {
+        Self {
+            name,
+            title,
+            default: None,
+            description: None,
+            required: false,
+            param_type: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum MenuFormParamType {
+    Text,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct MenuDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::action_menu::ActionMenuTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_menu() {
+        let option = MenuOption::new(
+            "obtain-email-cred".to_owned(),
+            "Obtain a verified email credential".to_owned(),
+        );
+        let content = MenuContent::new(vec![option]);
+
+        let decorators = MenuDecorators::default();
+
+        let expected = json!({
+            "options": [{
+                "name": "obtain-email-cred",
+                "title": "Obtain a verified email credential",
+                "disabled": false
+            }]
+        });
+
+        test_utils::test_msg(content, decorators, ActionMenuTypeV1_0::Menu, expected);
+    }
+
+    #[test]
+    fn test_extended_menu() {
+        let mut param = MenuFormParam::new("query".to_owned(), "Participant name".to_owned());
+        param.default = Some(String::new());
+        param.required = true;
+        param.param_type = Some(MaybeKnown::Known(MenuFormParamType::Text));
+
+        let mut form = MenuForm::default();
+        form.title = Some("Search introductions".to_owned());
+        form.description = Some("Enter a participant name below to perform a search.".to_owned());
+        form.params = vec![param];
+        form.submit_label = Some("Search".to_owned());
+
+        let mut option = MenuOption::new("search-introductions".to_owned(), "Search introductions".to_owned());
+        option.description = Some("Your email address must be verified to perform a search".to_owned());
+        option.disabled = true;
+        option.form = Some(form);
+
+        let mut content = MenuContent::new(vec![option]);
+        content.title = Some("Welcome to IIWBook".to_owned());
+        content.description = Some("IIWBook facilitates connections between attendees.".to_owned());
+        content.errormsg = Some("No IIWBook names were found.".to_owned());
+
+        let mut decorators = MenuDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "title": content.title,
+            "description": content.description,
+            "errormsg": content.errormsg,
+            "options": [{
+                "name": "search-introductions",
+                "title": "Search introductions",
+                "description": "Your email address must be verified to perform a search",
+                "disabled": true,
+                "form": {
+                    "title": "Search introductions",
+                    "description": "Enter a participant name below to perform a search.",
+                    "params": [{
+                        "name": "query",
+                        "title": "Participant name",
+                        "default": "",
+                        "required": true,
+                        "type": "text"
+                    }],
+                    "submit-label": "Search"
+                }
+            }],
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, ActionMenuTypeV1_0::Menu, expected);
+    }
+}
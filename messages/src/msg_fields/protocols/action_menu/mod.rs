@@ -0,0 +1,69 @@
+//! Module containing the `action menu` protocol messages, as defined in the [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0509-action-menu/README.md>).
+
+pub mod menu;
+pub mod menu_request;
+pub mod perform;
+
+use derive_more::From;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use self::{
+    menu::{Menu, MenuContent, MenuDecorators},
+    menu_request::{MenuRequest, MenuRequestContent, MenuRequestDecorators},
+    perform::{Perform, PerformContent, PerformDecorators},
+};
+use crate::{
+    misc::utils::{into_msg_with_type, transit_to_aries_msg},
+    msg_fields::traits::DelayedSerde,
+    msg_types::{
+        protocols::action_menu::{ActionMenuType as ActionMenuKind, ActionMenuTypeV1, ActionMenuTypeV1_0},
+        MsgWithType,
+    },
+};
+
+#[derive(Clone, Debug, From, PartialEq)]
+pub enum ActionMenu {
+    Menu(Menu),
+    MenuRequest(MenuRequest),
+    Perform(Perform),
+}
+
+impl DelayedSerde for ActionMenu {
+    type MsgType<'a> = (ActionMenuKind, &'a str);
+
+    fn delayed_deserialize<'de, D>(msg_type: Self::MsgType<'de>, deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let (protocol, kind_str) = msg_type;
+
+        let kind = match protocol {
+            ActionMenuKind::V1(ActionMenuTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
+        };
+
+        match kind.map_err(D::Error::custom)? {
+            ActionMenuTypeV1_0::Menu => Menu::deserialize(deserializer).map(From::from),
+            ActionMenuTypeV1_0::MenuRequest => MenuRequest::deserialize(deserializer).map(From::from),
+            ActionMenuTypeV1_0::Perform => Perform::deserialize(deserializer).map(From::from),
+        }
+    }
+
+    fn delayed_serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Self::Menu(v) => MsgWithType::from(v).serialize(serializer),
+            Self::MenuRequest(v) => MsgWithType::from(v).serialize(serializer),
+            Self::Perform(v) => MsgWithType::from(v).serialize(serializer),
+        }
+    }
+}
+
+transit_to_aries_msg!(MenuContent: MenuDecorators, ActionMenu);
+transit_to_aries_msg!(MenuRequestContent: MenuRequestDecorators, ActionMenu);
+transit_to_aries_msg!(PerformContent: PerformDecorators, ActionMenu);
+
+into_msg_with_type!(Menu, ActionMenuTypeV1_0, Menu);
+into_msg_with_type!(MenuRequest, ActionMenuTypeV1_0, MenuRequest);
+into_msg_with_type!(Perform, ActionMenuTypeV1_0, Perform);
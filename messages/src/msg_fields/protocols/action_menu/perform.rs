@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+};
+
+pub type Perform = MsgParts<PerformContent, PerformDecorators>;
+
+/// Selection of a menu option, along with the values of its form parameters, if any.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct PerformContent {
+    pub name: String,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub params: HashMap<String, String>,
+}
+
+impl PerformContent {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            params: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct PerformDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::action_menu::ActionMenuTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_perform() {
+        let content = PerformContent::new("obtain-email-cred".to_owned());
+
+        let decorators = PerformDecorators::default();
+
+        let expected = json!({
+            "name": content.name
+        });
+
+        test_utils::test_msg(content, decorators, ActionMenuTypeV1_0::Perform, expected);
+    }
+
+    #[test]
+    fn test_extended_perform() {
+        let mut content = PerformContent::new("search-introductions".to_owned());
+        content.params = HashMap::from([("query".to_owned(), "Alice".to_owned())]);
+
+        let mut decorators = PerformDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "name": content.name,
+            "params": content.params,
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, ActionMenuTypeV1_0::Perform, expected);
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use shared_vcx::maybe_known::MaybeKnown;
+
+/// Specifies the format of an attachment of a message, as used by the `formats` array of
+/// the [RFC 0453](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0453-issue-credential-v2/README.md>)
+/// and [RFC 0454](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0454-present-proof-v2/README.md>)
+/// protocols.
+///
+/// The type parameter is the enum of attachment format identifiers known for the message.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct AttachmentFormatSpecifier<F> {
+    pub attach_id: String,
+    pub format: MaybeKnown<F>,
+}
+
+impl<F> AttachmentFormatSpecifier<F> {
+    pub fn new(attach_id: String, format: MaybeKnown<F>) -> Self {
+        Self { attach_id, format }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+    enum TestFormat {
+        #[serde(rename = "test/format@v1.0")]
+        Test,
+    }
+
+    #[test]
+    fn test_known_format() {
+        let specifier = AttachmentFormatSpecifier::new("attach_id".to_owned(), MaybeKnown::Known(TestFormat::Test));
+        let json = json!({
+            "attach_id": "attach_id",
+            "format": "test/format@v1.0",
+        });
+
+        assert_eq!(serde_json::to_value(&specifier).unwrap(), json);
+        assert_eq!(
+            serde_json::from_value::<AttachmentFormatSpecifier<TestFormat>>(json).unwrap(),
+            specifier
+        );
+    }
+
+    #[test]
+    fn test_unknown_format() {
+        let json = json!({
+            "attach_id": "attach_id",
+            "format": "other/format@v1.0",
+        });
+
+        let specifier: AttachmentFormatSpecifier<TestFormat> = serde_json::from_value(json).unwrap();
+        assert_eq!(specifier.format, MaybeKnown::Unknown("other/format@v1.0".to_owned()));
+    }
+}
@@ -0,0 +1,3 @@
+//! Module containing types shared by multiple protocols.
+
+pub mod attachment_format_specifier;
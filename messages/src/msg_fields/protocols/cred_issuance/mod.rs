@@ -1,4 +1,5 @@
-//! Module containing the `issue credential` protocol messages, as defined in the [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0036-issue-credential/README.md>).
+//! Module containing the `issue credential` protocol messages, as defined in the [1.0 RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0036-issue-credential/README.md>)
+//! and the [2.0 RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0453-issue-credential-v2/README.md>).
 
 pub mod ack;
 pub mod issue_credential;
@@ -6,6 +7,7 @@ pub mod offer_credential;
 pub mod problem_report;
 pub mod propose_credential;
 pub mod request_credential;
+pub mod v2;
 
 use std::str::FromStr;
 
@@ -20,6 +22,14 @@ use self::{
     problem_report::{CredIssuanceProblemReport, CredIssuanceProblemReportContent},
     propose_credential::{ProposeCredential, ProposeCredentialContent, ProposeCredentialDecorators},
     request_credential::{RequestCredential, RequestCredentialContent, RequestCredentialDecorators},
+    v2::{
+        ack::{AckCredentialV2, AckCredentialV2Content},
+        issue_credential::{IssueCredentialV2, IssueCredentialV2Content, IssueCredentialV2Decorators},
+        offer_credential::{OfferCredentialV2, OfferCredentialV2Content, OfferCredentialV2Decorators},
+        problem_report::{CredIssuanceV2ProblemReport, CredIssuanceV2ProblemReportContent},
+        propose_credential::{ProposeCredentialV2, ProposeCredentialV2Content, ProposeCredentialV2Decorators},
+        request_credential::{RequestCredentialV2, RequestCredentialV2Content, RequestCredentialV2Decorators},
+    },
 };
 use super::{notification::ack::AckDecorators, report_problem::ProblemReportDecorators};
 use crate::{
@@ -31,6 +41,7 @@ use crate::{
     msg_types::{
         protocols::cred_issuance::{
             CredentialIssuanceType as CredentialIssuanceKind, CredentialIssuanceTypeV1, CredentialIssuanceTypeV1_0,
+            CredentialIssuanceTypeV2, CredentialIssuanceTypeV2_0,
         },
         traits::MessageKind,
         MessageType, MsgWithType, Protocol,
@@ -45,6 +56,12 @@ pub enum CredentialIssuance {
     IssueCredential(IssueCredential),
     Ack(AckCredential),
     ProblemReport(CredIssuanceProblemReport),
+    OfferCredentialV2(OfferCredentialV2),
+    ProposeCredentialV2(ProposeCredentialV2),
+    RequestCredentialV2(RequestCredentialV2),
+    IssueCredentialV2(IssueCredentialV2),
+    AckV2(AckCredentialV2),
+    ProblemReportV2(CredIssuanceV2ProblemReport),
 }
 
 impl DelayedSerde for CredentialIssuance {
@@ -55,24 +72,50 @@ impl DelayedSerde for CredentialIssuance {
         D: Deserializer<'de>,
     {
         let (protocol, kind_str) = msg_type;
-        let kind = match protocol {
-            CredentialIssuanceKind::V1(CredentialIssuanceTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
-        };
-
-        match kind.map_err(D::Error::custom)? {
-            CredentialIssuanceTypeV1_0::OfferCredential => OfferCredential::deserialize(deserializer).map(From::from),
-            CredentialIssuanceTypeV1_0::ProposeCredential => {
-                ProposeCredential::deserialize(deserializer).map(From::from)
-            }
-            CredentialIssuanceTypeV1_0::RequestCredential => {
-                RequestCredential::deserialize(deserializer).map(From::from)
+
+        match protocol {
+            CredentialIssuanceKind::V1(CredentialIssuanceTypeV1::V1_0(kind)) => {
+                match kind.kind_from_str(kind_str).map_err(D::Error::custom)? {
+                    CredentialIssuanceTypeV1_0::OfferCredential => {
+                        OfferCredential::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV1_0::ProposeCredential => {
+                        ProposeCredential::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV1_0::RequestCredential => {
+                        RequestCredential::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV1_0::IssueCredential => {
+                        IssueCredential::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV1_0::Ack => AckCredential::deserialize(deserializer).map(From::from),
+                    CredentialIssuanceTypeV1_0::ProblemReport => {
+                        CredIssuanceProblemReport::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV1_0::CredentialPreview => Err(utils::not_standalone_msg::<D>(kind_str)),
+                }
             }
-            CredentialIssuanceTypeV1_0::IssueCredential => IssueCredential::deserialize(deserializer).map(From::from),
-            CredentialIssuanceTypeV1_0::Ack => AckCredential::deserialize(deserializer).map(From::from),
-            CredentialIssuanceTypeV1_0::ProblemReport => {
-                CredIssuanceProblemReport::deserialize(deserializer).map(From::from)
+            CredentialIssuanceKind::V2(CredentialIssuanceTypeV2::V2_0(kind)) => {
+                match kind.kind_from_str(kind_str).map_err(D::Error::custom)? {
+                    CredentialIssuanceTypeV2_0::OfferCredential => {
+                        OfferCredentialV2::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV2_0::ProposeCredential => {
+                        ProposeCredentialV2::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV2_0::RequestCredential => {
+                        RequestCredentialV2::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV2_0::IssueCredential => {
+                        IssueCredentialV2::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV2_0::Ack => AckCredentialV2::deserialize(deserializer).map(From::from),
+                    CredentialIssuanceTypeV2_0::ProblemReport => {
+                        CredIssuanceV2ProblemReport::deserialize(deserializer).map(From::from)
+                    }
+                    CredentialIssuanceTypeV2_0::CredentialPreview => Err(utils::not_standalone_msg::<D>(kind_str)),
+                }
             }
-            CredentialIssuanceTypeV1_0::CredentialPreview => Err(utils::not_standalone_msg::<D>(kind_str)),
         }
     }
 
@@ -87,6 +130,12 @@ impl DelayedSerde for CredentialIssuance {
             Self::IssueCredential(v) => MsgWithType::from(v).serialize(serializer),
             Self::Ack(v) => MsgWithType::from(v).serialize(serializer),
             Self::ProblemReport(v) => MsgWithType::from(v).serialize(serializer),
+            Self::OfferCredentialV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::ProposeCredentialV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::RequestCredentialV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::IssueCredentialV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::AckV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::ProblemReportV2(v) => MsgWithType::from(v).serialize(serializer),
         }
     }
 }
@@ -192,3 +241,17 @@ into_msg_with_type!(RequestCredential, CredentialIssuanceTypeV1_0, RequestCreden
 into_msg_with_type!(IssueCredential, CredentialIssuanceTypeV1_0, IssueCredential);
 into_msg_with_type!(AckCredential, CredentialIssuanceTypeV1_0, Ack);
 into_msg_with_type!(CredIssuanceProblemReport, CredentialIssuanceTypeV1_0, ProblemReport);
+
+transit_to_aries_msg!(OfferCredentialV2Content: OfferCredentialV2Decorators, CredentialIssuance);
+transit_to_aries_msg!(ProposeCredentialV2Content: ProposeCredentialV2Decorators, CredentialIssuance);
+transit_to_aries_msg!(RequestCredentialV2Content: RequestCredentialV2Decorators, CredentialIssuance);
+transit_to_aries_msg!(IssueCredentialV2Content: IssueCredentialV2Decorators, CredentialIssuance);
+transit_to_aries_msg!(AckCredentialV2Content: AckDecorators, CredentialIssuance);
+transit_to_aries_msg!(CredIssuanceV2ProblemReportContent: ProblemReportDecorators, CredentialIssuance);
+
+into_msg_with_type!(OfferCredentialV2, CredentialIssuanceTypeV2_0, OfferCredential);
+into_msg_with_type!(ProposeCredentialV2, CredentialIssuanceTypeV2_0, ProposeCredential);
+into_msg_with_type!(RequestCredentialV2, CredentialIssuanceTypeV2_0, RequestCredential);
+into_msg_with_type!(IssueCredentialV2, CredentialIssuanceTypeV2_0, IssueCredential);
+into_msg_with_type!(AckCredentialV2, CredentialIssuanceTypeV2_0, Ack);
+into_msg_with_type!(CredIssuanceV2ProblemReport, CredentialIssuanceTypeV2_0, ProblemReport);
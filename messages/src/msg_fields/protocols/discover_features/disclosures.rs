@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use super::{FeatureType, ProtocolDescriptor};
+use crate::{
+    decorators::{thread::Thread, timing::Timing},
+    msg_parts::MsgParts,
+    msg_types::{Protocol, Role},
+};
+
+pub type Disclosures = MsgParts<DisclosuresContent, DisclosuresDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct DisclosuresContent {
+    pub disclosures: Vec<Disclosure>,
+}
+
+impl DisclosuresContent {
+    pub fn new(disclosures: Vec<Disclosure>) -> Self {
+        Self { disclosures }
+    }
+
+    /// Returns the disclosed protocols, in the same shape as the ones of a 1.0 disclose message.
+    /// Disclosures of other feature types are skipped.
+    pub fn protocols(&self) -> Vec<ProtocolDescriptor> {
+        self.disclosures
+            .iter()
+            .filter(|disclosure| disclosure.feature_type == MaybeKnown::Known(FeatureType::Protocol))
+            .map(|disclosure| {
+                let pid = match disclosure.id.parse::<Protocol>() {
+                    Ok(protocol) => MaybeKnown::Known(protocol),
+                    Err(_) => MaybeKnown::Unknown(disclosure.id.clone()),
+                };
+                ProtocolDescriptor {
+                    pid,
+                    roles: disclosure.roles.clone(),
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct Disclosure {
+    #[serde(rename = "feature-type")]
+    pub feature_type: MaybeKnown<FeatureType>,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub roles: Option<Vec<MaybeKnown<Role>>>,
+}
+
+impl Disclosure {
+    pub fn new(feature_type: FeatureType, id: String) -> Self {
+        Self {
+            feature_type: MaybeKnown::Known(feature_type),
+            id,
+            roles: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct DisclosuresDecorators {
+    #[serde(rename = "~thread")]
+    pub thread: Thread,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+impl DisclosuresDecorators {
+    pub fn new(thread: Thread) -> Self {
+        Self { thread, timing: None }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{thread::tests::make_extended_thread, timing::tests::make_extended_timing},
+        misc::test_utils,
+        msg_types::discover_features::{DiscoverFeaturesTypeV2, DiscoverFeaturesTypeV2_0},
+    };
+
+    #[test]
+    fn test_minimal_disclosures() {
+        let content = DisclosuresContent::new(vec![Disclosure::new(
+            FeatureType::GoalCode,
+            "aries.sell.goods.consumer".to_owned(),
+        )]);
+
+        let decorators = DisclosuresDecorators::new(make_extended_thread());
+
+        let expected = json!({
+            "disclosures": [
+                {
+                    "feature-type": "goal-code",
+                    "id": "aries.sell.goods.consumer"
+                }
+            ],
+            "~thread": decorators.thread
+        });
+
+        test_utils::test_msg(content, decorators, DiscoverFeaturesTypeV2_0::Disclosures, expected);
+    }
+
+    #[test]
+    fn test_extended_disclosures() {
+        let mut disclosure = Disclosure::new(
+            FeatureType::Protocol,
+            "https://didcomm.org/discover-features/2.0".to_owned(),
+        );
+        disclosure.roles = Some(vec![
+            MaybeKnown::Known(Role::Requester),
+            MaybeKnown::Known(Role::Responder),
+        ]);
+        let content = DisclosuresContent::new(vec![disclosure]);
+
+        let mut decorators = DisclosuresDecorators::new(make_extended_thread());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "disclosures": [
+                {
+                    "feature-type": "protocol",
+                    "id": "https://didcomm.org/discover-features/2.0",
+                    "roles": ["requester", "responder"]
+                }
+            ],
+            "~thread": decorators.thread,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DiscoverFeaturesTypeV2_0::Disclosures, expected);
+    }
+
+    #[test]
+    fn test_disclosures_protocols() {
+        let mut protocol = Disclosure::new(
+            FeatureType::Protocol,
+            "https://didcomm.org/discover-features/2.0".to_owned(),
+        );
+        protocol.roles = Some(vec![MaybeKnown::Known(Role::Responder)]);
+        let unknown = Disclosure::new(FeatureType::Protocol, "https://example.org/custom/1.0".to_owned());
+        let goal_code = Disclosure::new(FeatureType::GoalCode, "aries.sell.goods.consumer".to_owned());
+        let content = DisclosuresContent::new(vec![protocol, unknown, goal_code]);
+
+        let protocols = content.protocols();
+
+        assert_eq!(protocols.len(), 2);
+        assert_eq!(
+            protocols[0].pid,
+            MaybeKnown::Known(Protocol::from(DiscoverFeaturesTypeV2::new_v2_0()))
+        );
+        assert_eq!(protocols[0].roles, Some(vec![MaybeKnown::Known(Role::Responder)]));
+        assert_eq!(
+            protocols[1].pid,
+            MaybeKnown::Unknown("https://example.org/custom/1.0".to_owned())
+        );
+    }
+}
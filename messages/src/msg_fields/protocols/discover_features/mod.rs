@@ -1,6 +1,10 @@
-//! Module containing the `discover features` protocol messages, as defined in the [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0031-discover-features/README.md>).
+//! Module containing the `discover features` protocol messages, as defined in the
+//! [1.0 RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0031-discover-features/README.md>)
+//! and the [2.0 RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0557-discover-features-v2/README.md>).
 
 pub mod disclose;
+pub mod disclosures;
+pub mod queries;
 pub mod query;
 
 use derive_more::From;
@@ -9,6 +13,8 @@ use shared_vcx::maybe_known::MaybeKnown;
 
 use self::{
     disclose::{Disclose, DiscloseContent, DiscloseDecorators},
+    disclosures::{Disclosures, DisclosuresContent, DisclosuresDecorators},
+    queries::{Queries, QueriesContent, QueriesDecorators},
     query::{Query, QueryContent, QueryDecorators},
 };
 use crate::{
@@ -17,7 +23,9 @@ use crate::{
     msg_types::{
         protocols::discover_features::{
             DiscoverFeaturesType as DiscoverFeaturesKind, DiscoverFeaturesTypeV1, DiscoverFeaturesTypeV1_0,
+            DiscoverFeaturesTypeV2, DiscoverFeaturesTypeV2_0,
         },
+        registry::PROTOCOL_REGISTRY,
         MsgWithType, Protocol, Role,
     },
 };
@@ -26,6 +34,8 @@ use crate::{
 pub enum DiscoverFeatures {
     Query(Query),
     Disclose(Disclose),
+    Queries(Queries),
+    Disclosures(Disclosures),
 }
 
 impl DelayedSerde for DiscoverFeatures {
@@ -37,13 +47,19 @@ impl DelayedSerde for DiscoverFeatures {
     {
         let (protocol, kind_str) = msg_type;
 
-        let kind = match protocol {
-            DiscoverFeaturesKind::V1(DiscoverFeaturesTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
-        };
-
-        match kind.map_err(D::Error::custom)? {
-            DiscoverFeaturesTypeV1_0::Query => Query::deserialize(deserializer).map(From::from),
-            DiscoverFeaturesTypeV1_0::Disclose => Disclose::deserialize(deserializer).map(From::from),
+        match protocol {
+            DiscoverFeaturesKind::V1(DiscoverFeaturesTypeV1::V1_0(kind)) => {
+                match kind.kind_from_str(kind_str).map_err(D::Error::custom)? {
+                    DiscoverFeaturesTypeV1_0::Query => Query::deserialize(deserializer).map(From::from),
+                    DiscoverFeaturesTypeV1_0::Disclose => Disclose::deserialize(deserializer).map(From::from),
+                }
+            }
+            DiscoverFeaturesKind::V2(DiscoverFeaturesTypeV2::V2_0(kind)) => {
+                match kind.kind_from_str(kind_str).map_err(D::Error::custom)? {
+                    DiscoverFeaturesTypeV2_0::Queries => Queries::deserialize(deserializer).map(From::from),
+                    DiscoverFeaturesTypeV2_0::Disclosures => Disclosures::deserialize(deserializer).map(From::from),
+                }
+            }
         }
     }
 
@@ -54,6 +70,8 @@ impl DelayedSerde for DiscoverFeatures {
         match self {
             Self::Query(v) => MsgWithType::from(v).serialize(serializer),
             Self::Disclose(v) => MsgWithType::from(v).serialize(serializer),
+            Self::Queries(v) => MsgWithType::from(v).serialize(serializer),
+            Self::Disclosures(v) => MsgWithType::from(v).serialize(serializer),
         }
     }
 }
@@ -69,10 +87,112 @@ impl ProtocolDescriptor {
     pub fn new(pid: MaybeKnown<Protocol>) -> Self {
         Self { pid, roles: None }
     }
+
+    /// Returns the *pid* of the protocol, as used when matching queries.
+    pub fn str_pid(&self) -> String {
+        match &self.pid {
+            MaybeKnown::Known(protocol) => protocol.to_string(),
+            MaybeKnown::Unknown(pid) => pid.clone(),
+        }
+    }
+
+    /// Fills in the roles of a known protocol from the [`PROTOCOL_REGISTRY`], if none are set.
+    pub fn with_registry_roles(mut self) -> Self {
+        if let (None, MaybeKnown::Known(protocol)) = (&self.roles, &self.pid) {
+            let (name, major, minor) = protocol.as_parts();
+            self.roles = PROTOCOL_REGISTRY
+                .get(&(name, major))
+                .and_then(|entries| entries.iter().find(|entry| entry.minor == minor))
+                .map(|entry| entry.roles.clone());
+        }
+        self
+    }
+}
+
+/// The feature types that can be queried through the discover features 2.0 protocol.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum FeatureType {
+    Protocol,
+    GoalCode,
+}
+
+/// Matches a discover features query against a feature identifier.
+///
+/// The `*` character in the query acts as a wildcard matching any (possibly empty) sequence of characters,
+/// so `*` matches everything and `https://didcomm.org/connections/1.*` matches all minor versions of the
+/// connections protocol. Queries using the legacy `did:sov` prefix are matched as if they used the
+/// `https://didcomm.org` one.
+pub fn matches_query(query: &str, id: &str) -> bool {
+    let query = match query.strip_prefix(Protocol::DID_SOV_PREFIX) {
+        Some(rest) => format!("{}{}", Protocol::DID_COM_ORG_PREFIX, rest),
+        None => query.to_owned(),
+    };
+
+    let mut parts = query.split('*');
+    let mut remainder = match parts.next() {
+        Some(prefix) => match id.strip_prefix(prefix) {
+            Some(remainder) => remainder,
+            None => return false,
+        },
+        None => id,
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let Some((suffix, middle)) = parts.split_last() else {
+        return remainder.is_empty();
+    };
+
+    for part in middle {
+        match remainder.find(part) {
+            Some(idx) => remainder = &remainder[idx + part.len()..],
+            None => return false,
+        }
+    }
+
+    remainder.ends_with(suffix)
 }
 
 transit_to_aries_msg!(QueryContent: QueryDecorators, DiscoverFeatures);
 transit_to_aries_msg!(DiscloseContent: DiscloseDecorators, DiscoverFeatures);
+transit_to_aries_msg!(QueriesContent: QueriesDecorators, DiscoverFeatures);
+transit_to_aries_msg!(DisclosuresContent: DisclosuresDecorators, DiscoverFeatures);
 
 into_msg_with_type!(Query, DiscoverFeaturesTypeV1_0, Query);
 into_msg_with_type!(Disclose, DiscoverFeaturesTypeV1_0, Disclose);
+into_msg_with_type!(Queries, DiscoverFeaturesTypeV2_0, Queries);
+into_msg_with_type!(Disclosures, DiscoverFeaturesTypeV2_0, Disclosures);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::msg_types::protocols::connection::ConnectionTypeV1;
+
+    #[test]
+    fn test_matches_query() {
+        let pid = "https://didcomm.org/connections/1.0";
+
+        assert!(matches_query("*", pid));
+        assert!(matches_query(pid, pid));
+        assert!(matches_query("https://didcomm.org/connections/*", pid));
+        assert!(matches_query("https://didcomm.org/connections/1.*", pid));
+        assert!(matches_query("https://didcomm.org/*/1.0", pid));
+        assert!(matches_query("did:sov:BzCbsNYhMrjHiqZDTUASHg;spec/connections/*", pid));
+        assert!(!matches_query("https://didcomm.org/connections/2.*", pid));
+        assert!(!matches_query("https://didcomm.org/connections", pid));
+        assert!(!matches_query("https://didcomm.org/*/2.0", pid));
+        assert!(!matches_query("", pid));
+    }
+
+    #[test]
+    fn test_protocol_descriptor_with_registry_roles() {
+        let pid = ConnectionTypeV1::new_v1_0();
+        let pd = ProtocolDescriptor::new(MaybeKnown::Known(pid.into())).with_registry_roles();
+
+        assert_eq!(
+            pd.roles,
+            Some(vec![MaybeKnown::Known(Role::Inviter), MaybeKnown::Known(Role::Invitee)])
+        );
+        assert_eq!(pd.str_pid(), "https://didcomm.org/connections/1.0");
+    }
+}
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use shared_vcx::maybe_known::MaybeKnown;
+
+use super::{disclosures::Disclosure, matches_query, FeatureType};
+use crate::{decorators::timing::Timing, msg_parts::MsgParts, msg_types::registry::PROTOCOL_REGISTRY};
+
+pub type Queries = MsgParts<QueriesContent, QueriesDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct QueriesContent {
+    pub queries: Vec<FeatureQuery>,
+}
+
+impl QueriesContent {
+    pub fn new(queries: Vec<FeatureQuery>) -> Self {
+        Self { queries }
+    }
+
+    /// Looks up the protocol queries into the [`PROTOCOL_REGISTRY`] and returns a [`Vec<Disclosure>`]
+    /// matching them. Queries of other feature types are ignored.
+    pub fn lookup(&self) -> Vec<Disclosure> {
+        let mut disclosures = Vec::new();
+
+        for query in &self.queries {
+            if query.feature_type != MaybeKnown::Known(FeatureType::Protocol) {
+                continue;
+            }
+            for entries in PROTOCOL_REGISTRY.values() {
+                for entry in entries {
+                    if matches_query(&query.feature_match, &entry.str_pid) {
+                        let mut disclosure = Disclosure::new(FeatureType::Protocol, entry.str_pid.clone());
+                        disclosure.roles = Some(entry.roles.clone());
+                        disclosures.push(disclosure);
+                    }
+                }
+            }
+        }
+
+        disclosures
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct FeatureQuery {
+    #[serde(rename = "feature-type")]
+    pub feature_type: MaybeKnown<FeatureType>,
+    #[serde(rename = "match")]
+    pub feature_match: String,
+}
+
+impl FeatureQuery {
+    pub fn new(feature_type: FeatureType, feature_match: String) -> Self {
+        Self {
+            feature_type: MaybeKnown::Known(feature_type),
+            feature_match,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct QueriesDecorators {
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::timing::tests::make_extended_timing,
+        misc::test_utils,
+        msg_types::{
+            discover_features::DiscoverFeaturesTypeV2_0, protocols::connection::ConnectionTypeV1,
+            traits::ProtocolVersion, Protocol,
+        },
+    };
+
+    #[test]
+    fn test_minimal_queries() {
+        let content = QueriesContent::new(vec![FeatureQuery::new(FeatureType::Protocol, "*".to_owned())]);
+
+        let decorators = QueriesDecorators::default();
+
+        let expected = json!({
+            "queries": [
+                {
+                    "feature-type": "protocol",
+                    "match": "*"
+                }
+            ]
+        });
+
+        test_utils::test_msg(content, decorators, DiscoverFeaturesTypeV2_0::Queries, expected);
+    }
+
+    #[test]
+    fn test_extended_queries() {
+        let content = QueriesContent::new(vec![
+            FeatureQuery::new(FeatureType::Protocol, "https://didcomm.org/connections/1.*".to_owned()),
+            FeatureQuery::new(FeatureType::GoalCode, "aries.*".to_owned()),
+        ]);
+
+        let mut decorators = QueriesDecorators::default();
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "queries": content.queries,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, DiscoverFeaturesTypeV2_0::Queries, expected);
+    }
+
+    #[test]
+    fn test_unknown_feature_type() {
+        let query: FeatureQuery = serde_json::from_value(json!({
+            "feature-type": "header",
+            "match": "*"
+        }))
+        .unwrap();
+
+        assert_eq!(query.feature_type, MaybeKnown::Unknown("header".to_owned()));
+    }
+
+    #[test]
+    fn test_lookup_match_protocol() {
+        let content = QueriesContent::new(vec![
+            FeatureQuery::new(FeatureType::Protocol, "https://didcomm.org/connections/*".to_owned()),
+            FeatureQuery::new(FeatureType::GoalCode, "*".to_owned()),
+        ]);
+
+        let pid = ConnectionTypeV1::new_v1_0();
+        let mut disclosure = Disclosure::new(FeatureType::Protocol, Protocol::from(pid).to_string());
+        disclosure.roles = Some(pid.roles());
+
+        assert_eq!(vec![disclosure], content.lookup());
+    }
+}
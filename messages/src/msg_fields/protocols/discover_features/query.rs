@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use shared_vcx::maybe_known::MaybeKnown;
 
-use super::ProtocolDescriptor;
+use super::{matches_query, ProtocolDescriptor};
 use crate::{decorators::timing::Timing, msg_parts::MsgParts, msg_types::registry::PROTOCOL_REGISTRY};
 
 pub type Query = MsgParts<QueryContent, QueryDecorators>;
@@ -21,15 +21,10 @@ impl QueryContent {
     /// Looks up into the [`PROTOCOL_REGISTRY`] and returns a [`Vec<ProtocolDescriptor`] matching the inner query.
     pub fn lookup(&self) -> Vec<ProtocolDescriptor> {
         let mut protocols = Vec::new();
-        let query = self
-            .query
-            .split('*')
-            .next()
-            .expect("query must have at least an empty string before *");
 
         for entries in PROTOCOL_REGISTRY.values() {
             for entry in entries {
-                if entry.str_pid.starts_with(query) {
+                if matches_query(&self.query, &entry.str_pid) {
                     let pid = MaybeKnown::Known(entry.protocol);
                     let mut pd = ProtocolDescriptor::new(pid);
                     pd.roles = Some(entry.roles.clone());
@@ -40,6 +35,15 @@ impl QueryContent {
 
         protocols
     }
+
+    /// Returns the [`ProtocolDescriptor`] instances out of the provided ones that match the inner query.
+    pub fn lookup_in(&self, protocols: Vec<ProtocolDescriptor>) -> Vec<ProtocolDescriptor> {
+        protocols
+            .into_iter()
+            .filter(|pd| matches_query(&self.query, &pd.str_pid()))
+            .map(ProtocolDescriptor::with_registry_roles)
+            .collect()
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    decorators::{thread::Thread, timing::Timing, transport::Transport},
+    msg_parts::MsgParts,
+};
+
+pub type MessagesReceived = MsgParts<MessagesReceivedContent, MessagesReceivedDecorators>;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MessagesReceivedContent {
+    pub message_id_list: Vec<String>,
+}
+
+impl MessagesReceivedContent {
+    pub fn new(message_id_list: Vec<String>) -> Self {
+        Self { message_id_list }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, Default, PartialEq)]
+pub struct MessagesReceivedDecorators {
+    #[serde(rename = "~thread")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub thread: Option<Thread>,
+    #[serde(rename = "~transport")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transport: Option<Transport>,
+    #[serde(rename = "~timing")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timing: Option<Timing>,
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{
+            thread::tests::make_extended_thread, timing::tests::make_extended_timing,
+            transport::tests::make_extended_transport,
+        },
+        misc::test_utils,
+        msg_types::pickup::PickupTypeV2_0,
+    };
+
+    #[test]
+    fn test_minimal_messages_received() {
+        let content = MessagesReceivedContent::new(vec!["123456781".to_owned(), "123456782".to_owned()]);
+
+        let decorators = MessagesReceivedDecorators::default();
+
+        let expected = json!({
+            "message_id_list": ["123456781", "123456782"]
+        });
+
+        test_utils::test_msg(content, decorators, PickupTypeV2_0::MessagesReceived, expected);
+    }
+
+    #[test]
+    fn test_extended_messages_received() {
+        let content = MessagesReceivedContent::new(vec!["123456781".to_owned()]);
+
+        let mut decorators = MessagesReceivedDecorators::default();
+        decorators.thread = Some(make_extended_thread());
+        decorators.transport = Some(make_extended_transport());
+        decorators.timing = Some(make_extended_timing());
+
+        let expected = json!({
+            "message_id_list": ["123456781"],
+            "~thread": decorators.thread,
+            "~transport": decorators.transport,
+            "~timing": decorators.timing
+        });
+
+        test_utils::test_msg(content, decorators, PickupTypeV2_0::MessagesReceived, expected);
+    }
+}
@@ -1,10 +1,12 @@
-//! Module containing the `present proof` protocol messages, as defined in the [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0037-present-proof/README.md>).
+//! Module containing the `present proof` protocol messages, as defined in the [1.0 RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0037-present-proof/README.md>)
+//! and the [2.0 RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0454-present-proof-v2/README.md>).
 
 pub mod ack;
 pub mod present;
 pub mod problem_report;
 pub mod propose;
 pub mod request;
+pub mod v2;
 
 use derive_more::From;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
@@ -15,13 +17,22 @@ use self::{
     problem_report::{PresentProofProblemReport, PresentProofProblemReportContent},
     propose::{ProposePresentation, ProposePresentationContent, ProposePresentationDecorators},
     request::{RequestPresentation, RequestPresentationContent, RequestPresentationDecorators},
+    v2::{
+        ack::{AckPresentationV2, AckPresentationV2Content},
+        present::{PresentationV2, PresentationV2Content, PresentationV2Decorators},
+        problem_report::{PresentProofV2ProblemReport, PresentProofV2ProblemReportContent},
+        propose::{ProposePresentationV2, ProposePresentationV2Content, ProposePresentationV2Decorators},
+        request::{RequestPresentationV2, RequestPresentationV2Content, RequestPresentationV2Decorators},
+    },
 };
 use super::{notification::ack::AckDecorators, report_problem::ProblemReportDecorators};
 use crate::{
     misc::utils::{self, into_msg_with_type, transit_to_aries_msg},
     msg_fields::traits::DelayedSerde,
     msg_types::{
-        protocols::present_proof::{PresentProofType, PresentProofTypeV1, PresentProofTypeV1_0},
+        protocols::present_proof::{
+            PresentProofType, PresentProofTypeV1, PresentProofTypeV1_0, PresentProofTypeV2, PresentProofTypeV2_0,
+        },
         MsgWithType,
     },
 };
@@ -33,6 +44,11 @@ pub enum PresentProof {
     Presentation(Presentation),
     Ack(AckPresentation),
     ProblemReport(PresentProofProblemReport),
+    ProposePresentationV2(ProposePresentationV2),
+    RequestPresentationV2(RequestPresentationV2),
+    PresentationV2(PresentationV2),
+    AckV2(AckPresentationV2),
+    ProblemReportV2(PresentProofV2ProblemReport),
 }
 
 impl DelayedSerde for PresentProof {
@@ -44,17 +60,38 @@ impl DelayedSerde for PresentProof {
     {
         let (protocol, kind_str) = msg_type;
 
-        let kind = match protocol {
-            PresentProofType::V1(PresentProofTypeV1::V1_0(kind)) => kind.kind_from_str(kind_str),
-        };
-
-        match kind.map_err(D::Error::custom)? {
-            PresentProofTypeV1_0::ProposePresentation => ProposePresentation::deserialize(deserializer).map(From::from),
-            PresentProofTypeV1_0::RequestPresentation => RequestPresentation::deserialize(deserializer).map(From::from),
-            PresentProofTypeV1_0::Presentation => Presentation::deserialize(deserializer).map(From::from),
-            PresentProofTypeV1_0::Ack => AckPresentation::deserialize(deserializer).map(From::from),
-            PresentProofTypeV1_0::ProblemReport => PresentProofProblemReport::deserialize(deserializer).map(From::from),
-            PresentProofTypeV1_0::PresentationPreview => Err(utils::not_standalone_msg::<D>(kind_str)),
+        match protocol {
+            PresentProofType::V1(PresentProofTypeV1::V1_0(kind)) => {
+                match kind.kind_from_str(kind_str).map_err(D::Error::custom)? {
+                    PresentProofTypeV1_0::ProposePresentation => {
+                        ProposePresentation::deserialize(deserializer).map(From::from)
+                    }
+                    PresentProofTypeV1_0::RequestPresentation => {
+                        RequestPresentation::deserialize(deserializer).map(From::from)
+                    }
+                    PresentProofTypeV1_0::Presentation => Presentation::deserialize(deserializer).map(From::from),
+                    PresentProofTypeV1_0::Ack => AckPresentation::deserialize(deserializer).map(From::from),
+                    PresentProofTypeV1_0::ProblemReport => {
+                        PresentProofProblemReport::deserialize(deserializer).map(From::from)
+                    }
+                    PresentProofTypeV1_0::PresentationPreview => Err(utils::not_standalone_msg::<D>(kind_str)),
+                }
+            }
+            PresentProofType::V2(PresentProofTypeV2::V2_0(kind)) => {
+                match kind.kind_from_str(kind_str).map_err(D::Error::custom)? {
+                    PresentProofTypeV2_0::ProposePresentation => {
+                        ProposePresentationV2::deserialize(deserializer).map(From::from)
+                    }
+                    PresentProofTypeV2_0::RequestPresentation => {
+                        RequestPresentationV2::deserialize(deserializer).map(From::from)
+                    }
+                    PresentProofTypeV2_0::Presentation => PresentationV2::deserialize(deserializer).map(From::from),
+                    PresentProofTypeV2_0::Ack => AckPresentationV2::deserialize(deserializer).map(From::from),
+                    PresentProofTypeV2_0::ProblemReport => {
+                        PresentProofV2ProblemReport::deserialize(deserializer).map(From::from)
+                    }
+                }
+            }
         }
     }
 
@@ -68,6 +105,11 @@ impl DelayedSerde for PresentProof {
             Self::Presentation(v) => MsgWithType::from(v).serialize(serializer),
             Self::Ack(v) => MsgWithType::from(v).serialize(serializer),
             Self::ProblemReport(v) => MsgWithType::from(v).serialize(serializer),
+            Self::ProposePresentationV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::RequestPresentationV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::PresentationV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::AckV2(v) => MsgWithType::from(v).serialize(serializer),
+            Self::ProblemReportV2(v) => MsgWithType::from(v).serialize(serializer),
         }
     }
 }
@@ -77,9 +119,19 @@ transit_to_aries_msg!(RequestPresentationContent: RequestPresentationDecorators,
 transit_to_aries_msg!(PresentationContent: PresentationDecorators, PresentProof);
 transit_to_aries_msg!(AckPresentationContent: AckDecorators, PresentProof);
 transit_to_aries_msg!(PresentProofProblemReportContent: ProblemReportDecorators, PresentProof);
+transit_to_aries_msg!(ProposePresentationV2Content: ProposePresentationV2Decorators, PresentProof);
+transit_to_aries_msg!(RequestPresentationV2Content: RequestPresentationV2Decorators, PresentProof);
+transit_to_aries_msg!(PresentationV2Content: PresentationV2Decorators, PresentProof);
+transit_to_aries_msg!(AckPresentationV2Content: AckDecorators, PresentProof);
+transit_to_aries_msg!(PresentProofV2ProblemReportContent: ProblemReportDecorators, PresentProof);
 
 into_msg_with_type!(ProposePresentation, PresentProofTypeV1_0, ProposePresentation);
 into_msg_with_type!(RequestPresentation, PresentProofTypeV1_0, RequestPresentation);
 into_msg_with_type!(Presentation, PresentProofTypeV1_0, Presentation);
 into_msg_with_type!(AckPresentation, PresentProofTypeV1_0, Ack);
 into_msg_with_type!(PresentProofProblemReport, PresentProofTypeV1_0, ProblemReport);
+into_msg_with_type!(ProposePresentationV2, PresentProofTypeV2_0, ProposePresentation);
+into_msg_with_type!(RequestPresentationV2, PresentProofTypeV2_0, RequestPresentation);
+into_msg_with_type!(PresentationV2, PresentProofTypeV2_0, Presentation);
+into_msg_with_type!(AckPresentationV2, PresentProofTypeV2_0, Ack);
+into_msg_with_type!(PresentProofV2ProblemReport, PresentProofTypeV2_0, ProblemReport);
@@ -1,7 +1,9 @@
-//! Module containing the `revocation notification` protocol messages, as defined in the [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0721-revocation-notification-v2/README.md>).
+//! Module containing the `revocation notification` protocol messages, as defined in the [1.0 RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0183-revocation-notification/README.md>)
+//! and the [2.0 RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0721-revocation-notification-v2/README.md>).
 
 pub mod ack;
 pub mod revoke;
+pub mod v1;
 
 use derive_more::From;
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
@@ -9,13 +11,17 @@ use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 use self::{
     ack::{AckRevoke, AckRevokeContent},
     revoke::{Revoke, RevokeContent, RevokeDecorators},
+    v1::revoke::{RevokeV1, RevokeV1Content},
 };
 use super::notification::ack::AckDecorators;
 use crate::{
     misc::utils::{into_msg_with_type, transit_to_aries_msg},
     msg_fields::traits::DelayedSerde,
     msg_types::{
-        protocols::revocation::{RevocationType as RevocationKind, RevocationTypeV2, RevocationTypeV2_0},
+        protocols::revocation::{
+            RevocationType as RevocationKind, RevocationTypeV1, RevocationTypeV1_0, RevocationTypeV2,
+            RevocationTypeV2_0,
+        },
         MsgWithType,
     },
 };
@@ -24,6 +30,7 @@ use crate::{
 pub enum Revocation {
     Revoke(Revoke),
     Ack(AckRevoke),
+    RevokeV1(RevokeV1),
 }
 
 impl DelayedSerde for Revocation {
@@ -35,13 +42,18 @@ impl DelayedSerde for Revocation {
     {
         let (protocol, kind_str) = msg_type;
 
-        let kind = match protocol {
-            RevocationKind::V2(RevocationTypeV2::V2_0(kind)) => kind.kind_from_str(kind_str),
-        };
-
-        match kind.map_err(D::Error::custom)? {
-            RevocationTypeV2_0::Revoke => Revoke::deserialize(deserializer).map(From::from),
-            RevocationTypeV2_0::Ack => AckRevoke::deserialize(deserializer).map(From::from),
+        match protocol {
+            RevocationKind::V1(RevocationTypeV1::V1_0(kind)) => {
+                match kind.kind_from_str(kind_str).map_err(D::Error::custom)? {
+                    RevocationTypeV1_0::Revoke => RevokeV1::deserialize(deserializer).map(From::from),
+                }
+            }
+            RevocationKind::V2(RevocationTypeV2::V2_0(kind)) => {
+                match kind.kind_from_str(kind_str).map_err(D::Error::custom)? {
+                    RevocationTypeV2_0::Revoke => Revoke::deserialize(deserializer).map(From::from),
+                    RevocationTypeV2_0::Ack => AckRevoke::deserialize(deserializer).map(From::from),
+                }
+            }
         }
     }
 
@@ -52,12 +64,15 @@ impl DelayedSerde for Revocation {
         match self {
             Self::Revoke(v) => MsgWithType::from(v).serialize(serializer),
             Self::Ack(v) => MsgWithType::from(v).serialize(serializer),
+            Self::RevokeV1(v) => MsgWithType::from(v).serialize(serializer),
         }
     }
 }
 
 transit_to_aries_msg!(RevokeContent: RevokeDecorators, Revocation);
 transit_to_aries_msg!(AckRevokeContent: AckDecorators, Revocation);
+transit_to_aries_msg!(RevokeV1Content: RevokeDecorators, Revocation);
 
 into_msg_with_type!(Revoke, RevocationTypeV2_0, Revoke);
 into_msg_with_type!(AckRevoke, RevocationTypeV2_0, Ack);
+into_msg_with_type!(RevokeV1, RevocationTypeV1_0, Revoke);
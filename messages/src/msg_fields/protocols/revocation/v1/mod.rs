@@ -0,0 +1,3 @@
+//! Module containing the `revocation notification` 1.0 protocol messages, as defined in the [RFC](<https://github.com/hyperledger/aries-rfcs/blob/main/features/0183-revocation-notification/README.md>).
+
+pub mod revoke;
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{msg_fields::protocols::revocation::revoke::RevokeDecorators, msg_parts::MsgParts};
+
+pub type RevokeV1 = MsgParts<RevokeV1Content, RevokeDecorators>;
+
+/// Unlike its 2.0 counterpart, the 1.0 notification identifies the revoked credential
+/// through the thread ID of the issue credential protocol it was issued in.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct RevokeV1Content {
+    pub thread_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+impl RevokeV1Content {
+    pub fn new(thread_id: String) -> Self {
+        Self {
+            thread_id,
+            comment: None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+#[allow(clippy::field_reassign_with_default)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        decorators::{please_ack::tests::make_minimal_please_ack, thread::tests::make_extended_thread},
+        misc::test_utils,
+        msg_types::revocation::RevocationTypeV1_0,
+    };
+
+    #[test]
+    fn test_minimal_revoke_v1() {
+        let content = RevokeV1Content::new("test_thread_id".to_owned());
+
+        let decorators = RevokeDecorators::default();
+
+        let expected = json!({
+            "thread_id": content.thread_id
+        });
+
+        test_utils::test_msg(content, decorators, RevocationTypeV1_0::Revoke, expected);
+    }
+
+    #[test]
+    fn test_extended_revoke_v1() {
+        let mut content = RevokeV1Content::new("test_thread_id".to_owned());
+        content.comment = Some("test_comment".to_owned());
+
+        let mut decorators = RevokeDecorators::default();
+        decorators.please_ack = Some(make_minimal_please_ack());
+        decorators.thread = Some(make_extended_thread());
+
+        let expected = json!({
+            "thread_id": content.thread_id,
+            "comment": content.comment,
+            "~please_ack": decorators.please_ack,
+            "~thread": decorators.thread
+        });
+
+        test_utils::test_msg(content, decorators, RevocationTypeV1_0::Revoke, expected);
+    }
+}
@@ -0,0 +1,86 @@
+use derive_more::{From, TryInto};
+use messages_macros::MessageType;
+use strum_macros::{AsRefStr, EnumString};
+use transitive::TransitiveFrom;
+
+use super::Protocol;
+use crate::msg_types::{role::Role, MsgKindType};
+
+#[derive(Copy, Clone, Debug, From, TryInto, PartialEq, MessageType)]
+#[msg_type(protocol = "action-menu")]
+pub enum ActionMenuType {
+    V1(ActionMenuTypeV1),
+}
+
+#[derive(Copy, Clone, Debug, From, TryInto, PartialEq, TransitiveFrom, MessageType)]
+#[transitive(into(ActionMenuType, Protocol))]
+#[msg_type(major = 1)]
+pub enum ActionMenuTypeV1 {
+    #[msg_type(minor = 0, roles = "Role::Requester, Role::Responder")]
+    V1_0(MsgKindType<ActionMenuTypeV1_0>),
+}
+
+#[derive(Copy, Clone, Debug, AsRefStr, EnumString, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ActionMenuTypeV1_0 {
+    Menu,
+    MenuRequest,
+    Perform,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::misc::test_utils;
+
+    #[test]
+    fn test_protocol_action_menu() {
+        test_utils::test_serde(
+            Protocol::from(ActionMenuTypeV1::new_v1_0()),
+            json!("https://didcomm.org/action-menu/1.0"),
+        )
+    }
+
+    #[test]
+    fn test_version_resolution_action_menu() {
+        test_utils::test_msg_type_resolution("https://didcomm.org/action-menu/1.255", ActionMenuTypeV1::new_v1_0())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unsupported_version_action_menu() {
+        test_utils::test_serde(
+            Protocol::from(ActionMenuTypeV1::new_v1_0()),
+            json!("https://didcomm.org/action-menu/2.0"),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_menu() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/action-menu/1.0",
+            "menu",
+            ActionMenuTypeV1::new_v1_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_menu_request() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/action-menu/1.0",
+            "menu-request",
+            ActionMenuTypeV1::new_v1_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_perform() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/action-menu/1.0",
+            "perform",
+            ActionMenuTypeV1::new_v1_0(),
+        )
+    }
+}
@@ -0,0 +1,116 @@
+use derive_more::{From, TryInto};
+use messages_macros::MessageType;
+use strum_macros::{AsRefStr, EnumString};
+use transitive::TransitiveFrom;
+
+use super::Protocol;
+use crate::msg_types::{role::Role, MsgKindType};
+
+#[derive(Copy, Clone, Debug, From, TryInto, PartialEq, MessageType)]
+#[msg_type(protocol = "messagepickup")]
+pub enum PickupType {
+    V2(PickupTypeV2),
+}
+
+#[derive(Copy, Clone, Debug, From, TryInto, PartialEq, TransitiveFrom, MessageType)]
+#[transitive(into(PickupType, Protocol))]
+#[msg_type(major = 2)]
+pub enum PickupTypeV2 {
+    #[msg_type(minor = 0, roles = "Role::Mediator, Role::Recipient")]
+    V2_0(MsgKindType<PickupTypeV2_0>),
+}
+
+#[derive(Copy, Clone, Debug, AsRefStr, EnumString, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum PickupTypeV2_0 {
+    StatusRequest,
+    Status,
+    DeliveryRequest,
+    Delivery,
+    MessagesReceived,
+    LiveDeliveryChange,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::misc::test_utils;
+
+    #[test]
+    fn test_protocol_pickup() {
+        test_utils::test_serde(
+            Protocol::from(PickupTypeV2::new_v2_0()),
+            json!("https://didcomm.org/messagepickup/2.0"),
+        )
+    }
+
+    #[test]
+    fn test_version_resolution_pickup() {
+        test_utils::test_msg_type_resolution("https://didcomm.org/messagepickup/2.255", PickupTypeV2::new_v2_0())
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_unsupported_version_pickup() {
+        test_utils::test_serde(
+            Protocol::from(PickupTypeV2::new_v2_0()),
+            json!("https://didcomm.org/messagepickup/1.0"),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_status_request() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/messagepickup/2.0",
+            "status-request",
+            PickupTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_status() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/messagepickup/2.0",
+            "status",
+            PickupTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_delivery_request() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/messagepickup/2.0",
+            "delivery-request",
+            PickupTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_delivery() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/messagepickup/2.0",
+            "delivery",
+            PickupTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_messages_received() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/messagepickup/2.0",
+            "messages-received",
+            PickupTypeV2::new_v2_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_live_delivery_change() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/messagepickup/2.0",
+            "live-delivery-change",
+            PickupTypeV2::new_v2_0(),
+        )
+    }
+}
@@ -9,9 +9,24 @@ use crate::msg_types::{role::Role, MsgKindType};
 #[derive(Copy, Clone, Debug, From, PartialEq, MessageType)]
 #[msg_type(protocol = "revocation_notification")]
 pub enum RevocationType {
+    V1(RevocationTypeV1),
     V2(RevocationTypeV2),
 }
 
+#[derive(Copy, Clone, Debug, From, PartialEq, TransitiveFrom, MessageType)]
+#[transitive(into(RevocationType, Protocol))]
+#[msg_type(major = 1)]
+pub enum RevocationTypeV1 {
+    #[msg_type(minor = 0, roles = "Role::Holder, Role::Issuer")]
+    V1_0(MsgKindType<RevocationTypeV1_0>),
+}
+
+#[derive(Copy, Clone, Debug, AsRefStr, EnumString, PartialEq)]
+#[strum(serialize_all = "kebab-case")]
+pub enum RevocationTypeV1_0 {
+    Revoke,
+}
+
 #[derive(Copy, Clone, Debug, From, PartialEq, TransitiveFrom, MessageType)]
 #[transitive(into(RevocationType, Protocol))]
 #[msg_type(major = 2)]
@@ -34,6 +49,31 @@ mod tests {
     use super::*;
     use crate::misc::test_utils;
 
+    #[test]
+    fn test_protocol_revocation_notification_v1() {
+        test_utils::test_serde(
+            Protocol::from(RevocationTypeV1::new_v1_0()),
+            json!("https://didcomm.org/revocation_notification/1.0"),
+        )
+    }
+
+    #[test]
+    fn test_version_resolution_revocation_notification_v1() {
+        test_utils::test_msg_type_resolution(
+            "https://didcomm.org/revocation_notification/1.255",
+            RevocationTypeV1::new_v1_0(),
+        )
+    }
+
+    #[test]
+    fn test_msg_type_revoke_v1() {
+        test_utils::test_msg_type(
+            "https://didcomm.org/revocation_notification/1.0",
+            "revoke",
+            RevocationTypeV1::new_v1_0(),
+        )
+    }
+
     #[test]
     fn test_protocol_revocation_notification() {
         test_utils::test_serde(
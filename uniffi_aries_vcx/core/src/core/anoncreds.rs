@@ -0,0 +1,36 @@
+use std::sync::Arc;
+
+use aries_vcx::aries_vcx_core::anoncreds::base_anoncreds::BaseAnonCreds;
+
+use crate::{errors::error::VcxUniFFIResult, runtime::block_on};
+
+/// Exposes the holder and prover parts of [BaseAnonCreds], as uniffi cannot process trait objects.
+pub struct AnonCredsHolder {
+    inner: Arc<dyn BaseAnonCreds>,
+}
+
+impl AnonCredsHolder {
+    pub fn new(inner: Arc<dyn BaseAnonCreds>) -> Self {
+        Self { inner }
+    }
+    pub fn prover_get_credential(&self, cred_id: String) -> VcxUniFFIResult<String> {
+        Ok(block_on(self.inner.prover_get_credential(&cred_id))?)
+    }
+    pub fn prover_get_credentials(&self, filter_json: Option<String>) -> VcxUniFFIResult<String> {
+        Ok(block_on(self.inner.prover_get_credentials(filter_json.as_deref()))?)
+    }
+    pub fn prover_get_credentials_for_proof_req(&self, proof_request_json: String) -> VcxUniFFIResult<String> {
+        Ok(block_on(
+            self.inner.prover_get_credentials_for_proof_req(&proof_request_json),
+        )?)
+    }
+    pub fn prover_delete_credential(&self, cred_id: String) -> VcxUniFFIResult<()> {
+        Ok(block_on(self.inner.prover_delete_credential(&cred_id))?)
+    }
+    pub fn prover_create_link_secret(&self, link_secret_id: String) -> VcxUniFFIResult<String> {
+        Ok(block_on(self.inner.prover_create_link_secret(&link_secret_id))?)
+    }
+    pub fn generate_nonce(&self) -> VcxUniFFIResult<String> {
+        Ok(block_on(self.inner.generate_nonce())?)
+    }
+}
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use aries_vcx::aries_vcx_core::ledger::base_ledger::AnoncredsLedgerRead;
+
+use crate::{errors::error::VcxUniFFIResult, runtime::block_on};
+
+/// Wraps the `(rev_reg_id, json, timestamp)` tuples returned by [AnoncredsLedgerRead],
+/// as uniffi cannot process tuples
+pub struct RevocationRegistryEntry {
+    pub rev_reg_id: String,
+    pub json: String,
+    pub timestamp: u64,
+}
+
+impl From<(String, String, u64)> for RevocationRegistryEntry {
+    fn from((rev_reg_id, json, timestamp): (String, String, u64)) -> Self {
+        Self {
+            rev_reg_id,
+            json,
+            timestamp,
+        }
+    }
+}
+
+/// Exposes [AnoncredsLedgerRead], as uniffi cannot process trait objects.
+pub struct AnoncredsLedgerReadHolder {
+    inner: Arc<dyn AnoncredsLedgerRead>,
+}
+
+impl AnoncredsLedgerReadHolder {
+    pub fn new(inner: Arc<dyn AnoncredsLedgerRead>) -> Self {
+        Self { inner }
+    }
+    pub fn get_schema(&self, schema_id: String) -> VcxUniFFIResult<String> {
+        Ok(block_on(self.inner.get_schema(&schema_id, None))?)
+    }
+    pub fn get_cred_def(&self, cred_def_id: String) -> VcxUniFFIResult<String> {
+        Ok(block_on(self.inner.get_cred_def(&cred_def_id, None))?)
+    }
+    pub fn get_rev_reg_def_json(&self, rev_reg_id: String) -> VcxUniFFIResult<String> {
+        Ok(block_on(self.inner.get_rev_reg_def_json(&rev_reg_id))?)
+    }
+    pub fn get_rev_reg_delta_json(
+        &self,
+        rev_reg_id: String,
+        from: Option<u64>,
+        to: Option<u64>,
+    ) -> VcxUniFFIResult<RevocationRegistryEntry> {
+        let entry = block_on(self.inner.get_rev_reg_delta_json(&rev_reg_id, from, to))?;
+        Ok(entry.into())
+    }
+    pub fn get_rev_reg(&self, rev_reg_id: String, timestamp: u64) -> VcxUniFFIResult<RevocationRegistryEntry> {
+        let entry = block_on(self.inner.get_rev_reg(&rev_reg_id, timestamp))?;
+        Ok(entry.into())
+    }
+}
@@ -1,2 +1,4 @@
+pub mod anoncreds;
 pub mod http_client;
+pub mod ledger;
 pub mod profile;
@@ -16,7 +16,7 @@ use aries_vcx::aries_vcx_core::wallet::indy_wallet::IndySdkWallet;
 use aries_vcx::aries_vcx_core::WalletHandle;
 // use aries_vcx::aries_vcx_core::PoolHandle;
 use aries_vcx::core::profile::profile::Profile;
-use aries_vcx::errors::error::VcxResult;
+use aries_vcx::errors::error::{AriesVcxError, AriesVcxErrorKind, VcxResult};
 use async_trait::async_trait;
 
 use serde_json::Value;
@@ -29,7 +29,9 @@ use crate::handlers::TypeMessage;
 use crate::{errors::error::VcxUniFFIResult, runtime::block_on};
 use aries_vcx::transport::Transport;
 
+use super::anoncreds::AnonCredsHolder;
 use super::http_client::NativeClient;
+use super::ledger::AnoncredsLedgerReadHolder;
 
 pub trait NativeCryptoProvider: Send + Sync {
     fn encrypt(&self, data: Vec<u8>, key_handle: String) -> Result<Vec<u8>, CryptoError>;
@@ -93,10 +95,26 @@ impl SecureEnclaveProvider for NativeSecureEnclaveProvider {
 pub struct ProfileHolder {
     pub inner: Arc<dyn Profile>,
     pub transport: Arc<dyn Transport>,
-    wallet_handle: WalletHandle,
+    wallet_handle: Option<WalletHandle>,
 }
 
 impl ProfileHolder {
+    /// Wraps a profile which does not own an indy wallet, e.g. a mock profile used in tests.
+    pub fn from_profile(inner: Arc<dyn Profile>, transport: Arc<dyn Transport>) -> Self {
+        Self {
+            inner,
+            transport,
+            wallet_handle: None,
+        }
+    }
+    pub fn inject_anoncreds(&self) -> Arc<AnonCredsHolder> {
+        Arc::new(AnonCredsHolder::new(self.inner.inject_anoncreds()))
+    }
+    pub fn inject_anoncreds_ledger_read(&self) -> Arc<AnoncredsLedgerReadHolder> {
+        Arc::new(AnoncredsLedgerReadHolder::new(
+            self.inner.inject_anoncreds_ledger_read(),
+        ))
+    }
     pub fn get_credentials(&self) -> VcxUniFFIResult<String> {
         let w = self.inner.inject_anoncreds();
         let list = block_on(async { w.prover_get_credentials(None).await })?;
@@ -150,7 +168,9 @@ impl ProfileHolder {
 impl Drop for ProfileHolder {
     fn drop(&mut self) {
         println!("Finalizing ProfileHolder");
-        let _ = block_on(async move { close_wallet(self.wallet_handle).await });
+        if let Some(wallet_handle) = self.wallet_handle {
+            let _ = block_on(async move { close_wallet(wallet_handle).await });
+        }
     }
 }
 
@@ -169,7 +189,7 @@ pub fn new_indy_profile(
         Ok(Arc::new(ProfileHolder {
             inner,
             transport,
-            wallet_handle: wh,
+            wallet_handle: Some(wh),
         }))
     })
 }
@@ -179,11 +199,11 @@ pub struct DummyProfile(WalletHandle, String);
 
 impl Profile for DummyProfile {
     fn inject_indy_ledger_read(&self) -> Arc<dyn IndyLedgerRead> {
-        todo!()
+        Arc::new(DummyLedgerRead(self.1.clone()))
     }
 
     fn inject_indy_ledger_write(&self) -> Arc<dyn IndyLedgerWrite> {
-        todo!()
+        Arc::new(UnsupportedLedgerWrite)
     }
 
     fn inject_anoncreds(&self) -> Arc<dyn BaseAnonCreds> {
@@ -197,7 +217,7 @@ impl Profile for DummyProfile {
     }
 
     fn inject_anoncreds_ledger_write(&self) -> Arc<dyn AnoncredsLedgerWrite> {
-        todo!()
+        Arc::new(UnsupportedLedgerWrite)
     }
 
     fn inject_wallet(&self) -> Arc<dyn BaseWallet> {
@@ -205,14 +225,121 @@ impl Profile for DummyProfile {
         Arc::new(sdk_wallet)
     }
 
-    fn update_taa_configuration(&self, taa_options: TxnAuthrAgrmtOptions) -> VcxResult<()> {
-        todo!()
+    fn update_taa_configuration(&self, _taa_options: TxnAuthrAgrmtOptions) -> VcxResult<()> {
+        Err(AriesVcxError::from_msg(
+            AriesVcxErrorKind::ActionNotSupported,
+            "the mobile profile can't write to the ledger, so it has no TAA to configure",
+        ))
     }
 }
 
+// The mobile profile only reads from the ledger, through the HTTP API at the ledger base url:
+//  - GET /schemas/{schema_id}
+//  - GET /credential-definitions/{cred_def_id}
+//  - GET /rev_reg_def/{rev_reg_id}
+//  - GET /rev_reg_delta/{rev_reg_id}?from={from}&to={to}
+//  - GET /rev_reg/{rev_reg_id}/{timestamp}
 #[derive(Debug, Clone)]
 struct DummyLedgerRead(String);
 
+impl DummyLedgerRead {
+    fn fetch_rev_reg(&self, path: &str, field: &str, rev_reg_id: &str) -> VcxCoreResult<(String, String, u64)> {
+        let mut response = ureq::get(&format!("{}/{path}", self.0))
+            .call()
+            .map_err(|e| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidUrl, format!("{e}")))?
+            .into_json::<Value>()
+            .map_err(|e| AriesVcxCoreError::from_msg(AriesVcxCoreErrorKind::InvalidJson, format!("{e}")))?;
+        let timestamp = response.get("timestamp").and_then(Value::as_u64).ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidLedgerResponse,
+                "no timestamp in response".to_string(),
+            )
+        })?;
+        let json = response.get_mut(field).map(Value::take).ok_or_else(|| {
+            AriesVcxCoreError::from_msg(
+                AriesVcxCoreErrorKind::InvalidLedgerResponse,
+                format!("no {field} in response"),
+            )
+        })?;
+        Ok((rev_reg_id.to_string(), serde_json::to_string(&json)?, timestamp))
+    }
+}
+
+fn unsupported<T>(operation: &str) -> VcxCoreResult<T> {
+    Err(AriesVcxCoreError::from_msg(
+        AriesVcxCoreErrorKind::ActionNotSupported,
+        format!("{operation} is not supported by the mobile profile"),
+    ))
+}
+
+#[async_trait]
+impl IndyLedgerRead for DummyLedgerRead {
+    async fn get_attr(&self, _target_did: &str, _attr_name: &str) -> VcxCoreResult<String> {
+        unsupported("get_attr")
+    }
+    async fn get_nym(&self, _did: &str) -> VcxCoreResult<String> {
+        unsupported("get_nym")
+    }
+    async fn get_txn_author_agreement(&self) -> VcxCoreResult<Option<String>> {
+        Ok(None)
+    }
+    async fn get_ledger_txn(&self, _seq_no: i32, _submitter_did: Option<&str>) -> VcxCoreResult<String> {
+        unsupported("get_ledger_txn")
+    }
+}
+
+#[derive(Debug, Clone)]
+struct UnsupportedLedgerWrite;
+
+#[async_trait]
+impl IndyLedgerWrite for UnsupportedLedgerWrite {
+    async fn publish_nym(
+        &self,
+        _submitter_did: &str,
+        _target_did: &str,
+        _verkey: Option<&str>,
+        _data: Option<&str>,
+        _role: Option<&str>,
+    ) -> VcxCoreResult<String> {
+        unsupported("publish_nym")
+    }
+    async fn set_endorser(&self, _submitter_did: &str, _request: &str, _endorser: &str) -> VcxCoreResult<String> {
+        unsupported("set_endorser")
+    }
+    async fn endorse_transaction(&self, _endorser_did: &str, _request_json: &str) -> VcxCoreResult<()> {
+        unsupported("endorse_transaction")
+    }
+    async fn add_attr(&self, _target_did: &str, _attrib_json: &str) -> VcxCoreResult<String> {
+        unsupported("add_attr")
+    }
+}
+
+#[async_trait]
+impl AnoncredsLedgerWrite for UnsupportedLedgerWrite {
+    async fn publish_schema(
+        &self,
+        _schema_json: &str,
+        _submitter_did: &str,
+        _endorser_did: Option<String>,
+    ) -> VcxCoreResult<()> {
+        unsupported("publish_schema")
+    }
+    async fn publish_cred_def(&self, _cred_def_json: &str, _submitter_did: &str) -> VcxCoreResult<()> {
+        unsupported("publish_cred_def")
+    }
+    async fn publish_rev_reg_def(&self, _rev_reg_def: &str, _submitter_did: &str) -> VcxCoreResult<()> {
+        unsupported("publish_rev_reg_def")
+    }
+    async fn publish_rev_reg_delta(
+        &self,
+        _rev_reg_id: &str,
+        _rev_reg_entry_json: &str,
+        _submitter_did: &str,
+    ) -> VcxCoreResult<()> {
+        unsupported("publish_rev_reg_delta")
+    }
+}
+
 #[async_trait]
 impl AnoncredsLedgerRead for DummyLedgerRead {
     async fn get_schema(&self, schema_id: &str, _submitter_did: Option<&str>) -> VcxCoreResult<String> {
@@ -297,9 +424,88 @@ impl AnoncredsLedgerRead for DummyLedgerRead {
         from: Option<u64>,
         to: Option<u64>,
     ) -> VcxCoreResult<(String, String, u64)> {
-        todo! {}
+        let mut query = Vec::new();
+        if let Some(from) = from {
+            query.push(format!("from={from}"));
+        }
+        if let Some(to) = to {
+            query.push(format!("to={to}"));
+        }
+        let path = format!("rev_reg_delta/{rev_reg_id}?{}", query.join("&"));
+        self.fetch_rev_reg(&path, "rev_reg_delta", rev_reg_id)
     }
     async fn get_rev_reg(&self, rev_reg_id: &str, timestamp: u64) -> VcxCoreResult<(String, String, u64)> {
-        todo! {}
+        self.fetch_rev_reg(&format!("rev_reg/{rev_reg_id}/{timestamp}"), "rev_reg", rev_reg_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use aries_vcx::{
+        transport::in_process::InProcessTransport,
+        utils::{
+            constants::{CREDS_FROM_PROOF_REQ, REV_REG_ID, SCHEMA_ID, SCHEMA_JSON},
+            mockdata::{
+                mock_settings::MockBuilder, mockdata_credex::ARIES_CREDENTIAL_OFFER,
+                mockdata_proof::ARIES_PROOF_REQUEST_PRESENTATION, profile::mock_profile::MockProfile,
+            },
+        },
+    };
+
+    use super::ProfileHolder;
+    use crate::handlers::{
+        connection::connection::create_inviter,
+        issuance::{issuance::create_vc_receiver, IssuanceState},
+        proof::{proof::Proof, ProofState},
+    };
+
+    fn mock_profile_holder() -> Arc<ProfileHolder> {
+        Arc::new(ProfileHolder::from_profile(
+            Arc::new(MockProfile),
+            Arc::new(InProcessTransport::new()),
+        ))
+    }
+
+    #[test]
+    fn test_ledger_read_against_mock_profile() {
+        let ledger = mock_profile_holder().inject_anoncreds_ledger_read();
+
+        assert_eq!(ledger.get_schema(SCHEMA_ID.to_string()).unwrap(), SCHEMA_JSON);
+        let entry = ledger.get_rev_reg(REV_REG_ID.to_string(), 1).unwrap();
+        assert_eq!(entry.rev_reg_id, REV_REG_ID);
+        assert_eq!(entry.timestamp, 1);
+    }
+
+    #[test]
+    fn test_holder_flow_against_mock_profile() {
+        let profile = mock_profile_holder();
+        let connection = create_inviter(profile.clone()).unwrap();
+        let issuance = create_vc_receiver("test_holder".to_string(), connection).unwrap();
+
+        issuance.receive_offer(ARIES_CREDENTIAL_OFFER.to_string()).unwrap();
+
+        assert!(matches!(issuance.get_state().unwrap(), IssuanceState::OfferReceived));
+        assert!(!issuance.get_cred_def_id().unwrap().is_empty());
+        assert!(profile.inject_anoncreds().generate_nonce().is_ok());
+    }
+
+    #[test]
+    fn test_prover_flow_against_mock_profile() {
+        let _mock_builder = MockBuilder::init().set_mock_creds_retrieved_for_proof_request(CREDS_FROM_PROOF_REQ);
+        let profile = mock_profile_holder();
+        let proof = Proof::create_from_request("test_prover".to_string(), ARIES_PROOF_REQUEST_PRESENTATION.to_string())
+            .unwrap();
+        assert!(matches!(
+            proof.get_state().unwrap(),
+            ProofState::PresentationRequestReceived
+        ));
+
+        let retrieved_credentials = proof.select_credentials(profile).unwrap();
+        let selected_credentials = proof.choose_credentials(retrieved_credentials).unwrap();
+
+        assert!(selected_credentials.contains("height_1"));
+        assert!(selected_credentials.contains("zip_2"));
     }
 }
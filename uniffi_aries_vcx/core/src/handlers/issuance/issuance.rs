@@ -25,6 +25,8 @@ use crate::{
     runtime::block_on,
 };
 
+use super::IssuanceState;
+
 pub struct Message {
     pub message: AriesMessage,
 }
@@ -99,6 +101,14 @@ impl Issuance {
             Ok(())
         })
     }
+    pub fn get_state(&self) -> VcxUniFFIResult<IssuanceState> {
+        let guard = self.handler.lock()?;
+        Ok(guard.get_state().into())
+    }
+    pub fn is_revoked(&self, profile: Arc<ProfileHolder>) -> VcxUniFFIResult<bool> {
+        let holder = self.handler.lock()?.clone();
+        Ok(block_on(async move { holder.is_revoked(&profile.inner).await })?)
+    }
     pub fn get_credential(&self) -> VcxUniFFIResult<CredentialEntry> {
         let guard = self.handler.lock()?;
         let (credential_id, cred) = guard.get_credential()?;
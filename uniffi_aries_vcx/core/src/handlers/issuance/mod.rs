@@ -5,3 +5,28 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 
 pub mod issuance;
+
+use aries_vcx::protocols::issuance::holder::state_machine::HolderState;
+
+/// Mirrors [HolderState], so it can be exposed through uniffi
+pub enum IssuanceState {
+    Initial,
+    ProposalSent,
+    OfferReceived,
+    RequestSent,
+    Finished,
+    Failed,
+}
+
+impl From<HolderState> for IssuanceState {
+    fn from(value: HolderState) -> Self {
+        match value {
+            HolderState::Initial => IssuanceState::Initial,
+            HolderState::ProposalSent => IssuanceState::ProposalSent,
+            HolderState::OfferReceived => IssuanceState::OfferReceived,
+            HolderState::RequestSent => IssuanceState::RequestSent,
+            HolderState::Finished => IssuanceState::Finished,
+            HolderState::Failed => IssuanceState::Failed,
+        }
+    }
+}
@@ -5,3 +5,32 @@
 // file, You can obtain one at http://mozilla.org/MPL/2.0/.
 pub mod proof;
 pub mod verify;
+
+use aries_vcx::protocols::proof_presentation::prover::state_machine::ProverState;
+
+/// Mirrors [ProverState], so it can be exposed through uniffi
+pub enum ProofState {
+    Initial,
+    PresentationProposalSent,
+    PresentationRequestReceived,
+    PresentationPrepared,
+    PresentationPreparationFailed,
+    PresentationSent,
+    Finished,
+    Failed,
+}
+
+impl From<ProverState> for ProofState {
+    fn from(value: ProverState) -> Self {
+        match value {
+            ProverState::Initial => ProofState::Initial,
+            ProverState::PresentationProposalSent => ProofState::PresentationProposalSent,
+            ProverState::PresentationRequestReceived => ProofState::PresentationRequestReceived,
+            ProverState::PresentationPrepared => ProofState::PresentationPrepared,
+            ProverState::PresentationPreparationFailed => ProofState::PresentationPreparationFailed,
+            ProverState::PresentationSent => ProofState::PresentationSent,
+            ProverState::Finished => ProofState::Finished,
+            ProverState::Failed => ProofState::Failed,
+        }
+    }
+}
@@ -27,6 +27,8 @@ use crate::{
     runtime::block_on,
 };
 
+use super::ProofState;
+
 pub struct Proof {
     handler: Mutex<Prover>,
 }
@@ -77,6 +79,27 @@ impl Proof {
 
         Ok(serde_json::to_string(&selected)?)
     }
+    pub fn get_state(&self) -> VcxUniFFIResult<ProofState> {
+        let guard = self.handler.lock()?;
+        Ok(guard.get_state().into())
+    }
+    pub fn decline_presentation_request(
+        &self,
+        profile: Arc<ProfileHolder>,
+        connection: Arc<Connection>,
+        reason: String,
+    ) -> VcxUniFFIResult<()> {
+        let mut guard = self.handler.lock()?;
+        let mut prove = guard.clone();
+        block_on(async {
+            let send_message = connection.send_message(profile);
+            prove
+                .decline_presentation_request(send_message, Some(reason), None)
+                .await?;
+            *guard = prove;
+            Ok(())
+        })
+    }
     pub fn get_proof_attachment(&self) -> VcxUniFFIResult<String> {
         let guard = self.handler.lock()?;
         let attachment = guard.get_proof_request_attachment()?;
@@ -5,8 +5,10 @@ pub mod errors;
 pub mod handlers;
 pub mod runtime;
 
+use crate::core::anoncreds::*;
 use crate::core::http_client::NativeClient;
 use crate::core::http_client::NativeTransport;
+use crate::core::ledger::*;
 use crate::core::profile::*;
 use crate::errors::error::*;
 use crate::handlers::*;